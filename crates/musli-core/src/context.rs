@@ -6,7 +6,7 @@ use core::str;
 
 use crate::alloc::Allocator;
 use crate::de::{DecodeBytes, DecodeUnsized, DecodeUnsizedBytes};
-use crate::{Decode, Decoder};
+use crate::{Decode, Decoder, ErrorKind};
 
 /// Provides ergonomic access to the serialization context.
 ///
@@ -136,6 +136,40 @@ pub trait Context {
         self.custom(message)
     }
 
+    /// Report a message as an error of the given [`ErrorKind`].
+    ///
+    /// By default this ignores the kind and forwards to [`message`].
+    ///
+    /// [`message`]: Context::message
+    #[allow(unused_variables)]
+    #[inline(always)]
+    fn message_with_kind<T>(&self, kind: ErrorKind, message: T) -> Self::Error
+    where
+        T: fmt::Display,
+    {
+        self.message(message)
+    }
+
+    /// Report a message as an error of the given [`ErrorKind`] based on a
+    /// mark.
+    ///
+    /// By default this ignores the kind and forwards to [`marked_message`].
+    ///
+    /// [`marked_message`]: Context::marked_message
+    #[allow(unused_variables)]
+    #[inline(always)]
+    fn marked_message_with_kind<T>(
+        &self,
+        mark: Self::Mark,
+        kind: ErrorKind,
+        message: T,
+    ) -> Self::Error
+    where
+        T: fmt::Display,
+    {
+        self.marked_message(mark, message)
+    }
+
     /// Advance the context by `n` bytes of input.
     ///
     /// This is typically used to move the mark forward as produced by
@@ -156,13 +190,40 @@ pub trait Context {
         Self::Mark::default()
     }
 
+    /// Restore the context to a mark previously returned by [`Context::mark`].
+    ///
+    /// This is called by decoders which rewind their input to a prior state,
+    /// such as when recovering from an error in lenient mode.
+    #[allow(unused_variables)]
+    #[inline(always)]
+    fn restore(&self, mark: Self::Mark) {}
+
+    /// Test if the context is in lenient mode.
+    ///
+    /// In lenient mode, a field which has a default value and which fails to
+    /// decode is skipped and replaced with its default value if the format
+    /// supports it. The error which caused it is still reported to the
+    /// context, so this only makes sense for contexts which can collect
+    /// multiple errors.
+    ///
+    /// See [`MapDecoder::try_recover`].
+    ///
+    /// [`MapDecoder::try_recover`]: crate::de::MapDecoder::try_recover
+    #[inline(always)]
+    fn is_lenient(&self) -> bool {
+        false
+    }
+
     /// Report that an invalid variant tag was encountered.
     #[inline(always)]
     fn invalid_variant_tag<T>(&self, _: &'static str, tag: &T) -> Self::Error
     where
         T: ?Sized + fmt::Debug,
     {
-        self.message(format_args!("Invalid variant tag {tag:?}"))
+        self.message_with_kind(
+            ErrorKind::UnknownVariant,
+            format_args!("Invalid variant tag {tag:?}"),
+        )
     }
 
     /// The value for the given tag could not be collected.
@@ -171,13 +232,19 @@ pub trait Context {
     where
         T: ?Sized + fmt::Debug,
     {
-        self.message(format_args!("Expected tag: {tag:?}"))
+        self.message_with_kind(
+            ErrorKind::MissingField,
+            format_args!("Expected tag: {tag:?}"),
+        )
     }
 
     /// Trying to decode an uninhabitable type.
     #[inline(always)]
     fn uninhabitable(&self, _: &'static str) -> Self::Error {
-        self.message(format_args!("Cannot decode uninhabitable types"))
+        self.message_with_kind(
+            ErrorKind::Uninhabitable,
+            format_args!("Cannot decode uninhabitable types"),
+        )
     }
 
     /// Encountered an unsupported field tag.
@@ -186,7 +253,10 @@ pub trait Context {
     where
         T: ?Sized + fmt::Debug,
     {
-        self.message(format_args!("Invalid field tag {tag:?}"))
+        self.message_with_kind(
+            ErrorKind::UnknownField,
+            format_args!("Invalid field tag {tag:?}"),
+        )
     }
 
     /// Expected another field to decode.
@@ -196,9 +266,10 @@ pub trait Context {
         T: ?Sized + fmt::Debug,
         C: ?Sized + fmt::Debug,
     {
-        self.message(format_args!(
-            "Expected adjacent field {tag:?} or {content:?}"
-        ))
+        self.message_with_kind(
+            ErrorKind::UnknownField,
+            format_args!("Expected adjacent field {tag:?} or {content:?}"),
+        )
     }
 
     /// Missing adjacent tag when decoding.
@@ -207,14 +278,20 @@ pub trait Context {
     where
         T: ?Sized + fmt::Debug,
     {
-        self.message(format_args!("Missing adjacent tag {tag:?}"))
+        self.message_with_kind(
+            ErrorKind::MissingField,
+            format_args!("Missing adjacent tag {tag:?}"),
+        )
     }
 
     /// Encountered an unsupported field tag.
     #[inline(always)]
     fn invalid_field_string_tag(&self, _: &'static str, field: Self::String<'_>) -> Self::Error {
         let field = field.as_ref();
-        self.message(format_args!("Invalid field tag `{field}`"))
+        self.message_with_kind(
+            ErrorKind::UnknownField,
+            format_args!("Invalid field tag `{field}`"),
+        )
     }
 
     /// Missing variant field required to decode.
@@ -224,14 +301,17 @@ pub trait Context {
     where
         T: ?Sized + fmt::Debug,
     {
-        self.message(format_args!("Missing variant field: {tag:?}"))
+        self.message_with_kind(
+            ErrorKind::MissingField,
+            format_args!("Missing variant field: {tag:?}"),
+        )
     }
 
     /// Indicate that a variant tag could not be determined.
     #[allow(unused_variables)]
     #[inline(always)]
     fn missing_variant_tag(&self, name: &'static str) -> Self::Error {
-        self.message(format_args!("Missing variant tag"))
+        self.message_with_kind(ErrorKind::MissingField, format_args!("Missing variant tag"))
     }
 
     /// Encountered an unsupported variant field.
@@ -247,18 +327,79 @@ pub trait Context {
         V: ?Sized + fmt::Debug,
         T: ?Sized + fmt::Debug,
     {
-        self.message(format_args!(
-            "Invalid variant field tag `{tag:?}` for variant `{variant:?}`",
-        ))
+        self.message_with_kind(
+            ErrorKind::UnknownField,
+            format_args!("Invalid variant field tag `{tag:?}` for variant `{variant:?}`",),
+        )
     }
 
     /// Missing variant field required to decode.
     #[allow(unused_variables)]
     #[inline(always)]
     fn alloc_failed(&self) -> Self::Error {
-        self.message("Failed to allocate")
+        self.message_with_kind(ErrorKind::AllocFailed, "Failed to allocate")
+    }
+
+    /// Check that `n` more bytes of input can be consumed.
+    ///
+    /// This is called by readers before they consume input, so that a context
+    /// can enforce a limit on the total amount of input processed. The number
+    /// of bytes which have actually been consumed is reported through
+    /// [`advance`].
+    ///
+    /// [`advance`]: Context::advance
+    #[allow(unused_variables)]
+    #[inline(always)]
+    fn check_input(&self, n: usize) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Check the number of elements in a sequence or a map before it is
+    /// decoded.
+    ///
+    /// This is called by decoders with the length indicated by the input, so
+    /// that a context can refuse to process collections which are too large.
+    /// Formats which don't record the length of collections instead call this
+    /// with the number of elements seen so far as each element is decoded.
+    #[allow(unused_variables)]
+    #[inline(always)]
+    fn check_length(&self, len: usize) -> Result<(), Self::Error> {
+        Ok(())
     }
 
+    /// Check the number of bytes in a string or a byte sequence before it is
+    /// decoded.
+    ///
+    /// This is called by decoders with the length indicated by the input, so
+    /// that a context can refuse to process strings or bytes which are too
+    /// large. Formats which don't record the length of strings call this once
+    /// the string has been parsed, but before it is handed to the caller.
+    #[allow(unused_variables)]
+    #[inline(always)]
+    fn check_size(&self, len: usize) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Indicate that a decoder is about to descend into a nested value, such
+    /// as a sequence, a map or a variant.
+    ///
+    /// This allows a context to limit how deeply values can be nested, which
+    /// protects against exhausting the stack while decoding untrusted input.
+    ///
+    /// If this succeeds, it will be matched with a corresponding call to
+    /// [`leave_nested`].
+    ///
+    /// [`leave_nested`]: Context::leave_nested
+    #[inline(always)]
+    fn enter_nested(&self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Indicate that a decoder has left the last nested value that was
+    /// entered.
+    #[inline(always)]
+    fn leave_nested(&self) {}
+
     /// Indicate that we've entered a struct with the given `name`.
     ///
     /// The `name` variable corresponds to the identifiers of the struct.
@@ -935,6 +935,35 @@ pub trait Decoder<'de>: Sized {
         )))
     }
 
+    /// Decode the little-endian bytes of a fixed-width value, like an
+    /// integer.
+    ///
+    /// This is the counterpart of [`Encoder::encode_fixed`], by default the
+    /// bytes are decoded using [`Decoder::decode_array`].
+    ///
+    /// [`Encoder::encode_fixed`]: crate::Encoder::encode_fixed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Decode, Decoder};
+    ///
+    /// struct Checksum(u32);
+    ///
+    /// impl<'de, M> Decode<'de, M> for Checksum {
+    ///     fn decode<D>(cx: &D::Cx, decoder: D) -> Result<Self, D::Error>
+    ///     where
+    ///         D: Decoder<'de>,
+    ///     {
+    ///         Ok(Self(u32::from_le_bytes(decoder.decode_fixed()?)))
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    fn decode_fixed<const N: usize>(self) -> Result<[u8; N], <Self::Cx as Context>::Error> {
+        self.decode_array()
+    }
+
     /// Decode a sequence of bytes whos length is encoded in the payload.
     ///
     /// # Examples
@@ -1002,6 +1031,70 @@ pub trait Decoder<'de>: Sized {
         )))
     }
 
+    /// Decode the payload of an extension value with the given format-specific
+    /// type code.
+    ///
+    /// This is the counterpart of [`Encoder::encode_extension`], formats with a
+    /// dedicated representation for extension values must ensure that the
+    /// decoded value has the expected type code. By default the payload is
+    /// decoded using [`Decoder::decode_bytes`].
+    ///
+    /// [`Encoder::encode_extension`]: crate::Encoder::encode_extension
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fmt;
+    ///
+    /// use musli::{Context, Decode, Decoder};
+    /// use musli::de::UnsizedVisitor;
+    ///
+    /// struct Timestamp(u32);
+    ///
+    /// impl<'de, M> Decode<'de, M> for Timestamp {
+    ///     fn decode<D>(cx: &D::Cx, decoder: D) -> Result<Self, D::Error>
+    ///     where
+    ///         D: Decoder<'de>,
+    ///     {
+    ///         struct Visitor;
+    ///
+    ///         impl<C> UnsizedVisitor<'_, C, [u8]> for Visitor
+    ///         where
+    ///             C: ?Sized + Context,
+    ///         {
+    ///             type Ok = Timestamp;
+    ///
+    ///             #[inline]
+    ///             fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///                 write!(f, "a timestamp")
+    ///             }
+    ///
+    ///             #[inline]
+    ///             fn visit_ref(self, cx: &C, bytes: &[u8]) -> Result<Self::Ok, C::Error> {
+    ///                 let Ok(bytes) = <[u8; 4]>::try_from(bytes) else {
+    ///                     return Err(cx.message("Bad timestamp"));
+    ///                 };
+    ///
+    ///                 Ok(Timestamp(u32::from_be_bytes(bytes)))
+    ///             }
+    ///         }
+    ///
+    ///         decoder.decode_extension(-1, Visitor)
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    fn decode_extension<V>(
+        self,
+        code: i64,
+        visitor: V,
+    ) -> Result<V::Ok, <Self::Cx as Context>::Error>
+    where
+        V: UnsizedVisitor<'de, Self::Cx, [u8]>,
+    {
+        self.decode_bytes(visitor)
+    }
+
     /// Decode a string slice from the current decoder.
     ///
     /// # Examples
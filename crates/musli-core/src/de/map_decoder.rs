@@ -21,6 +21,17 @@ pub trait MapDecoder<'de> {
         SizeHint::any()
     }
 
+    /// Count the number of remaining entries without consuming them.
+    ///
+    /// This is used when the exact number of entries is needed up front but
+    /// isn't provided by [`MapDecoder::size_hint`], like when transcoding
+    /// into a format which requires it. This is only supported by formats
+    /// which can scan ahead in their input, by default it returns `None`.
+    #[inline]
+    fn count_remaining(&mut self) -> Result<Option<usize>, <Self::Cx as Context>::Error> {
+        Ok(None)
+    }
+
     /// Decode the next key. This returns `Ok(None)` where there are no more
     /// elements to decode.
     #[must_use = "Decoders must be consumed"]
@@ -28,6 +39,19 @@ pub trait MapDecoder<'de> {
         &mut self,
     ) -> Result<Option<Self::DecodeEntry<'_>>, <Self::Cx as Context>::Error>;
 
+    /// Try to recover after the value of the last entry returned by
+    /// [`MapDecoder::decode_entry`] failed to decode, by skipping over it.
+    ///
+    /// Returns `true` if the decoder recovered, in which case decoding can
+    /// continue with the next entry. This is only supported by some formats
+    /// when the context [is lenient], by default it returns `false`.
+    ///
+    /// [is lenient]: crate::Context::is_lenient
+    #[inline]
+    fn try_recover(&mut self) -> Result<bool, <Self::Cx as Context>::Error> {
+        Ok(false)
+    }
+
     /// Return simplified decoder for remaining entries.
     fn decode_remaining_entries(
         &mut self,
@@ -22,6 +22,18 @@ pub trait SequenceDecoder<'de> {
         SizeHint::any()
     }
 
+    /// Count the number of remaining elements without consuming them.
+    ///
+    /// This is used when the exact number of elements is needed up front but
+    /// isn't provided by [`SequenceDecoder::size_hint`], like when
+    /// transcoding into a format which requires it. This is only supported by
+    /// formats which can scan ahead in their input, by default it returns
+    /// `None`.
+    #[inline]
+    fn count_remaining(&mut self) -> Result<Option<usize>, <Self::Cx as Context>::Error> {
+        Ok(None)
+    }
+
     /// Return decoder to decode the next element.
     ///
     /// This will error or provide garbled data in case the next element is not
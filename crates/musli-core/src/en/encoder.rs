@@ -69,6 +69,18 @@ pub trait Encoder: Sized {
     /// report that something unexpected happened.
     fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
 
+    /// Test if the encoder needs to know the exact size of sequences and maps
+    /// before they are encoded.
+    ///
+    /// Formats which delimit collections instead of prefixing them with their
+    /// length, like JSON, can return `false` here. Transcoding then streams
+    /// collections of unknown length instead of buffering them, so the size in
+    /// [`SequenceHint`] and [`MapHint`] can't be relied on.
+    #[inline]
+    fn is_size_required(&self) -> bool {
+        true
+    }
+
     /// Encode the value `T` into the current encoder.
     ///
     /// This calls the appropriate [`Encode`] implementation for the given type.
@@ -778,6 +790,37 @@ pub trait Encoder: Sized {
         )))
     }
 
+    /// Encode a fixed-width value, like an integer, from its little-endian
+    /// bytes.
+    ///
+    /// Formats which have a dedicated representation for fixed-width values
+    /// use it, like the `fixed32` and `fixed64` types of protobuf. By default
+    /// the bytes are encoded using [`Encoder::encode_array`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Encode, Encoder};
+    ///
+    /// struct Checksum(u32);
+    ///
+    /// impl<M> Encode<M> for Checksum {
+    ///     fn encode<E>(&self, cx: &E::Cx, encoder: E) -> Result<E::Ok, E::Error>
+    ///     where
+    ///         E: Encoder,
+    ///     {
+    ///         encoder.encode_fixed(&self.0.to_le_bytes())
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    fn encode_fixed<const N: usize>(
+        self,
+        bytes: &[u8; N],
+    ) -> Result<Self::Ok, <Self::Cx as Context>::Error> {
+        self.encode_array(bytes)
+    }
+
     /// Encode a sequence of bytes.
     ///
     /// # Examples
@@ -876,6 +919,38 @@ pub trait Encoder: Sized {
         )))
     }
 
+    /// Encode an extension value, which is a payload of bytes identified by a
+    /// format-specific type code.
+    ///
+    /// Formats which have a dedicated representation for the given type code,
+    /// like the extension types of MessagePack, use it. By default the payload
+    /// is encoded using [`Encoder::encode_bytes`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Encode, Encoder};
+    ///
+    /// struct Timestamp(u32);
+    ///
+    /// impl<M> Encode<M> for Timestamp {
+    ///     fn encode<E>(&self, cx: &E::Cx, encoder: E) -> Result<E::Ok, E::Error>
+    ///     where
+    ///         E: Encoder,
+    ///     {
+    ///         encoder.encode_extension(-1, &self.0.to_be_bytes())
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    fn encode_extension(
+        self,
+        code: i64,
+        payload: &[u8],
+    ) -> Result<Self::Ok, <Self::Cx as Context>::Error> {
+        self.encode_bytes(payload)
+    }
+
     /// Encode a string.
     ///
     /// # Examples
@@ -0,0 +1,77 @@
+use core::fmt;
+
+/// The kind of an error raised while encoding or decoding.
+///
+/// Errors are raised through a [`Context`], and contexts which keep track of
+/// errors expose the kind of the errors they've collected. This can be used to
+/// handle errors programmatically without having to inspect their messages.
+///
+/// New kinds might be added in the future, so any errors which are not
+/// classified should be treated like [`ErrorKind::Other`].
+///
+/// [`Context`]: crate::Context
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// An error which doesn't belong to any other kind, such as custom errors
+    /// raised by [`Encode`] or [`Decode`] implementations.
+    ///
+    /// [`Encode`]: crate::Encode
+    /// [`Decode`]: crate::Decode
+    Other,
+    /// The input ended before a value could be completely decoded.
+    UnexpectedEof,
+    /// The input contained a type tag or a token which isn't valid at the
+    /// position it was encountered in.
+    InvalidTag,
+    /// A string was not valid UTF-8.
+    InvalidUtf8,
+    /// A number did not fit in the type it was being decoded into.
+    NumberOverflow,
+    /// A variant tag did not match any variant of an enum.
+    UnknownVariant,
+    /// A field tag did not match any field of a struct or variant.
+    UnknownField,
+    /// A field or a tag which is required was missing.
+    MissingField,
+    /// A configured limit on the resources used while decoding was exceeded.
+    LimitExceeded,
+    /// An allocation failed.
+    AllocFailed,
+    /// A value of a type which can't be constructed was encountered.
+    Uninhabitable,
+}
+
+impl ErrorKind {
+    /// Get a stable string representation of the error kind.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::context::ErrorKind;
+    ///
+    /// assert_eq!(ErrorKind::UnexpectedEof.as_str(), "unexpected-eof");
+    /// ```
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            ErrorKind::Other => "other",
+            ErrorKind::UnexpectedEof => "unexpected-eof",
+            ErrorKind::InvalidTag => "invalid-tag",
+            ErrorKind::InvalidUtf8 => "invalid-utf8",
+            ErrorKind::NumberOverflow => "number-overflow",
+            ErrorKind::UnknownVariant => "unknown-variant",
+            ErrorKind::UnknownField => "unknown-field",
+            ErrorKind::MissingField => "missing-field",
+            ErrorKind::LimitExceeded => "limit-exceeded",
+            ErrorKind::AllocFailed => "alloc-failed",
+            ErrorKind::Uninhabitable => "uninhabitable",
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
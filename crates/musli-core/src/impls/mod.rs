@@ -161,15 +161,17 @@ where
     T: Encode<M>,
 {
     #[inline]
-    fn encode<E>(&self, _: &E::Cx, encoder: E) -> Result<E::Ok, E::Error>
+    fn encode<E>(&self, cx: &E::Cx, encoder: E) -> Result<E::Ok, E::Error>
     where
         E: Encoder<Mode = M>,
     {
         let hint = SequenceHint::with_size(N);
 
         encoder.encode_sequence_fn(&hint, |seq| {
-            for value in self.iter() {
+            for (index, value) in self.iter().enumerate() {
+                cx.enter_sequence_index(index);
                 seq.push(value)?;
+                cx.leave_sequence_index();
             }
 
             Ok(())
@@ -215,13 +217,15 @@ where
     T: Encode<M>,
 {
     #[inline]
-    fn encode_packed<E>(&self, _: &E::Cx, encoder: E) -> Result<E::Ok, E::Error>
+    fn encode_packed<E>(&self, cx: &E::Cx, encoder: E) -> Result<E::Ok, E::Error>
     where
         E: Encoder<Mode = M>,
     {
         encoder.encode_pack_fn(|seq| {
-            for value in self.iter() {
+            for (index, value) in self.iter().enumerate() {
+                cx.enter_sequence_index(index);
                 seq.push(value)?;
+                cx.leave_sequence_index();
             }
 
             Ok(())
@@ -417,7 +421,7 @@ where
                 cx.enter_sequence_index(index);
                 seq.encode_next()?.encode(value)?;
                 cx.leave_sequence_index();
-                index = index.wrapping_add(index);
+                index = index.wrapping_add(1);
             }
 
             Ok(())
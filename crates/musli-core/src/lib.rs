@@ -22,6 +22,10 @@ mod context;
 #[doc(inline)]
 pub use self::context::Context;
 
+mod error_kind;
+#[doc(inline)]
+pub use self::error_kind::ErrorKind;
+
 pub mod de;
 #[doc(inline)]
 pub use self::de::{Decode, Decoder};
@@ -803,6 +803,7 @@ fn decode_tagged(
     let type_decoder_var = b.cx.ident("type_decoder");
     let value_var = b.cx.ident("value");
     let binding_var = b.cx.ident("value");
+    let error_var = b.cx.ident("error");
 
     let type_name = &st.name;
 
@@ -853,8 +854,25 @@ fn decode_tagged(
                     }
                 });
 
-                let decode = quote! {
-                    #var = #option_some(#decode_path(#ctx_var, #struct_decoder_var)?);
+                // Fields with a default value can be recovered from if the
+                // context is lenient, in which case the error is collected by
+                // the context and the default value is used.
+                let decode = match &f.default_attr {
+                    Some(..) => quote! {
+                        match #decode_path(#ctx_var, #struct_decoder_var) {
+                            #result_ok(#value_var) => {
+                                #var = #option_some(#value_var);
+                            }
+                            #result_err(#error_var) => {
+                                if !#map_decoder_t::try_recover(#type_decoder_var)? {
+                                    return #result_err(#error_var);
+                                }
+                            }
+                        }
+                    },
+                    None => quote! {
+                        #var = #option_some(#decode_path(#ctx_var, #struct_decoder_var)?);
+                    },
                 };
 
                 fields_with.push((f, decode, (enter, leave)));
@@ -1041,7 +1059,11 @@ fn decode_tagged(
         static #struct_hint_static: #map_hint = #map_hint::with_size(#fields_len);
 
         #decoder_t::decode_map_hint(#decoder_var, &#struct_hint_static, move |#type_decoder_var| {
-            while let #option_some(mut #struct_decoder_var) = #map_decoder_t::decode_entry(#type_decoder_var)? {
+            loop {
+                let #option_some(mut #struct_decoder_var) = #map_decoder_t::decode_entry(#type_decoder_var)? else {
+                    break;
+                };
+
                 let #name_var: #name_type = {
                     let #struct_decoder_var = #struct_field_decoder_t::decode_key(&mut #struct_decoder_var)?;
                     #decode_tag
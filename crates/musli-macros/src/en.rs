@@ -309,11 +309,22 @@ fn encode_enum(cx: &Ctxt<'_>, b: &Build<'_>, en: &Enum<'_>) -> Result<TokenStrea
     }
 
     // Special case: uninhabitable types.
-    Ok(if variants.is_empty() {
-        quote!(#result_err(#context_t::uninhabitable(#ctx_var, #type_name)))
-    } else {
-        quote!(#result_ok(match self { #(#variants),* }))
-    })
+    if variants.is_empty() {
+        return Ok(quote!(#result_err(#context_t::uninhabitable(#ctx_var, #type_name))));
+    }
+
+    if !cx.trace {
+        return Ok(quote!(#result_ok(match self { #(#variants),* })));
+    }
+
+    let output_var = b.cx.ident("output");
+
+    Ok(quote! {{
+        #context_t::enter_enum(#ctx_var, #type_name);
+        let #output_var = match self { #(#variants),* };
+        #context_t::leave_enum(#ctx_var);
+        #result_ok(#output_var)
+    }})
 }
 
 /// Setup encoding for a single variant. that is externally tagged.
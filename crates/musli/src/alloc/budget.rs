@@ -0,0 +1,191 @@
+use core::cell::Cell;
+use core::mem::size_of;
+
+use super::{Allocator, RawVec};
+
+/// An allocator adapter which limits the number of bytes which can be
+/// allocated through a wrapped allocator.
+///
+/// Every buffer allocated through this allocator is charged against a shared
+/// byte budget as it grows, and the charge is returned to the budget once the
+/// buffer is dropped. Once an allocation would exceed the budget it fails,
+/// which decoders surface through [`Context::alloc_failed`].
+///
+/// Only the number of bytes requested is counted, so any bookkeeping or
+/// over-allocation performed by the wrapped allocator is not included.
+///
+/// [`Context::alloc_failed`]: crate::Context::alloc_failed
+///
+/// ## Examples
+///
+/// ```
+/// use musli::alloc::{ArrayBuffer, Budget, Slice, Vec};
+///
+/// let mut buf = ArrayBuffer::new();
+/// let alloc = Budget::new(Slice::new(&mut buf), 8);
+///
+/// let mut a = Vec::new_in(&alloc);
+/// assert!(a.write(b"Hello"));
+/// assert_eq!(alloc.used(), 5);
+///
+/// let mut b = Vec::new_in(&alloc);
+/// assert!(!b.write(b"World"));
+/// assert!(b.write(b"Wor"));
+/// assert_eq!(alloc.remaining(), 0);
+///
+/// drop(a);
+/// assert_eq!(alloc.remaining(), 5);
+/// assert!(b.write(b"ld"));
+/// assert_eq!(b.as_slice(), b"World");
+/// ```
+///
+/// Decoding with a budget, where the scratch buffer needed to unescape a
+/// string doesn't fit. Note that the diagnostics collected by the context are
+/// allocated from the same budget, so they can only be recorded if there is
+/// enough of it left:
+///
+/// ```
+/// use musli::alloc::{Budget, System};
+/// use musli::context;
+///
+/// let input = format!(r#"["Hello\n{}"]"#, "x".repeat(1024));
+///
+/// let alloc = Budget::new(System::new(), 512);
+/// let cx = context::with_alloc(&alloc);
+///
+/// let result = musli::json::DEFAULT.from_slice_with::<_, Vec<String>>(&cx, input.as_bytes());
+/// assert!(result.is_err());
+/// assert_eq!(cx.report().to_string(), "[0]: Failed to allocate (at byte 1033)\n");
+/// ```
+pub struct Budget<A> {
+    alloc: A,
+    budget: usize,
+    used: Cell<usize>,
+}
+
+impl<A> Budget<A> {
+    /// Construct a new budget allocator which allows at most `budget` bytes to
+    /// be allocated through `alloc` at any one time.
+    #[inline]
+    pub const fn new(alloc: A, budget: usize) -> Self {
+        Self {
+            alloc,
+            budget,
+            used: Cell::new(0),
+        }
+    }
+
+    /// Get the total number of bytes in the budget.
+    #[inline]
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+
+    /// Get the number of bytes which are currently allocated.
+    #[inline]
+    pub fn used(&self) -> usize {
+        self.used.get()
+    }
+
+    /// Get the number of bytes which can still be allocated.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.budget.saturating_sub(self.used.get())
+    }
+}
+
+impl<A> Allocator for Budget<A>
+where
+    A: Allocator,
+{
+    type RawVec<'this, T> = BudgetBuf<'this, T, A> where Self: 'this, T: 'this;
+
+    #[inline]
+    fn new_raw_vec<'a, T>(&'a self) -> Self::RawVec<'a, T>
+    where
+        T: 'a,
+    {
+        BudgetBuf {
+            buf: self.alloc.new_raw_vec::<T>(),
+            budget: self.budget,
+            used: &self.used,
+            charged: 0,
+        }
+    }
+}
+
+/// A buffer allocated through a [`Budget`] allocator.
+pub struct BudgetBuf<'a, T, A>
+where
+    A: 'a + Allocator,
+    T: 'a,
+{
+    buf: A::RawVec<'a, T>,
+    budget: usize,
+    used: &'a Cell<usize>,
+    /// The number of bytes charged to the budget by this buffer.
+    charged: usize,
+}
+
+impl<'a, T, A> RawVec<T> for BudgetBuf<'a, T, A>
+where
+    A: 'a + Allocator,
+    T: 'a,
+{
+    #[inline]
+    fn resize(&mut self, len: usize, additional: usize) -> bool {
+        let Some(required) = len
+            .checked_add(additional)
+            .and_then(|n| n.checked_mul(size_of::<T>()))
+        else {
+            return false;
+        };
+
+        let Some(extra) = required.checked_sub(self.charged) else {
+            return self.buf.resize(len, additional);
+        };
+
+        let used = self.used.get();
+
+        if extra > self.budget.saturating_sub(used) {
+            return false;
+        }
+
+        if !self.buf.resize(len, additional) {
+            return false;
+        }
+
+        self.used.set(used + extra);
+        self.charged = required;
+        true
+    }
+
+    #[inline]
+    fn as_ptr(&self) -> *const T {
+        self.buf.as_ptr()
+    }
+
+    #[inline]
+    fn as_mut_ptr(&mut self) -> *mut T {
+        self.buf.as_mut_ptr()
+    }
+
+    #[inline]
+    fn try_merge<B>(&mut self, _: usize, other: B, _: usize) -> Result<(), B>
+    where
+        B: RawVec<T>,
+    {
+        Err(other)
+    }
+}
+
+impl<'a, T, A> Drop for BudgetBuf<'a, T, A>
+where
+    A: 'a + Allocator,
+    T: 'a,
+{
+    #[inline]
+    fn drop(&mut self) {
+        self.used.set(self.used.get().saturating_sub(self.charged));
+    }
+}
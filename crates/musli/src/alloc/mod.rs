@@ -6,6 +6,9 @@
 //! * The [`Slice`] allocator, which can allocate buffers from a fixed-size
 //!   slice.
 //!
+//! Any allocator can be wrapped in a [`Budget`] to put a cap on the number of
+//! bytes which can be allocated through it.
+//!
 //! The following types are also provided for convenience:
 //! * [`Vec`] which can be used as a vector of allocations.
 //! * [`String`] which can be used as a safe string container.
@@ -99,6 +102,10 @@ mod stack;
 #[doc(inline)]
 pub use self::stack::Slice;
 
+mod budget;
+#[doc(inline)]
+pub use self::budget::{Budget, BudgetBuf};
+
 mod array_buffer;
 pub use self::array_buffer::ArrayBuffer;
 
@@ -2,12 +2,17 @@ use core::fmt;
 use core::ops::Deref;
 use core::str;
 
+use crate::de::{DecodeIn, Decoder, UnsizedVisitor};
+use crate::en::{Encode, Encoder};
 use crate::fixed::CapacityError;
 use crate::Context;
 
 use super::{Allocator, Vec};
 
 /// Wrapper around a buffer that is guaranteed to be a valid utf-8 string.
+///
+/// This is encoded in the same way as a [`str`], and is decoded through
+/// [`DecodeIn`] since it borrows the allocator it was allocated from.
 pub struct String<'a, A>
 where
     A: 'a + ?Sized + Allocator,
@@ -26,7 +31,7 @@ where
     let mut string = String::new_in(cx.alloc());
 
     if write!(string, "{value}").is_err() {
-        return Err(cx.message("Failed to write to string"));
+        return Err(cx.alloc_failed());
     }
 
     Ok(string)
@@ -115,6 +120,60 @@ where
     }
 }
 
+impl<'a, A, M> Encode<M> for String<'a, A>
+where
+    A: 'a + ?Sized + Allocator,
+{
+    #[inline]
+    fn encode<E>(&self, cx: &E::Cx, encoder: E) -> Result<E::Ok, E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        self.as_str().encode(cx, encoder)
+    }
+}
+
+impl<'de, 'a, A, M> DecodeIn<'de, 'a, M, A> for String<'a, A>
+where
+    A: 'a + ?Sized + Allocator,
+{
+    #[inline]
+    fn decode_in<D>(_: &D::Cx, decoder: D, alloc: &'a A) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = M>,
+    {
+        struct Visitor<'a, A>(&'a A)
+        where
+            A: ?Sized;
+
+        impl<'de, 'a, C, A> UnsizedVisitor<'de, C, str> for Visitor<'a, A>
+        where
+            C: ?Sized + Context,
+            A: 'a + ?Sized + Allocator,
+        {
+            type Ok = String<'a, A>;
+
+            #[inline]
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "string")
+            }
+
+            #[inline]
+            fn visit_ref(self, cx: &C, string: &str) -> Result<Self::Ok, C::Error> {
+                let mut out = String::new_in(self.0);
+
+                if out.try_push_str(string).is_err() {
+                    return Err(cx.alloc_failed());
+                }
+
+                Ok(out)
+            }
+        }
+
+        decoder.decode_string(Visitor(alloc))
+    }
+}
+
 impl<'a, A> AsRef<str> for String<'a, A>
 where
     A: ?Sized + Allocator,
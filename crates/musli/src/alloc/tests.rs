@@ -114,3 +114,85 @@ fn stack_zst() {
     let alloc = super::Slice::new(&mut buf);
     zst_allocations(&alloc);
 }
+
+#[test]
+fn budget_basic() {
+    let alloc = super::Budget::new(super::System::new(), 4096 * 4);
+    basic_allocations(&alloc);
+    grow_allocations(&alloc);
+    zst_allocations(&alloc);
+    assert_eq!(alloc.used(), 0);
+}
+
+#[test]
+fn budget_exhausted() {
+    let mut buf = super::ArrayBuffer::<4096>::new();
+    let alloc = super::Budget::new(super::Slice::new(&mut buf), 16);
+
+    let mut a = Vec::new_in(&alloc);
+    let mut b = Vec::<u32, _>::new_in(&alloc);
+
+    assert!(a.write(b"abcdefgh"));
+    assert!(b.push(1));
+    assert!(!b.write(&[2, 3]));
+    assert_eq!(alloc.used(), 12);
+    assert!(b.push(2));
+    assert!(!a.push(b'i'));
+
+    drop(b);
+    assert_eq!(alloc.used(), 8);
+    assert!(a.write(b"ijklmnop"));
+    assert_eq!(a.as_slice(), b"abcdefghijklmnop");
+    assert_eq!(alloc.remaining(), 0);
+
+    drop(a);
+    assert_eq!(alloc.used(), 0);
+}
+
+#[test]
+#[cfg(all(feature = "storage", feature = "json"))]
+fn collections() {
+    let mut buf = super::ArrayBuffer::<4096>::with_size();
+    let alloc = super::Slice::new(&mut buf);
+
+    let mut values = Vec::new_in(&alloc);
+    assert!(values.write(&[1u32, 2, 3]));
+
+    let mut string = super::String::new_in(&alloc);
+    core::fmt::Write::write_str(&mut string, "Hello World").unwrap();
+
+    let bytes = crate::storage::to_fixed_bytes::<128, _>(&values).unwrap();
+    let expected = crate::storage::to_fixed_bytes::<128, _>(&[1u32, 2, 3][..]).unwrap();
+    assert_eq!(bytes.as_slice(), expected.as_slice());
+
+    let decoded: Vec<'_, u32, _> = crate::storage::DEFAULT
+        .from_slice_in(&alloc, bytes.as_slice())
+        .unwrap();
+    assert_eq!(decoded.as_slice(), [1, 2, 3]);
+
+    let json = crate::json::to_fixed_bytes::<128, _>(&string).unwrap();
+    assert_eq!(json.as_slice(), b"\"Hello World\"");
+
+    let decoded: super::String<'_, _> = crate::json::DEFAULT
+        .from_slice_in(&alloc, json.as_slice())
+        .unwrap();
+    assert_eq!(&*decoded, "Hello World");
+
+    let strings: Vec<'_, super::String<'_, _>, _> = crate::json::DEFAULT
+        .from_slice_in(&alloc, br#"["a\nb", "c"]"#)
+        .unwrap();
+    assert_eq!(&*strings.as_slice()[0], "a\nb");
+}
+
+#[test]
+#[cfg(feature = "storage")]
+fn collections_exhausted() {
+    let mut buf = super::ArrayBuffer::<16>::with_size();
+    let alloc = super::Slice::new(&mut buf);
+
+    let bytes = crate::storage::to_fixed_bytes::<128, _>(&[1u32; 16][..]).unwrap();
+
+    let result =
+        crate::storage::DEFAULT.from_slice_in::<_, Vec<'_, u32, _>>(&alloc, bytes.as_slice());
+    assert!(result.is_err());
+}
@@ -5,9 +5,20 @@ use core::ops::{Deref, DerefMut};
 use core::ptr;
 use core::slice;
 
+use crate::de::{DecodeIn, Decoder, SequenceDecoder};
+use crate::en::{Encode, Encoder};
+use crate::Context;
+
 use super::{Allocator, RawVec};
 
 /// A vector backed by an [`Allocator`].
+///
+/// This is encoded in the same way as a slice, so it can be used to encode and
+/// decode collections without the `alloc` feature. Since it borrows the
+/// allocator it was allocated from, it is decoded through [`DecodeIn`] rather
+/// than [`Decode`].
+///
+/// [`Decode`]: crate::Decode
 pub struct Vec<'a, T, A>
 where
     A: 'a + ?Sized + Allocator,
@@ -211,6 +222,13 @@ where
         unsafe { slice::from_raw_parts(self.buf.as_ptr(), self.len) }
     }
 
+    /// Get the initialized part of the buffer as a mutable slice.
+    #[cfg(feature = "urlencoded")]
+    pub(crate) fn as_mut_slice(&mut self) -> &mut [T] {
+        // SAFETY: We know that the buffer is initialized up to `self.len`.
+        unsafe { slice::from_raw_parts_mut(self.buf.as_mut_ptr(), self.len) }
+    }
+
     #[inline]
     fn into_raw_parts(self) -> (A::RawVec<'a, T>, usize) {
         let this = ManuallyDrop::new(self);
@@ -357,6 +375,51 @@ where
     }
 }
 
+impl<'a, T, A, M> Encode<M> for Vec<'a, T, A>
+where
+    A: 'a + ?Sized + Allocator,
+    T: 'a + Encode<M>,
+{
+    #[inline]
+    fn encode<E>(&self, cx: &E::Cx, encoder: E) -> Result<E::Ok, E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        self.as_slice().encode(cx, encoder)
+    }
+}
+
+impl<'de, 'a, T, A, M> DecodeIn<'de, 'a, M, A> for Vec<'a, T, A>
+where
+    A: 'a + ?Sized + Allocator,
+    T: 'a + DecodeIn<'de, 'a, M, A>,
+{
+    #[inline]
+    fn decode_in<D>(cx: &D::Cx, decoder: D, alloc: &'a A) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = M>,
+    {
+        decoder.decode_sequence(|seq| {
+            let mut out = Vec::new_in(alloc);
+            let mut index = 0;
+
+            while let Some(value) = seq.try_decode_next()? {
+                cx.enter_sequence_index(index);
+                let value = T::decode_in(cx, value, alloc)?;
+
+                if !out.push(value) {
+                    return Err(cx.alloc_failed());
+                }
+
+                cx.leave_sequence_index();
+                index = index.wrapping_add(1);
+            }
+
+            Ok(out)
+        })
+    }
+}
+
 impl<'a, T, A> Drop for Vec<'a, T, A>
 where
     A: 'a + ?Sized + Allocator,
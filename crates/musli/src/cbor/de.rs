@@ -0,0 +1,1177 @@
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use rust_alloc::vec::Vec;
+
+use crate::alloc::Vec as BufVec;
+use crate::de::{
+    Decode, DecodeUnsized, Decoder, EntriesDecoder, EntryDecoder, MapDecoder, SequenceDecoder,
+    SizeHint, Skip, UnsizedVisitor, VariantDecoder, Visitor,
+};
+#[cfg(feature = "value")]
+use crate::options;
+use crate::reader::Limit;
+use crate::storage::de::StorageDecoder;
+use crate::Context;
+use crate::{Options, Reader};
+
+use super::header::{
+    self, Header, ARRAY, BREAK, BYTES, F16, F32, F64, FALSE, MAP, NEGATIVE, NEGATIVE_BIGNUM, NULL,
+    POSITIVE_BIGNUM, SIMPLE, STRING, TAG, TRUE, UNDEFINED, UNSIGNED,
+};
+
+#[cfg(feature = "value")]
+const BUFFER_OPTIONS: Options = options::new().build();
+
+/// A decoded integer, before it has been converted into its final type.
+#[derive(Clone, Copy)]
+enum Integer {
+    /// An unsigned integer.
+    Unsigned(u128),
+    /// A negative integer with the value `-1 - n`.
+    Negative(u128),
+}
+
+/// A CBOR decoder.
+pub struct CborDecoder<'a, R, const OPT: Options, C: ?Sized> {
+    cx: &'a C,
+    reader: R,
+}
+
+impl<'a, R, const OPT: Options, C: ?Sized> CborDecoder<'a, R, OPT, C> {
+    /// Construct a new CBOR decoder.
+    #[inline]
+    pub(crate) fn new(cx: &'a C, reader: R) -> Self {
+        Self { cx, reader }
+    }
+}
+
+impl<'a, 'de, R, const OPT: Options, C> CborDecoder<'a, Limit<R>, OPT, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    #[inline]
+    fn end(mut self) -> Result<(), C::Error> {
+        if self.reader.remaining() > 0 {
+            self.reader.skip(self.cx, self.reader.remaining())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, 'de, R, const OPT: Options, C> CborDecoder<'a, R, OPT, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    #[inline]
+    fn read_header(&mut self) -> Result<Header, C::Error> {
+        Ok(Header::from_byte(self.reader.read_byte(self.cx)?))
+    }
+
+    /// Skip over a single data item, including any items nested in it.
+    ///
+    /// This doesn't recurse. Items in containers with a definite length are
+    /// added to a counter of remaining items, while the state of the enclosing
+    /// container is stored on a stack when an indefinite length container is
+    /// entered, which counts towards the nesting depth of the context.
+    fn skip_item(&mut self) -> Result<(), C::Error> {
+        let mut stack = BufVec::new_in(self.cx.alloc());
+        let result = self.skip_items(&mut stack);
+
+        for _ in 0..stack.len() {
+            self.cx.leave_nested();
+        }
+
+        result
+    }
+
+    fn skip_items(
+        &mut self,
+        stack: &mut BufVec<'_, (usize, usize), C::Allocator>,
+    ) -> Result<(), C::Error> {
+        // The number of items left to skip, and the number of items in each
+        // entry of the current indefinite length container, or zero if there
+        // is none.
+        let mut remaining = 1usize;
+        let mut indefinite = 0usize;
+
+        loop {
+            if remaining == 0 {
+                if indefinite == 0 {
+                    return Ok(());
+                }
+
+                if self.try_break()? {
+                    let Some((outer, outer_indefinite)) = stack.pop() else {
+                        return Ok(());
+                    };
+
+                    self.cx.leave_nested();
+                    remaining = outer;
+                    indefinite = outer_indefinite;
+                    continue;
+                }
+
+                remaining = indefinite;
+            }
+
+            remaining -= 1;
+
+            let pos = self.cx.mark();
+            let header = self.read_header()?;
+
+            let items = match header.major() {
+                UNSIGNED | NEGATIVE => {
+                    header::decode_argument(self.cx, self.reader.borrow_mut(), header)?;
+                    0
+                }
+                BYTES | STRING => match self.decode_len(header)? {
+                    Some(len) => {
+                        self.reader.skip(self.cx, len)?;
+                        0
+                    }
+                    None => {
+                        self.enter_indefinite(stack, &mut remaining, &mut indefinite, 1)?;
+                        0
+                    }
+                },
+                ARRAY | MAP => {
+                    let entry = if header.major() == MAP { 2 } else { 1 };
+
+                    match self.decode_len(header)? {
+                        Some(len) => {
+                            self.cx.check_length(len)?;
+
+                            let Some(items) = len.checked_mul(entry) else {
+                                return Err(self.cx.marked_message(pos, "Too many items to skip"));
+                            };
+
+                            items
+                        }
+                        None => {
+                            self.enter_indefinite(stack, &mut remaining, &mut indefinite, entry)?;
+                            0
+                        }
+                    }
+                }
+                TAG => {
+                    header::decode_argument(self.cx, self.reader.borrow_mut(), header)?;
+                    1
+                }
+                _ => match header.info() {
+                    0..=23 => 0,
+                    24..=27 => {
+                        header::decode_argument(self.cx, self.reader.borrow_mut(), header)?;
+                        0
+                    }
+                    _ => {
+                        return Err(self
+                            .cx
+                            .marked_message(pos, format_args!("Cannot skip over {header:?}")));
+                    }
+                },
+            };
+
+            let Some(next) = remaining.checked_add(items) else {
+                return Err(self.cx.marked_message(pos, "Too many items to skip"));
+            };
+
+            remaining = next;
+        }
+    }
+
+    /// Store the state of the current container and enter an indefinite
+    /// length container whose entries consist of `entry` items.
+    fn enter_indefinite(
+        &mut self,
+        stack: &mut BufVec<'_, (usize, usize), C::Allocator>,
+        remaining: &mut usize,
+        indefinite: &mut usize,
+        entry: usize,
+    ) -> Result<(), C::Error> {
+        self.cx.enter_nested()?;
+
+        if !stack.push((*remaining, *indefinite)) {
+            self.cx.leave_nested();
+            return Err(self.cx.alloc_failed());
+        }
+
+        *remaining = 0;
+        *indefinite = entry;
+        Ok(())
+    }
+
+    /// Consume the break stop code if it's next in the input.
+    #[inline]
+    fn try_break(&mut self) -> Result<bool, C::Error> {
+        if self.reader.peek() != Some(BREAK) {
+            return Ok(false);
+        }
+
+        self.reader.skip(self.cx, 1)?;
+        Ok(true)
+    }
+
+    /// Decode the length argument of a header, where `None` indicates an
+    /// indefinite length.
+    #[inline]
+    fn decode_len(&mut self, header: Header) -> Result<Option<usize>, C::Error> {
+        if header.is_indefinite() {
+            return Ok(None);
+        }
+
+        let len = header::decode_argument(self.cx, self.reader.borrow_mut(), header)?;
+
+        match usize::try_from(len) {
+            Ok(len) => Ok(Some(len)),
+            Err(..) => Err(self.cx.message(format_args!("Length {len} out of bounds"))),
+        }
+    }
+
+    /// Decode the length of an item with the given major type.
+    #[inline]
+    fn decode_prefix(&mut self, major: u8, mark: C::Mark) -> Result<Option<usize>, C::Error> {
+        let header = self.read_header()?;
+
+        if header.major() != major {
+            return Err(self.cx.marked_message(
+                mark,
+                Expected {
+                    expected: major,
+                    actual: header,
+                },
+            ));
+        }
+
+        self.decode_len(header)
+    }
+
+    /// Decode the length of an item which must have a definite length.
+    #[inline]
+    fn decode_definite_prefix(&mut self, major: u8, mark: C::Mark) -> Result<usize, C::Error> {
+        match self.decode_prefix(major, mark)? {
+            Some(len) => Ok(len),
+            None => Err(self
+                .cx
+                .marked_message(mark, "Expected definite length, but got indefinite")),
+        }
+    }
+
+    // Standard function for decoding a pair sequence.
+    #[inline]
+    fn shared_decode_map(mut self) -> Result<RemainingCborDecoder<'a, R, OPT, C>, C::Error> {
+        let pos = self.cx.mark();
+        let len = self.decode_prefix(MAP, pos)?;
+        Ok(RemainingCborDecoder::new(self.cx, self.reader, len))
+    }
+
+    // Standard function for decoding a sequence.
+    #[inline]
+    fn shared_decode_sequence(mut self) -> Result<RemainingCborDecoder<'a, R, OPT, C>, C::Error> {
+        let pos = self.cx.mark();
+        let len = self.decode_prefix(ARRAY, pos)?;
+        Ok(RemainingCborDecoder::new(self.cx, self.reader, len))
+    }
+
+    /// Decode a byte or text string, concatenating chunks if it has an
+    /// indefinite length.
+    fn decode_chunked<V>(mut self, major: u8, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: UnsizedVisitor<'de, C, [u8]>,
+    {
+        let pos = self.cx.mark();
+
+        if let Some(len) = self.decode_prefix(major, pos)? {
+            return self.reader.read_bytes(self.cx, len, visitor);
+        }
+
+        let mut buffer = BufVec::new_in(self.cx.alloc());
+        let mut chunk = [0u8; 256];
+
+        while !self.try_break()? {
+            let pos = self.cx.mark();
+            let header = self.read_header()?;
+
+            if header.major() != major || header.is_indefinite() {
+                return Err(self.cx.marked_message(
+                    pos,
+                    format_args!("Bad chunk in indefinite length string, got {header:?}"),
+                ));
+            }
+
+            let Some(mut remaining) = self.decode_len(header)? else {
+                return Err(self
+                    .cx
+                    .marked_message(pos, "Expected definite length chunk"));
+            };
+
+            while remaining > 0 {
+                let n = remaining.min(chunk.len());
+                self.reader.read(self.cx, &mut chunk[..n])?;
+
+                if !buffer.write(&chunk[..n]) {
+                    return Err(self.cx.alloc_failed());
+                }
+
+                remaining -= n;
+            }
+        }
+
+        visitor.visit_ref(self.cx, buffer.as_slice())
+    }
+
+    /// Decode an integer, including tagged bignums.
+    #[inline]
+    fn decode_integer(&mut self) -> Result<Integer, C::Error> {
+        let pos = self.cx.mark();
+        let header = self.read_header()?;
+
+        match header.major() {
+            UNSIGNED => {
+                let value = header::decode_argument(self.cx, self.reader.borrow_mut(), header)?;
+                Ok(Integer::Unsigned(value.into()))
+            }
+            NEGATIVE => {
+                let value = header::decode_argument(self.cx, self.reader.borrow_mut(), header)?;
+                Ok(Integer::Negative(value.into()))
+            }
+            TAG => {
+                let tag = header::decode_argument(self.cx, self.reader.borrow_mut(), header)?;
+                self.decode_bignum(tag, pos)
+            }
+            _ => Err(self
+                .cx
+                .marked_message(pos, format_args!("Expected integer, but got {header:?}"))),
+        }
+    }
+
+    /// Decode the byte string of a bignum with the given tag.
+    fn decode_bignum(&mut self, tag: u64, pos: C::Mark) -> Result<Integer, C::Error> {
+        let integer: fn(u128) -> Integer = match tag {
+            POSITIVE_BIGNUM => Integer::Unsigned,
+            NEGATIVE_BIGNUM => Integer::Negative,
+            tag => {
+                return Err(self
+                    .cx
+                    .marked_message(pos, format_args!("Expected bignum, but got tag {tag}")));
+            }
+        };
+
+        let mut len = self.decode_definite_prefix(BYTES, pos)?;
+
+        // Leading zeros are permitted, as long as the value fits.
+        while len > 16 {
+            if self.reader.read_byte(self.cx)? != 0 {
+                return Err(self.cx.marked_message(pos, "Bignum out of range"));
+            }
+
+            len -= 1;
+        }
+
+        let mut bytes = [0u8; 16];
+        self.reader.read(self.cx, &mut bytes[16 - len..])?;
+        Ok(integer(u128::from_be_bytes(bytes)))
+    }
+
+    #[inline]
+    fn decode_unsigned<T>(mut self) -> Result<T, C::Error>
+    where
+        T: TryFrom<u128>,
+    {
+        let pos = self.cx.mark();
+
+        let value = match self.decode_integer()? {
+            Integer::Unsigned(value) => T::try_from(value).ok(),
+            Integer::Negative(..) => None,
+        };
+
+        let Some(value) = value else {
+            return Err(self.cx.marked_message(pos, "Integer out of range"));
+        };
+
+        Ok(value)
+    }
+
+    #[inline]
+    fn decode_signed<T>(mut self) -> Result<T, C::Error>
+    where
+        T: TryFrom<i128>,
+    {
+        let pos = self.cx.mark();
+
+        let value = match self.decode_integer()? {
+            Integer::Unsigned(value) => i128::try_from(value).ok(),
+            Integer::Negative(value) => i128::try_from(value).ok().map(|value| !value),
+        };
+
+        let Some(value) = value.and_then(|value| T::try_from(value).ok()) else {
+            return Err(self.cx.marked_message(pos, "Integer out of range"));
+        };
+
+        Ok(value)
+    }
+
+    /// Visit an integer in the smallest 64-bit or 128-bit representation that
+    /// fits it.
+    #[inline]
+    fn visit_integer<V>(&self, integer: Integer, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: Visitor<'de, C>,
+    {
+        match integer {
+            Integer::Unsigned(value) => match u64::try_from(value) {
+                Ok(value) => visitor.visit_u64(self.cx, value),
+                Err(..) => visitor.visit_u128(self.cx, value),
+            },
+            Integer::Negative(value) => {
+                if let Ok(value) = i64::try_from(value) {
+                    visitor.visit_i64(self.cx, !value)
+                } else if let Ok(value) = i128::try_from(value) {
+                    visitor.visit_i128(self.cx, !value)
+                } else {
+                    Err(self.cx.message("Negative integer out of range"))
+                }
+            }
+        }
+    }
+}
+
+/// A length-prefixed decode wrapper, where a length of `None` indicates that
+/// the items are terminated by a break stop code.
+#[doc(hidden)]
+pub struct RemainingCborDecoder<'a, R, const OPT: Options, C: ?Sized> {
+    cx: &'a C,
+    reader: R,
+    remaining: Option<usize>,
+}
+
+impl<'a, 'de, R, const OPT: Options, C> RemainingCborDecoder<'a, R, OPT, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    #[inline]
+    fn new(cx: &'a C, reader: R, remaining: Option<usize>) -> Self {
+        Self {
+            cx,
+            reader,
+            remaining,
+        }
+    }
+
+    /// Test if there is another item, consuming the break stop code of
+    /// indefinite length items once it's reached.
+    #[inline]
+    fn has_next(&mut self) -> Result<bool, C::Error> {
+        match &mut self.remaining {
+            Some(0) => Ok(false),
+            Some(remaining) => {
+                *remaining -= 1;
+                Ok(true)
+            }
+            None => {
+                if self.reader.peek() != Some(BREAK) {
+                    return Ok(true);
+                }
+
+                self.reader.skip(self.cx, 1)?;
+                self.remaining = Some(0);
+                Ok(false)
+            }
+        }
+    }
+
+    #[inline]
+    fn skip_sequence_remaining(mut self) -> Result<(), C::Error> {
+        while let Some(item) = self.try_decode_next()? {
+            item.skip()?;
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn skip_map_remaining(mut self) -> Result<(), C::Error> {
+        loop {
+            let Some(key) = self.decode_entry_key()? else {
+                break;
+            };
+
+            key.skip()?;
+            self.decode_entry_value()?.skip()?;
+        }
+
+        Ok(())
+    }
+}
+
+#[crate::decoder(crate)]
+impl<'a, 'de, R, const OPT: Options, C> Decoder<'de> for CborDecoder<'a, R, OPT, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = C::Mode;
+    type WithContext<'this, U> = CborDecoder<'this, R, OPT, U> where U: 'this + Context;
+    #[cfg(feature = "value")]
+    type DecodeBuffer = crate::value::AsValueDecoder<'a, BUFFER_OPTIONS, C>;
+    type DecodePack = CborDecoder<'a, Limit<R>, OPT, C>;
+    type DecodeSome = Self;
+    type DecodeSequence = RemainingCborDecoder<'a, R, OPT, C>;
+    type DecodeMap = RemainingCborDecoder<'a, R, OPT, C>;
+    type DecodeMapEntries = RemainingCborDecoder<'a, R, OPT, C>;
+    type DecodeVariant = Self;
+
+    #[inline]
+    fn cx(&self) -> &C {
+        self.cx
+    }
+
+    #[inline]
+    fn with_context<U>(self, cx: &U) -> Result<Self::WithContext<'_, U>, C::Error>
+    where
+        U: Context,
+    {
+        Ok(CborDecoder::new(cx, self.reader))
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "type supported by the CBOR decoder")
+    }
+
+    #[inline]
+    fn decode<T>(self) -> Result<T, Self::Error>
+    where
+        T: Decode<'de, Self::Mode>,
+    {
+        self.cx.decode(self)
+    }
+
+    #[inline]
+    fn decode_unsized<T, F, O>(self, f: F) -> Result<O, Self::Error>
+    where
+        T: ?Sized + DecodeUnsized<'de, Self::Mode>,
+        F: FnOnce(&T) -> Result<O, Self::Error>,
+    {
+        self.cx.decode_unsized(self, f)
+    }
+
+    #[inline]
+    fn skip(mut self) -> Result<(), C::Error> {
+        self.skip_item()
+    }
+
+    #[inline]
+    fn try_skip(self) -> Result<Skip, C::Error> {
+        self.skip()?;
+        Ok(Skip::Skipped)
+    }
+
+    #[cfg(feature = "value")]
+    #[inline]
+    fn decode_buffer(self) -> Result<Self::DecodeBuffer, C::Error> {
+        let cx = self.cx;
+        let value = self.decode::<crate::value::Value>()?;
+        Ok(value.into_value_decoder(cx))
+    }
+
+    #[inline]
+    fn decode_empty(self) -> Result<(), C::Error> {
+        self.skip()
+    }
+
+    #[inline]
+    fn decode_pack<F, O>(mut self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodePack) -> Result<O, C::Error>,
+    {
+        let pos = self.cx.mark();
+        let len = self.decode_definite_prefix(BYTES, pos)?;
+        let mut decoder = CborDecoder::new(self.cx, self.reader.limit(len));
+        let output = f(&mut decoder)?;
+        decoder.end()?;
+        Ok(output)
+    }
+
+    #[inline]
+    fn decode_array<const N: usize>(mut self) -> Result<[u8; N], C::Error> {
+        let pos = self.cx.mark();
+        let len = self.decode_definite_prefix(BYTES, pos)?;
+
+        if len != N {
+            return Err(self.cx.marked_message(
+                pos,
+                format_args! {
+                    "Bad length, got {len} but expect {N}"
+                },
+            ));
+        }
+
+        self.reader.read_array(self.cx)
+    }
+
+    #[inline]
+    fn decode_bytes<V>(self, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: UnsizedVisitor<'de, C, [u8]>,
+    {
+        self.decode_chunked(BYTES, visitor)
+    }
+
+    #[inline]
+    fn decode_string<V>(self, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: UnsizedVisitor<'de, C, str>,
+    {
+        struct Visitor<V>(V);
+
+        impl<'de, C, V> UnsizedVisitor<'de, C, [u8]> for Visitor<V>
+        where
+            C: ?Sized + Context,
+            V: UnsizedVisitor<'de, C, str>,
+        {
+            type Ok = V::Ok;
+
+            #[inline]
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.expecting(f)
+            }
+
+            #[cfg(feature = "alloc")]
+            #[inline]
+            fn visit_owned(self, cx: &C, bytes: Vec<u8>) -> Result<Self::Ok, C::Error> {
+                let string =
+                    crate::str::from_utf8_owned(bytes).map_err(crate::str::map_utf8_error(cx))?;
+                self.0.visit_owned(cx, string)
+            }
+
+            #[inline]
+            fn visit_borrowed(self, cx: &C, bytes: &'de [u8]) -> Result<Self::Ok, C::Error> {
+                let string =
+                    crate::str::from_utf8(bytes).map_err(crate::str::map_utf8_error(cx))?;
+                self.0.visit_borrowed(cx, string)
+            }
+
+            #[inline]
+            fn visit_ref(self, cx: &C, bytes: &[u8]) -> Result<Self::Ok, C::Error> {
+                let string =
+                    crate::str::from_utf8(bytes).map_err(crate::str::map_utf8_error(cx))?;
+                self.0.visit_ref(cx, string)
+            }
+        }
+
+        self.decode_chunked(STRING, Visitor(visitor))
+    }
+
+    #[inline]
+    fn decode_bool(mut self) -> Result<bool, C::Error> {
+        let pos = self.cx.mark();
+
+        match self.reader.read_byte(self.cx)? {
+            FALSE => Ok(false),
+            TRUE => Ok(true),
+            byte => Err(self.cx.marked_message(
+                pos,
+                format_args! {
+                    "Expected boolean, got {:?}", Header::from_byte(byte)
+                },
+            )),
+        }
+    }
+
+    #[inline]
+    fn decode_char(mut self) -> Result<char, C::Error> {
+        struct Visitor;
+
+        impl<C> UnsizedVisitor<'_, C, str> for Visitor
+        where
+            C: ?Sized + Context,
+        {
+            type Ok = char;
+
+            #[inline]
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "string with a single character")
+            }
+
+            #[inline]
+            fn visit_ref(self, cx: &C, string: &str) -> Result<Self::Ok, C::Error> {
+                let mut it = string.chars();
+
+                match (it.next(), it.next()) {
+                    (Some(c), None) => Ok(c),
+                    _ => Err(cx.message("Expected string with a single character")),
+                }
+            }
+        }
+
+        let cx = self.cx;
+        let pos = cx.mark();
+
+        // Permit decoding characters from their numerical code point as well.
+        if self.reader.peek().map(Header::from_byte).map(Header::major) == Some(UNSIGNED) {
+            let Some(c) = char::from_u32(self.decode_u32()?) else {
+                return Err(cx.marked_message(pos, "Bad character"));
+            };
+
+            return Ok(c);
+        }
+
+        self.decode_string(Visitor)
+    }
+
+    #[inline]
+    fn decode_number<V>(mut self, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: Visitor<'de, C>,
+    {
+        let cx = self.cx;
+
+        match self.reader.peek() {
+            Some(F16 | F32) => {
+                let value = self.decode_f32()?;
+                visitor.visit_f32(cx, value)
+            }
+            Some(F64) => {
+                let value = self.decode_f64()?;
+                visitor.visit_f64(cx, value)
+            }
+            _ => {
+                let integer = self.decode_integer()?;
+                self.visit_integer(integer, visitor)
+            }
+        }
+    }
+
+    #[inline]
+    fn decode_u8(self) -> Result<u8, C::Error> {
+        self.decode_unsigned()
+    }
+
+    #[inline]
+    fn decode_u16(self) -> Result<u16, C::Error> {
+        self.decode_unsigned()
+    }
+
+    #[inline]
+    fn decode_u32(self) -> Result<u32, C::Error> {
+        self.decode_unsigned()
+    }
+
+    #[inline]
+    fn decode_u64(self) -> Result<u64, C::Error> {
+        self.decode_unsigned()
+    }
+
+    #[inline]
+    fn decode_u128(self) -> Result<u128, C::Error> {
+        self.decode_unsigned()
+    }
+
+    #[inline]
+    fn decode_i8(self) -> Result<i8, C::Error> {
+        self.decode_signed()
+    }
+
+    #[inline]
+    fn decode_i16(self) -> Result<i16, C::Error> {
+        self.decode_signed()
+    }
+
+    #[inline]
+    fn decode_i32(self) -> Result<i32, C::Error> {
+        self.decode_signed()
+    }
+
+    #[inline]
+    fn decode_i64(self) -> Result<i64, C::Error> {
+        self.decode_signed()
+    }
+
+    #[inline]
+    fn decode_i128(self) -> Result<i128, C::Error> {
+        self.decode_signed()
+    }
+
+    #[inline]
+    fn decode_usize(self) -> Result<usize, C::Error> {
+        self.decode_unsigned()
+    }
+
+    #[inline]
+    fn decode_isize(self) -> Result<isize, C::Error> {
+        self.decode_signed()
+    }
+
+    /// Decode a 32-bit floating point value, which also accepts half-precision
+    /// floats and truncates double-precision floats.
+    #[inline]
+    fn decode_f32(mut self) -> Result<f32, C::Error> {
+        let pos = self.cx.mark();
+
+        match self.reader.read_byte(self.cx)? {
+            F16 => Ok(header::f16_to_f32(u16::from_be_bytes(
+                self.reader.read_array(self.cx)?,
+            ))),
+            F32 => Ok(f32::from_be_bytes(self.reader.read_array(self.cx)?)),
+            F64 => Ok(f64::from_be_bytes(self.reader.read_array(self.cx)?) as f32),
+            byte => Err(self.cx.marked_message(
+                pos,
+                format_args!("Expected float, got {:?}", Header::from_byte(byte)),
+            )),
+        }
+    }
+
+    /// Decode a 64-bit floating point value, which also accepts half and
+    /// single-precision floats.
+    #[inline]
+    fn decode_f64(mut self) -> Result<f64, C::Error> {
+        let pos = self.cx.mark();
+
+        match self.reader.read_byte(self.cx)? {
+            F16 => {
+                Ok(header::f16_to_f32(u16::from_be_bytes(self.reader.read_array(self.cx)?)).into())
+            }
+            F32 => Ok(f32::from_be_bytes(self.reader.read_array(self.cx)?).into()),
+            F64 => Ok(f64::from_be_bytes(self.reader.read_array(self.cx)?)),
+            byte => Err(self.cx.marked_message(
+                pos,
+                format_args!("Expected float, got {:?}", Header::from_byte(byte)),
+            )),
+        }
+    }
+
+    #[inline]
+    fn decode_option(mut self) -> Result<Option<Self::DecodeSome>, C::Error> {
+        // Options are encoded as null, or the value itself.
+        match self.reader.peek() {
+            Some(NULL | UNDEFINED) => {
+                self.reader.skip(self.cx, 1)?;
+                Ok(None)
+            }
+            _ => Ok(Some(self)),
+        }
+    }
+
+    #[inline]
+    fn decode_sequence<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeSequence) -> Result<O, C::Error>,
+    {
+        let mut decoder = self.shared_decode_sequence()?;
+        let output = f(&mut decoder)?;
+        decoder.skip_sequence_remaining()?;
+        Ok(output)
+    }
+
+    #[inline]
+    fn decode_map<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeMap) -> Result<O, C::Error>,
+    {
+        let mut decoder = self.shared_decode_map()?;
+        let output = f(&mut decoder)?;
+        decoder.skip_map_remaining()?;
+        Ok(output)
+    }
+
+    #[inline]
+    fn decode_map_entries<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeMapEntries) -> Result<O, C::Error>,
+    {
+        let mut decoder = self.shared_decode_map()?;
+        let output = f(&mut decoder)?;
+        decoder.skip_map_remaining()?;
+        Ok(output)
+    }
+
+    #[inline]
+    fn decode_variant<F, O>(mut self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeVariant) -> Result<O, C::Error>,
+    {
+        // Variants are encoded as maps with a single entry.
+        let pos = self.cx.mark();
+
+        match self.decode_prefix(MAP, pos)? {
+            Some(1) => f(&mut self),
+            None => {
+                let output = f(&mut self)?;
+
+                if !self.try_break()? {
+                    return Err(self
+                        .cx
+                        .marked_message(pos, "Expected variant to only contain a single entry"));
+                }
+
+                Ok(output)
+            }
+            Some(len) => Err(self.cx.marked_message(
+                pos,
+                format_args!("Expected variant to contain a single entry, but got {len}"),
+            )),
+        }
+    }
+
+    #[inline]
+    fn decode_any<V>(mut self, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: Visitor<'de, C>,
+    {
+        let cx = self.cx;
+
+        let Some(header) = self.reader.peek().map(Header::from_byte) else {
+            return Err(cx.message("Expected header in input"));
+        };
+
+        let hint = match header.info() {
+            info @ 0..=23 => SizeHint::exact(info as usize),
+            _ => SizeHint::any(),
+        };
+
+        match header.major() {
+            UNSIGNED | NEGATIVE => {
+                let integer = self.decode_integer()?;
+                self.visit_integer(integer, visitor)
+            }
+            BYTES => {
+                let visitor = visitor.visit_bytes(cx, hint)?;
+                self.decode_bytes(visitor)
+            }
+            STRING => {
+                let visitor = visitor.visit_string(cx, hint)?;
+                self.decode_string(visitor)
+            }
+            ARRAY => {
+                let mut sequence = self.shared_decode_sequence()?;
+                let output = visitor.visit_sequence(cx, &mut sequence)?;
+                sequence.skip_sequence_remaining()?;
+                Ok(output)
+            }
+            MAP => {
+                let mut map = self.shared_decode_map()?;
+                let output = visitor.visit_map(cx, &mut map)?;
+                map.skip_map_remaining()?;
+                Ok(output)
+            }
+            TAG => {
+                let pos = cx.mark();
+                let header = self.read_header()?;
+                let tag = header::decode_argument(cx, self.reader.borrow_mut(), header)?;
+
+                match tag {
+                    POSITIVE_BIGNUM | NEGATIVE_BIGNUM => {
+                        let integer = self.decode_bignum(tag, pos)?;
+                        self.visit_integer(integer, visitor)
+                    }
+                    // Other tags carry no meaning in the data model, so the
+                    // tagged item is decoded as-is.
+                    _ => self.decode_any(visitor),
+                }
+            }
+            SIMPLE => match header.byte() {
+                FALSE | TRUE => {
+                    let value = self.decode_bool()?;
+                    visitor.visit_bool(cx, value)
+                }
+                NULL | UNDEFINED => {
+                    self.reader.skip(cx, 1)?;
+                    visitor.visit_empty(cx)
+                }
+                F16 | F32 => {
+                    let value = self.decode_f32()?;
+                    visitor.visit_f32(cx, value)
+                }
+                F64 => {
+                    let value = self.decode_f64()?;
+                    visitor.visit_f64(cx, value)
+                }
+                _ => Err(cx.message(format_args!("Unsupported simple value {header:?}"))),
+            },
+            _ => Err(cx.message(format_args!("Unsupported header {header:?}"))),
+        }
+    }
+}
+
+impl<'a, 'de, R, const OPT: Options, C> SequenceDecoder<'de> for CborDecoder<'a, Limit<R>, OPT, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeNext<'this>
+        = StorageDecoder<'a, <Limit<R> as Reader<'de>>::Mut<'this>, OPT, C>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn try_decode_next(&mut self) -> Result<Option<Self::DecodeNext<'_>>, C::Error> {
+        Ok(Some(StorageDecoder::new(self.cx, self.reader.borrow_mut())))
+    }
+
+    #[inline]
+    fn decode_next(&mut self) -> Result<Self::DecodeNext<'_>, C::Error> {
+        Ok(StorageDecoder::new(self.cx, self.reader.borrow_mut()))
+    }
+}
+
+impl<'a, 'de, R, const OPT: Options, C> SequenceDecoder<'de> for RemainingCborDecoder<'a, R, OPT, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeNext<'this> = CborDecoder<'a, R::Mut<'this>, OPT, C> where Self: 'this;
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::from(self.remaining)
+    }
+
+    #[inline]
+    fn try_decode_next(&mut self) -> Result<Option<Self::DecodeNext<'_>>, C::Error> {
+        if !self.has_next()? {
+            return Ok(None);
+        }
+
+        Ok(Some(CborDecoder::new(self.cx, self.reader.borrow_mut())))
+    }
+
+    #[inline]
+    fn decode_next(&mut self) -> Result<Self::DecodeNext<'_>, <Self::Cx as Context>::Error> {
+        let cx = self.cx;
+
+        let Some(decoder) = self.try_decode_next()? else {
+            return Err(cx.message("No remaining elements"));
+        };
+
+        Ok(decoder)
+    }
+}
+
+impl<'a, 'de, R, const OPT: Options, C> MapDecoder<'de> for RemainingCborDecoder<'a, R, OPT, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeEntry<'this> = CborDecoder<'a, R::Mut<'this>, OPT, C> where Self: 'this;
+    type DecodeRemainingEntries<'this>
+        = RemainingCborDecoder<'a, R::Mut<'this>, OPT, C>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::from(self.remaining)
+    }
+
+    #[inline]
+    fn decode_entry(&mut self) -> Result<Option<Self::DecodeEntry<'_>>, C::Error> {
+        if !self.has_next()? {
+            return Ok(None);
+        }
+
+        Ok(Some(CborDecoder::new(self.cx, self.reader.borrow_mut())))
+    }
+
+    #[inline]
+    fn decode_remaining_entries(&mut self) -> Result<Self::DecodeRemainingEntries<'_>, C::Error> {
+        Ok(RemainingCborDecoder::new(
+            self.cx,
+            self.reader.borrow_mut(),
+            self.remaining.replace(0),
+        ))
+    }
+}
+
+impl<'a, 'de, R, const OPT: Options, C> EntriesDecoder<'de> for RemainingCborDecoder<'a, R, OPT, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeEntryKey<'this> = CborDecoder<'a, R::Mut<'this>, OPT, C> where Self: 'this;
+    type DecodeEntryValue<'this> = CborDecoder<'a, R::Mut<'this>, OPT, C> where Self: 'this;
+
+    #[inline]
+    fn decode_entry_key(&mut self) -> Result<Option<Self::DecodeEntryKey<'_>>, C::Error> {
+        if !self.has_next()? {
+            return Ok(None);
+        }
+
+        Ok(Some(CborDecoder::new(self.cx, self.reader.borrow_mut())))
+    }
+
+    #[inline]
+    fn decode_entry_value(&mut self) -> Result<Self::DecodeEntryValue<'_>, C::Error> {
+        Ok(CborDecoder::new(self.cx, self.reader.borrow_mut()))
+    }
+
+    #[inline]
+    fn end_entries(self) -> Result<(), <Self::Cx as Context>::Error> {
+        self.skip_map_remaining()?;
+        Ok(())
+    }
+}
+
+impl<'a, 'de, R, const OPT: Options, C> EntryDecoder<'de> for CborDecoder<'a, R, OPT, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeKey<'this> = CborDecoder<'a, R::Mut<'this>, OPT, C> where Self: 'this;
+    type DecodeValue = Self;
+
+    #[inline]
+    fn decode_key(&mut self) -> Result<Self::DecodeKey<'_>, C::Error> {
+        Ok(CborDecoder::new(self.cx, self.reader.borrow_mut()))
+    }
+
+    #[inline]
+    fn decode_value(self) -> Result<Self::DecodeValue, C::Error> {
+        Ok(self)
+    }
+}
+
+impl<'a, 'de, R, const OPT: Options, C> VariantDecoder<'de> for CborDecoder<'a, R, OPT, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeTag<'this> = CborDecoder<'a, R::Mut<'this>, OPT, C> where Self: 'this;
+    type DecodeValue<'this> = CborDecoder<'a, R::Mut<'this>, OPT, C> where Self: 'this;
+
+    #[inline]
+    fn decode_tag(&mut self) -> Result<Self::DecodeTag<'_>, C::Error> {
+        Ok(CborDecoder::new(self.cx, self.reader.borrow_mut()))
+    }
+
+    #[inline]
+    fn decode_value(&mut self) -> Result<Self::DecodeValue<'_>, C::Error> {
+        Ok(CborDecoder::new(self.cx, self.reader.borrow_mut()))
+    }
+}
+
+struct Expected {
+    expected: u8,
+    actual: Header,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { expected, actual } = *self;
+        let expected = header::major_name(expected);
+
+        write!(f, "Expected {expected} but was {actual:?}",)
+    }
+}
@@ -0,0 +1,497 @@
+use core::fmt;
+
+use crate::en::{
+    Encoder, EntriesEncoder, EntryEncoder, MapEncoder, SequenceEncoder, VariantEncoder,
+};
+use crate::hint::{MapHint, SequenceHint};
+use crate::storage::en::StorageEncoder;
+use crate::writer::BufWriter;
+use crate::{Context, Encode, Options, Writer};
+
+use super::header::{
+    self, ARRAY, BYTES, F32, F64, FALSE, MAP, NEGATIVE, NEGATIVE_BIGNUM, NULL, POSITIVE_BIGNUM,
+    STRING, TAG, TRUE, UNSIGNED,
+};
+
+/// A CBOR encoder.
+pub struct CborEncoder<'a, W, const OPT: Options, C: ?Sized> {
+    cx: &'a C,
+    writer: W,
+}
+
+impl<'a, W, const OPT: Options, C: ?Sized> CborEncoder<'a, W, OPT, C> {
+    /// Construct a new CBOR encoder.
+    #[inline]
+    pub(crate) fn new(cx: &'a C, writer: W) -> Self {
+        Self { cx, writer }
+    }
+}
+
+/// Encoder for packed values, which are stored in a single byte string.
+pub struct CborPackEncoder<'a, W, const OPT: Options, C>
+where
+    C: ?Sized + Context,
+{
+    cx: &'a C,
+    writer: W,
+    buffer: BufWriter<'a, C::Allocator>,
+}
+
+impl<'a, W, const OPT: Options, C> CborPackEncoder<'a, W, OPT, C>
+where
+    C: ?Sized + Context,
+{
+    #[inline]
+    pub(crate) fn new(cx: &'a C, writer: W) -> Self {
+        Self {
+            cx,
+            writer,
+            buffer: BufWriter::new(cx.alloc()),
+        }
+    }
+}
+
+#[crate::encoder(crate)]
+impl<'a, W, const OPT: Options, C> Encoder for CborEncoder<'a, W, OPT, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Ok = ();
+    type Mode = C::Mode;
+    type WithContext<'this, U> = CborEncoder<'this, W, OPT, U> where U: 'this + Context;
+    type EncodePack = CborPackEncoder<'a, W, OPT, C>;
+    type EncodeSome = Self;
+    type EncodeSequence = Self;
+    type EncodeMap = Self;
+    type EncodeMapEntries = Self;
+    type EncodeVariant = Self;
+    type EncodeSequenceVariant = Self;
+    type EncodeMapVariant = Self;
+
+    #[inline]
+    fn cx(&self) -> &Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn with_context<U>(self, cx: &U) -> Result<Self::WithContext<'_, U>, C::Error>
+    where
+        U: Context,
+    {
+        Ok(CborEncoder::new(cx, self.writer))
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "type supported by the CBOR encoder")
+    }
+
+    #[inline]
+    fn encode<T>(self, value: T) -> Result<Self::Ok, C::Error>
+    where
+        T: Encode<Self::Mode>,
+    {
+        value.encode(self.cx, self)
+    }
+
+    #[inline]
+    fn encode_empty(mut self) -> Result<Self::Ok, C::Error> {
+        self.writer.write_byte(self.cx, NULL)
+    }
+
+    #[inline]
+    fn encode_pack(self) -> Result<Self::EncodePack, C::Error> {
+        Ok(CborPackEncoder::new(self.cx, self.writer))
+    }
+
+    #[inline]
+    fn encode_array<const N: usize>(self, array: &[u8; N]) -> Result<Self::Ok, C::Error> {
+        self.encode_bytes(array)
+    }
+
+    #[inline]
+    fn encode_bytes(mut self, bytes: &[u8]) -> Result<Self::Ok, C::Error> {
+        header::encode(self.cx, self.writer.borrow_mut(), BYTES, bytes.len() as u64)?;
+        self.writer.write_bytes(self.cx, bytes)
+    }
+
+    #[inline]
+    fn encode_bytes_vectored<I>(mut self, len: usize, vectors: I) -> Result<Self::Ok, C::Error>
+    where
+        I: IntoIterator<Item: AsRef<[u8]>>,
+    {
+        header::encode(self.cx, self.writer.borrow_mut(), BYTES, len as u64)?;
+
+        for bytes in vectors {
+            self.writer.write_bytes(self.cx, bytes.as_ref())?;
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn encode_string(mut self, string: &str) -> Result<Self::Ok, C::Error> {
+        header::encode(
+            self.cx,
+            self.writer.borrow_mut(),
+            STRING,
+            string.len() as u64,
+        )?;
+        self.writer.write_bytes(self.cx, string.as_bytes())
+    }
+
+    #[inline]
+    fn collect_string<T>(self, value: &T) -> Result<Self::Ok, <Self::Cx as Context>::Error>
+    where
+        T: ?Sized + fmt::Display,
+    {
+        let buf = self.cx.collect_string(value)?;
+        self.encode_string(buf.as_ref())
+    }
+
+    #[inline]
+    fn encode_bool(mut self, value: bool) -> Result<Self::Ok, C::Error> {
+        self.writer
+            .write_byte(self.cx, if value { TRUE } else { FALSE })
+    }
+
+    #[inline]
+    fn encode_char(self, value: char) -> Result<Self::Ok, C::Error> {
+        self.encode_string(value.encode_utf8(&mut [0, 0, 0, 0]))
+    }
+
+    #[inline]
+    fn encode_u8(mut self, value: u8) -> Result<Self::Ok, C::Error> {
+        encode_unsigned(self.cx, self.writer.borrow_mut(), value.into())
+    }
+
+    #[inline]
+    fn encode_u16(mut self, value: u16) -> Result<Self::Ok, C::Error> {
+        encode_unsigned(self.cx, self.writer.borrow_mut(), value.into())
+    }
+
+    #[inline]
+    fn encode_u32(mut self, value: u32) -> Result<Self::Ok, C::Error> {
+        encode_unsigned(self.cx, self.writer.borrow_mut(), value.into())
+    }
+
+    #[inline]
+    fn encode_u64(mut self, value: u64) -> Result<Self::Ok, C::Error> {
+        encode_unsigned(self.cx, self.writer.borrow_mut(), value.into())
+    }
+
+    #[inline]
+    fn encode_u128(mut self, value: u128) -> Result<Self::Ok, C::Error> {
+        encode_unsigned(self.cx, self.writer.borrow_mut(), value)
+    }
+
+    #[inline]
+    fn encode_usize(mut self, value: usize) -> Result<Self::Ok, C::Error> {
+        encode_unsigned(self.cx, self.writer.borrow_mut(), value as u128)
+    }
+
+    #[inline]
+    fn encode_i8(mut self, value: i8) -> Result<Self::Ok, C::Error> {
+        encode_signed(self.cx, self.writer.borrow_mut(), value.into())
+    }
+
+    #[inline]
+    fn encode_i16(mut self, value: i16) -> Result<Self::Ok, C::Error> {
+        encode_signed(self.cx, self.writer.borrow_mut(), value.into())
+    }
+
+    #[inline]
+    fn encode_i32(mut self, value: i32) -> Result<Self::Ok, C::Error> {
+        encode_signed(self.cx, self.writer.borrow_mut(), value.into())
+    }
+
+    #[inline]
+    fn encode_i64(mut self, value: i64) -> Result<Self::Ok, C::Error> {
+        encode_signed(self.cx, self.writer.borrow_mut(), value.into())
+    }
+
+    #[inline]
+    fn encode_i128(mut self, value: i128) -> Result<Self::Ok, C::Error> {
+        encode_signed(self.cx, self.writer.borrow_mut(), value)
+    }
+
+    #[inline]
+    fn encode_isize(mut self, value: isize) -> Result<Self::Ok, C::Error> {
+        encode_signed(self.cx, self.writer.borrow_mut(), value as i128)
+    }
+
+    #[inline]
+    fn encode_f32(mut self, value: f32) -> Result<Self::Ok, C::Error> {
+        self.writer.write_byte(self.cx, F32)?;
+        self.writer.write_bytes(self.cx, &value.to_be_bytes())
+    }
+
+    #[inline]
+    fn encode_f64(mut self, value: f64) -> Result<Self::Ok, C::Error> {
+        self.writer.write_byte(self.cx, F64)?;
+        self.writer.write_bytes(self.cx, &value.to_be_bytes())
+    }
+
+    #[inline]
+    fn encode_some(self) -> Result<Self::EncodeSome, C::Error> {
+        Ok(self)
+    }
+
+    #[inline]
+    fn encode_none(self) -> Result<Self::Ok, C::Error> {
+        self.encode_empty()
+    }
+
+    #[inline]
+    fn encode_sequence(mut self, hint: &SequenceHint) -> Result<Self::EncodeSequence, C::Error> {
+        header::encode(self.cx, self.writer.borrow_mut(), ARRAY, hint.size as u64)?;
+        Ok(self)
+    }
+
+    #[inline]
+    fn encode_map(mut self, hint: &MapHint) -> Result<Self::EncodeMap, C::Error> {
+        header::encode(self.cx, self.writer.borrow_mut(), MAP, hint.size as u64)?;
+        Ok(self)
+    }
+
+    #[inline]
+    fn encode_map_entries(mut self, hint: &MapHint) -> Result<Self::EncodeMapEntries, C::Error> {
+        header::encode(self.cx, self.writer.borrow_mut(), MAP, hint.size as u64)?;
+        Ok(self)
+    }
+
+    #[inline]
+    fn encode_variant(mut self) -> Result<Self::EncodeVariant, C::Error> {
+        header::encode(self.cx, self.writer.borrow_mut(), MAP, 1)?;
+        Ok(self)
+    }
+
+    #[inline]
+    fn encode_sequence_variant<T>(
+        mut self,
+        tag: &T,
+        hint: &SequenceHint,
+    ) -> Result<Self::EncodeSequenceVariant, C::Error>
+    where
+        T: ?Sized + Encode<C::Mode>,
+    {
+        header::encode(self.cx, self.writer.borrow_mut(), MAP, 1)?;
+        CborEncoder::<_, OPT, _>::new(self.cx, self.writer.borrow_mut()).encode(tag)?;
+        self.encode_sequence(hint)
+    }
+
+    #[inline]
+    fn encode_map_variant<T>(
+        mut self,
+        tag: &T,
+        hint: &MapHint,
+    ) -> Result<Self::EncodeMapVariant, C::Error>
+    where
+        T: ?Sized + Encode<C::Mode>,
+    {
+        header::encode(self.cx, self.writer.borrow_mut(), MAP, 1)?;
+        CborEncoder::<_, OPT, _>::new(self.cx, self.writer.borrow_mut()).encode(tag)?;
+        self.encode_map(hint)
+    }
+}
+
+impl<'a, W, const OPT: Options, C> SequenceEncoder for CborPackEncoder<'a, W, OPT, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Ok = ();
+    type EncodeNext<'this>
+        = StorageEncoder<'a, &'this mut BufWriter<'a, C::Allocator>, OPT, C>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn encode_next(&mut self) -> Result<Self::EncodeNext<'_>, C::Error> {
+        Ok(StorageEncoder::new(self.cx, &mut self.buffer))
+    }
+
+    #[inline]
+    fn finish_sequence(mut self) -> Result<Self::Ok, C::Error> {
+        let buffer = self.buffer.into_inner();
+        header::encode(
+            self.cx,
+            self.writer.borrow_mut(),
+            BYTES,
+            buffer.len() as u64,
+        )?;
+        self.writer.extend(self.cx, buffer)?;
+        Ok(())
+    }
+}
+
+impl<'a, W, const OPT: Options, C> SequenceEncoder for CborEncoder<'a, W, OPT, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Ok = ();
+    type EncodeNext<'this> = CborEncoder<'a, W::Mut<'this>, OPT, C> where Self: 'this;
+
+    #[inline]
+    fn encode_next(&mut self) -> Result<Self::EncodeNext<'_>, C::Error> {
+        Ok(CborEncoder::new(self.cx, self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn finish_sequence(self) -> Result<Self::Ok, C::Error> {
+        Ok(())
+    }
+}
+
+impl<'a, W, const OPT: Options, C> MapEncoder for CborEncoder<'a, W, OPT, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Ok = ();
+    type EncodeEntry<'this> = CborEncoder<'a, W::Mut<'this>, OPT, C> where Self: 'this;
+
+    #[inline]
+    fn encode_entry(&mut self) -> Result<Self::EncodeEntry<'_>, C::Error> {
+        Ok(CborEncoder::new(self.cx, self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn finish_map(self) -> Result<Self::Ok, C::Error> {
+        Ok(())
+    }
+}
+
+impl<'a, W, const OPT: Options, C> EntryEncoder for CborEncoder<'a, W, OPT, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Ok = ();
+    type EncodeKey<'this> = CborEncoder<'a, W::Mut<'this>, OPT, C> where Self: 'this;
+    type EncodeValue<'this> = CborEncoder<'a, W::Mut<'this>, OPT, C> where Self: 'this;
+
+    #[inline]
+    fn encode_key(&mut self) -> Result<Self::EncodeKey<'_>, C::Error> {
+        Ok(CborEncoder::new(self.cx, self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn encode_value(&mut self) -> Result<Self::EncodeValue<'_>, C::Error> {
+        Ok(CborEncoder::new(self.cx, self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn finish_entry(self) -> Result<Self::Ok, C::Error> {
+        Ok(())
+    }
+}
+
+impl<'a, W, const OPT: Options, C> EntriesEncoder for CborEncoder<'a, W, OPT, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Ok = ();
+    type EncodeEntryKey<'this> = CborEncoder<'a, W::Mut<'this>, OPT, C> where Self: 'this;
+    type EncodeEntryValue<'this> = CborEncoder<'a, W::Mut<'this>, OPT, C> where Self: 'this;
+
+    #[inline]
+    fn encode_entry_key(&mut self) -> Result<Self::EncodeEntryKey<'_>, C::Error> {
+        Ok(CborEncoder::new(self.cx, self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn encode_entry_value(&mut self) -> Result<Self::EncodeEntryValue<'_>, C::Error> {
+        Ok(CborEncoder::new(self.cx, self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn finish_entries(self) -> Result<Self::Ok, C::Error> {
+        Ok(())
+    }
+}
+
+impl<'a, W, const OPT: Options, C> VariantEncoder for CborEncoder<'a, W, OPT, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Ok = ();
+    type EncodeTag<'this> = CborEncoder<'a, W::Mut<'this>, OPT, C> where Self: 'this;
+    type EncodeData<'this> = CborEncoder<'a, W::Mut<'this>, OPT, C> where Self: 'this;
+
+    #[inline]
+    fn encode_tag(&mut self) -> Result<Self::EncodeTag<'_>, C::Error> {
+        Ok(CborEncoder::new(self.cx, self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn encode_data(&mut self) -> Result<Self::EncodeData<'_>, C::Error> {
+        Ok(CborEncoder::new(self.cx, self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn finish_variant(self) -> Result<Self::Ok, C::Error> {
+        Ok(())
+    }
+}
+
+/// Encode an unsigned integer, falling back to a bignum if it doesn't fit in
+/// 64 bits.
+#[inline]
+fn encode_unsigned<C, W>(cx: &C, writer: W, value: u128) -> Result<(), C::Error>
+where
+    C: ?Sized + Context,
+    W: Writer,
+{
+    match u64::try_from(value) {
+        Ok(value) => header::encode(cx, writer, UNSIGNED, value),
+        Err(..) => encode_bignum(cx, writer, POSITIVE_BIGNUM, value),
+    }
+}
+
+/// Encode a signed integer, falling back to a bignum if it doesn't fit in 64
+/// bits.
+#[inline]
+fn encode_signed<C, W>(cx: &C, writer: W, value: i128) -> Result<(), C::Error>
+where
+    C: ?Sized + Context,
+    W: Writer,
+{
+    if value >= 0 {
+        return encode_unsigned(cx, writer, value as u128);
+    }
+
+    // Negative numbers are stored as `-1 - n`, which for two's complement is
+    // the same as inverting all bits.
+    let value = !value as u128;
+
+    match u64::try_from(value) {
+        Ok(value) => header::encode(cx, writer, NEGATIVE, value),
+        Err(..) => encode_bignum(cx, writer, NEGATIVE_BIGNUM, value),
+    }
+}
+
+/// Encode a bignum as a tagged big-endian byte string without leading zeros.
+fn encode_bignum<C, W>(cx: &C, mut writer: W, tag: u64, value: u128) -> Result<(), C::Error>
+where
+    C: ?Sized + Context,
+    W: Writer,
+{
+    let bytes = value.to_be_bytes();
+    let bytes = &bytes[(value.leading_zeros() / 8) as usize..];
+    header::encode(cx, writer.borrow_mut(), TAG, tag)?;
+    header::encode(cx, writer.borrow_mut(), BYTES, bytes.len() as u64)?;
+    writer.write_bytes(cx, bytes)
+}
@@ -0,0 +1,15 @@
+crate::macros::error_type! {
+    /// Error raised during CBOR encoding.
+    pub struct Error;
+
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::context::ErrorKind;
+    ///
+    /// let error = musli::cbor::from_slice::<u32>(b"").unwrap_err();
+    /// assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    /// ```
+    pub fn kind;
+}
@@ -0,0 +1,191 @@
+//! Initial bytes and argument encoding used by `musli::cbor`.
+//!
+//! Every data item in CBOR starts with a single byte where the high 3 bits is
+//! the *major type* and the low 5 bits is the *additional information*, which
+//! either holds a small argument inline or indicates how many bytes of
+//! argument follows.
+
+use core::fmt;
+
+use crate::{Context, Reader, Writer};
+
+/// An unsigned integer.
+pub(crate) const UNSIGNED: u8 = 0;
+/// A negative integer, encoded as `-1 - n`.
+pub(crate) const NEGATIVE: u8 = 1;
+/// A byte string.
+pub(crate) const BYTES: u8 = 2;
+/// A UTF-8 text string.
+pub(crate) const STRING: u8 = 3;
+/// An array of data items.
+pub(crate) const ARRAY: u8 = 4;
+/// A map of pairs of data items.
+pub(crate) const MAP: u8 = 5;
+/// A tagged data item.
+pub(crate) const TAG: u8 = 6;
+/// Floating-point numbers and simple values.
+pub(crate) const SIMPLE: u8 = 7;
+
+/// Additional information which indicates an indefinite length.
+pub(crate) const INDEFINITE: u8 = 31;
+
+/// The simple value `false`.
+pub(crate) const FALSE: u8 = 0xf4;
+/// The simple value `true`.
+pub(crate) const TRUE: u8 = 0xf5;
+/// The simple value `null`.
+pub(crate) const NULL: u8 = 0xf6;
+/// The simple value `undefined`.
+pub(crate) const UNDEFINED: u8 = 0xf7;
+/// A half-precision float follows.
+pub(crate) const F16: u8 = 0xf9;
+/// A single-precision float follows.
+pub(crate) const F32: u8 = 0xfa;
+/// A double-precision float follows.
+pub(crate) const F64: u8 = 0xfb;
+/// The "break" stop code which terminates indefinite length items.
+pub(crate) const BREAK: u8 = 0xff;
+
+/// Tag for an unsigned bignum stored as a big-endian byte string.
+pub(crate) const POSITIVE_BIGNUM: u64 = 2;
+/// Tag for a negative bignum stored as a big-endian byte string.
+pub(crate) const NEGATIVE_BIGNUM: u64 = 3;
+
+/// The initial byte of a data item.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Header {
+    byte: u8,
+}
+
+impl Header {
+    /// Construct a header from a raw byte.
+    #[inline]
+    pub(crate) const fn from_byte(byte: u8) -> Self {
+        Self { byte }
+    }
+
+    /// Get the raw byte of the header.
+    #[inline]
+    pub(crate) const fn byte(self) -> u8 {
+        self.byte
+    }
+
+    /// The major type of the header.
+    #[inline]
+    pub(crate) const fn major(self) -> u8 {
+        self.byte >> 5
+    }
+
+    /// The additional information of the header.
+    #[inline]
+    pub(crate) const fn info(self) -> u8 {
+        self.byte & 0b11111
+    }
+
+    /// Test if the header indicates an indefinite length item.
+    #[inline]
+    pub(crate) const fn is_indefinite(self) -> bool {
+        self.info() == INDEFINITE
+    }
+}
+
+impl fmt::Debug for Header {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}({:#04x})", major_name(self.major()), self.byte)
+    }
+}
+
+/// Get the name of a major type.
+pub(crate) fn major_name(major: u8) -> &'static str {
+    match major {
+        UNSIGNED => "Unsigned",
+        NEGATIVE => "Negative",
+        BYTES => "Bytes",
+        STRING => "String",
+        ARRAY => "Array",
+        MAP => "Map",
+        TAG => "Tag",
+        _ => "Simple",
+    }
+}
+
+/// Encode the header of a data item with the given major type and argument,
+/// using the shortest possible encoding for the argument.
+#[inline]
+pub(crate) fn encode<C, W>(cx: &C, mut writer: W, major: u8, value: u64) -> Result<(), C::Error>
+where
+    C: ?Sized + Context,
+    W: Writer,
+{
+    let major = major << 5;
+
+    if value < 24 {
+        writer.write_byte(cx, major | value as u8)
+    } else if let Ok(value) = u8::try_from(value) {
+        writer.write_bytes(cx, &[major | 24, value])
+    } else if let Ok(value) = u16::try_from(value) {
+        writer.write_byte(cx, major | 25)?;
+        writer.write_bytes(cx, &value.to_be_bytes())
+    } else if let Ok(value) = u32::try_from(value) {
+        writer.write_byte(cx, major | 26)?;
+        writer.write_bytes(cx, &value.to_be_bytes())
+    } else {
+        writer.write_byte(cx, major | 27)?;
+        writer.write_bytes(cx, &value.to_be_bytes())
+    }
+}
+
+/// Decode the argument which follows the given header.
+///
+/// Indefinite lengths must be handled by the caller before this is called.
+#[inline]
+pub(crate) fn decode_argument<'de, C, R>(
+    cx: &C,
+    mut reader: R,
+    header: Header,
+) -> Result<u64, C::Error>
+where
+    C: ?Sized + Context,
+    R: Reader<'de>,
+{
+    match header.info() {
+        info @ 0..=23 => Ok(u64::from(info)),
+        24 => Ok(u64::from(reader.read_byte(cx)?)),
+        25 => Ok(u64::from(u16::from_be_bytes(reader.read_array(cx)?))),
+        26 => Ok(u64::from(u32::from_be_bytes(reader.read_array(cx)?))),
+        27 => Ok(u64::from_be_bytes(reader.read_array(cx)?)),
+        _ => Err(cx.message(format_args!(
+            "Unsupported additional information in {header:?}"
+        ))),
+    }
+}
+
+/// Convert the bits of an IEEE 754 half-precision float into an `f32`.
+///
+/// Every half-precision value is exactly representable as a single-precision
+/// float, so this conversion is lossless.
+pub(crate) fn f16_to_f32(half: u16) -> f32 {
+    let sign = u32::from(half & 0x8000) << 16;
+    let exponent = u32::from((half >> 10) & 0x1f);
+    let mantissa = u32::from(half & 0x3ff);
+
+    let bits = match (exponent, mantissa) {
+        (0, 0) => sign,
+        // Subnormal, which needs to be normalized since single-precision
+        // floats have a wider exponent range.
+        (0, mut mantissa) => {
+            let mut exponent = 127 - 15 + 1;
+
+            while mantissa & 0x400 == 0 {
+                mantissa <<= 1;
+                exponent -= 1;
+            }
+
+            sign | (exponent << 23) | ((mantissa & 0x3ff) << 13)
+        }
+        (0x1f, mantissa) => sign | 0x7f80_0000 | (mantissa << 13),
+        (exponent, mantissa) => sign | ((exponent + 127 - 15) << 23) | (mantissa << 13),
+    };
+
+    f32::from_bits(bits)
+}
@@ -0,0 +1,134 @@
+//! Support for [CBOR] (RFC 8949), the Concise Binary Object Representation.
+//!
+//! CBOR is a self-descriptive format, and this implementation is fully upgrade
+//! stable:
+//!
+//! * ✔ Can tolerate missing fields if they are annotated with
+//!   `#[musli(default)]`.
+//! * ✔ Can skip over unknown fields.
+//! * ✔ Can be decoded into dynamic containers such as the [`Value`] type.
+//! * ✔ Can handle coercion from different types of primitive types, such as
+//!   signed to unsigned integers. So primitive field types can be assuming they
+//!   only inhabit compatible values.
+//!
+//! [CBOR]: https://www.rfc-editor.org/rfc/rfc8949.html
+//! [`Value`]: crate::value
+//!
+//! ```
+//! use musli::{Encode, Decode};
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! struct Version1 {
+//!     name: String,
+//! }
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! struct Version2 {
+//!     name: String,
+//!     #[musli(default)]
+//!     age: Option<u32>,
+//! }
+//!
+//! let version2 = musli::cbor::to_vec(&Version2 {
+//!     name: String::from("Aristotle"),
+//!     age: Some(61),
+//! })?;
+//!
+//! let version1: Version1 = musli::cbor::from_slice(version2.as_slice())?;
+//!
+//! assert_eq!(version1, Version1 {
+//!     name: String::from("Aristotle"),
+//! });
+//! # Ok::<_, musli::cbor::Error>(())
+//! ```
+//!
+//! <br>
+//!
+//! ## Configuring
+//!
+//! To configure the behavior of the CBOR format you can use the [`Encoding`]
+//! type:
+//!
+//! ```
+//! use musli::{Encode, Decode};
+//! use musli::cbor::Encoding;
+//!
+//! const CONFIG: Encoding = Encoding::new();
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! struct Person<'a> {
+//!     name: &'a str,
+//!     age: u32,
+//! }
+//!
+//! let mut out = Vec::new();
+//!
+//! let expected = Person {
+//!     name: "Aristotle",
+//!     age: 61,
+//! };
+//!
+//! CONFIG.encode(&mut out, &expected)?;
+//! let actual = CONFIG.decode(&out[..])?;
+//!
+//! assert_eq!(expected, actual);
+//! # Ok::<_, musli::cbor::Error>(())
+//! ```
+//!
+//! <br>
+//!
+//! ## Implementation details
+//!
+//! Values are mapped onto the CBOR data model like this:
+//!
+//! * Integers use major types 0 and 1 in their shortest form. 128-bit integers
+//!   which do not fit in 64 bits are encoded as tagged bignums (tags 2 and 3).
+//! * Floats are encoded with their own precision. Half-precision floats are
+//!   supported when decoding.
+//! * Strings and bytes are text and byte strings. Characters are encoded as
+//!   strings.
+//! * Sequences and maps are arrays and maps, and structs are maps keyed by
+//!   their field names.
+//! * Variants are maps with a single entry, where the key is the variant tag.
+//! * `None` and empty values are encoded as `null`, while `Some` is encoded as
+//!   the value itself.
+//! * Packed values are stored in a byte string.
+//!
+//! Encoding always produces definite lengths. When decoding, items with
+//! indefinite lengths are supported, and semantic tags other than bignums are
+//! ignored.
+
+#![cfg(feature = "cbor")]
+#![cfg_attr(doc_cfg, doc(cfg(feature = "cbor")))]
+
+#[cfg(test)]
+mod tests;
+
+mod de;
+mod en;
+mod encoding;
+mod error;
+mod header;
+
+#[cfg(feature = "test")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "test")))]
+#[doc(hidden)]
+pub mod test;
+
+/// Convenient result alias for use with `musli::cbor`.
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[doc(inline)]
+pub use self::encoding::to_vec;
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+#[doc(inline)]
+pub use self::encoding::to_writer;
+#[doc(inline)]
+pub use self::encoding::{
+    decode, encode, from_slice, from_slice_exact, to_fixed_bytes, Encoding, DEFAULT, OPTIONS,
+};
+#[doc(inline)]
+pub use self::error::Error;
@@ -0,0 +1,284 @@
+//! Test vectors are taken from Appendix A of RFC 8949.
+
+use rust_alloc::string::String;
+use rust_alloc::vec;
+use rust_alloc::vec::Vec;
+
+use crate::compat::Bytes;
+use crate::value::Value;
+use crate::{Decode, Encode};
+
+use super::header::f16_to_f32;
+use super::test::{decodes, roundtrips};
+
+#[test]
+fn integers() {
+    roundtrips(0u8, b"\x00");
+    roundtrips(1u8, b"\x01");
+    roundtrips(10u8, b"\x0a");
+    roundtrips(23u8, b"\x17");
+    roundtrips(24u8, b"\x18\x18");
+    roundtrips(25u8, b"\x18\x19");
+    roundtrips(100u8, b"\x18\x64");
+    roundtrips(1000u16, b"\x19\x03\xe8");
+    roundtrips(1000000u32, b"\x1a\x00\x0f\x42\x40");
+    roundtrips(1000000000000u64, b"\x1b\x00\x00\x00\xe8\xd4\xa5\x10\x00");
+    roundtrips(u64::MAX, b"\x1b\xff\xff\xff\xff\xff\xff\xff\xff");
+    roundtrips(-1i8, b"\x20");
+    roundtrips(-10i8, b"\x29");
+    roundtrips(-100i8, b"\x38\x63");
+    roundtrips(-1000i16, b"\x39\x03\xe7");
+    roundtrips(
+        -18446744073709551616i128,
+        b"\x3b\xff\xff\xff\xff\xff\xff\xff\xff",
+    );
+
+    // Values which are coerced between signed and unsigned types.
+    decodes(b"\x18\x64", 100i32);
+    decodes(b"\x1b\xff\xff\xff\xff\xff\xff\xff\xff", u64::MAX as i128);
+    assert!(super::from_slice::<u8>(b"\x19\x03\xe8").is_err());
+    assert!(super::from_slice::<u32>(b"\x20").is_err());
+}
+
+#[test]
+fn bignums() {
+    roundtrips(
+        18446744073709551616u128,
+        b"\xc2\x49\x01\x00\x00\x00\x00\x00\x00\x00\x00",
+    );
+    roundtrips(
+        -18446744073709551617i128,
+        b"\xc3\x49\x01\x00\x00\x00\x00\x00\x00\x00\x00",
+    );
+    roundtrips(u128::MAX, &[&[0xc2, 0x50][..], &[0xff; 16]].concat());
+    roundtrips(i128::MIN, &[&[0xc3, 0x50, 0x7f][..], &[0xff; 15]].concat());
+
+    // Bignums which fit in 64 bits and with leading zeros are permitted.
+    decodes(b"\xc2\x41\x2a", 42u8);
+    decodes(b"\xc2\x44\x00\x00\x00\x2a", 42u64);
+    decodes(&[&[0xc2, 0x52, 0, 0][..], &[0xff; 16]].concat(), u128::MAX);
+    assert!(super::from_slice::<u128>(&[&[0xc2, 0x51, 0x01][..], &[0; 16]].concat()).is_err());
+}
+
+#[test]
+fn floats() {
+    roundtrips(100000.0f32, b"\xfa\x47\xc3\x50\x00");
+    roundtrips(f32::MAX, b"\xfa\x7f\x7f\xff\xff");
+    roundtrips(1.1f64, b"\xfb\x3f\xf1\x99\x99\x99\x99\x99\x9a");
+    roundtrips(-4.1f64, b"\xfb\xc0\x10\x66\x66\x66\x66\x66\x66");
+    roundtrips(1.0e+300f64, b"\xfb\x7e\x37\xe4\x3c\x88\x00\x75\x9c");
+
+    decodes(b"\xf9\x00\x00", 0.0f32);
+    decodes(b"\xf9\x3c\x00", 1.0f64);
+    decodes(b"\xf9\x3e\x00", 1.5f32);
+    decodes(b"\xf9\x7b\xff", 65504.0f64);
+    decodes(b"\xf9\xc4\x00", -4.0f32);
+    decodes(b"\xf9\x7c\x00", f32::INFINITY);
+    decodes(b"\xf9\xfc\x00", f64::NEG_INFINITY);
+    decodes(b"\xfa\x47\xc3\x50\x00", 100000.0f64);
+}
+
+#[test]
+fn half_precision() {
+    assert_eq!(f16_to_f32(0x8000).to_bits(), (-0.0f32).to_bits());
+    assert_eq!(f16_to_f32(0x0001), 5.9604645e-8);
+    assert_eq!(f16_to_f32(0x03ff), 6.097555e-5);
+    assert_eq!(f16_to_f32(0x0400), 6.1035156e-5);
+    assert!(f16_to_f32(0x7e00).is_nan());
+}
+
+#[test]
+fn simple_values() {
+    roundtrips(false, b"\xf4");
+    roundtrips(true, b"\xf5");
+    roundtrips(None::<u32>, b"\xf6");
+    roundtrips(Some(1u32), b"\x01");
+    roundtrips((), b"\xf6");
+    decodes(b"\xf7", None::<u32>);
+}
+
+#[test]
+fn strings() {
+    roundtrips(String::new(), b"\x60");
+    roundtrips(String::from("a"), b"\x61\x61");
+    roundtrips(String::from("IETF"), b"\x64\x49\x45\x54\x46");
+    roundtrips(String::from("\"\\"), b"\x62\x22\x5c");
+    roundtrips(String::from("\u{00fc}"), b"\x62\xc3\xbc");
+    roundtrips(String::from("\u{6c34}"), b"\x63\xe6\xb0\xb4");
+    roundtrips('\u{6c34}', b"\x63\xe6\xb0\xb4");
+    decodes(b"\x18\x61", 'a');
+
+    roundtrips(Bytes(Vec::<u8>::new()), b"\x40");
+    roundtrips(Bytes(vec![1u8, 2, 3, 4]), b"\x44\x01\x02\x03\x04");
+    roundtrips(Bytes([1u8, 2, 3, 4]), b"\x44\x01\x02\x03\x04");
+
+    assert!(super::from_slice::<String>(b"\x62\xc3\x28").is_err());
+}
+
+#[test]
+fn indefinite_strings() {
+    decodes(
+        b"\x7f\x65\x73\x74\x72\x65\x61\x64\x6d\x69\x6e\x67\xff",
+        String::from("streaming"),
+    );
+    decodes(
+        b"\x5f\x42\x01\x02\x43\x03\x04\x05\xff",
+        Bytes(vec![1u8, 2, 3, 4, 5]),
+    );
+    decodes(b"\x7f\xff", String::new());
+
+    // Chunks must be definite strings of the same type.
+    assert!(super::from_slice::<String>(b"\x7f\x42\x61\x62\xff").is_err());
+    assert!(super::from_slice::<String>(b"\x7f\x7f\xff\xff").is_err());
+}
+
+#[test]
+fn arrays_and_maps() {
+    roundtrips(Vec::<u32>::new(), b"\x80");
+    roundtrips(vec![1u32, 2, 3], b"\x83\x01\x02\x03");
+    roundtrips(
+        (1u32, vec![2u32, 3], vec![4u32, 5]),
+        b"\x83\x01\x82\x02\x03\x82\x04\x05",
+    );
+
+    let mut expected = b"\x98\x19".to_vec();
+    expected.extend(1u8..=23);
+    expected.extend_from_slice(b"\x18\x18\x18\x19");
+    roundtrips((1u32..=25).collect::<Vec<_>>(), &expected);
+
+    let mut map = rust_alloc::collections::BTreeMap::new();
+    map.insert(1u32, 2u32);
+    map.insert(3u32, 4u32);
+    roundtrips(map, b"\xa2\x01\x02\x03\x04");
+}
+
+#[test]
+fn indefinite_arrays_and_maps() {
+    decodes(b"\x9f\xff", Vec::<u32>::new());
+    decodes(
+        b"\x9f\x01\x82\x02\x03\x9f\x04\x05\xff\xff",
+        (1u32, vec![2u32, 3], vec![4u32, 5]),
+    );
+    decodes(
+        b"\x83\x01\x82\x02\x03\x9f\x04\x05\xff",
+        (1u32, vec![2u32, 3], vec![4u32, 5]),
+    );
+
+    #[derive(Debug, PartialEq, Decode)]
+    #[musli(crate, name_all = "name")]
+    struct Map {
+        a: u32,
+        b: Vec<u32>,
+    }
+
+    decodes(
+        b"\xbf\x61\x61\x01\x61\x62\x9f\x02\x03\xff\xff",
+        Map {
+            a: 1,
+            b: vec![2, 3],
+        },
+    );
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate, name_all = "name")]
+struct Person {
+    name: String,
+    age: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate, name_all = "name")]
+enum Animal {
+    Cat,
+    #[musli(name_all = "name")]
+    Dog {
+        name: String,
+    },
+}
+
+#[test]
+fn structs_and_variants() {
+    roundtrips(
+        Person {
+            name: String::from("Aristotle"),
+            age: 61,
+        },
+        b"\xa2\x64name\x69Aristotle\x63age\x18\x3d",
+    );
+
+    roundtrips(Animal::Cat, b"\xa1\x63Cat\xa0");
+    roundtrips(
+        Animal::Dog {
+            name: String::from("Fido"),
+        },
+        b"\xa1\x63Dog\xa1\x64name\x64Fido",
+    );
+
+    decodes(b"\xbf\x63Cat\xa0\xff", Animal::Cat);
+    assert!(super::from_slice::<Animal>(b"\xa0").is_err());
+    assert!(super::from_slice::<Animal>(b"\xa2\x63Cat\xa0\x63Cat\xa0").is_err());
+}
+
+#[test]
+fn skip_unknown() {
+    // Unknown fields containing tags, floats and indefinite length items are
+    // skipped over.
+    decodes(
+        b"\xa4\x64name\x69Aristotle\x65extra\xc1\xfb\x41\xd4\x52\xd9\xec\x20\x00\x00\x63age\x18\x3d\x64more\xbf\x61\x61\x5f\x41\x01\xff\x61\x62\x9f\xf9\x3c\x00\xf5\xff\xff",
+        Person {
+            name: String::from("Aristotle"),
+            age: 61,
+        },
+    );
+}
+
+#[test]
+fn skip_untrusted() {
+    // Lengths which overflow when counting the items of a map.
+    assert!(super::from_slice::<Person>(b"\xa1\x05\xbb\xff\xff\xff\xff\xff\xff\xff\xff").is_err());
+    assert!(super::from_slice::<Person>(b"\xa1\x61x\xbb\xff\xff\xff\xff\xff\xff\xff\xff").is_err());
+
+    // Deeply nested items are skipped without recursing.
+    let mut bytes = b"\xa3\x64name\x69Aristotle\x63age\x18\x3d\x61x".to_vec();
+
+    for _ in 0..100_000 {
+        bytes.extend_from_slice(b"\x9f\x81");
+    }
+
+    bytes.push(0x00);
+    bytes.extend((0..100_000).map(|_| 0xff));
+
+    decodes(
+        &bytes,
+        Person {
+            name: String::from("Aristotle"),
+            age: 61,
+        },
+    );
+}
+
+#[test]
+fn decode_any() {
+    let value: Value = super::from_slice(
+        b"\xa3\x61\x61\x20\x61\x62\x9f\x02\xc2\x49\x01\x00\x00\x00\x00\x00\x00\x00\x00\xff\x61\x63\xd8\x20\x63\x61\x62\x63",
+    )
+    .expect("failed to decode");
+
+    let string = |s: &str| Value::String(String::from(s));
+
+    assert_eq!(
+        value,
+        Value::Map(vec![
+            (string("a"), Value::Number((-1i64).into())),
+            (
+                string("b"),
+                Value::Sequence(vec![
+                    Value::Number(2u64.into()),
+                    Value::Number(18446744073709551616u128.into()),
+                ])
+            ),
+            (string("c"), string("abc")),
+        ])
+    );
+}
@@ -8,7 +8,7 @@ use crate::alloc::{self, Allocator, String};
 use crate::alloc::{System, SYSTEM};
 use crate::Context;
 
-use super::{ContextError, ErrorMarker};
+use super::{ContextError, ErrorKind, ErrorMarker};
 
 /// A simple non-diagnostical capturing context.
 pub struct Capture<M, E, A>
@@ -116,6 +116,30 @@ where
 
         ErrorMarker
     }
+
+    #[inline]
+    fn message_with_kind<T>(&self, kind: ErrorKind, message: T) -> ErrorMarker
+    where
+        T: fmt::Display,
+    {
+        // SAFETY: We're restricting access to the context, so that this is
+        // safe.
+        unsafe {
+            self.error
+                .get()
+                .replace(Some(E::message_with_kind(kind, message)));
+        }
+
+        ErrorMarker
+    }
+
+    #[inline]
+    fn marked_message_with_kind<T>(&self, _: (), kind: ErrorKind, message: T) -> ErrorMarker
+    where
+        T: fmt::Display,
+    {
+        self.message_with_kind(kind, message)
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -11,6 +11,8 @@ use core::fmt;
 #[cfg(feature = "alloc")]
 use rust_alloc::string::{String, ToString};
 
+use super::ErrorKind;
+
 /// Trait governing errors raised during encodeing or decoding.
 #[diagnostic::on_unimplemented(
     message = "`ContextError` must be implemented for `{Self}`, or any error type captured by custom contexts",
@@ -30,6 +32,19 @@ pub trait ContextError: Sized + 'static + Send + Sync + fmt::Display + fmt::Debu
     fn message<T>(message: T) -> Self
     where
         T: fmt::Display;
+
+    /// Construct an error of the given [`ErrorKind`] from a message.
+    ///
+    /// By default the kind is ignored and this forwards to
+    /// [`ContextError::message`].
+    #[allow(unused_variables)]
+    #[inline]
+    fn message_with_kind<T>(kind: ErrorKind, message: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        Self::message(message)
+    }
 }
 
 #[cfg(feature = "std")]
@@ -47,6 +62,20 @@ impl ContextError for std::io::Error {
     {
         std::io::Error::new(std::io::ErrorKind::Other, std::format!("{message}"))
     }
+
+    fn message_with_kind<T>(kind: ErrorKind, message: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        let kind = match kind {
+            ErrorKind::UnexpectedEof => std::io::ErrorKind::UnexpectedEof,
+            ErrorKind::AllocFailed => std::io::ErrorKind::OutOfMemory,
+            ErrorKind::Other => std::io::ErrorKind::Other,
+            _ => std::io::ErrorKind::InvalidData,
+        };
+
+        std::io::Error::new(kind, std::format!("{message}"))
+    }
 }
 
 #[cfg(feature = "alloc")]
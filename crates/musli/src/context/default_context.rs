@@ -12,7 +12,8 @@ use crate::alloc::System;
 use crate::alloc::{self, Allocator, String, Vec};
 use crate::Context;
 
-use super::{Access, ErrorMarker, Shared};
+use super::limits::{Limiter, Limits};
+use super::{Access, ErrorKind, ErrorMarker, Shared};
 
 /// The default context which uses an allocator to track the location of errors.
 ///
@@ -29,17 +30,39 @@ where
 {
     alloc: &'a A,
     mark: Cell<usize>,
-    errors: UnsafeCell<Vec<'a, (Range<usize>, String<'a, A>), A>>,
+    // Whether the input has been advanced through, which indicates that the
+    // format being decoded tracks positions.
+    advanced: Cell<bool>,
+    errors: UnsafeCell<Vec<'a, Collected<'a, A>, A>>,
+    // Snapshots of the paths at which collected errors were raised.
+    error_paths: UnsafeCell<Vec<'a, Step<'a, A>, A>>,
     path: UnsafeCell<Vec<'a, Step<'a, A>, A>>,
     // How many elements of `path` we've gone over capacity.
     cap: Cell<usize>,
     include_type: bool,
+    lenient: bool,
+    limiter: Limiter,
     access: Access,
     _marker: PhantomData<M>,
 }
 
 impl<'a, A, M> DefaultContext<'a, A, M> where A: ?Sized + Allocator {}
 
+/// A mark in a [`DefaultContext`], as returned by [`Context::mark`].
+///
+/// Apart from the position in the input, this also records how deep into the
+/// decoded value the context was and how much input has been consumed, so
+/// that [`Context::restore`] can unwind the path and the resources tracked for
+/// [`Limits`] left behind by a value which failed to decode.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Mark {
+    offset: usize,
+    path: usize,
+    cap: usize,
+    depth: usize,
+    input: usize,
+}
+
 #[cfg(feature = "alloc")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 impl<M> DefaultContext<'static, System, M> {
@@ -66,15 +89,20 @@ where
     /// configurable number of diagnostics.
     pub(super) fn with_alloc(alloc: &'a A) -> Self {
         let errors = Vec::new_in(alloc);
+        let error_paths = Vec::new_in(alloc);
         let path = Vec::new_in(alloc);
 
         Self {
             alloc,
             mark: Cell::new(0),
+            advanced: Cell::new(false),
             errors: UnsafeCell::new(errors),
+            error_paths: UnsafeCell::new(error_paths),
             path: UnsafeCell::new(path),
             cap: Cell::new(0),
             include_type: false,
+            lenient: false,
+            limiter: Limiter::new(Limits::new()),
             access: Access::new(),
             _marker: PhantomData,
         }
@@ -87,10 +115,181 @@ where
         self
     }
 
+    /// Configure the context to decode leniently.
+    ///
+    /// When a field which has a `#[musli(default)]` value fails to decode, the
+    /// error is collected and the field is skipped over and given its default
+    /// value, allowing decoding to continue. This makes it possible to load
+    /// partially corrupted data and report all problems with it at once.
+    ///
+    /// Recovering is only possible for formats which can reliably skip over
+    /// values, which are [`json`], [`wire`] and [`descriptive`]. In other
+    /// formats, or if the value can't be skipped because the input is
+    /// malformed, decoding fails like it normally would.
+    ///
+    /// Note that decoding succeeds even if errors were recovered from, so the
+    /// collected [`errors`] need to be checked separately.
+    ///
+    /// [`json`]: crate::json
+    /// [`wire`]: crate::wire
+    /// [`descriptive`]: crate::descriptive
+    /// [`errors`]: Self::errors
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Decode, Encode};
+    /// use musli::context;
+    /// use musli::json::Encoding;
+    ///
+    /// const ENCODING: Encoding = Encoding::new();
+    ///
+    /// #[derive(Debug, PartialEq, Decode, Encode)]
+    /// struct Record {
+    ///     name: String,
+    ///     #[musli(default)]
+    ///     age: u32,
+    ///     #[musli(default)]
+    ///     tags: Vec<String>,
+    /// }
+    ///
+    /// let input = br#"{"name": "Aristotle", "age": "old", "tags": ["a", 2]}"#;
+    ///
+    /// let mut cx = context::new();
+    /// cx.lenient();
+    ///
+    /// let record: Record = ENCODING.from_slice_with(&cx, input)?;
+    ///
+    /// assert_eq!(record, Record {
+    ///     name: String::from("Aristotle"),
+    ///     age: 0,
+    ///     tags: Vec::new(),
+    /// });
+    ///
+    /// let report = cx.report().to_string();
+    /// assert_eq!(report, "\
+    /// .age: Invalid numeric (at bytes 29-30)
+    /// .tags[1]: Expected string, found <number> (at byte 50)
+    /// ");
+    /// # Ok::<_, musli::context::ErrorMarker>(())
+    /// ```
+    pub fn lenient(&mut self) -> &mut Self {
+        self.lenient = true;
+        self
+    }
+
+    /// Configure the [`Limits`] which are enforced while decoding using this
+    /// context.
+    ///
+    /// Errors raised because a limit was exceeded are reported at the
+    /// position where it happened.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::context::{self, Limits};
+    /// use musli::json::Encoding;
+    ///
+    /// const ENCODING: Encoding = Encoding::new();
+    ///
+    /// let mut cx = context::new();
+    /// cx.with_limits(Limits::new().with_max_depth(4));
+    ///
+    /// let data = b"[[[[[[[[1]]]]]]]]";
+    /// let result = ENCODING.from_slice_with::<_, musli::value::Value>(&cx, data);
+    /// assert!(result.is_err());
+    ///
+    /// let report = cx.report().to_string();
+    /// assert_eq!(report, ": Values are nested deeper than the maximum depth of 4 (at byte 4)\n");
+    /// ```
+    pub fn with_limits(&mut self, limits: Limits) -> &mut Self {
+        self.limiter.set_limits(limits);
+        self
+    }
+
+    /// Reset the context so that it can be reused for another operation.
+    ///
+    /// This clears all collected errors, the current path, and the resources
+    /// tracked for the configured [`Limits`]. Configuration set through
+    /// [`include_type`], [`lenient`] and [`with_limits`] is kept, and so is the capacity of
+    /// the buffers holding diagnostics, so reusing a context avoids
+    /// allocating them again.
+    ///
+    /// Encoding and decoding through a context also clears it, so this only
+    /// needs to be called to release collected diagnostics early.
+    ///
+    /// [`include_type`]: Self::include_type
+    /// [`lenient`]: Self::lenient
+    /// [`with_limits`]: Self::with_limits
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::context;
+    /// use musli::json::Encoding;
+    ///
+    /// const ENCODING: Encoding = Encoding::new();
+    ///
+    /// let mut cx = context::new();
+    ///
+    /// let result = ENCODING.from_slice_with::<_, u32>(&cx, b"\"hello\"");
+    /// assert!(result.is_err());
+    /// assert_eq!(cx.errors().count(), 1);
+    ///
+    /// cx.reset();
+    /// assert_eq!(cx.errors().count(), 0);
+    ///
+    /// let value: u32 = ENCODING.from_slice_with(&cx, b"42")?;
+    /// assert_eq!(value, 42);
+    /// # Ok::<_, musli::context::ErrorMarker>(())
+    /// ```
+    pub fn reset(&mut self) {
+        self.mark.set(0);
+        self.advanced.set(false);
+        self.cap.set(0);
+        self.limiter.clear();
+        self.errors.get_mut().clear();
+        self.error_paths.get_mut().clear();
+        self.path.get_mut().clear();
+    }
+
+    /// Convert the context into one which uses a different mode, keeping its
+    /// configuration and allocated buffers.
+    #[cfg(all(feature = "std", feature = "alloc"))]
+    pub(super) fn into_mode<T>(self) -> DefaultContext<'a, A, T> {
+        DefaultContext {
+            alloc: self.alloc,
+            mark: self.mark,
+            advanced: self.advanced,
+            errors: self.errors,
+            error_paths: self.error_paths,
+            path: self.path,
+            cap: self.cap,
+            include_type: self.include_type,
+            lenient: self.lenient,
+            limiter: self.limiter,
+            access: self.access,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Restore the default configuration of the context.
+    #[cfg(all(feature = "std", feature = "alloc"))]
+    pub(super) fn reset_config(&mut self) {
+        self.include_type = false;
+        self.lenient = false;
+        self.limiter.set_limits(Limits::new());
+    }
+
     /// Generate a line-separated report of all collected errors.
+    ///
+    /// Positions are reported as byte offsets into the input, use
+    /// [`Report::with_source`] to report them as lines and columns instead, or
+    /// [`Report::with_hexdump`] to show the bytes surrounding each error.
     pub fn report(&self) -> Report<'_, 'a, A> {
         Report {
             errors: self.errors(),
+            source: None,
         }
     }
 
@@ -99,23 +298,71 @@ where
         let access = self.access.shared();
 
         Errors {
-            path: unsafe { (*self.path.get()).as_slice() },
+            paths: unsafe { (*self.error_paths.get()).as_slice() },
             errors: unsafe { (*self.errors.get()).as_slice().iter() },
-            cap: self.cap.get(),
             _access: access,
         }
     }
 
-    /// Push an error into the collection.
-    fn push_error(&self, range: Range<usize>, error: String<'a, A>) {
+    /// Push an error into the collection, together with a snapshot of the
+    /// current path.
+    fn push_error(&self, range: Option<Range<usize>>, kind: ErrorKind, message: String<'a, A>) {
         let _access = self.access.exclusive();
 
         // SAFETY: We've checked that we have exclusive access just above.
         unsafe {
-            _ = (*self.errors.get()).push((range, error));
+            let path = (*self.path.get()).as_slice();
+            let error_paths = &mut *self.error_paths.get();
+
+            let start = error_paths.len();
+            let mut cap = self.cap.get();
+
+            for (n, step) in path.iter().enumerate() {
+                let pushed = match self.copy_step(step) {
+                    Some(step) => error_paths.push(step),
+                    None => false,
+                };
+
+                // Steps which can't be stored are capped, just like steps
+                // which couldn't be added to the path.
+                if !pushed {
+                    cap += path.len() - n;
+                    break;
+                }
+            }
+
+            _ = (*self.errors.get()).push(Collected {
+                range,
+                kind,
+                message,
+                path: start..error_paths.len(),
+                cap,
+            });
         }
     }
 
+    /// The position of an error which wasn't raised at a mark.
+    ///
+    /// This is only known if the format being decoded tracks positions, which
+    /// it does if it has advanced through the input.
+    #[inline]
+    fn position(&self) -> Option<Range<usize>> {
+        let offset = self.mark.get();
+        self.advanced.get().then_some(offset..offset)
+    }
+
+    fn copy_step(&self, step: &Step<'a, A>) -> Option<Step<'a, A>> {
+        Some(match step {
+            Step::Struct(name) => Step::Struct(name),
+            Step::Enum(name) => Step::Enum(name),
+            Step::Variant(name) => Step::Variant(name),
+            Step::Named(name) => Step::Named(name),
+            Step::Unnamed(index) => Step::Unnamed(*index),
+            Step::Index(index) => Step::Index(*index),
+            Step::Key(key) => Step::Key(self.format_string(key)?),
+        })
+    }
+
     /// Push a path.
     fn push_path(&self, step: Step<'a, A>) {
         let _access = self.access.exclusive();
@@ -162,18 +409,22 @@ where
 {
     type Mode = M;
     type Error = ErrorMarker;
-    type Mark = usize;
+    type Mark = Mark;
     type Allocator = A;
     type String<'this> = String<'this, A> where Self: 'this;
 
     #[inline]
     fn clear(&self) {
         self.mark.set(0);
+        self.advanced.set(false);
+        self.cap.set(0);
+        self.limiter.clear();
         let _access = self.access.exclusive();
 
         // SAFETY: We have acquired exclusive access just above.
         unsafe {
             (*self.errors.get()).clear();
+            (*self.error_paths.get()).clear();
             (*self.path.get()).clear();
         }
     }
@@ -197,7 +448,7 @@ where
         T: 'static + Send + Sync + fmt::Display + fmt::Debug,
     {
         if let Some(string) = self.format_string(message) {
-            self.push_error(self.mark.get()..self.mark.get(), string);
+            self.push_error(self.position(), ErrorKind::Other, string);
         }
 
         ErrorMarker
@@ -205,23 +456,44 @@ where
 
     #[inline]
     fn message<T>(&self, message: T) -> Self::Error
+    where
+        T: fmt::Display,
+    {
+        self.message_with_kind(ErrorKind::Other, message)
+    }
+
+    #[inline]
+    fn marked_message<T>(&self, mark: Self::Mark, message: T) -> Self::Error
+    where
+        T: fmt::Display,
+    {
+        self.marked_message_with_kind(mark, ErrorKind::Other, message)
+    }
+
+    #[inline]
+    fn message_with_kind<T>(&self, kind: ErrorKind, message: T) -> Self::Error
     where
         T: fmt::Display,
     {
         if let Some(string) = self.format_string(message) {
-            self.push_error(self.mark.get()..self.mark.get(), string);
+            self.push_error(self.position(), kind, string);
         }
 
         ErrorMarker
     }
 
     #[inline]
-    fn marked_message<T>(&self, mark: Self::Mark, message: T) -> Self::Error
+    fn marked_message_with_kind<T>(
+        &self,
+        mark: Self::Mark,
+        kind: ErrorKind,
+        message: T,
+    ) -> Self::Error
     where
         T: fmt::Display,
     {
         if let Some(string) = self.format_string(message) {
-            self.push_error(mark..self.mark.get(), string);
+            self.push_error(Some(mark.offset..self.mark.get()), kind, string);
         }
 
         ErrorMarker
@@ -233,7 +505,7 @@ where
         T: 'static + Send + Sync + fmt::Display + fmt::Debug,
     {
         if let Some(string) = self.format_string(message) {
-            self.push_error(mark..self.mark.get(), string);
+            self.push_error(Some(mark.offset..self.mark.get()), ErrorKind::Other, string);
         }
 
         ErrorMarker
@@ -241,12 +513,83 @@ where
 
     #[inline]
     fn mark(&self) -> Self::Mark {
-        self.mark.get()
+        let _access = self.access.shared();
+
+        // SAFETY: We've checked that we have shared access just above.
+        let path = unsafe { (*self.path.get()).len() };
+
+        Mark {
+            offset: self.mark.get(),
+            path,
+            cap: self.cap.get(),
+            depth: self.limiter.depth(),
+            input: self.limiter.input(),
+        }
     }
 
     #[inline]
     fn advance(&self, n: usize) {
         self.mark.set(self.mark.get().wrapping_add(n));
+        self.advanced.set(true);
+        self.limiter.advance(n);
+    }
+
+    #[inline]
+    fn restore(&self, mark: Self::Mark) {
+        self.mark.set(mark.offset);
+        self.limiter.set_depth(mark.depth);
+        self.limiter.set_input(mark.input);
+
+        // Steps pushed after the mark was taken were left behind by a value
+        // which failed to decode, since errors skip the matching `leave_*`.
+        let _access = self.access.exclusive();
+
+        // SAFETY: We've checked that we have exclusive access just above.
+        let path = unsafe { &mut (*self.path.get()) };
+
+        while path.len() > mark.path {
+            path.pop();
+        }
+
+        self.cap.set(mark.cap);
+    }
+
+    #[inline]
+    fn is_lenient(&self) -> bool {
+        self.lenient
+    }
+
+    #[inline]
+    fn check_input(&self, n: usize) -> Result<(), Self::Error> {
+        self.limiter
+            .check_input(n)
+            .map_err(|error| self.message_with_kind(ErrorKind::LimitExceeded, error))
+    }
+
+    #[inline]
+    fn check_length(&self, len: usize) -> Result<(), Self::Error> {
+        self.limiter
+            .check_length(len)
+            .map_err(|error| self.message_with_kind(ErrorKind::LimitExceeded, error))
+    }
+
+    #[inline]
+    fn check_size(&self, len: usize) -> Result<(), Self::Error> {
+        self.limiter
+            .check_size(len)
+            .map_err(|error| self.message_with_kind(ErrorKind::LimitExceeded, error))
+    }
+
+    #[inline]
+    fn enter_nested(&self) -> Result<(), Self::Error> {
+        self.limiter
+            .enter_nested()
+            .map_err(|error| self.message_with_kind(ErrorKind::LimitExceeded, error))
+    }
+
+    #[inline]
+    fn leave_nested(&self) {
+        self.limiter.leave_nested();
     }
 
     #[inline]
@@ -340,6 +683,114 @@ where
     A: 'a + ?Sized + Allocator,
 {
     errors: Errors<'b, 'a, A>,
+    source: Option<Source<'b>>,
+}
+
+/// The source a [`Report`] is rendered against.
+#[derive(Clone, Copy)]
+enum Source<'b> {
+    Text(&'b [u8]),
+    Binary(&'b [u8]),
+}
+
+impl<'b, 'a, A> Report<'b, 'a, A>
+where
+    A: 'a + ?Sized + Allocator,
+{
+    /// Render the report against the input that was decoded.
+    ///
+    /// Instead of byte offsets, the position of each error is reported as a
+    /// line and a column followed by a snippet of the offending line, with
+    /// the range of the error marked by carets. This is useful for text
+    /// formats like JSON where errors are read by humans.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Decode, Encode};
+    /// use musli::context;
+    /// use musli::json::Encoding;
+    ///
+    /// const ENCODING: Encoding = Encoding::new();
+    ///
+    /// #[derive(Decode, Encode)]
+    /// struct Config {
+    ///     name: String,
+    ///     port: u16,
+    /// }
+    ///
+    /// let input = "{\n  \"name\": \"server\",\n  \"port\": 100000\n}";
+    ///
+    /// let cx = context::new();
+    /// let result = ENCODING.from_str_with::<_, Config>(&cx, input);
+    /// assert!(result.is_err());
+    ///
+    /// let report = cx.report().with_source(input.as_bytes()).to_string();
+    /// assert_eq!(report, "\
+    /// .port: Arithmetic overflow (at line 3, column 11)
+    ///   |
+    /// 3 |   \"port\": 100000
+    ///   |           ^^^^^
+    /// ");
+    /// ```
+    pub fn with_source(self, source: &'b [u8]) -> Self {
+        Self {
+            errors: self.errors,
+            source: Some(Source::Text(source)),
+        }
+    }
+
+    /// Render the report against the binary input that was decoded.
+    ///
+    /// Each error is followed by a hexdump of the input surrounding it, with
+    /// the bytes it refers to marked by carets. This is useful for binary
+    /// formats like [`storage`], [`wire`] or [`descriptive`]. Consider
+    /// enabling [`DefaultContext::include_type`] so that the path shows the
+    /// types which were being decoded.
+    ///
+    /// [`storage`]: crate::storage
+    /// [`wire`]: crate::wire
+    /// [`descriptive`]: crate::descriptive
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Decode, Encode};
+    /// use musli::context;
+    /// use musli::storage::Encoding;
+    ///
+    /// const ENCODING: Encoding = Encoding::new();
+    ///
+    /// #[derive(Decode, Encode)]
+    /// struct Record {
+    ///     id: u32,
+    ///     enabled: bool,
+    /// }
+    ///
+    /// let input = ENCODING.to_vec(&Record { id: 42, enabled: true })?;
+    /// let mut corrupt = input.clone();
+    /// *corrupt.last_mut().unwrap() = 7;
+    ///
+    /// let mut cx = context::new();
+    /// cx.include_type();
+    ///
+    /// let result = ENCODING.from_slice_with::<_, Record>(&cx, &corrupt);
+    /// assert!(result.is_err());
+    ///
+    /// let report = cx.report().with_hexdump(&corrupt).to_string();
+    /// assert_eq!(report, "\
+    /// Record { .enabled }: Bad boolean byte 0x07 (at bytes 4-5)
+    /// 00000000: 02 00 2a 01 07                                   |..*..|
+    ///                       ^^
+    /// ");
+    /// # Ok::<_, musli::storage::Error>(())
+    /// ```
+    pub fn with_hexdump(self, source: &'b [u8]) -> Self {
+        Self {
+            errors: self.errors,
+            source: Some(Source::Binary(source)),
+        }
+    }
 }
 
 impl<'b, 'a, A> fmt::Display for Report<'b, 'a, A>
@@ -348,7 +799,13 @@ where
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for error in self.errors.clone() {
-            writeln!(f, "{error}")?;
+            match (self.source, &error.range) {
+                (Some(Source::Text(source)), Some(range)) => error.fmt_snippet(f, source, range)?,
+                (Some(Source::Binary(source)), Some(range)) => {
+                    error.fmt_hexdump(f, source, range)?
+                }
+                _ => writeln!(f, "{error}")?,
+            }
         }
 
         Ok(())
@@ -362,9 +819,8 @@ pub struct Errors<'b, 'a, A>
 where
     A: 'a + ?Sized + Allocator,
 {
-    path: &'b [Step<'a, A>],
-    cap: usize,
-    errors: slice::Iter<'b, (Range<usize>, String<'a, A>)>,
+    paths: &'b [Step<'a, A>],
+    errors: slice::Iter<'b, Collected<'a, A>>,
     _access: Shared<'b>,
 }
 
@@ -376,8 +832,15 @@ where
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        let (range, error) = self.errors.next()?;
-        Some(Error::new(self.path, self.cap, range.clone(), error))
+        let error = self.errors.next()?;
+
+        Some(Error::new(
+            &self.paths[error.path.clone()],
+            error.cap,
+            error.range.clone(),
+            error.kind,
+            &error.message,
+        ))
     }
 }
 
@@ -387,14 +850,27 @@ where
 {
     fn clone(&self) -> Self {
         Self {
-            path: self.path,
-            cap: self.cap,
+            paths: self.paths,
             errors: self.errors.clone(),
             _access: self._access.clone(),
         }
     }
 }
 
+/// An error collected by a [`DefaultContext`].
+struct Collected<'a, A>
+where
+    A: 'a + ?Sized + Allocator,
+{
+    /// The range of input the error refers to, if it has a position.
+    range: Option<Range<usize>>,
+    kind: ErrorKind,
+    message: String<'a, A>,
+    /// The range of steps in `error_paths` which make up the path of the error.
+    path: Range<usize>,
+    cap: usize,
+}
+
 /// A collected error which has been context decorated.
 pub struct Error<'b, 'a, A>
 where
@@ -402,7 +878,8 @@ where
 {
     path: &'b [Step<'a, A>],
     cap: usize,
-    range: Range<usize>,
+    range: Option<Range<usize>>,
+    kind: ErrorKind,
     error: &'b str,
 }
 
@@ -410,35 +887,326 @@ impl<'b, 'a, A> Error<'b, 'a, A>
 where
     A: 'a + ?Sized + Allocator,
 {
-    fn new(path: &'b [Step<'a, A>], cap: usize, range: Range<usize>, error: &'b str) -> Self {
+    fn new(
+        path: &'b [Step<'a, A>],
+        cap: usize,
+        range: Option<Range<usize>>,
+        kind: ErrorKind,
+        error: &'b str,
+    ) -> Self {
         Self {
             path,
             cap,
             range,
+            kind,
             error,
         }
     }
+
+    /// Get the kind of the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::context::{self, ErrorKind};
+    /// use musli::json::Encoding;
+    ///
+    /// const ENCODING: Encoding = Encoding::new();
+    ///
+    /// let cx = context::new();
+    /// let result = ENCODING.from_slice_with::<_, u8>(&cx, b"1000");
+    /// assert!(result.is_err());
+    ///
+    /// let error = cx.errors().next().unwrap();
+    /// assert_eq!(error.kind(), ErrorKind::NumberOverflow);
+    /// assert_eq!(error.range(), 0..3);
+    /// assert_eq!(error.message(), "Arithmetic overflow");
+    /// ```
+    #[inline]
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Get the range of bytes in the input which the error refers to.
+    ///
+    /// This is empty if the error refers to a single position, or if the
+    /// format doesn't track positions.
+    #[inline]
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone().unwrap_or_default()
+    }
+
+    /// Get the line and column in `source` at which the error starts.
+    ///
+    /// Both the line and the column are 1-based, and columns are counted in
+    /// characters. Returns `None` if the error doesn't have a position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::context;
+    /// use musli::json::Encoding;
+    ///
+    /// const ENCODING: Encoding = Encoding::new();
+    ///
+    /// let input = "[\n  1,\n  true\n]";
+    ///
+    /// let cx = context::new();
+    /// let result = ENCODING.from_str_with::<_, Vec<u32>>(&cx, input);
+    /// assert!(result.is_err());
+    ///
+    /// let error = cx.errors().next().unwrap();
+    /// assert_eq!(error.line_column(input.as_bytes()), Some((3, 3)));
+    /// ```
+    pub fn line_column(&self, source: &[u8]) -> Option<(usize, usize)> {
+        let range = self.range.as_ref()?;
+        let line = locate(source, range.start);
+        Some((line.number, line.column))
+    }
+
+    /// Get the message of the error, without its path or position.
+    #[inline]
+    pub fn message(&self) -> &'b str {
+        self.error
+    }
+
+    /// Get the path to the value where the error was raised, formatted in the
+    /// same way as in a [`Report`].
+    #[inline]
+    pub fn path(&self) -> ErrorPath<'b, 'a, A> {
+        ErrorPath::new(self.path, self.cap)
+    }
 }
 
+impl<'b, 'a, A> Error<'b, 'a, A>
+where
+    A: 'a + ?Sized + Allocator,
+{
+    /// Format the error with its `range` resolved against `source`, followed
+    /// by a caret-annotated snippet of the line it starts on.
+    fn fmt_snippet(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        source: &[u8],
+        range: &Range<usize>,
+    ) -> fmt::Result {
+        let path = ErrorPath::new(self.path, self.cap);
+        let line = locate(source, range.start);
+
+        writeln!(
+            f,
+            "{path}: {} (at line {}, column {})",
+            self.error, line.number, line.column
+        )?;
+
+        let text = &source[line.start..line.end];
+        let text = text.strip_suffix(b"\r").unwrap_or(text);
+        let start = range.start.min(line.start + text.len()) - line.start;
+        let end = range.end.clamp(line.start + start, line.start + text.len()) - line.start;
+
+        let number = line.number;
+        let width = count_digits(number);
+
+        writeln!(f, "{:width$} |", "")?;
+        writeln!(f, "{number} | {}", Utf8Lossy(text))?;
+        write!(f, "{:width$} | ", "")?;
+
+        // Preserve tabs so that the carets line up with the snippet.
+        for c in Utf8Lossy(&text[..start]).chars() {
+            f.write_char(if c == '\t' { '\t' } else { ' ' })?;
+        }
+
+        for _ in 0..Utf8Lossy(&text[start..end]).chars().count().max(1) {
+            f.write_char('^')?;
+        }
+
+        writeln!(f)
+    }
+
+    /// Format the error followed by an annotated hexdump of the rows of
+    /// `source` surrounding its `range`.
+    fn fmt_hexdump(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        source: &[u8],
+        range: &Range<usize>,
+    ) -> fmt::Result {
+        writeln!(f, "{self}")?;
+
+        // An empty range marks the single byte at its position.
+        let marked = range.start..range.end.max(range.start + 1);
+
+        // Include one row of context on each side of the marked rows, but only
+        // if it contains input.
+        let end_row = source.len().saturating_sub(1) / HEX_WIDTH;
+        let first = (marked.start / HEX_WIDTH).saturating_sub(1);
+        let last = (marked.end - 1) / HEX_WIDTH;
+        let last = last.max((last + 1).min(end_row));
+
+        let total = last - first + 1;
+
+        for row in first..=last {
+            if total > HEX_MAX_ROWS {
+                let skip = first + HEX_MAX_ROWS / 2..=last - HEX_MAX_ROWS / 2;
+
+                if row == *skip.start() {
+                    writeln!(f, "...")?;
+                }
+
+                if skip.contains(&row) {
+                    continue;
+                }
+            }
+
+            let offset = row * HEX_WIDTH;
+            let bytes = source.get(offset..).unwrap_or_default();
+            let bytes = &bytes[..bytes.len().min(HEX_WIDTH)];
+
+            write!(f, "{offset:08x}:")?;
+
+            for n in 0..HEX_WIDTH {
+                match bytes.get(n) {
+                    Some(b) => write!(f, " {b:02x}")?,
+                    None => write!(f, "   ")?,
+                }
+            }
+
+            write!(f, "  |")?;
+
+            for &b in bytes {
+                let c = if b.is_ascii_graphic() || b == b' ' {
+                    b as char
+                } else {
+                    '.'
+                };
+
+                f.write_char(c)?;
+            }
+
+            writeln!(f, "|")?;
+
+            let start = marked.start.max(offset);
+            let end = marked.end.min(offset + HEX_WIDTH);
+
+            if start < end {
+                write!(f, "{:1$}", "", 10 + (start - offset) * 3)?;
+
+                for n in start..end {
+                    if n > start {
+                        f.write_char(' ')?;
+                    }
+
+                    f.write_str("^^")?;
+                }
+
+                writeln!(f)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The number of bytes in each row of a hexdump.
+const HEX_WIDTH: usize = 16;
+
+/// The maximum number of rows in a hexdump before rows in the middle are
+/// elided.
+const HEX_MAX_ROWS: usize = 8;
+
 impl<'a, A> fmt::Display for Error<'_, 'a, A>
 where
     A: 'a + ?Sized + Allocator,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let path = FormatPath::new(self.path, self.cap);
+        let path = ErrorPath::new(self.path, self.cap);
 
-        if self.range.start != 0 || self.range.end != 0 {
-            if self.range.start == self.range.end {
-                write!(f, "{path}: {} (at byte {})", self.error, self.range.start)?;
-            } else {
+        match &self.range {
+            Some(range) if range.start == range.end => {
+                write!(f, "{path}: {} (at byte {})", self.error, range.start)?;
+            }
+            Some(range) => {
                 write!(
                     f,
                     "{path}: {} (at bytes {}-{})",
-                    self.error, self.range.start, self.range.end
+                    self.error, range.start, range.end
                 )?;
             }
-        } else {
-            write!(f, "{path}: {}", self.error)?;
+            None => {
+                write!(f, "{path}: {}", self.error)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The line in a source which contains a given offset.
+struct Line {
+    /// The 1-based line number.
+    number: usize,
+    /// The 1-based column of the offset in characters.
+    column: usize,
+    /// The byte offset at which the line starts.
+    start: usize,
+    /// The byte offset at which the line ends, excluding the line feed.
+    end: usize,
+}
+
+/// Locate the line which contains `offset` in `source`.
+fn locate(source: &[u8], offset: usize) -> Line {
+    let offset = offset.min(source.len());
+    let before = &source[..offset];
+
+    let start = before
+        .iter()
+        .rposition(|&b| b == b'\n')
+        .map_or(0, |n| n + 1);
+
+    let end = source[offset..]
+        .iter()
+        .position(|&b| b == b'\n')
+        .map_or(source.len(), |n| offset + n);
+
+    let number = before.iter().filter(|&&b| b == b'\n').count() + 1;
+    let column = Utf8Lossy(&before[start..]).chars().count() + 1;
+
+    Line {
+        number,
+        column,
+        start,
+        end,
+    }
+}
+
+fn count_digits(mut n: usize) -> usize {
+    let mut digits = 1;
+
+    while n >= 10 {
+        n /= 10;
+        digits += 1;
+    }
+
+    digits
+}
+
+/// Helper to treat a byte slice as text, where each invalid UTF-8 sequence is
+/// treated as a single replacement character.
+struct Utf8Lossy<'s>(&'s [u8]);
+
+impl<'s> Utf8Lossy<'s> {
+    fn chars(&self) -> impl Iterator<Item = char> + 's {
+        self.0.utf8_chunks().flat_map(|chunk| {
+            let invalid = (!chunk.invalid().is_empty()).then_some(char::REPLACEMENT_CHARACTER);
+            chunk.valid().chars().chain(invalid)
+        })
+    }
+}
+
+impl fmt::Display for Utf8Lossy<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.chars() {
+            f.write_char(c)?;
         }
 
         Ok(())
@@ -460,7 +1228,10 @@ where
     Key(String<'a, A>),
 }
 
-struct FormatPath<'b, 'a, A>
+/// The path to the value where an error was raised.
+///
+/// See [`Error::path`].
+pub struct ErrorPath<'b, 'a, A>
 where
     A: 'a + ?Sized + Allocator,
 {
@@ -468,7 +1239,7 @@ where
     cap: usize,
 }
 
-impl<'b, 'a, A> FormatPath<'b, 'a, A>
+impl<'b, 'a, A> ErrorPath<'b, 'a, A>
 where
     A: 'a + ?Sized + Allocator,
 {
@@ -477,7 +1248,7 @@ where
     }
 }
 
-impl<'a, A> fmt::Display for FormatPath<'_, 'a, A>
+impl<'a, A> fmt::Display for ErrorPath<'_, 'a, A>
 where
     A: 'a + ?Sized + Allocator,
 {
@@ -0,0 +1,258 @@
+use core::cell::Cell;
+use core::fmt;
+
+/// Limits on the resources which can be used while decoding.
+///
+/// These are enforced by the decoders of the [`storage`], [`wire`],
+/// [`descriptive`], [`json`], [`cbor`], [`msgpack`] and [`protobuf`] formats
+/// when used with a context which has been configured using them, like
+/// [`DefaultContext::with_limits`] or [`Same::with_limits`]. This makes it
+/// possible to safely decode untrusted input, since it can't nest values
+/// arbitrarily deep or make the decoder allocate based on attacker-supplied
+/// lengths. The `csv`, `toml`, `urlencoded` and `env` formats don't enforce
+/// them.
+///
+/// By default no limits are enforced.
+///
+/// [`storage`]: crate::storage
+/// [`wire`]: crate::wire
+/// [`descriptive`]: crate::descriptive
+/// [`json`]: crate::json
+/// [`cbor`]: crate::cbor
+/// [`msgpack`]: crate::msgpack
+/// [`protobuf`]: crate::protobuf
+/// [`DefaultContext::with_limits`]: super::DefaultContext::with_limits
+/// [`Same::with_limits`]: super::Same::with_limits
+///
+/// # Examples
+///
+/// ```
+/// use musli::context::{Limits, Same};
+/// use musli::mode::Binary;
+/// use musli::wire::Error;
+///
+/// const LIMITS: Limits = Limits::new().with_max_depth(2);
+///
+/// let data = musli::wire::to_vec(&vec![vec![vec![1u32]]])?;
+///
+/// let cx = Same::<Binary, Error, _>::new().with_limits(LIMITS);
+/// let error = musli::wire::DEFAULT.from_slice_with::<_, Vec<Vec<Vec<u32>>>>(&cx, &data).unwrap_err();
+/// assert_eq!(error.to_string(), "Values are nested deeper than the maximum depth of 2");
+///
+/// let cx = Same::<Binary, Error, _>::new().with_limits(Limits::new().with_max_depth(3));
+/// let value: Vec<Vec<Vec<u32>>> = musli::wire::DEFAULT.from_slice_with(&cx, &data)?;
+/// assert_eq!(value, [[[1]]]);
+/// # Ok::<_, Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Limits {
+    max_depth: usize,
+    max_length: usize,
+    max_size: usize,
+    max_input: usize,
+}
+
+impl Limits {
+    /// Construct a new set of limits, where nothing is limited.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            max_depth: usize::MAX,
+            max_length: usize::MAX,
+            max_size: usize::MAX,
+            max_input: usize::MAX,
+        }
+    }
+
+    /// Set the maximum depth to which sequences, maps and variants can be
+    /// nested.
+    #[inline]
+    pub const fn with_max_depth(self, max_depth: usize) -> Self {
+        Self { max_depth, ..self }
+    }
+
+    /// Set the maximum number of elements in a sequence or a map.
+    #[inline]
+    pub const fn with_max_length(self, max_length: usize) -> Self {
+        Self { max_length, ..self }
+    }
+
+    /// Set the maximum number of bytes in a string or a byte sequence.
+    #[inline]
+    pub const fn with_max_size(self, max_size: usize) -> Self {
+        Self { max_size, ..self }
+    }
+
+    /// Set the maximum number of bytes of input which can be consumed.
+    #[inline]
+    pub const fn with_max_input(self, max_input: usize) -> Self {
+        Self { max_input, ..self }
+    }
+
+    /// Get the maximum depth to which values can be nested.
+    #[inline]
+    pub const fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Get the maximum number of elements in a sequence or a map.
+    #[inline]
+    pub const fn max_length(&self) -> usize {
+        self.max_length
+    }
+
+    /// Get the maximum number of bytes in a string or a byte sequence.
+    #[inline]
+    pub const fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    /// Get the maximum number of bytes of input which can be consumed.
+    #[inline]
+    pub const fn max_input(&self) -> usize {
+        self.max_input
+    }
+}
+
+impl Default for Limits {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Tracks the resources used while decoding and enforces [`Limits`].
+pub(super) struct Limiter {
+    limits: Limits,
+    depth: Cell<usize>,
+    input: Cell<usize>,
+}
+
+impl Limiter {
+    #[inline]
+    pub(super) const fn new(limits: Limits) -> Self {
+        Self {
+            limits,
+            depth: Cell::new(0),
+            input: Cell::new(0),
+        }
+    }
+
+    #[inline]
+    pub(super) fn set_limits(&mut self, limits: Limits) {
+        self.limits = limits;
+    }
+
+    #[inline]
+    pub(super) fn clear(&self) {
+        self.depth.set(0);
+        self.input.set(0);
+    }
+
+    #[inline]
+    pub(super) fn advance(&self, n: usize) {
+        self.input.set(self.input.get().saturating_add(n));
+    }
+
+    #[inline]
+    pub(super) fn check_input(&self, n: usize) -> Result<(), LimitError> {
+        if self.input.get().saturating_add(n) > self.limits.max_input {
+            return Err(LimitError::Input(self.limits.max_input));
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    pub(super) fn check_length(&self, len: usize) -> Result<(), LimitError> {
+        if len > self.limits.max_length {
+            return Err(LimitError::Length(len, self.limits.max_length));
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    pub(super) fn check_size(&self, len: usize) -> Result<(), LimitError> {
+        if len > self.limits.max_size {
+            return Err(LimitError::Size(len, self.limits.max_size));
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    pub(super) fn enter_nested(&self) -> Result<(), LimitError> {
+        let depth = self.depth.get();
+
+        if depth >= self.limits.max_depth {
+            return Err(LimitError::Depth(self.limits.max_depth));
+        }
+
+        self.depth.set(depth + 1);
+        Ok(())
+    }
+
+    #[inline]
+    pub(super) fn input(&self) -> usize {
+        self.input.get()
+    }
+
+    #[inline]
+    pub(super) fn set_input(&self, input: usize) {
+        self.input.set(input);
+    }
+
+    #[inline]
+    pub(super) fn depth(&self) -> usize {
+        self.depth.get()
+    }
+
+    #[inline]
+    pub(super) fn set_depth(&self, depth: usize) {
+        self.depth.set(depth);
+    }
+
+    #[inline]
+    pub(super) fn leave_nested(&self) {
+        self.depth.set(self.depth.get().saturating_sub(1));
+    }
+}
+
+/// An error raised when a limit is exceeded.
+pub(super) enum LimitError {
+    Input(usize),
+    Length(usize, usize),
+    Size(usize, usize),
+    Depth(usize),
+}
+
+impl fmt::Display for LimitError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            LimitError::Input(max) => {
+                write!(f, "Input exceeds the maximum of {max} bytes")
+            }
+            LimitError::Length(len, max) => {
+                write!(
+                    f,
+                    "Collection of {len} elements exceeds the maximum length of {max}"
+                )
+            }
+            LimitError::Size(len, max) => {
+                write!(
+                    f,
+                    "String or bytes of {len} bytes exceeds the maximum size of {max} bytes"
+                )
+            }
+            LimitError::Depth(max) => {
+                write!(
+                    f,
+                    "Values are nested deeper than the maximum depth of {max}"
+                )
+            }
+        }
+    }
+}
@@ -9,9 +9,12 @@ mod error_marker;
 #[doc(inline)]
 pub use self::error_marker::ErrorMarker;
 
+#[doc(inline)]
+pub use musli_core::ErrorKind;
+
 mod default_context;
 #[doc(inline)]
-pub use self::default_context::{DefaultContext, Error};
+pub use self::default_context::{DefaultContext, Error, ErrorPath, Errors, Mark, Report};
 
 mod context_error;
 #[doc(inline)]
@@ -29,6 +32,28 @@ mod ignore;
 #[doc(inline)]
 pub use self::ignore::Ignore;
 
+mod limits;
+#[doc(inline)]
+pub use self::limits::Limits;
+
+#[cfg(feature = "alloc")]
+mod profile;
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use self::profile::{Profile, ProfileEntry, ProfileReport};
+
+#[cfg(feature = "tracing")]
+mod tracing;
+#[cfg(feature = "tracing")]
+#[doc(inline)]
+pub use self::tracing::Tracing;
+
+#[cfg(all(feature = "std", feature = "alloc"))]
+mod pool;
+#[cfg(all(feature = "std", feature = "alloc"))]
+#[doc(inline)]
+pub use self::pool::pooled;
+
 use crate::alloc::Allocator;
 #[cfg(feature = "alloc")]
 use crate::alloc::System;
@@ -0,0 +1,83 @@
+use core::cell::RefCell;
+
+use rust_alloc::vec::Vec;
+
+use crate::alloc::System;
+
+use super::DefaultContext;
+
+/// The maximum number of contexts which are kept around per thread.
+const MAX_POOLED: usize = 8;
+
+std::thread_local! {
+    static POOL: RefCell<Vec<DefaultContext<'static, System, ()>>> =
+        const { RefCell::new(Vec::new()) };
+}
+
+/// Call the given closure with a [`DefaultContext`] borrowed from a pool local
+/// to the current thread.
+///
+/// Once the closure returns the context is reset and returned to the pool,
+/// which means that the buffers it has allocated to hold diagnostics are
+/// reused the next time a context is borrowed from the pool. This avoids
+/// constructing a new context and allocating new buffers for every operation
+/// in a loop processing many messages.
+///
+/// A context borrowed from the pool is always in its default state. It has no
+/// collected errors or path, and any configuration like
+/// [`DefaultContext::include_type`] or [`DefaultContext::with_limits`] from
+/// a previous use is not retained. Only the capacity of its buffers survives
+/// between uses.
+///
+/// This can be called recursively, in which case a separate context is
+/// borrowed.
+///
+/// # Examples
+///
+/// ```
+/// use musli::context;
+/// use musli::json::Encoding;
+///
+/// const ENCODING: Encoding = Encoding::new();
+///
+/// let inputs: [&[u8]; 3] = [b"1", b"\"two\"", b"3"];
+/// let mut values = Vec::new();
+///
+/// for input in inputs {
+///     let result = context::pooled(|cx| {
+///         ENCODING
+///             .from_slice_with::<_, u32>(cx, input)
+///             .map_err(|_| cx.report().to_string())
+///     });
+///
+///     values.push(result);
+/// }
+///
+/// assert_eq!(values[0], Ok(1));
+/// assert!(values[1].is_err());
+/// assert_eq!(values[2], Ok(3));
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "std", feature = "alloc"))))]
+pub fn pooled<M, O>(f: impl FnOnce(&mut DefaultContext<'static, System, M>) -> O) -> O
+where
+    M: 'static,
+{
+    let cx = POOL.with(|pool| pool.borrow_mut().pop());
+    let mut cx = cx.unwrap_or_default().into_mode::<M>();
+
+    let output = f(&mut cx);
+
+    let mut cx = cx.into_mode::<()>();
+    cx.reset();
+    cx.reset_config();
+
+    POOL.with(|pool| {
+        let mut pool = pool.borrow_mut();
+
+        if pool.len() < MAX_POOLED {
+            pool.push(cx);
+        }
+    });
+
+    output
+}
@@ -0,0 +1,509 @@
+use core::cell::{Cell, UnsafeCell};
+use core::error::Error;
+use core::fmt;
+use core::marker::PhantomData;
+
+use rust_alloc::vec::Vec;
+
+use crate::alloc::{self, Allocator, String, System, SYSTEM};
+use crate::Context;
+
+use super::{Access, ContextError, ErrorKind, Shared};
+
+/// A context which profiles how many bytes are attributed to each field.
+///
+/// Every time a field, a variant, a sequence element or a map entry is entered
+/// the current position is recorded, and once it is left the number of bytes
+/// which were written or read in between is added to the total for its path.
+/// Totals are inclusive, so the bytes of a nested field also count towards
+/// every field which contains it.
+///
+/// Totals are aggregated over every message processed with the context, since
+/// [`Context::clear`] which is called at the start of each message only
+/// resets the position. Use [`Profile::reset`] to discard the totals.
+///
+/// To keep the number of paths bounded, sequence elements are aggregated under
+/// `[]` and map entries under `{}` regardless of their index or key. Paths
+/// are only recorded for types which are traced, which is the default for types
+/// using the derives. Bytes which are produced into an intermediate buffer,
+/// such as the fields of a packed value in [`wire`], are attributed to the
+/// field which contains it.
+///
+/// Errors are handled the same as in [`Same`].
+///
+/// [`wire`]: crate::wire
+/// [`Same`]: super::Same
+///
+/// # Examples
+///
+/// ```
+/// use musli::{Decode, Encode};
+/// use musli::context::Profile;
+/// use musli::mode::Binary;
+/// use musli::storage::{Encoding, Error};
+///
+/// const ENCODING: Encoding = Encoding::new();
+///
+/// #[derive(Encode, Decode)]
+/// struct Record {
+///     id: u32,
+///     name: String,
+///     values: Vec<u64>,
+/// }
+///
+/// let cx = Profile::<Binary, Error, _>::new();
+///
+/// for n in 0..4 {
+///     let record = Record {
+///         id: n,
+///         name: String::from("Aristotle"),
+///         values: vec![u64::MAX; 3],
+///     };
+///
+///     ENCODING.to_vec_with(&cx, &record)?;
+/// }
+///
+/// let report = cx.report();
+/// assert_eq!(report.iter().next().unwrap().path().to_string(), ".values");
+///
+/// assert_eq!(report.to_string(), "\
+/// 184 100.0%      total
+/// 128  69.6%   4  .values
+/// 120  65.2%  12  .values[]
+///  44  23.9%   4  .name
+///   8   4.3%   4  .id
+/// ");
+/// # Ok::<_, Error>(())
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub struct Profile<M, E, A>
+where
+    E: ContextError,
+{
+    alloc: A,
+    position: Cell<usize>,
+    total: Cell<usize>,
+    // Every path which has been observed, where the first node is the root.
+    nodes: UnsafeCell<Vec<Node>>,
+    // The nodes we're currently inside of, and the position they were entered
+    // at.
+    stack: UnsafeCell<Vec<(usize, usize)>>,
+    access: Access,
+    _marker: PhantomData<(M, E)>,
+}
+
+impl<M, E> Profile<M, E, &'static System>
+where
+    E: ContextError,
+{
+    /// Construct a new profiling context using the [`System`] allocator.
+    pub fn new() -> Self {
+        Self::with_alloc(&SYSTEM)
+    }
+}
+
+impl<M, E, A> Profile<M, E, A>
+where
+    E: ContextError,
+{
+    /// Construct a new profiling context with a custom allocator.
+    pub fn with_alloc(alloc: A) -> Self {
+        Self {
+            alloc,
+            position: Cell::new(0),
+            total: Cell::new(0),
+            nodes: UnsafeCell::new(rust_alloc::vec![Node::root()]),
+            stack: UnsafeCell::new(Vec::new()),
+            access: Access::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Discard all collected totals.
+    pub fn reset(&mut self) {
+        self.position.set(0);
+        self.total.set(0);
+        self.nodes.get_mut().truncate(1);
+        self.nodes.get_mut()[0].children.clear();
+        self.stack.get_mut().clear();
+    }
+
+    /// Get the total number of bytes processed.
+    pub fn total(&self) -> usize {
+        self.total.get()
+    }
+
+    /// Generate a report of the collected totals, sorted from the largest to
+    /// the smallest.
+    pub fn report(&self) -> ProfileReport<'_> {
+        let access = self.access.shared();
+
+        // SAFETY: We've acquired shared access just above.
+        let nodes = unsafe { &*self.nodes.get() };
+
+        let mut order = (1..nodes.len()).collect::<Vec<_>>();
+        order.sort_by(|&a, &b| nodes[b].bytes.cmp(&nodes[a].bytes).then(a.cmp(&b)));
+
+        ProfileReport {
+            nodes,
+            order,
+            total: self.total.get(),
+            _access: access,
+        }
+    }
+
+    fn enter(&self, step: Step) {
+        let _access = self.access.exclusive();
+
+        // SAFETY: We've checked that we have exclusive access just above.
+        unsafe {
+            let nodes = &mut *self.nodes.get();
+            let stack = &mut *self.stack.get();
+
+            let parent = stack.last().map_or(0, |&(index, _)| index);
+
+            let found = nodes[parent]
+                .children
+                .iter()
+                .copied()
+                .find(|&child| nodes[child].step == step);
+
+            let index = match found {
+                Some(index) => index,
+                None => {
+                    let index = nodes.len();
+                    nodes.push(Node::new(parent, step));
+                    nodes[parent].children.push(index);
+                    index
+                }
+            };
+
+            stack.push((index, self.position.get()));
+        }
+    }
+
+    fn leave(&self) {
+        let _access = self.access.exclusive();
+
+        // SAFETY: We've checked that we have exclusive access just above.
+        unsafe {
+            let nodes = &mut *self.nodes.get();
+            let stack = &mut *self.stack.get();
+
+            if let Some((index, start)) = stack.pop() {
+                let node = &mut nodes[index];
+                node.bytes = node
+                    .bytes
+                    .wrapping_add(self.position.get().saturating_sub(start));
+                node.count = node.count.wrapping_add(1);
+            }
+        }
+    }
+}
+
+impl<M, E, A> Context for Profile<M, E, A>
+where
+    A: Allocator,
+    M: 'static,
+    E: ContextError,
+{
+    type Mode = M;
+    type Error = E;
+    type Mark = usize;
+    type Allocator = A;
+    type String<'this> = String<'this, A> where Self: 'this;
+
+    #[inline]
+    fn clear(&self) {
+        self.position.set(0);
+
+        let _access = self.access.exclusive();
+
+        // SAFETY: We've checked that we have exclusive access just above.
+        unsafe {
+            (*self.stack.get()).clear();
+        }
+    }
+
+    #[inline]
+    fn alloc(&self) -> &Self::Allocator {
+        &self.alloc
+    }
+
+    #[inline]
+    fn collect_string<T>(&self, value: &T) -> Result<Self::String<'_>, Self::Error>
+    where
+        T: ?Sized + fmt::Display,
+    {
+        alloc::collect_string(self, value)
+    }
+
+    #[inline]
+    fn custom<T>(&self, message: T) -> Self::Error
+    where
+        T: 'static + Send + Sync + Error,
+    {
+        E::custom(message)
+    }
+
+    #[inline]
+    fn message<T>(&self, message: T) -> Self::Error
+    where
+        T: fmt::Display,
+    {
+        E::message(message)
+    }
+
+    #[inline]
+    fn message_with_kind<T>(&self, kind: ErrorKind, message: T) -> Self::Error
+    where
+        T: fmt::Display,
+    {
+        E::message_with_kind(kind, message)
+    }
+
+    #[inline]
+    fn marked_message_with_kind<T>(&self, _: usize, kind: ErrorKind, message: T) -> Self::Error
+    where
+        T: fmt::Display,
+    {
+        E::message_with_kind(kind, message)
+    }
+
+    #[inline]
+    fn advance(&self, n: usize) {
+        self.position.set(self.position.get().wrapping_add(n));
+        self.total.set(self.total.get().wrapping_add(n));
+    }
+
+    #[inline]
+    fn mark(&self) -> Self::Mark {
+        self.position.get()
+    }
+
+    #[inline]
+    fn restore(&self, mark: Self::Mark) {
+        self.position.set(mark);
+    }
+
+    #[inline]
+    fn enter_named_field<T>(&self, name: &'static str, _: &T)
+    where
+        T: ?Sized + fmt::Display,
+    {
+        self.enter(Step::Named(name));
+    }
+
+    #[inline]
+    fn enter_unnamed_field<T>(&self, index: u32, _: &T)
+    where
+        T: ?Sized + fmt::Display,
+    {
+        self.enter(Step::Unnamed(index));
+    }
+
+    #[inline]
+    fn leave_field(&self) {
+        self.leave();
+    }
+
+    #[inline]
+    fn enter_variant<T>(&self, name: &'static str, _: T) {
+        self.enter(Step::Variant(name));
+    }
+
+    #[inline]
+    fn leave_variant(&self) {
+        self.leave();
+    }
+
+    #[inline]
+    fn enter_sequence_index(&self, _: usize) {
+        self.enter(Step::Index);
+    }
+
+    #[inline]
+    fn leave_sequence_index(&self) {
+        self.leave();
+    }
+
+    #[inline]
+    fn enter_map_key<T>(&self, _: T)
+    where
+        T: fmt::Display,
+    {
+        self.enter(Step::Key);
+    }
+
+    #[inline]
+    fn leave_map_key(&self) {
+        self.leave();
+    }
+}
+
+impl<M, E> Default for Profile<M, E, &'static System>
+where
+    E: ContextError,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A report of the totals collected by a [`Profile`] context.
+///
+/// When formatted, this prints one line per path with the number of bytes, the
+/// percentage of the total and the number of times it was observed.
+pub struct ProfileReport<'a> {
+    nodes: &'a [Node],
+    order: Vec<usize>,
+    total: usize,
+    _access: Shared<'a>,
+}
+
+impl ProfileReport<'_> {
+    /// Iterate over the entries in the report, from the largest to the
+    /// smallest.
+    pub fn iter(&self) -> impl Iterator<Item = ProfileEntry<'_>> {
+        let nodes = self.nodes;
+
+        self.order
+            .iter()
+            .map(move |&index| ProfileEntry { nodes, index })
+    }
+}
+
+impl fmt::Display for ProfileReport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bytes = count_digits(self.total);
+        let count = self
+            .iter()
+            .map(|entry| count_digits(entry.count()))
+            .max()
+            .unwrap_or(1);
+
+        writeln!(f, "{:>bytes$} 100.0%  {:count$}  total", self.total, "")?;
+
+        for entry in self.iter() {
+            let percent = if self.total == 0 {
+                0.0
+            } else {
+                entry.bytes() as f64 * 100.0 / self.total as f64
+            };
+
+            writeln!(
+                f,
+                "{:>bytes$} {percent:>5.1}%  {:>count$}  {}",
+                entry.bytes(),
+                entry.count(),
+                entry.path()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A single entry in a [`ProfileReport`].
+pub struct ProfileEntry<'a> {
+    nodes: &'a [Node],
+    index: usize,
+}
+
+impl<'a> ProfileEntry<'a> {
+    /// The path of the entry, like `.values[].name`.
+    pub fn path(&self) -> impl fmt::Display + 'a {
+        ProfilePath {
+            nodes: self.nodes,
+            index: self.index,
+        }
+    }
+
+    /// The total number of bytes attributed to the path.
+    pub fn bytes(&self) -> usize {
+        self.nodes[self.index].bytes
+    }
+
+    /// The number of times the path was observed.
+    pub fn count(&self) -> usize {
+        self.nodes[self.index].count
+    }
+}
+
+struct ProfilePath<'a> {
+    nodes: &'a [Node],
+    index: usize,
+}
+
+impl ProfilePath<'_> {
+    fn fmt_node(&self, f: &mut fmt::Formatter<'_>, index: usize) -> fmt::Result {
+        if index == 0 {
+            return Ok(());
+        }
+
+        let node = &self.nodes[index];
+        self.fmt_node(f, node.parent)?;
+
+        match node.step {
+            Step::Root => Ok(()),
+            Step::Variant(name) => write!(f, "::{name}"),
+            Step::Named(name) => write!(f, ".{name}"),
+            Step::Unnamed(index) => write!(f, ".{index}"),
+            Step::Index => write!(f, "[]"),
+            Step::Key => write!(f, "{{}}"),
+        }
+    }
+}
+
+impl fmt::Display for ProfilePath<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_node(f, self.index)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Root,
+    Variant(&'static str),
+    Named(&'static str),
+    Unnamed(u32),
+    Index,
+    Key,
+}
+
+struct Node {
+    parent: usize,
+    step: Step,
+    bytes: usize,
+    count: usize,
+    children: Vec<usize>,
+}
+
+impl Node {
+    fn root() -> Self {
+        Self::new(0, Step::Root)
+    }
+
+    fn new(parent: usize, step: Step) -> Self {
+        Self {
+            parent,
+            step,
+            bytes: 0,
+            count: 0,
+            children: Vec::new(),
+        }
+    }
+}
+
+fn count_digits(mut n: usize) -> usize {
+    let mut digits = 1;
+
+    while n >= 10 {
+        n /= 10;
+        digits += 1;
+    }
+
+    digits
+}
@@ -9,9 +9,10 @@ use crate::alloc::{System, SYSTEM};
 use crate::mode::Binary;
 use crate::Context;
 
-use super::ContextError;
+use super::limits::{Limiter, Limits};
 #[cfg(test)]
 use super::ErrorMarker;
+use super::{ContextError, ErrorKind};
 
 /// A simple non-diagnostical capturing context which simply emits the original
 /// error.
@@ -23,6 +24,7 @@ where
     E: ContextError,
 {
     alloc: A,
+    limiter: Limiter,
     _marker: PhantomData<(M, E)>,
 }
 
@@ -46,9 +48,33 @@ where
     pub fn with_alloc(alloc: A) -> Self {
         Self {
             alloc,
+            limiter: Limiter::new(Limits::new()),
             _marker: PhantomData,
         }
     }
+
+    /// Configure the [`Limits`] which are enforced while decoding using this
+    /// context.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::context::{Limits, Same};
+    /// use musli::mode::Binary;
+    /// use musli::storage::Error;
+    ///
+    /// let cx = Same::<Binary, Error, _>::new().with_limits(Limits::new().with_max_size(4));
+    ///
+    /// let data = musli::storage::to_vec("Hello World")?;
+    /// let error = musli::storage::DEFAULT.from_slice_with::<_, String>(&cx, &data).unwrap_err();
+    /// assert_eq!(error.to_string(), "String or bytes of 11 bytes exceeds the maximum size of 4 bytes");
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[inline]
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limiter.set_limits(limits);
+        self
+    }
 }
 
 #[cfg(test)]
@@ -72,7 +98,9 @@ where
     type String<'this> = String<'this, A> where Self: 'this;
 
     #[inline]
-    fn clear(&self) {}
+    fn clear(&self) {
+        self.limiter.clear();
+    }
 
     #[inline]
     fn alloc(&self) -> &Self::Allocator {
@@ -102,6 +130,60 @@ where
     {
         E::message(message)
     }
+
+    #[inline]
+    fn message_with_kind<T>(&self, kind: ErrorKind, message: T) -> Self::Error
+    where
+        T: fmt::Display,
+    {
+        E::message_with_kind(kind, message)
+    }
+
+    #[inline]
+    fn marked_message_with_kind<T>(&self, _: (), kind: ErrorKind, message: T) -> Self::Error
+    where
+        T: fmt::Display,
+    {
+        E::message_with_kind(kind, message)
+    }
+
+    #[inline]
+    fn advance(&self, n: usize) {
+        self.limiter.advance(n);
+    }
+
+    #[inline]
+    fn check_input(&self, n: usize) -> Result<(), Self::Error> {
+        self.limiter.check_input(n).map_err(limit_exceeded)
+    }
+
+    #[inline]
+    fn check_length(&self, len: usize) -> Result<(), Self::Error> {
+        self.limiter.check_length(len).map_err(limit_exceeded)
+    }
+
+    #[inline]
+    fn check_size(&self, len: usize) -> Result<(), Self::Error> {
+        self.limiter.check_size(len).map_err(limit_exceeded)
+    }
+
+    #[inline]
+    fn enter_nested(&self) -> Result<(), Self::Error> {
+        self.limiter.enter_nested().map_err(limit_exceeded)
+    }
+
+    #[inline]
+    fn leave_nested(&self) {
+        self.limiter.leave_nested();
+    }
+}
+
+#[inline]
+fn limit_exceeded<E>(error: impl fmt::Display) -> E
+where
+    E: ContextError,
+{
+    E::message_with_kind(ErrorKind::LimitExceeded, error)
 }
 
 #[cfg(feature = "alloc")]
@@ -0,0 +1,435 @@
+use core::cell::{Cell, UnsafeCell};
+use core::error::Error;
+use core::fmt;
+
+use rust_alloc::vec::Vec;
+
+use ::tracing::span::Id;
+use ::tracing::Span;
+
+use crate::Context;
+
+use super::ErrorKind;
+
+/// A context which forwards diagnostics to the [`tracing`] ecosystem.
+///
+/// This wraps another context, which is still responsible for constructing
+/// errors and enforcing limits. In addition to that, every struct, enum, field,
+/// variant, sequence index and map key which is entered is recorded as a
+/// `DEBUG` span, and every error which is reported is emitted as an `ERROR`
+/// event with its message and [`ErrorKind`] if it is known. Events are emitted
+/// inside of the spans which were active when the error was raised, so
+/// subscribers see the full data path leading up to it. Spans are opened and
+/// closed as data is processed, which also makes it possible to time how long
+/// it takes to process any part of the data.
+///
+/// The spans are only used as the parents of each other and of events, and are
+/// never entered. So nothing which is logged outside of this context is
+/// attributed to them, even if decoding fails before they are closed.
+///
+/// Everything is recorded with the `musli::context::tracing` target, which can
+/// be used to filter it.
+///
+/// Note that spans are only created for types which are traced, which is the
+/// default for types using the derives. To limit the overhead of deeply nested
+/// data, a depth cutoff can be configured using [`Tracing::with_max_depth`].
+///
+/// [`tracing`]: https://docs.rs/tracing
+///
+/// # Examples
+///
+/// ```
+/// use musli::{Decode, Encode};
+/// use musli::context::{self, Tracing};
+/// use musli::json::Encoding;
+///
+/// const ENCODING: Encoding = Encoding::new();
+///
+/// #[derive(Debug, PartialEq, Decode, Encode)]
+/// struct Record {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// let cx = Tracing::new(context::new()).with_max_depth(8);
+///
+/// let result = ENCODING.from_str_with::<_, Record>(&cx, r#"{"name": "Aristotle", "age": "old"}"#);
+/// assert!(result.is_err());
+///
+/// let report = cx.inner().report().to_string();
+/// assert_eq!(report, ".age: Invalid numeric (at bytes 29-30)\n");
+/// ```
+#[cfg_attr(doc_cfg, doc(cfg(feature = "tracing")))]
+pub struct Tracing<C> {
+    inner: C,
+    max_depth: usize,
+    depth: Cell<usize>,
+    spans: UnsafeCell<Spans>,
+}
+
+/// The stack of spans which have been entered.
+struct Spans(Vec<Span>);
+
+impl Drop for Spans {
+    #[inline]
+    fn drop(&mut self) {
+        // Close spans innermost first.
+        while self.0.pop().is_some() {}
+    }
+}
+
+impl<C> Tracing<C> {
+    /// Wrap the given context.
+    #[inline]
+    pub fn new(inner: C) -> Self {
+        Self {
+            inner,
+            max_depth: usize::MAX,
+            depth: Cell::new(0),
+            spans: UnsafeCell::new(Spans(Vec::new())),
+        }
+    }
+
+    /// Configure the maximum depth at which spans are created.
+    ///
+    /// Spans nested deeper than this are not created, but errors are still
+    /// emitted as events inside of the deepest span which was created.
+    #[inline]
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Access the wrapped context.
+    #[inline]
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Access the wrapped context mutably.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut C {
+        &mut self.inner
+    }
+
+    /// Unwrap into the wrapped context.
+    #[inline]
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+
+    /// The parent of new spans and events, which is the innermost span
+    /// created by this context or the current span of the caller.
+    #[inline]
+    fn parent(&self) -> Option<Id> {
+        // SAFETY: We're restricting access to the context, so that this is
+        // safe.
+        let spans = unsafe { &*self.spans.get() };
+
+        match spans.0.last() {
+            Some(span) => span.id(),
+            None => Span::current().id(),
+        }
+    }
+
+    #[inline]
+    fn enter(&self, span: impl FnOnce(Option<Id>) -> Span) {
+        let depth = self.depth.get();
+        self.depth.set(depth.wrapping_add(1));
+
+        if depth < self.max_depth {
+            let span = span(self.parent());
+
+            // SAFETY: We're restricting access to the context, so that this is
+            // safe.
+            unsafe {
+                (*self.spans.get()).0.push(span);
+            }
+        }
+    }
+
+    #[inline]
+    fn leave(&self) {
+        let depth = self.depth.get().saturating_sub(1);
+        self.depth.set(depth);
+
+        if depth < self.max_depth {
+            // SAFETY: We're restricting access to the context, so that this is
+            // safe.
+            unsafe {
+                (*self.spans.get()).0.pop();
+            }
+        }
+    }
+
+    /// Close spans until only those entered up until `depth` are left,
+    /// innermost first.
+    #[inline]
+    fn truncate(&self, depth: usize) {
+        self.depth.set(depth);
+
+        // SAFETY: We're restricting access to the context, so that this is
+        // safe.
+        let spans = unsafe { &mut *self.spans.get() };
+
+        while spans.0.len() > depth.min(self.max_depth) {
+            spans.0.pop();
+        }
+    }
+}
+
+macro_rules! span {
+    ($parent:expr, $name:literal $(, $($field:tt)*)?) => {
+        ::tracing::span!(
+            target: "musli::context::tracing",
+            parent: $parent,
+            ::tracing::Level::DEBUG,
+            $name
+            $(, $($field)*)?
+        )
+    };
+}
+
+macro_rules! error {
+    ($cx:expr, $($field:tt)*) => {
+        ::tracing::event!(
+            target: "musli::context::tracing",
+            parent: $cx.parent(),
+            ::tracing::Level::ERROR,
+            $($field)*
+        )
+    };
+}
+
+impl<C> Context for Tracing<C>
+where
+    C: Context,
+{
+    type Mode = C::Mode;
+    type Error = C::Error;
+    type Mark = (C::Mark, usize);
+    type Allocator = C::Allocator;
+    type String<'this> = C::String<'this> where Self: 'this;
+
+    #[inline]
+    fn clear(&self) {
+        self.truncate(0);
+        self.inner.clear();
+    }
+
+    #[inline]
+    fn alloc(&self) -> &Self::Allocator {
+        self.inner.alloc()
+    }
+
+    #[inline]
+    fn collect_string<T>(&self, value: &T) -> Result<Self::String<'_>, Self::Error>
+    where
+        T: ?Sized + fmt::Display,
+    {
+        self.inner.collect_string(value)
+    }
+
+    #[inline]
+    fn custom<T>(&self, error: T) -> Self::Error
+    where
+        T: 'static + Send + Sync + Error,
+    {
+        error!(self, message = %error);
+        self.inner.custom(error)
+    }
+
+    #[inline]
+    fn message<T>(&self, message: T) -> Self::Error
+    where
+        T: fmt::Display,
+    {
+        error!(self, message = %message);
+        self.inner.message(message)
+    }
+
+    #[inline]
+    fn marked_message<T>(&self, mark: Self::Mark, message: T) -> Self::Error
+    where
+        T: fmt::Display,
+    {
+        error!(self, message = %message);
+        self.inner.marked_message(mark.0, message)
+    }
+
+    #[inline]
+    fn marked_custom<T>(&self, mark: Self::Mark, error: T) -> Self::Error
+    where
+        T: 'static + Send + Sync + Error,
+    {
+        error!(self, message = %error);
+        self.inner.marked_custom(mark.0, error)
+    }
+
+    #[inline]
+    fn message_with_kind<T>(&self, kind: ErrorKind, message: T) -> Self::Error
+    where
+        T: fmt::Display,
+    {
+        error!(self, kind = ?kind, message = %message);
+        self.inner.message_with_kind(kind, message)
+    }
+
+    #[inline]
+    fn marked_message_with_kind<T>(
+        &self,
+        mark: Self::Mark,
+        kind: ErrorKind,
+        message: T,
+    ) -> Self::Error
+    where
+        T: fmt::Display,
+    {
+        error!(self, kind = ?kind, message = %message);
+        self.inner.marked_message_with_kind(mark.0, kind, message)
+    }
+
+    #[inline]
+    fn advance(&self, n: usize) {
+        self.inner.advance(n);
+    }
+
+    #[inline]
+    fn mark(&self) -> Self::Mark {
+        (self.inner.mark(), self.depth.get())
+    }
+
+    #[inline]
+    fn restore(&self, (mark, depth): Self::Mark) {
+        self.truncate(depth);
+        self.inner.restore(mark);
+    }
+
+    #[inline]
+    fn is_lenient(&self) -> bool {
+        self.inner.is_lenient()
+    }
+
+    #[inline]
+    fn check_input(&self, n: usize) -> Result<(), Self::Error> {
+        self.inner.check_input(n).inspect_err(|_| {
+            error!(self, kind = ?ErrorKind::LimitExceeded, input = n);
+        })
+    }
+
+    #[inline]
+    fn check_length(&self, len: usize) -> Result<(), Self::Error> {
+        self.inner.check_length(len).inspect_err(|_| {
+            error!(self, kind = ?ErrorKind::LimitExceeded, length = len);
+        })
+    }
+
+    #[inline]
+    fn check_size(&self, len: usize) -> Result<(), Self::Error> {
+        self.inner.check_size(len).inspect_err(|_| {
+            error!(self, kind = ?ErrorKind::LimitExceeded, size = len);
+        })
+    }
+
+    #[inline]
+    fn enter_nested(&self) -> Result<(), Self::Error> {
+        self.inner.enter_nested().inspect_err(|_| {
+            error!(self, kind = ?ErrorKind::LimitExceeded);
+        })
+    }
+
+    #[inline]
+    fn leave_nested(&self) {
+        self.inner.leave_nested();
+    }
+
+    #[inline]
+    fn enter_struct(&self, name: &'static str) {
+        self.enter(|parent| span!(parent, "struct", name));
+        self.inner.enter_struct(name);
+    }
+
+    #[inline]
+    fn leave_struct(&self) {
+        self.inner.leave_struct();
+        self.leave();
+    }
+
+    #[inline]
+    fn enter_enum(&self, name: &'static str) {
+        self.enter(|parent| span!(parent, "enum", name));
+        self.inner.enter_enum(name);
+    }
+
+    #[inline]
+    fn leave_enum(&self) {
+        self.inner.leave_enum();
+        self.leave();
+    }
+
+    #[inline]
+    fn enter_named_field<T>(&self, name: &'static str, tag: &T)
+    where
+        T: ?Sized + fmt::Display,
+    {
+        self.enter(|parent| span!(parent, "field", name, tag = %tag));
+        self.inner.enter_named_field(name, tag);
+    }
+
+    #[inline]
+    fn enter_unnamed_field<T>(&self, index: u32, tag: &T)
+    where
+        T: ?Sized + fmt::Display,
+    {
+        self.enter(|parent| span!(parent, "field", index, tag = %tag));
+        self.inner.enter_unnamed_field(index, tag);
+    }
+
+    #[inline]
+    fn leave_field(&self) {
+        self.inner.leave_field();
+        self.leave();
+    }
+
+    #[inline]
+    fn enter_variant<T>(&self, name: &'static str, tag: T)
+    where
+        T: fmt::Display,
+    {
+        self.enter(|parent| span!(parent, "variant", name, tag = %tag));
+        self.inner.enter_variant(name, tag);
+    }
+
+    #[inline]
+    fn leave_variant(&self) {
+        self.inner.leave_variant();
+        self.leave();
+    }
+
+    #[inline]
+    fn enter_map_key<T>(&self, field: T)
+    where
+        T: fmt::Display,
+    {
+        self.enter(|parent| span!(parent, "key", key = %field));
+        self.inner.enter_map_key(field);
+    }
+
+    #[inline]
+    fn leave_map_key(&self) {
+        self.inner.leave_map_key();
+        self.leave();
+    }
+
+    #[inline]
+    fn enter_sequence_index(&self, index: usize) {
+        self.enter(|parent| span!(parent, "index", index));
+        self.inner.enter_sequence_index(index);
+    }
+
+    #[inline]
+    fn leave_sequence_index(&self) {
+        self.inner.leave_sequence_index();
+        self.leave();
+    }
+}
@@ -0,0 +1,864 @@
+use core::fmt;
+use core::str::FromStr;
+
+use crate::alloc::Vec;
+use crate::de::{
+    Decode, DecodeUnsized, Decoder, EntriesDecoder, EntryDecoder, MapDecoder, SequenceDecoder,
+    SizeHint, Skip, UnsizedVisitor,
+};
+use crate::{Context, Reader};
+
+/// The root decoder of a CSV document.
+pub struct CsvDecoder<'a, R, const D: u8, C: ?Sized> {
+    cx: &'a C,
+    reader: R,
+}
+
+impl<'a, R, const D: u8, C: ?Sized> CsvDecoder<'a, R, D, C> {
+    /// Construct a new CSV decoder.
+    #[inline]
+    pub fn new(cx: &'a C, reader: R) -> Self {
+        Self { cx, reader }
+    }
+}
+
+/// A CSV document, consisting of a header followed by records.
+///
+/// The header is read when this is constructed, and the names of each column
+/// are kept around so that they can be provided as the keys of each record.
+pub struct Records<'a, R, const D: u8, C>
+where
+    C: ?Sized + Context,
+{
+    cx: &'a C,
+    reader: R,
+    /// The names of all columns in the header, stored back to back.
+    names: Vec<'a, u8, C::Allocator>,
+    /// The offset in `names` where each column name ends.
+    ends: Vec<'a, usize, C::Allocator>,
+    /// The contents of the most recently read field.
+    field: Vec<'a, u8, C::Allocator>,
+    /// Whether the most recently read field was quoted.
+    quoted: bool,
+    /// The number of fields read from the current record.
+    column: usize,
+    /// Whether the end of the current record has been reached.
+    done: bool,
+}
+
+impl<'a, 'de, R, const D: u8, C> Records<'a, R, D, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    #[inline]
+    fn new(cx: &'a C, reader: R) -> Result<Self, C::Error> {
+        let mut this = Self {
+            cx,
+            reader,
+            names: Vec::new_in(cx.alloc()),
+            ends: Vec::new_in(cx.alloc()),
+            field: Vec::new_in(cx.alloc()),
+            quoted: false,
+            column: 0,
+            done: true,
+        };
+
+        if this.reader.peek().is_some() {
+            loop {
+                let last = this.read_field()?;
+
+                if !this.names.write(this.field.as_slice()) || !this.ends.push(this.names.len()) {
+                    return Err(cx.message("Scratch buffer overflow"));
+                }
+
+                if last {
+                    break;
+                }
+            }
+        }
+
+        Ok(this)
+    }
+
+    /// Start decoding the next record.
+    #[inline]
+    fn start_record(&mut self) -> Result<(), C::Error> {
+        if self.reader.peek().is_none() {
+            return Err(self.cx.message("Expected record, but found end of input"));
+        }
+
+        self.column = 0;
+        self.done = false;
+        Ok(())
+    }
+
+    /// Read the next field in the current record, returning its column or
+    /// `None` if all fields in the record have been read.
+    fn next_field(&mut self) -> Result<Option<usize>, C::Error> {
+        let expected = self.ends.len();
+
+        if self.column == expected {
+            if !self.done {
+                return Err(self.cx.message(format_args!(
+                    "Expected {expected} fields in record, but found more"
+                )));
+            }
+
+            return Ok(None);
+        }
+
+        if self.done {
+            return Err(self.cx.message(format_args!(
+                "Expected {expected} fields in record, but found {}",
+                self.column
+            )));
+        }
+
+        self.done = self.read_field()?;
+        self.column += 1;
+        Ok(Some(self.column - 1))
+    }
+
+    /// Skip over any fields which remain in the current record.
+    #[inline]
+    fn finish_record(&mut self) -> Result<(), C::Error> {
+        while self.next_field()?.is_some() {}
+        Ok(())
+    }
+
+    /// The name of the given column.
+    #[inline]
+    fn name(&self, column: usize) -> &[u8] {
+        let ends = self.ends.as_slice();
+        let start = column.checked_sub(1).map_or(0, |n| ends[n]);
+        &self.names.as_slice()[start..ends[column]]
+    }
+
+    #[inline]
+    fn entry(&mut self) -> Result<Option<FieldEntryDecoder<'a, '_, C>>, C::Error> {
+        let Some(column) = self.next_field()? else {
+            return Ok(None);
+        };
+
+        Ok(Some(FieldEntryDecoder {
+            cx: self.cx,
+            name: self.name(column),
+            field: self.field.as_slice(),
+            quoted: self.quoted,
+        }))
+    }
+
+    #[inline]
+    fn key(&mut self) -> Result<Option<KeyDecoder<'a, '_, C>>, C::Error> {
+        let Some(column) = self.next_field()? else {
+            return Ok(None);
+        };
+
+        Ok(Some(KeyDecoder::new(self.cx, self.name(column))))
+    }
+
+    #[inline]
+    fn value(&mut self) -> FieldDecoder<'a, '_, C> {
+        FieldDecoder::new(self.cx, self.field.as_slice(), self.quoted)
+    }
+
+    #[inline]
+    fn remaining(&self) -> SizeHint {
+        SizeHint::exact(self.ends.len() - self.column)
+    }
+
+    /// Read a single field into the field buffer, returning `true` if it
+    /// was the last field in its record.
+    fn read_field(&mut self) -> Result<bool, C::Error> {
+        let cx = self.cx;
+        let mark = cx.mark();
+
+        self.field.clear();
+        self.quoted = self.reader.peek() == Some(b'"');
+
+        if !self.quoted {
+            loop {
+                let Some(b) = self.reader.peek() else {
+                    return Ok(true);
+                };
+
+                self.reader.skip(cx, 1)?;
+
+                match b {
+                    b if b == D => return Ok(false),
+                    b'\n' => return Ok(true),
+                    b'\r' => return self.end_of_line(),
+                    b => {
+                        if !self.field.push(b) {
+                            return Err(cx.message("Scratch buffer overflow"));
+                        }
+                    }
+                }
+            }
+        }
+
+        self.reader.skip(cx, 1)?;
+
+        loop {
+            let Some(b) = self.reader.peek() else {
+                return Err(cx.marked_message(mark, "Unterminated quoted field"));
+            };
+
+            self.reader.skip(cx, 1)?;
+
+            if b == b'"' {
+                if self.reader.peek() != Some(b'"') {
+                    break;
+                }
+
+                self.reader.skip(cx, 1)?;
+            }
+
+            if !self.field.push(b) {
+                return Err(cx.message("Scratch buffer overflow"));
+            }
+        }
+
+        let Some(b) = self.reader.peek() else {
+            return Ok(true);
+        };
+
+        self.reader.skip(cx, 1)?;
+
+        match b {
+            b if b == D => Ok(false),
+            b'\n' => Ok(true),
+            b'\r' => self.end_of_line(),
+            b => Err(cx.marked_message(mark, UnexpectedAfterQuote { b })),
+        }
+    }
+
+    /// Consume the line feed which should follow a carriage return.
+    #[inline]
+    fn end_of_line(&mut self) -> Result<bool, C::Error> {
+        if self.reader.peek() == Some(b'\n') {
+            self.reader.skip(self.cx, 1)?;
+        }
+
+        Ok(true)
+    }
+}
+
+/// Decoder for a single record in a sequence of records.
+pub struct RowDecoder<'a, 'b, R, const D: u8, C>
+where
+    C: ?Sized + Context,
+{
+    cx: &'a C,
+    records: &'b mut Records<'a, R, D, C>,
+}
+
+/// Decoder for a name and value pair in a record.
+pub struct FieldEntryDecoder<'a, 'b, C: ?Sized> {
+    cx: &'a C,
+    name: &'b [u8],
+    field: &'b [u8],
+    quoted: bool,
+}
+
+/// Decoder for the name of a column.
+pub struct KeyDecoder<'a, 'b, C: ?Sized> {
+    cx: &'a C,
+    name: &'b [u8],
+}
+
+impl<'a, 'b, C: ?Sized> KeyDecoder<'a, 'b, C> {
+    #[inline]
+    fn new(cx: &'a C, name: &'b [u8]) -> Self {
+        Self { cx, name }
+    }
+}
+
+/// Decoder for a single field.
+pub struct FieldDecoder<'a, 'b, C: ?Sized> {
+    cx: &'a C,
+    field: &'b [u8],
+    quoted: bool,
+}
+
+impl<'a, 'b, C> FieldDecoder<'a, 'b, C>
+where
+    C: ?Sized + Context,
+{
+    #[inline]
+    fn new(cx: &'a C, field: &'b [u8], quoted: bool) -> Self {
+        Self { cx, field, quoted }
+    }
+
+    #[inline]
+    fn parse<T>(self, what: &'static str) -> Result<T, C::Error>
+    where
+        T: FromStr,
+    {
+        let string =
+            crate::str::from_utf8(self.field).map_err(crate::str::map_utf8_error(self.cx))?;
+
+        match string.parse() {
+            Ok(value) => Ok(value),
+            Err(..) => Err(self
+                .cx
+                .message(format_args!("Expected {what}, but found {string:?}"))),
+        }
+    }
+}
+
+#[crate::decoder(crate)]
+impl<'a, 'de, R, const D: u8, C> Decoder<'de> for CsvDecoder<'a, R, D, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = C::Mode;
+    type WithContext<'this, U> = CsvDecoder<'this, R, D, U> where U: 'this + Context;
+    type DecodeSequence = Records<'a, R, D, C>;
+    type DecodeMap = Records<'a, R, D, C>;
+    type DecodeMapEntries = Records<'a, R, D, C>;
+
+    #[inline]
+    fn cx(&self) -> &Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn with_context<U>(self, cx: &U) -> Result<Self::WithContext<'_, U>, C::Error>
+    where
+        U: Context,
+    {
+        Ok(CsvDecoder::new(cx, self.reader))
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "sequence of records or a record that can be decoded from CSV"
+        )
+    }
+
+    #[inline]
+    fn decode<T>(self) -> Result<T, Self::Error>
+    where
+        T: Decode<'de, Self::Mode>,
+    {
+        self.cx.decode(self)
+    }
+
+    #[inline]
+    fn decode_unsized<T, F, O>(self, f: F) -> Result<O, Self::Error>
+    where
+        T: ?Sized + DecodeUnsized<'de, Self::Mode>,
+        F: FnOnce(&T) -> Result<O, Self::Error>,
+    {
+        self.cx.decode_unsized(self, f)
+    }
+
+    #[inline]
+    fn decode_sequence<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeSequence) -> Result<O, C::Error>,
+    {
+        let mut records = Records::new(self.cx, self.reader)?;
+        f(&mut records)
+    }
+
+    #[inline]
+    fn decode_map<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeMap) -> Result<O, C::Error>,
+    {
+        let mut records = Records::new(self.cx, self.reader)?;
+        records.start_record()?;
+        let output = f(&mut records)?;
+        records.finish_record()?;
+        Ok(output)
+    }
+
+    #[inline]
+    fn decode_map_entries<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeMapEntries) -> Result<O, C::Error>,
+    {
+        self.decode_map(f)
+    }
+}
+
+impl<'a, 'de, R, const D: u8, C> SequenceDecoder<'de> for Records<'a, R, D, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeNext<'this> = RowDecoder<'a, 'this, R, D, C> where Self: 'this;
+
+    #[inline]
+    fn decode_next(&mut self) -> Result<Self::DecodeNext<'_>, C::Error> {
+        self.start_record()?;
+        Ok(RowDecoder {
+            cx: self.cx,
+            records: self,
+        })
+    }
+
+    #[inline]
+    fn try_decode_next(&mut self) -> Result<Option<Self::DecodeNext<'_>>, C::Error> {
+        if self.reader.peek().is_none() {
+            return Ok(None);
+        }
+
+        Ok(Some(self.decode_next()?))
+    }
+}
+
+impl<'a, 'de, R, const D: u8, C> MapDecoder<'de> for Records<'a, R, D, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeEntry<'this> = FieldEntryDecoder<'a, 'this, C> where Self: 'this;
+    type DecodeRemainingEntries<'this> = RowDecoder<'a, 'this, R, D, C> where Self: 'this;
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        self.remaining()
+    }
+
+    #[inline]
+    fn decode_entry(&mut self) -> Result<Option<Self::DecodeEntry<'_>>, C::Error> {
+        self.entry()
+    }
+
+    #[inline]
+    fn decode_remaining_entries(&mut self) -> Result<Self::DecodeRemainingEntries<'_>, C::Error> {
+        Ok(RowDecoder {
+            cx: self.cx,
+            records: self,
+        })
+    }
+}
+
+impl<'a, 'de, R, const D: u8, C> EntriesDecoder<'de> for Records<'a, R, D, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeEntryKey<'this> = KeyDecoder<'a, 'this, C> where Self: 'this;
+    type DecodeEntryValue<'this> = FieldDecoder<'a, 'this, C> where Self: 'this;
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        self.remaining()
+    }
+
+    #[inline]
+    fn decode_entry_key(&mut self) -> Result<Option<Self::DecodeEntryKey<'_>>, C::Error> {
+        self.key()
+    }
+
+    #[inline]
+    fn decode_entry_value(&mut self) -> Result<Self::DecodeEntryValue<'_>, C::Error> {
+        Ok(self.value())
+    }
+
+    #[inline]
+    fn end_entries(mut self) -> Result<(), C::Error> {
+        self.finish_record()
+    }
+}
+
+#[crate::decoder(crate)]
+impl<'a, 'b, 'de, R, const D: u8, C> Decoder<'de> for RowDecoder<'a, 'b, R, D, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = C::Mode;
+    type DecodeMap = Self;
+    type DecodeMapEntries = Self;
+
+    #[inline]
+    fn cx(&self) -> &Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "record that can be decoded from CSV")
+    }
+
+    #[inline]
+    fn decode<T>(self) -> Result<T, Self::Error>
+    where
+        T: Decode<'de, Self::Mode>,
+    {
+        self.cx.decode(self)
+    }
+
+    #[inline]
+    fn decode_unsized<T, F, O>(self, f: F) -> Result<O, Self::Error>
+    where
+        T: ?Sized + DecodeUnsized<'de, Self::Mode>,
+        F: FnOnce(&T) -> Result<O, Self::Error>,
+    {
+        self.cx.decode_unsized(self, f)
+    }
+
+    #[inline]
+    fn skip(self) -> Result<(), C::Error> {
+        self.records.finish_record()
+    }
+
+    #[inline]
+    fn try_skip(self) -> Result<Skip, C::Error> {
+        self.skip()?;
+        Ok(Skip::Skipped)
+    }
+
+    #[inline]
+    fn decode_map<F, O>(mut self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeMap) -> Result<O, C::Error>,
+    {
+        let output = f(&mut self)?;
+        self.records.finish_record()?;
+        Ok(output)
+    }
+
+    #[inline]
+    fn decode_map_entries<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeMapEntries) -> Result<O, C::Error>,
+    {
+        self.decode_map(f)
+    }
+}
+
+impl<'a, 'b, 'de, R, const D: u8, C> MapDecoder<'de> for RowDecoder<'a, 'b, R, D, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeEntry<'this> = FieldEntryDecoder<'a, 'this, C> where Self: 'this;
+    type DecodeRemainingEntries<'this> = RowDecoder<'a, 'this, R, D, C> where Self: 'this;
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        self.records.remaining()
+    }
+
+    #[inline]
+    fn decode_entry(&mut self) -> Result<Option<Self::DecodeEntry<'_>>, C::Error> {
+        self.records.entry()
+    }
+
+    #[inline]
+    fn decode_remaining_entries(&mut self) -> Result<Self::DecodeRemainingEntries<'_>, C::Error> {
+        Ok(RowDecoder {
+            cx: self.cx,
+            records: &mut *self.records,
+        })
+    }
+}
+
+impl<'a, 'b, 'de, R, const D: u8, C> EntriesDecoder<'de> for RowDecoder<'a, 'b, R, D, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeEntryKey<'this> = KeyDecoder<'a, 'this, C> where Self: 'this;
+    type DecodeEntryValue<'this> = FieldDecoder<'a, 'this, C> where Self: 'this;
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        self.records.remaining()
+    }
+
+    #[inline]
+    fn decode_entry_key(&mut self) -> Result<Option<Self::DecodeEntryKey<'_>>, C::Error> {
+        self.records.key()
+    }
+
+    #[inline]
+    fn decode_entry_value(&mut self) -> Result<Self::DecodeEntryValue<'_>, C::Error> {
+        Ok(self.records.value())
+    }
+
+    #[inline]
+    fn end_entries(self) -> Result<(), C::Error> {
+        self.records.finish_record()
+    }
+}
+
+impl<'a, 'b, 'de, C> EntryDecoder<'de> for FieldEntryDecoder<'a, 'b, C>
+where
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeKey<'this> = KeyDecoder<'a, 'this, C> where Self: 'this;
+    type DecodeValue = FieldDecoder<'a, 'b, C>;
+
+    #[inline]
+    fn decode_key(&mut self) -> Result<Self::DecodeKey<'_>, C::Error> {
+        Ok(KeyDecoder::new(self.cx, self.name))
+    }
+
+    #[inline]
+    fn decode_value(self) -> Result<Self::DecodeValue, C::Error> {
+        Ok(FieldDecoder::new(self.cx, self.field, self.quoted))
+    }
+}
+
+#[crate::decoder(crate)]
+impl<'a, 'b, 'de, C> Decoder<'de> for KeyDecoder<'a, 'b, C>
+where
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = C::Mode;
+    type WithContext<'this, U> = KeyDecoder<'this, 'b, U> where U: 'this + Context;
+
+    #[inline]
+    fn cx(&self) -> &Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn with_context<U>(self, cx: &U) -> Result<Self::WithContext<'_, U>, C::Error>
+    where
+        U: Context,
+    {
+        Ok(KeyDecoder::new(cx, self.name))
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "column name that can be decoded from CSV")
+    }
+
+    #[inline]
+    fn decode<T>(self) -> Result<T, Self::Error>
+    where
+        T: Decode<'de, Self::Mode>,
+    {
+        self.cx.decode(self)
+    }
+
+    #[inline]
+    fn decode_unsized<T, F, O>(self, f: F) -> Result<O, Self::Error>
+    where
+        T: ?Sized + DecodeUnsized<'de, Self::Mode>,
+        F: FnOnce(&T) -> Result<O, Self::Error>,
+    {
+        self.cx.decode_unsized(self, f)
+    }
+
+    #[inline]
+    fn skip(self) -> Result<(), C::Error> {
+        Ok(())
+    }
+
+    #[inline]
+    fn try_skip(self) -> Result<Skip, C::Error> {
+        Ok(Skip::Skipped)
+    }
+
+    #[inline]
+    fn decode_string<V>(self, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: UnsizedVisitor<'de, C, str>,
+    {
+        let string =
+            crate::str::from_utf8(self.name).map_err(crate::str::map_utf8_error(self.cx))?;
+        visitor.visit_ref(self.cx, string)
+    }
+}
+
+#[crate::decoder(crate)]
+impl<'a, 'b, 'de, C> Decoder<'de> for FieldDecoder<'a, 'b, C>
+where
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = C::Mode;
+    type WithContext<'this, U> = FieldDecoder<'this, 'b, U> where U: 'this + Context;
+    type DecodeSome = Self;
+
+    #[inline]
+    fn cx(&self) -> &Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn with_context<U>(self, cx: &U) -> Result<Self::WithContext<'_, U>, C::Error>
+    where
+        U: Context,
+    {
+        Ok(FieldDecoder::new(cx, self.field, self.quoted))
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "field that can be decoded from CSV")
+    }
+
+    #[inline]
+    fn decode<T>(self) -> Result<T, Self::Error>
+    where
+        T: Decode<'de, Self::Mode>,
+    {
+        self.cx.decode(self)
+    }
+
+    #[inline]
+    fn decode_unsized<T, F, O>(self, f: F) -> Result<O, Self::Error>
+    where
+        T: ?Sized + DecodeUnsized<'de, Self::Mode>,
+        F: FnOnce(&T) -> Result<O, Self::Error>,
+    {
+        self.cx.decode_unsized(self, f)
+    }
+
+    #[inline]
+    fn skip(self) -> Result<(), C::Error> {
+        Ok(())
+    }
+
+    #[inline]
+    fn try_skip(self) -> Result<Skip, C::Error> {
+        Ok(Skip::Skipped)
+    }
+
+    #[inline]
+    fn decode_empty(self) -> Result<(), C::Error> {
+        if !self.field.is_empty() {
+            return Err(self.cx.message("Expected empty field"));
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn decode_bool(self) -> Result<bool, C::Error> {
+        self.parse("boolean")
+    }
+
+    #[inline]
+    fn decode_char(self) -> Result<char, C::Error> {
+        self.parse("character")
+    }
+
+    #[inline]
+    fn decode_u8(self) -> Result<u8, C::Error> {
+        self.parse("u8")
+    }
+
+    #[inline]
+    fn decode_u16(self) -> Result<u16, C::Error> {
+        self.parse("u16")
+    }
+
+    #[inline]
+    fn decode_u32(self) -> Result<u32, C::Error> {
+        self.parse("u32")
+    }
+
+    #[inline]
+    fn decode_u64(self) -> Result<u64, C::Error> {
+        self.parse("u64")
+    }
+
+    #[inline]
+    fn decode_u128(self) -> Result<u128, C::Error> {
+        self.parse("u128")
+    }
+
+    #[inline]
+    fn decode_i8(self) -> Result<i8, C::Error> {
+        self.parse("i8")
+    }
+
+    #[inline]
+    fn decode_i16(self) -> Result<i16, C::Error> {
+        self.parse("i16")
+    }
+
+    #[inline]
+    fn decode_i32(self) -> Result<i32, C::Error> {
+        self.parse("i32")
+    }
+
+    #[inline]
+    fn decode_i64(self) -> Result<i64, C::Error> {
+        self.parse("i64")
+    }
+
+    #[inline]
+    fn decode_i128(self) -> Result<i128, C::Error> {
+        self.parse("i128")
+    }
+
+    #[inline]
+    fn decode_usize(self) -> Result<usize, C::Error> {
+        self.parse("usize")
+    }
+
+    #[inline]
+    fn decode_isize(self) -> Result<isize, C::Error> {
+        self.parse("isize")
+    }
+
+    #[inline]
+    fn decode_f32(self) -> Result<f32, C::Error> {
+        self.parse("f32")
+    }
+
+    #[inline]
+    fn decode_f64(self) -> Result<f64, C::Error> {
+        self.parse("f64")
+    }
+
+    #[inline]
+    fn decode_string<V>(self, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: UnsizedVisitor<'de, C, str>,
+    {
+        let string =
+            crate::str::from_utf8(self.field).map_err(crate::str::map_utf8_error(self.cx))?;
+        visitor.visit_ref(self.cx, string)
+    }
+
+    #[inline]
+    fn decode_option(self) -> Result<Option<Self::DecodeSome>, C::Error> {
+        if self.field.is_empty() && !self.quoted {
+            return Ok(None);
+        }
+
+        Ok(Some(self))
+    }
+}
+
+struct UnexpectedAfterQuote {
+    b: u8,
+}
+
+impl fmt::Display for UnexpectedAfterQuote {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { b } = *self;
+        write!(f, "Unexpected byte {b:#04x} after closing quote")
+    }
+}
@@ -0,0 +1,691 @@
+use core::fmt;
+
+use crate::en::{Encoder, EntriesEncoder, EntryEncoder, MapEncoder, SequenceEncoder};
+use crate::hint::{MapHint, SequenceHint};
+use crate::writer::BufWriter;
+use crate::{Context, Encode, Writer};
+
+/// The terminator written after each record.
+const LINE: &[u8] = b"\r\n";
+
+/// The root encoder of a CSV document.
+pub struct CsvEncoder<'a, W, const D: u8, C: ?Sized> {
+    cx: &'a C,
+    writer: W,
+}
+
+impl<'a, W, const D: u8, C: ?Sized> CsvEncoder<'a, W, D, C> {
+    /// Construct a new CSV encoder.
+    #[inline]
+    pub fn new(cx: &'a C, writer: W) -> Self {
+        Self { cx, writer }
+    }
+}
+
+/// Encoder for a sequence of records.
+pub struct RecordsEncoder<'a, W, const D: u8, C: ?Sized> {
+    cx: &'a C,
+    writer: W,
+    columns: Option<usize>,
+}
+
+/// Encoder for one element in a sequence of records.
+pub struct RowEncoder<'a, 'b, W, const D: u8, C: ?Sized> {
+    cx: &'a C,
+    writer: W,
+    columns: &'b mut Option<usize>,
+}
+
+/// Encoder for the fields of a single record.
+///
+/// The first record buffers its field names, so that the header can be
+/// written before it.
+pub struct RecordEncoder<'a, 'b, W, const D: u8, C>
+where
+    C: ?Sized + Context,
+{
+    cx: &'a C,
+    writer: W,
+    expected: Option<usize>,
+    columns: Option<&'b mut Option<usize>>,
+    len: usize,
+    keys: BufWriter<'a, C::Allocator>,
+    values: BufWriter<'a, C::Allocator>,
+}
+
+impl<'a, 'b, W, const D: u8, C> RecordEncoder<'a, 'b, W, D, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    #[inline]
+    fn new(cx: &'a C, writer: W, columns: Option<&'b mut Option<usize>>) -> Self {
+        Self {
+            cx,
+            writer,
+            expected: columns.as_deref().copied().flatten(),
+            columns,
+            len: 0,
+            keys: BufWriter::new(cx.alloc()),
+            values: BufWriter::new(cx.alloc()),
+        }
+    }
+
+    /// Start a new field, returning the buffer that its name should be
+    /// written to if the header hasn't been written yet.
+    #[inline]
+    #[allow(clippy::type_complexity)]
+    fn next_field(
+        &mut self,
+    ) -> Result<
+        (
+            Option<&mut BufWriter<'a, C::Allocator>>,
+            &mut BufWriter<'a, C::Allocator>,
+        ),
+        C::Error,
+    > {
+        let mut keys = match self.expected {
+            Some(..) => None,
+            None => Some(&mut self.keys),
+        };
+
+        if self.len > 0 {
+            if let Some(keys) = keys.as_deref_mut() {
+                keys.write_byte(self.cx, D)?;
+            }
+
+            self.values.write_byte(self.cx, D)?;
+        }
+
+        self.len += 1;
+        Ok((keys, &mut self.values))
+    }
+
+    #[inline]
+    fn finish(mut self) -> Result<(), C::Error> {
+        match self.expected {
+            Some(expected) => {
+                if self.len != expected {
+                    return Err(self.cx.message(ColumnMismatch {
+                        expected,
+                        actual: self.len,
+                    }));
+                }
+            }
+            None => {
+                self.writer.extend(self.cx, self.keys.into_inner())?;
+                self.writer.write_bytes(self.cx, LINE)?;
+
+                if let Some(columns) = self.columns {
+                    *columns = Some(self.len);
+                }
+            }
+        }
+
+        self.writer.extend(self.cx, self.values.into_inner())?;
+        self.writer.write_bytes(self.cx, LINE)?;
+        Ok(())
+    }
+}
+
+/// Encoder for a name and value pair in a record.
+pub struct RecordEntryEncoder<'a, 'b, const D: u8, C>
+where
+    C: ?Sized + Context,
+{
+    cx: &'a C,
+    key: Option<&'b mut BufWriter<'a, C::Allocator>>,
+    value: &'b mut BufWriter<'a, C::Allocator>,
+}
+
+/// Encoder for the name of a field, which is only written as part of the
+/// header.
+pub struct KeyEncoder<'a, W, const D: u8, C: ?Sized> {
+    cx: &'a C,
+    writer: Option<W>,
+}
+
+impl<'a, W, const D: u8, C: ?Sized> KeyEncoder<'a, W, D, C> {
+    #[inline]
+    fn new(cx: &'a C, writer: Option<W>) -> Self {
+        Self { cx, writer }
+    }
+}
+
+/// Encoder for a single field.
+pub struct FieldEncoder<'a, W, const D: u8, C: ?Sized> {
+    cx: &'a C,
+    writer: W,
+}
+
+impl<'a, W, const D: u8, C: ?Sized> FieldEncoder<'a, W, D, C> {
+    #[inline]
+    fn new(cx: &'a C, writer: W) -> Self {
+        Self { cx, writer }
+    }
+}
+
+#[crate::encoder(crate)]
+impl<'a, W, const D: u8, C> Encoder for CsvEncoder<'a, W, D, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Ok = ();
+    type Mode = C::Mode;
+    type WithContext<'this, U> = CsvEncoder<'this, W, D, U> where U: 'this + Context;
+    type EncodeSequence = RecordsEncoder<'a, W, D, C>;
+    type EncodeMap = RecordEncoder<'a, 'static, W, D, C>;
+    type EncodeMapEntries = RecordEncoder<'a, 'static, W, D, C>;
+
+    #[inline]
+    fn cx(&self) -> &Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn with_context<U>(self, cx: &U) -> Result<Self::WithContext<'_, U>, C::Error>
+    where
+        U: Context,
+    {
+        Ok(CsvEncoder::new(cx, self.writer))
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "sequence of records or a record that can be encoded to CSV"
+        )
+    }
+
+    #[inline]
+    fn encode<T>(self, value: T) -> Result<Self::Ok, Self::Error>
+    where
+        T: Encode<Self::Mode>,
+    {
+        value.encode(self.cx, self)
+    }
+
+    #[inline]
+    fn encode_sequence(self, _: &SequenceHint) -> Result<Self::EncodeSequence, C::Error> {
+        Ok(RecordsEncoder {
+            cx: self.cx,
+            writer: self.writer,
+            columns: None,
+        })
+    }
+
+    #[inline]
+    fn encode_map(self, _: &MapHint) -> Result<Self::EncodeMap, C::Error> {
+        Ok(RecordEncoder::new(self.cx, self.writer, None))
+    }
+
+    #[inline]
+    fn encode_map_entries(self, _: &MapHint) -> Result<Self::EncodeMapEntries, C::Error> {
+        Ok(RecordEncoder::new(self.cx, self.writer, None))
+    }
+}
+
+#[crate::encoder(crate)]
+impl<'a, 'b, W, const D: u8, C> Encoder for RowEncoder<'a, 'b, W, D, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Ok = ();
+    type Mode = C::Mode;
+    type WithContext<'this, U> = RowEncoder<'this, 'b, W, D, U> where U: 'this + Context;
+    type EncodeMap = RecordEncoder<'a, 'b, W, D, C>;
+    type EncodeMapEntries = RecordEncoder<'a, 'b, W, D, C>;
+
+    #[inline]
+    fn cx(&self) -> &Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn with_context<U>(self, cx: &U) -> Result<Self::WithContext<'_, U>, C::Error>
+    where
+        U: Context,
+    {
+        Ok(RowEncoder {
+            cx,
+            writer: self.writer,
+            columns: self.columns,
+        })
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "record that can be encoded to CSV")
+    }
+
+    #[inline]
+    fn encode<T>(self, value: T) -> Result<Self::Ok, Self::Error>
+    where
+        T: Encode<Self::Mode>,
+    {
+        value.encode(self.cx, self)
+    }
+
+    #[inline]
+    fn encode_map(self, _: &MapHint) -> Result<Self::EncodeMap, C::Error> {
+        Ok(RecordEncoder::new(self.cx, self.writer, Some(self.columns)))
+    }
+
+    #[inline]
+    fn encode_map_entries(self, _: &MapHint) -> Result<Self::EncodeMapEntries, C::Error> {
+        Ok(RecordEncoder::new(self.cx, self.writer, Some(self.columns)))
+    }
+}
+
+#[crate::encoder(crate)]
+impl<'a, W, const D: u8, C> Encoder for KeyEncoder<'a, W, D, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Ok = ();
+    type Mode = C::Mode;
+    type WithContext<'this, U> = KeyEncoder<'this, W, D, U> where U: 'this + Context;
+
+    #[inline]
+    fn cx(&self) -> &Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn with_context<U>(self, cx: &U) -> Result<Self::WithContext<'_, U>, C::Error>
+    where
+        U: Context,
+    {
+        Ok(KeyEncoder::new(cx, self.writer))
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "field name that can be encoded to CSV")
+    }
+
+    #[inline]
+    fn encode<T>(self, value: T) -> Result<Self::Ok, Self::Error>
+    where
+        T: Encode<Self::Mode>,
+    {
+        value.encode(self.cx, self)
+    }
+
+    #[inline]
+    fn encode_string(self, string: &str) -> Result<Self::Ok, C::Error> {
+        let Some(writer) = self.writer else {
+            return Ok(());
+        };
+
+        FieldEncoder::<_, D, _>::new(self.cx, writer).encode_string(string)
+    }
+
+    #[inline]
+    fn collect_string<T>(self, value: &T) -> Result<Self::Ok, C::Error>
+    where
+        T: ?Sized + fmt::Display,
+    {
+        let buf = self.cx.collect_string(value)?;
+        self.encode_string(buf.as_ref())
+    }
+
+    #[inline]
+    fn encode_u8(self, value: u8) -> Result<Self::Ok, C::Error> {
+        self.encode_usize(value as usize)
+    }
+
+    #[inline]
+    fn encode_u16(self, value: u16) -> Result<Self::Ok, C::Error> {
+        self.encode_usize(value as usize)
+    }
+
+    #[inline]
+    fn encode_u32(self, value: u32) -> Result<Self::Ok, C::Error> {
+        self.encode_usize(value as usize)
+    }
+
+    #[inline]
+    fn encode_usize(self, value: usize) -> Result<Self::Ok, C::Error> {
+        let Some(writer) = self.writer else {
+            return Ok(());
+        };
+
+        FieldEncoder::<_, D, _>::new(self.cx, writer).encode_usize(value)
+    }
+}
+
+#[crate::encoder(crate)]
+impl<'a, W, const D: u8, C> Encoder for FieldEncoder<'a, W, D, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Ok = ();
+    type Mode = C::Mode;
+    type WithContext<'this, U> = FieldEncoder<'this, W, D, U> where U: 'this + Context;
+    type EncodeSome = Self;
+
+    #[inline]
+    fn cx(&self) -> &Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn with_context<U>(self, cx: &U) -> Result<Self::WithContext<'_, U>, C::Error>
+    where
+        U: Context,
+    {
+        Ok(FieldEncoder::new(cx, self.writer))
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "field that can be encoded to CSV")
+    }
+
+    #[inline]
+    fn encode<T>(self, value: T) -> Result<Self::Ok, Self::Error>
+    where
+        T: Encode<Self::Mode>,
+    {
+        value.encode(self.cx, self)
+    }
+
+    #[inline]
+    fn encode_empty(self) -> Result<Self::Ok, C::Error> {
+        Ok(())
+    }
+
+    #[inline]
+    fn encode_bool(mut self, value: bool) -> Result<Self::Ok, C::Error> {
+        let value: &[u8] = if value { b"true" } else { b"false" };
+        self.writer.write_bytes(self.cx, value)
+    }
+
+    #[inline]
+    fn encode_char(self, value: char) -> Result<Self::Ok, C::Error> {
+        self.encode_string(value.encode_utf8(&mut [0, 0, 0, 0]))
+    }
+
+    #[inline]
+    fn encode_u8(mut self, value: u8) -> Result<Self::Ok, C::Error> {
+        let mut buffer = itoa::Buffer::new();
+        self.writer
+            .write_bytes(self.cx, buffer.format(value).as_bytes())
+    }
+
+    #[inline]
+    fn encode_u16(mut self, value: u16) -> Result<Self::Ok, C::Error> {
+        let mut buffer = itoa::Buffer::new();
+        self.writer
+            .write_bytes(self.cx, buffer.format(value).as_bytes())
+    }
+
+    #[inline]
+    fn encode_u32(mut self, value: u32) -> Result<Self::Ok, C::Error> {
+        let mut buffer = itoa::Buffer::new();
+        self.writer
+            .write_bytes(self.cx, buffer.format(value).as_bytes())
+    }
+
+    #[inline]
+    fn encode_u64(mut self, value: u64) -> Result<Self::Ok, C::Error> {
+        let mut buffer = itoa::Buffer::new();
+        self.writer
+            .write_bytes(self.cx, buffer.format(value).as_bytes())
+    }
+
+    #[inline]
+    fn encode_u128(mut self, value: u128) -> Result<Self::Ok, C::Error> {
+        let mut buffer = itoa::Buffer::new();
+        self.writer
+            .write_bytes(self.cx, buffer.format(value).as_bytes())
+    }
+
+    #[inline]
+    fn encode_i8(mut self, value: i8) -> Result<Self::Ok, C::Error> {
+        let mut buffer = itoa::Buffer::new();
+        self.writer
+            .write_bytes(self.cx, buffer.format(value).as_bytes())
+    }
+
+    #[inline]
+    fn encode_i16(mut self, value: i16) -> Result<Self::Ok, C::Error> {
+        let mut buffer = itoa::Buffer::new();
+        self.writer
+            .write_bytes(self.cx, buffer.format(value).as_bytes())
+    }
+
+    #[inline]
+    fn encode_i32(mut self, value: i32) -> Result<Self::Ok, C::Error> {
+        let mut buffer = itoa::Buffer::new();
+        self.writer
+            .write_bytes(self.cx, buffer.format(value).as_bytes())
+    }
+
+    #[inline]
+    fn encode_i64(mut self, value: i64) -> Result<Self::Ok, C::Error> {
+        let mut buffer = itoa::Buffer::new();
+        self.writer
+            .write_bytes(self.cx, buffer.format(value).as_bytes())
+    }
+
+    #[inline]
+    fn encode_i128(mut self, value: i128) -> Result<Self::Ok, C::Error> {
+        let mut buffer = itoa::Buffer::new();
+        self.writer
+            .write_bytes(self.cx, buffer.format(value).as_bytes())
+    }
+
+    #[inline]
+    fn encode_usize(mut self, value: usize) -> Result<Self::Ok, C::Error> {
+        let mut buffer = itoa::Buffer::new();
+        self.writer
+            .write_bytes(self.cx, buffer.format(value).as_bytes())
+    }
+
+    #[inline]
+    fn encode_isize(mut self, value: isize) -> Result<Self::Ok, C::Error> {
+        let mut buffer = itoa::Buffer::new();
+        self.writer
+            .write_bytes(self.cx, buffer.format(value).as_bytes())
+    }
+
+    #[inline]
+    fn encode_f32(mut self, value: f32) -> Result<Self::Ok, C::Error> {
+        let mut buffer = ryu::Buffer::new();
+        self.writer
+            .write_bytes(self.cx, buffer.format(value).as_bytes())
+    }
+
+    #[inline]
+    fn encode_f64(mut self, value: f64) -> Result<Self::Ok, C::Error> {
+        let mut buffer = ryu::Buffer::new();
+        self.writer
+            .write_bytes(self.cx, buffer.format(value).as_bytes())
+    }
+
+    #[inline]
+    fn encode_string(mut self, string: &str) -> Result<Self::Ok, C::Error> {
+        let bytes = string.as_bytes();
+
+        let needs_quotes = bytes.is_empty()
+            || bytes
+                .iter()
+                .any(|&b| matches!(b, b'"' | b'\r' | b'\n') || b == D);
+
+        if !needs_quotes {
+            return self.writer.write_bytes(self.cx, bytes);
+        }
+
+        self.writer.write_byte(self.cx, b'"')?;
+
+        for (n, part) in bytes.split(|&b| b == b'"').enumerate() {
+            if n > 0 {
+                self.writer.write_bytes(self.cx, b"\"\"")?;
+            }
+
+            self.writer.write_bytes(self.cx, part)?;
+        }
+
+        self.writer.write_byte(self.cx, b'"')
+    }
+
+    #[inline]
+    fn collect_string<T>(self, value: &T) -> Result<Self::Ok, C::Error>
+    where
+        T: ?Sized + fmt::Display,
+    {
+        let buf = self.cx.collect_string(value)?;
+        self.encode_string(buf.as_ref())
+    }
+
+    #[inline]
+    fn encode_some(self) -> Result<Self::EncodeSome, C::Error> {
+        Ok(self)
+    }
+
+    #[inline]
+    fn encode_none(self) -> Result<Self::Ok, C::Error> {
+        Ok(())
+    }
+}
+
+impl<'a, W, const D: u8, C> SequenceEncoder for RecordsEncoder<'a, W, D, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Ok = ();
+    type EncodeNext<'this> = RowEncoder<'a, 'this, W::Mut<'this>, D, C> where Self: 'this;
+
+    #[inline]
+    fn encode_next(&mut self) -> Result<Self::EncodeNext<'_>, C::Error> {
+        Ok(RowEncoder {
+            cx: self.cx,
+            writer: self.writer.borrow_mut(),
+            columns: &mut self.columns,
+        })
+    }
+
+    #[inline]
+    fn finish_sequence(self) -> Result<Self::Ok, C::Error> {
+        Ok(())
+    }
+}
+
+impl<'a, 'b, W, const D: u8, C> MapEncoder for RecordEncoder<'a, 'b, W, D, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Ok = ();
+    type EncodeEntry<'this> = RecordEntryEncoder<'a, 'this, D, C> where Self: 'this;
+
+    #[inline]
+    fn encode_entry(&mut self) -> Result<Self::EncodeEntry<'_>, C::Error> {
+        let cx = self.cx;
+        let (key, value) = self.next_field()?;
+        Ok(RecordEntryEncoder { cx, key, value })
+    }
+
+    #[inline]
+    fn finish_map(self) -> Result<Self::Ok, C::Error> {
+        self.finish()
+    }
+}
+
+impl<'a, 'b, W, const D: u8, C> EntriesEncoder for RecordEncoder<'a, 'b, W, D, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Ok = ();
+    type EncodeEntryKey<'this>
+        = KeyEncoder<'a, &'this mut BufWriter<'a, C::Allocator>, D, C>
+    where
+        Self: 'this;
+    type EncodeEntryValue<'this>
+        = FieldEncoder<'a, &'this mut BufWriter<'a, C::Allocator>, D, C>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn encode_entry_key(&mut self) -> Result<Self::EncodeEntryKey<'_>, C::Error> {
+        let cx = self.cx;
+        let (key, _) = self.next_field()?;
+        Ok(KeyEncoder::new(cx, key))
+    }
+
+    #[inline]
+    fn encode_entry_value(&mut self) -> Result<Self::EncodeEntryValue<'_>, C::Error> {
+        Ok(FieldEncoder::new(self.cx, &mut self.values))
+    }
+
+    #[inline]
+    fn finish_entries(self) -> Result<Self::Ok, C::Error> {
+        self.finish()
+    }
+}
+
+impl<'a, 'b, const D: u8, C> EntryEncoder for RecordEntryEncoder<'a, 'b, D, C>
+where
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Ok = ();
+    type EncodeKey<'this>
+        = KeyEncoder<'a, &'this mut BufWriter<'a, C::Allocator>, D, C>
+    where
+        Self: 'this;
+    type EncodeValue<'this>
+        = FieldEncoder<'a, &'this mut BufWriter<'a, C::Allocator>, D, C>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn encode_key(&mut self) -> Result<Self::EncodeKey<'_>, C::Error> {
+        Ok(KeyEncoder::new(self.cx, self.key.as_deref_mut()))
+    }
+
+    #[inline]
+    fn encode_value(&mut self) -> Result<Self::EncodeValue<'_>, C::Error> {
+        Ok(FieldEncoder::new(self.cx, &mut *self.value))
+    }
+
+    #[inline]
+    fn finish_entry(self) -> Result<Self::Ok, C::Error> {
+        Ok(())
+    }
+}
+
+struct ColumnMismatch {
+    expected: usize,
+    actual: usize,
+}
+
+impl fmt::Display for ColumnMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { expected, actual } = *self;
+        write!(
+            f,
+            "Record has {actual} fields, but the header has {expected} columns"
+        )
+    }
+}
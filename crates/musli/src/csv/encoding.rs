@@ -0,0 +1,298 @@
+//! Module that defines [`Encoding`] whith allows for customization of the
+//! encoding format, and the [`DEFAULT`] encoding configuration.
+
+use core::marker;
+
+#[cfg(feature = "alloc")]
+use rust_alloc::string::String;
+
+use crate::mode::Text;
+#[cfg(feature = "alloc")]
+use crate::Encode;
+use crate::{Decode, IntoReader};
+
+use super::de::CsvDecoder;
+use super::en::CsvEncoder;
+use super::error::Error;
+
+/// The default configuration, which uses `,` as a delimiter.
+pub const DEFAULT: Encoding = Encoding::new();
+
+crate::macros::bare_encoding!(Text, DEFAULT, csv, IntoReader);
+
+/// Encode the given value to a [`String`] using the [`DEFAULT`] [`Encoding`].
+///
+/// # Examples
+///
+/// ```
+/// use musli::{Decode, Encode};
+/// use musli::csv;
+/// # use musli::csv::Error;
+///
+/// #[derive(Debug, PartialEq, Decode, Encode)]
+/// struct Person {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// let people = vec![
+///     Person { name: "Aristotle".to_string(), age: 61 },
+///     Person { name: "Plato, the Elder".to_string(), age: 80 },
+/// ];
+///
+/// let data = csv::to_string(&people)?;
+/// assert_eq!(data, "name,age\r\nAristotle,61\r\n\"Plato, the Elder\",80\r\n");
+///
+/// let decoded: Vec<Person> = csv::from_str(&data[..])?;
+/// assert_eq!(decoded, people);
+/// # Ok::<(), Error>(())
+/// ```
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn to_string<T>(value: &T) -> Result<String, Error>
+where
+    T: ?Sized + Encode<Text>,
+{
+    DEFAULT.to_string(value)
+}
+
+/// Decode the given type `T` from the given string using the [`DEFAULT`]
+/// [`Encoding`].
+///
+/// # Examples
+///
+/// ```
+/// use musli::{Decode, Encode};
+/// use musli::csv;
+/// # use musli::csv::Error;
+///
+/// #[derive(Decode, Encode)]
+/// struct Person {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// let people: Vec<Person> = csv::from_str("age,name\r\n61,Aristotle\r\n80,Plato\r\n")?;
+/// assert_eq!(people.len(), 2);
+/// assert_eq!(people[0].name, "Aristotle");
+/// assert_eq!(people[1].age, 80);
+/// # Ok::<(), Error>(())
+/// ```
+#[inline]
+pub fn from_str<'de, T>(string: &'de str) -> Result<T, Error>
+where
+    T: Decode<'de, Text>,
+{
+    DEFAULT.from_str(string)
+}
+
+/// Setting up encoding with parameters.
+pub struct Encoding<const D: u8 = b',', M = Text>
+where
+    M: 'static,
+{
+    _marker: marker::PhantomData<M>,
+}
+
+impl Default for Encoding<b',', Text> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Encoding<b',', Text> {
+    /// Construct a new [`Encoding`].
+    ///
+    /// You can modify this using the available factory methods:
+    ///
+    /// ```
+    /// use musli::{Encode, Decode};
+    /// use musli::csv::{self, Encoding};
+    /// # use musli::csv::Error;
+    ///
+    /// const CONFIG: Encoding<b'\t'> = Encoding::new().with_delimiter();
+    ///
+    /// #[derive(Debug, PartialEq, Encode, Decode)]
+    /// struct Person {
+    ///     name: String,
+    ///     age: u32,
+    /// }
+    ///
+    /// let expected = vec![Person {
+    ///     name: "Aristotle".to_string(),
+    ///     age: 61,
+    /// }];
+    ///
+    /// let out = CONFIG.to_string(&expected)?;
+    /// assert_eq!(out, "name\tage\r\nAristotle\t61\r\n");
+    ///
+    /// let actual: Vec<Person> = CONFIG.from_str(&out)?;
+    /// assert_eq!(expected, actual);
+    /// # Ok::<(), Error>(())
+    /// ```
+    #[inline]
+    pub const fn new() -> Self {
+        Encoding {
+            _marker: marker::PhantomData,
+        }
+    }
+}
+
+impl<const D: u8, M> Encoding<D, M>
+where
+    M: 'static,
+{
+    /// Change the mode of the encoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::csv::Encoding;
+    ///
+    /// enum Custom {}
+    ///
+    /// const CONFIG: Encoding<b',', Custom> = Encoding::new().with_mode();
+    /// ```
+    pub const fn with_mode<T>(self) -> Encoding<D, T> {
+        Encoding {
+            _marker: marker::PhantomData,
+        }
+    }
+
+    /// Change the delimiter used to separate fields.
+    ///
+    /// The delimiter must not be `"`, `\r` or `\n`, which is checked at compile
+    /// time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::csv::Encoding;
+    ///
+    /// const CONFIG: Encoding<b';'> = Encoding::new().with_delimiter();
+    /// ```
+    ///
+    /// Using a quote as a delimiter fails to compile:
+    ///
+    /// ```compile_fail
+    /// use musli::csv::Encoding;
+    ///
+    /// const CONFIG: Encoding<b'"'> = Encoding::new().with_delimiter();
+    /// ```
+    pub const fn with_delimiter<const U: u8>(self) -> Encoding<U, M> {
+        const {
+            assert!(
+                U != b'"' && U != b'\r' && U != b'\n',
+                "CSV delimiter must not be a quote or a line break"
+            );
+        }
+
+        Encoding {
+            _marker: marker::PhantomData,
+        }
+    }
+
+    crate::macros::encoding_impls!(
+        M,
+        csv,
+        CsvEncoder::<_, D, _>::new,
+        CsvDecoder::<_, D, _>::new,
+        IntoReader::into_reader,
+        Reader,
+    );
+
+    /// Encode the given value to a [`String`] using the current [`Encoding`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Decode, Encode};
+    /// use musli::csv;
+    /// # use musli::csv::Error;
+    ///
+    /// const ENCODING: csv::Encoding<b';'> = csv::Encoding::new().with_delimiter();
+    ///
+    /// #[derive(Decode, Encode)]
+    /// struct Person {
+    ///     name: String,
+    ///     age: u32,
+    /// }
+    ///
+    /// let data = ENCODING.to_string(&vec![Person {
+    ///     name: "Aristotle".to_string(),
+    ///     age: 61,
+    /// }])?;
+    ///
+    /// assert_eq!(data, "name;age\r\nAristotle;61\r\n");
+    /// # Ok::<(), Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn to_string<T>(self, value: &T) -> Result<String, Error>
+    where
+        T: ?Sized + Encode<M>,
+    {
+        crate::alloc::default!(|alloc| {
+            let cx = crate::context::Same::with_alloc(alloc);
+            self.to_string_with(&cx, value)
+        })
+    }
+
+    /// Encode the given value to a [`String`] using the current [`Encoding`].
+    ///
+    /// This is the same as [`Encoding::to_string`] but allows for using a
+    /// configurable [`Context`].
+    ///
+    /// [`Context`]: crate::Context
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Decode, Encode};
+    /// use musli::csv;
+    /// use musli::context::Same;
+    /// # use musli::csv::Error;
+    ///
+    /// const ENCODING: csv::Encoding = csv::Encoding::new();
+    ///
+    /// #[derive(Decode, Encode)]
+    /// struct Person {
+    ///     name: String,
+    ///     age: u32,
+    /// }
+    ///
+    /// let cx = Same::new();
+    ///
+    /// let data = ENCODING.to_string_with(&cx, &vec![Person {
+    ///     name: "Aristotle".to_string(),
+    ///     age: 61,
+    /// }])?;
+    ///
+    /// let people: Vec<Person> = ENCODING.from_str_with(&cx, &data[..])?;
+    /// assert_eq!(people[0].name, "Aristotle");
+    /// assert_eq!(people[0].age, 61);
+    /// # Ok::<(), Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn to_string_with<T, C>(self, cx: &C, value: &T) -> Result<String, C::Error>
+    where
+        C: ?Sized + crate::Context<Mode = M>,
+        T: ?Sized + Encode<M>,
+    {
+        cx.clear();
+        let mut data = rust_alloc::vec::Vec::with_capacity(128);
+        T::encode(value, cx, CsvEncoder::<_, D, _>::new(cx, &mut data))?;
+        crate::str::from_utf8_owned(data).map_err(crate::str::map_utf8_error(cx))
+    }
+}
+
+impl<const D: u8, M> Clone for Encoding<D, M> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<const D: u8, M> Copy for Encoding<D, M> {}
@@ -0,0 +1,22 @@
+crate::macros::error_type! {
+    /// Error raised during CSV encoding.
+    pub struct Error;
+
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::Decode;
+    /// use musli::context::ErrorKind;
+    ///
+    /// #[derive(Debug, Decode)]
+    /// struct Person {
+    ///     name: String,
+    ///     age: u32,
+    /// }
+    ///
+    /// let error = musli::csv::from_str::<Vec<Person>>("name\nAristotle\n").unwrap_err();
+    /// assert_eq!(error.kind(), ErrorKind::MissingField);
+    /// ```
+    pub fn kind;
+}
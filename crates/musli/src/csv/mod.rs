@@ -0,0 +1,138 @@
+//! Support for [CSV], a tabular text format where each line is a record of
+//! fields separated by a delimiter.
+//!
+//! A sequence of structs is encoded as a header line containing the names of
+//! the fields, followed by one line for each struct. Field names are decided
+//! by the [`Text`] mode, so they can be customized with `#[musli(name_all =
+//! "..")]` or `#[musli(name = "..")]`. When decoding, columns are matched up
+//! to fields by their name in the header, so they can appear in any order:
+//!
+//! * ✔ Can tolerate missing columns if the fields are annotated with
+//!   `#[musli(default)]`.
+//! * ✔ Can skip over unknown columns.
+//!
+//! [CSV]: https://www.rfc-editor.org/rfc/rfc4180
+//! [`Text`]: crate::mode::Text
+//!
+//! ```
+//! use musli::{Encode, Decode};
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! #[musli(name_all = "PascalCase")]
+//! struct Person {
+//!     name: String,
+//!     age: u32,
+//!     #[musli(default)]
+//!     nickname: Option<String>,
+//! }
+//!
+//! let people = vec![
+//!     Person {
+//!         name: String::from("Aristotle"),
+//!         age: 61,
+//!         nickname: None,
+//!     },
+//!     Person {
+//!         name: String::from("Aristocles"),
+//!         age: 80,
+//!         nickname: Some(String::from("Plato, \"the broad\"")),
+//!     },
+//! ];
+//!
+//! let data = musli::csv::to_string(&people)?;
+//!
+//! assert_eq!(
+//!     data,
+//!     "Name,Age,Nickname\r\nAristotle,61,\r\nAristocles,80,\"Plato, \"\"the broad\"\"\"\r\n"
+//! );
+//!
+//! let decoded: Vec<Person> = musli::csv::from_str(&data)?;
+//! assert_eq!(decoded, people);
+//! # Ok::<_, musli::csv::Error>(())
+//! ```
+//!
+//! <br>
+//!
+//! ## Fields
+//!
+//! Fields are quoted as described in [RFC 4180] when they contain the
+//! delimiter, a quote or a line break, in which case any quotes inside of
+//! them are doubled. Records are terminated by `\r\n`, but both `\r\n` and
+//! `\n` are accepted when decoding.
+//!
+//! Only primitive values, strings and optional values can be used as fields.
+//! `None` is encoded as an empty field, while an empty string is encoded as
+//! `""` so that the two can be told apart. Enums where all variants are units
+//! are encoded as the name of the variant.
+//!
+//! A top-level struct which isn't in a sequence is encoded as a header
+//! followed by a single record.
+//!
+//! [RFC 4180]: https://www.rfc-editor.org/rfc/rfc4180
+//!
+//! <br>
+//!
+//! ## Delimiters
+//!
+//! The delimiter defaults to `,` and can be changed through
+//! [`Encoding::with_delimiter`]:
+//!
+//! ```
+//! use musli::{Encode, Decode};
+//! use musli::csv::Encoding;
+//!
+//! const TSV: Encoding<b'\t'> = Encoding::new().with_delimiter();
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! struct Point {
+//!     x: i32,
+//!     y: i32,
+//! }
+//!
+//! let points = vec![Point { x: 1, y: -2 }, Point { x: 3, y: 4 }];
+//!
+//! let data = TSV.to_string(&points)?;
+//! assert_eq!(data, "x\ty\r\n1\t-2\r\n3\t4\r\n");
+//!
+//! let decoded: Vec<Point> = TSV.from_str(&data)?;
+//! assert_eq!(decoded, points);
+//! # Ok::<_, musli::csv::Error>(())
+//! ```
+
+#![cfg(feature = "csv")]
+#![cfg_attr(doc_cfg, doc(cfg(feature = "csv")))]
+
+#[cfg(test)]
+mod tests;
+
+mod de;
+mod en;
+mod encoding;
+mod error;
+
+#[cfg(feature = "test")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "test")))]
+#[doc(hidden)]
+pub mod test;
+
+/// Convenient result alias for use with `musli::csv`.
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[doc(inline)]
+pub use self::encoding::to_string;
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[doc(inline)]
+pub use self::encoding::to_vec;
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+#[doc(inline)]
+pub use self::encoding::to_writer;
+#[doc(inline)]
+pub use self::encoding::{
+    decode, encode, from_slice, from_slice_exact, from_str, to_fixed_bytes, Encoding, DEFAULT,
+};
+#[doc(inline)]
+pub use self::error::Error;
@@ -0,0 +1,197 @@
+use core::fmt;
+
+use rust_alloc::string::{String, ToString};
+use rust_alloc::vec;
+use rust_alloc::vec::Vec;
+
+use crate::mode::Text;
+use crate::reader::SliceReader;
+use crate::{Decode, Encode};
+
+use super::Encoding;
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Person {
+    name: String,
+    age: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+enum Kind {
+    Cat,
+    Dog,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(name_all = "kebab-case")]
+struct Pet {
+    pet_name: String,
+    kind: Kind,
+    weight: f64,
+    vaccinated: bool,
+    #[musli(default)]
+    owner: Option<String>,
+}
+
+#[track_caller]
+fn roundtrips<T>(value: T, expected: &str)
+where
+    T: Encode<Text> + for<'de> Decode<'de, Text> + fmt::Debug + PartialEq,
+{
+    let out = super::to_string(&value).expect("failed to encode");
+    assert_eq!(out, expected, "encoding {value:?}");
+    let actual: T = super::from_str(expected).expect("failed to decode");
+    assert_eq!(actual, value);
+}
+
+#[track_caller]
+fn fails<T>(input: &str, message: &str)
+where
+    T: for<'de> Decode<'de, Text> + fmt::Debug,
+{
+    let error = super::from_str::<T>(input).expect_err("expected decoding to fail");
+    assert_eq!(error.to_string(), message);
+}
+
+fn person(name: &str, age: u32) -> Person {
+    Person {
+        name: name.to_string(),
+        age,
+    }
+}
+
+#[test]
+fn records() {
+    roundtrips(
+        vec![person("Aristotle", 61), person("Plato", 80)],
+        "name,age\r\nAristotle,61\r\nPlato,80\r\n",
+    );
+
+    roundtrips(Vec::<Person>::new(), "");
+    roundtrips(person("Aristotle", 61), "name,age\r\nAristotle,61\r\n");
+}
+
+#[test]
+fn quoting() {
+    roundtrips(
+        vec![
+            person("Plato, the Elder", 80),
+            person("Socrates \"the Gadfly\"", 70),
+            person("Line\r\nBreak", 1),
+            person("", 0),
+        ],
+        "name,age\r\n\"Plato, the Elder\",80\r\n\"Socrates \"\"the Gadfly\"\"\",70\r\n\"Line\r\nBreak\",1\r\n\"\",0\r\n",
+    );
+}
+
+#[test]
+fn field_types() {
+    roundtrips(
+        vec![
+            Pet {
+                pet_name: "Tom".to_string(),
+                kind: Kind::Cat,
+                weight: 4.5,
+                vaccinated: true,
+                owner: None,
+            },
+            Pet {
+                pet_name: "Rex".to_string(),
+                kind: Kind::Dog,
+                weight: 30.0,
+                vaccinated: false,
+                owner: Some(String::new()),
+            },
+        ],
+        "pet-name,kind,weight,vaccinated,owner\r\nTom,Cat,4.5,true,\r\nRex,Dog,30.0,false,\"\"\r\n",
+    );
+}
+
+#[test]
+fn delimiter() {
+    const SEMI: Encoding<b';'> = Encoding::new().with_delimiter();
+
+    let people = vec![person("Plato; the Elder", 80), person("Aristotle, 2", 61)];
+    let out = SEMI.to_string(&people).unwrap();
+    assert_eq!(
+        out,
+        "name;age\r\n\"Plato; the Elder\";80\r\nAristotle, 2;61\r\n"
+    );
+
+    let actual: Vec<Person> = SEMI.from_str(&out).unwrap();
+    assert_eq!(actual, people);
+}
+
+#[test]
+fn decode_by_header() {
+    let people: Vec<Person> =
+        super::from_str("age,ignored,name\n61,x,Aristotle\n80,\"y\nz\",Plato").unwrap();
+    assert_eq!(people, vec![person("Aristotle", 61), person("Plato", 80)]);
+}
+
+#[test]
+fn missing_columns() {
+    let pets: Vec<Pet> =
+        super::from_str("weight,kind,vaccinated,pet-name\r\n1.5,Cat,true,Tom\r\n").unwrap();
+
+    assert_eq!(
+        pets,
+        vec![Pet {
+            pet_name: "Tom".to_string(),
+            kind: Kind::Cat,
+            weight: 1.5,
+            vaccinated: true,
+            owner: None,
+        }]
+    );
+}
+
+#[test]
+fn trailing_data() {
+    let mut reader = SliceReader::new(b"name,age\r\nAristotle,61\r\nrest");
+    let actual: Person = super::decode(&mut reader).unwrap();
+    assert_eq!(actual, person("Aristotle", 61));
+    assert_eq!(reader.as_slice(), b"rest");
+}
+
+#[test]
+fn errors() {
+    fails::<Vec<Person>>(
+        "name,age\r\nAristotle\r\n",
+        "Expected 2 fields in record, but found 1",
+    );
+    fails::<Vec<Person>>(
+        "name,age\r\nAristotle,61,extra\r\n",
+        "Expected 2 fields in record, but found more",
+    );
+    fails::<Vec<Person>>(
+        "name,age\r\nAristotle,old\r\n",
+        "Expected u32, but found \"old\"",
+    );
+    fails::<Vec<Person>>(
+        "name,age\r\n\"Aristotle,61\r\n",
+        "Unterminated quoted field",
+    );
+    fails::<Vec<Person>>(
+        "name,age\r\n\"Aristotle\"x,61\r\n",
+        "Unexpected byte 0x78 after closing quote",
+    );
+}
+
+#[test]
+fn mismatched_records() {
+    #[derive(Encode)]
+    struct Row {
+        a: u32,
+        #[musli(skip_encoding_if = Option::is_none)]
+        b: Option<u32>,
+    }
+
+    let error = super::to_string(&vec![Row { a: 1, b: Some(2) }, Row { a: 3, b: None }])
+        .expect_err("expected encoding to fail");
+
+    assert_eq!(
+        error.to_string(),
+        "Record has 1 fields, but the header has 2 columns"
+    );
+}
@@ -22,3 +22,18 @@ pub use musli_core::de::{
     DecodeUnsizedBytes, Decoder, EntriesDecoder, EntryDecoder, MapDecoder, SequenceDecoder,
     SizeHint, Skip, UnsizedVisitor, VariantDecoder, Visitor,
 };
+
+mod decode_in;
+#[doc(inline)]
+pub use self::decode_in::DecodeIn;
+
+mod decode_iter;
+#[doc(inline)]
+pub use self::decode_iter::DecodeIter;
+#[cfg(any(
+    feature = "storage",
+    feature = "wire",
+    feature = "descriptive",
+    feature = "value"
+))]
+pub(crate) use self::decode_iter::decode_next;
@@ -0,0 +1,103 @@
+use crate::alloc::Allocator;
+
+use super::{Decode, Decoder};
+
+/// Trait governing how types are decoded into memory allocated from a
+/// specific [`Allocator`].
+///
+/// This is what makes it possible to decode into collections like
+/// [`alloc::Vec`] and [`alloc::String`], which borrow the allocator they were
+/// allocated from for the lifetime `'a`. Since the allocator is handed to the
+/// value being decoded directly, these can be used without the `alloc`
+/// feature, like when decoding using the [`Slice`] allocator.
+///
+/// This is also implemented for primitive types and borrowed strings and
+/// bytes, so that they can be used as the elements of a collection. Other
+/// types which don't allocate can implement it by forwarding to [`Decode`].
+/// Values are decoded through this trait using the `from_slice_in` method of
+/// an encoding, such as [`storage::Encoding::from_slice_in`].
+///
+/// [`alloc::Vec`]: crate::alloc::Vec
+/// [`alloc::String`]: crate::alloc::String
+/// [`Slice`]: crate::alloc::Slice
+/// [`storage::Encoding::from_slice_in`]: crate::storage::Encoding::from_slice_in
+///
+/// # Examples
+///
+/// ```
+/// use musli::alloc::{ArrayBuffer, Slice, String, Vec};
+///
+/// let data = musli::storage::to_vec(&["Hello", "World"])?;
+///
+/// let mut buf = ArrayBuffer::<1024>::with_size();
+/// let alloc = Slice::new(&mut buf);
+///
+/// let values: Vec<'_, String<'_, _>, _> = musli::storage::DEFAULT.from_slice_in(&alloc, &data)?;
+/// assert_eq!(values.len(), 2);
+/// assert_eq!(&*values.as_slice()[0], "Hello");
+/// assert_eq!(&*values.as_slice()[1], "World");
+/// # Ok::<_, musli::storage::Error>(())
+/// ```
+///
+/// Implementing it for a type which doesn't allocate:
+///
+/// ```
+/// use musli::{Decode, Decoder};
+/// use musli::alloc::Allocator;
+/// use musli::de::DecodeIn;
+///
+/// #[derive(Decode)]
+/// struct Reading {
+///     sensor: u8,
+///     value: u32,
+/// }
+///
+/// impl<'de, 'a, M, A> DecodeIn<'de, 'a, M, A> for Reading
+/// where
+///     A: 'a + ?Sized + Allocator,
+///     Reading: Decode<'de, M>,
+/// {
+///     fn decode_in<D>(cx: &D::Cx, decoder: D, _: &'a A) -> Result<Self, D::Error>
+///     where
+///         D: Decoder<'de, Mode = M>,
+///     {
+///         Decode::decode(cx, decoder)
+///     }
+/// }
+/// ```
+pub trait DecodeIn<'de, 'a, M, A>: Sized
+where
+    A: 'a + ?Sized + Allocator,
+{
+    /// Decode the given input, allocating from `alloc`.
+    fn decode_in<D>(cx: &D::Cx, decoder: D, alloc: &'a A) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = M>;
+}
+
+macro_rules! via_decode {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl<'de, 'a, M, A> DecodeIn<'de, 'a, M, A> for $ty
+            where
+                A: 'a + ?Sized + Allocator,
+            {
+                #[inline]
+                fn decode_in<D>(cx: &D::Cx, decoder: D, _: &'a A) -> Result<Self, D::Error>
+                where
+                    D: Decoder<'de, Mode = M>,
+                {
+                    Decode::decode(cx, decoder)
+                }
+            }
+        )*
+    };
+}
+
+via_decode! {
+    (), bool, char,
+    u8, u16, u32, u64, u128, usize,
+    i8, i16, i32, i64, i128, isize,
+    f32, f64,
+    &'de str, &'de [u8],
+}
@@ -0,0 +1,106 @@
+use core::fmt;
+
+/// An iterator which decodes values that have been concatenated in a byte
+/// slice, until the slice is exhausted.
+///
+/// This is constructed through the `decode_iter` and `decode_iter_with`
+/// methods of an encoding, such as [`storage::Encoding::decode_iter`].
+///
+/// Once a value fails to decode the iterator is exhausted, and
+/// [`DecodeIter::offset`] reports the byte offset of the value which failed.
+/// Since the iterator would otherwise never end, a value which decodes without
+/// consuming any input is also treated as an error.
+///
+/// [`storage::Encoding::decode_iter`]: crate::storage::Encoding::decode_iter
+// Note that this is constructed directly by the encoding macros, which are only
+// expanded when a format is enabled, so that it doesn't need a constructor which
+// would otherwise be unused.
+pub struct DecodeIter<'de, F> {
+    pub(crate) bytes: &'de [u8],
+    pub(crate) len: usize,
+    pub(crate) offset: usize,
+    pub(crate) done: bool,
+    pub(crate) decode: F,
+}
+
+impl<'de, F> DecodeIter<'de, F> {
+    /// The byte offset at which the value most recently returned by the
+    /// iterator starts.
+    ///
+    /// If the iterator returned an error, this is the offset of the value which
+    /// failed to decode.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The bytes which have not yet been decoded.
+    #[inline]
+    pub fn remaining(&self) -> &'de [u8] {
+        self.bytes
+    }
+}
+
+impl<'de, F, T, E> Iterator for DecodeIter<'de, F>
+where
+    F: FnMut(&mut &'de [u8]) -> Result<T, E>,
+{
+    type Item = Result<T, E>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.bytes.is_empty() {
+            return None;
+        }
+
+        self.offset = self.len - self.bytes.len();
+        let mut bytes = self.bytes;
+
+        match (self.decode)(&mut bytes) {
+            Ok(value) => {
+                self.bytes = bytes;
+                Some(Ok(value))
+            }
+            Err(error) => {
+                self.done = true;
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+impl<F> fmt::Debug for DecodeIter<'_, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DecodeIter")
+            .field("offset", &self.offset)
+            .field("remaining", &self.bytes.len())
+            .finish()
+    }
+}
+
+/// Decode the next value using `decode`, raising an error if it didn't consume
+/// any input.
+#[cfg(any(
+    feature = "storage",
+    feature = "wire",
+    feature = "descriptive",
+    feature = "value"
+))]
+#[inline]
+pub(crate) fn decode_next<'de, C, T>(
+    cx: &C,
+    bytes: &mut &'de [u8],
+    decode: impl FnOnce(&mut &'de [u8]) -> Result<T, C::Error>,
+) -> Result<T, C::Error>
+where
+    C: ?Sized + crate::Context,
+{
+    let len = bytes.len();
+    let value = decode(bytes)?;
+
+    if bytes.len() == len {
+        return Err(cx.message("Decoded value did not consume any input"));
+    }
+
+    Ok(value)
+}
@@ -4,6 +4,7 @@ use core::mem::take;
 #[cfg(feature = "alloc")]
 use rust_alloc::vec::Vec;
 
+use crate::context::ErrorKind;
 use crate::de::{
     Decode, DecodeUnsized, Decoder, EntriesDecoder, EntryDecoder, MapDecoder, SequenceDecoder,
     SizeHint, Skip, UnsizedVisitor, VariantDecoder, Visitor,
@@ -111,17 +112,21 @@ where
 
     // Standard function for decoding a pair sequence.
     #[inline]
-    fn shared_decode_map(mut self) -> Result<RemainingSelfDecoder<'a, R, OPT, C>, C::Error> {
+    fn shared_decode_map(mut self) -> Result<RemainingSelfDecoder<'a, 'de, R, OPT, C>, C::Error> {
         let pos = self.cx.mark();
         let len = self.decode_prefix(Kind::Map, pos)?;
+        self.cx.check_length(len)?;
         Ok(RemainingSelfDecoder::new(self.cx, self.reader, len))
     }
 
     // Standard function for decoding a pair sequence.
     #[inline]
-    fn shared_decode_sequence(mut self) -> Result<RemainingSelfDecoder<'a, R, OPT, C>, C::Error> {
+    fn shared_decode_sequence(
+        mut self,
+    ) -> Result<RemainingSelfDecoder<'a, 'de, R, OPT, C>, C::Error> {
         let pos = self.cx.mark();
         let len = self.decode_prefix(Kind::Sequence, pos)?;
+        self.cx.check_length(len)?;
         Ok(RemainingSelfDecoder::new(self.cx, self.reader, len))
     }
 
@@ -131,8 +136,9 @@ where
         let tag = Tag::from_byte(self.reader.read_byte(self.cx)?);
 
         if tag.kind() != kind {
-            return Err(self.cx.marked_message(
+            return Err(self.cx.marked_message_with_kind(
                 mark,
+                ErrorKind::InvalidTag,
                 Expected {
                     expected: kind,
                     actual: tag,
@@ -159,7 +165,11 @@ where
 
         match tag.kind() {
             Kind::Bytes => self.decode_len(tag),
-            _ => Err(self.cx.marked_message(start, "Expected prefix or pack")),
+            _ => Err(self.cx.marked_message_with_kind(
+                start,
+                ErrorKind::InvalidTag,
+                "Expected prefix or pack",
+            )),
         }
     }
 }
@@ -169,13 +179,19 @@ where
 /// This simplifies implementing decoders that do not have any special handling
 /// for length-prefixed types.
 #[doc(hidden)]
-pub struct RemainingSelfDecoder<'a, R, const OPT: Options, C: ?Sized> {
+pub struct RemainingSelfDecoder<'a, 'de, R, const OPT: Options, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
     cx: &'a C,
     reader: R,
     remaining: usize,
+    /// Where the last entry started, used to recover from errors.
+    entry: Option<(R::Checkpoint, C::Mark)>,
 }
 
-impl<'a, 'de, R, const OPT: Options, C> RemainingSelfDecoder<'a, R, OPT, C>
+impl<'a, 'de, R, const OPT: Options, C> RemainingSelfDecoder<'a, 'de, R, OPT, C>
 where
     R: Reader<'de>,
     C: ?Sized + Context,
@@ -186,6 +202,7 @@ where
             cx,
             reader,
             remaining,
+            entry: None,
         }
     }
 
@@ -227,9 +244,9 @@ where
     type DecodeBuffer = crate::value::AsValueDecoder<'a, BUFFER_OPTIONS, C>;
     type DecodePack = SelfDecoder<'a, Limit<R>, OPT, C>;
     type DecodeSome = Self;
-    type DecodeSequence = RemainingSelfDecoder<'a, R, OPT, C>;
-    type DecodeMap = RemainingSelfDecoder<'a, R, OPT, C>;
-    type DecodeMapEntries = RemainingSelfDecoder<'a, R, OPT, C>;
+    type DecodeSequence = RemainingSelfDecoder<'a, 'de, R, OPT, C>;
+    type DecodeMap = RemainingSelfDecoder<'a, 'de, R, OPT, C>;
+    type DecodeMapEntries = RemainingSelfDecoder<'a, 'de, R, OPT, C>;
     type DecodeVariant = Self;
 
     #[inline]
@@ -298,9 +315,11 @@ where
     {
         let pos = self.cx.mark();
         let len = self.decode_pack_length(pos)?;
+        self.cx.enter_nested()?;
         let mut decoder = SelfDecoder::new(self.cx, self.reader.limit(len));
         let output = f(&mut decoder)?;
         decoder.end()?;
+        self.cx.leave_nested();
         Ok(output)
     }
 
@@ -328,6 +347,7 @@ where
     {
         let pos = self.cx.mark();
         let len = self.decode_prefix(Kind::Bytes, pos)?;
+        self.cx.check_size(len)?;
         self.reader.read_bytes(self.cx, len, visitor)
     }
 
@@ -353,25 +373,29 @@ where
             #[cfg(feature = "alloc")]
             #[inline]
             fn visit_owned(self, cx: &C, bytes: Vec<u8>) -> Result<Self::Ok, C::Error> {
-                let string = crate::str::from_utf8_owned(bytes).map_err(cx.map())?;
+                let string =
+                    crate::str::from_utf8_owned(bytes).map_err(crate::str::map_utf8_error(cx))?;
                 self.0.visit_owned(cx, string)
             }
 
             #[inline]
             fn visit_borrowed(self, cx: &C, bytes: &'de [u8]) -> Result<Self::Ok, C::Error> {
-                let string = crate::str::from_utf8(bytes).map_err(cx.map())?;
+                let string =
+                    crate::str::from_utf8(bytes).map_err(crate::str::map_utf8_error(cx))?;
                 self.0.visit_borrowed(cx, string)
             }
 
             #[inline]
             fn visit_ref(self, cx: &C, bytes: &[u8]) -> Result<Self::Ok, C::Error> {
-                let string = crate::str::from_utf8(bytes).map_err(cx.map())?;
+                let string =
+                    crate::str::from_utf8(bytes).map_err(crate::str::map_utf8_error(cx))?;
                 self.0.visit_ref(cx, string)
             }
         }
 
         let pos = self.cx.mark();
         let len = self.decode_prefix(Kind::String, pos)?;
+        self.cx.check_size(len)?;
         self.reader.read_bytes(self.cx, len, Visitor(visitor))
     }
 
@@ -386,8 +410,9 @@ where
         match tag {
             FALSE => Ok(false),
             TRUE => Ok(true),
-            tag => Err(self.cx.marked_message(
+            tag => Err(self.cx.marked_message_with_kind(
                 pos,
+                ErrorKind::InvalidTag,
                 format_args! {
                     "Bad boolean, got {tag:?}"
                 },
@@ -474,9 +499,15 @@ where
                     let value = self.decode_f64()?;
                     visitor.visit_f64(cx, value)
                 }
-                _ => Err(cx.message(format_args!("Unsupported number tag, got {tag:?}"))),
+                _ => Err(cx.message_with_kind(
+                    ErrorKind::InvalidTag,
+                    format_args!("Unsupported number tag, got {tag:?}"),
+                )),
             },
-            _ => Err(cx.message(format_args!("Expected number, but got {tag:?}"))),
+            _ => Err(cx.message_with_kind(
+                ErrorKind::InvalidTag,
+                format_args!("Expected number, but got {tag:?}"),
+            )),
         }
     }
 
@@ -568,8 +599,9 @@ where
         match tag {
             NONE => Ok(None),
             SOME => Ok(Some(self)),
-            tag => Err(self.cx.marked_message(
+            tag => Err(self.cx.marked_message_with_kind(
                 pos,
+                ErrorKind::InvalidTag,
                 format_args! {
                     "Expected option, was {tag:?}"
                 },
@@ -582,9 +614,12 @@ where
     where
         F: FnOnce(&mut Self::DecodeSequence) -> Result<O, C::Error>,
     {
+        let cx = self.cx;
+        cx.enter_nested()?;
         let mut decoder = self.shared_decode_sequence()?;
         let output = f(&mut decoder)?;
         decoder.skip_sequence_remaining()?;
+        cx.leave_nested();
         Ok(output)
     }
 
@@ -593,9 +628,12 @@ where
     where
         F: FnOnce(&mut Self::DecodeMap) -> Result<O, C::Error>,
     {
+        let cx = self.cx;
+        cx.enter_nested()?;
         let mut decoder = self.shared_decode_map()?;
         let output = f(&mut decoder)?;
         decoder.skip_map_remaining()?;
+        cx.leave_nested();
         Ok(output)
     }
 
@@ -604,9 +642,12 @@ where
     where
         F: FnOnce(&mut Self::DecodeMapEntries) -> Result<O, C::Error>,
     {
+        let cx = self.cx;
+        cx.enter_nested()?;
         let mut decoder = self.shared_decode_map()?;
         let output = f(&mut decoder)?;
         decoder.skip_map_remaining()?;
+        cx.leave_nested();
         Ok(output)
     }
 
@@ -620,13 +661,19 @@ where
         let tag = Tag::from_byte(self.reader.read_byte(self.cx)?);
 
         if tag != VARIANT {
-            return Err(self.cx.message(Expected {
-                expected: Kind::Mark,
-                actual: tag,
-            }));
+            return Err(self.cx.message_with_kind(
+                ErrorKind::InvalidTag,
+                Expected {
+                    expected: Kind::Mark,
+                    actual: tag,
+                },
+            ));
         }
 
-        f(&mut self)
+        self.cx.enter_nested()?;
+        let output = f(&mut self)?;
+        self.cx.leave_nested();
+        Ok(output)
     }
 
     #[inline]
@@ -699,15 +746,19 @@ where
                 }
             }
             Kind::Sequence => {
+                cx.enter_nested()?;
                 let mut sequence = self.shared_decode_sequence()?;
                 let output = visitor.visit_sequence(cx, &mut sequence)?;
                 sequence.skip_sequence_remaining()?;
+                cx.leave_nested();
                 Ok(output)
             }
             Kind::Map => {
+                cx.enter_nested()?;
                 let mut map = self.shared_decode_map()?;
                 let output = visitor.visit_map(cx, &mut map)?;
                 map.skip_map_remaining()?;
+                cx.leave_nested();
                 Ok(output)
             }
             Kind::Bytes => {
@@ -770,7 +821,8 @@ where
     }
 }
 
-impl<'a, 'de, R, const OPT: Options, C> SequenceDecoder<'de> for RemainingSelfDecoder<'a, R, OPT, C>
+impl<'a, 'de, R, const OPT: Options, C> SequenceDecoder<'de>
+    for RemainingSelfDecoder<'a, 'de, R, OPT, C>
 where
     R: Reader<'de>,
     C: ?Sized + Context,
@@ -805,7 +857,7 @@ where
     }
 }
 
-impl<'a, 'de, R, const OPT: Options, C> MapDecoder<'de> for RemainingSelfDecoder<'a, R, OPT, C>
+impl<'a, 'de, R, const OPT: Options, C> MapDecoder<'de> for RemainingSelfDecoder<'a, 'de, R, OPT, C>
 where
     R: Reader<'de>,
     C: ?Sized + Context,
@@ -814,7 +866,7 @@ where
     type DecodeEntry<'this> = SelfDecoder<'a, R::Mut<'this>, OPT, C>
     where
         Self: 'this;
-    type DecodeRemainingEntries<'this> = RemainingSelfDecoder<'a, R::Mut<'this>, OPT, C>
+    type DecodeRemainingEntries<'this> = RemainingSelfDecoder<'a, 'de, R::Mut<'this>, OPT, C>
     where
         Self: 'this;
 
@@ -829,10 +881,28 @@ where
             return Ok(None);
         }
 
+        if self.cx.is_lenient() {
+            self.entry = Some((self.reader.checkpoint(), self.cx.mark()));
+        }
+
         self.remaining -= 1;
         Ok(Some(SelfDecoder::new(self.cx, self.reader.borrow_mut())))
     }
 
+    #[inline]
+    fn try_recover(&mut self) -> Result<bool, C::Error> {
+        let Some((checkpoint, mark)) = self.entry.take() else {
+            return Ok(false);
+        };
+
+        self.reader.rewind(checkpoint);
+        self.cx.restore(mark);
+
+        SelfDecoder::<_, OPT, _>::new(self.cx, self.reader.borrow_mut()).skip()?;
+        SelfDecoder::<_, OPT, _>::new(self.cx, self.reader.borrow_mut()).skip()?;
+        Ok(true)
+    }
+
     #[inline]
     fn decode_remaining_entries(&mut self) -> Result<Self::DecodeRemainingEntries<'_>, C::Error> {
         Ok(RemainingSelfDecoder::new(
@@ -843,7 +913,8 @@ where
     }
 }
 
-impl<'a, 'de, R, const OPT: Options, C> EntriesDecoder<'de> for RemainingSelfDecoder<'a, R, OPT, C>
+impl<'a, 'de, R, const OPT: Options, C> EntriesDecoder<'de>
+    for RemainingSelfDecoder<'a, 'de, R, OPT, C>
 where
     R: Reader<'de>,
     C: ?Sized + Context,
@@ -122,6 +122,7 @@ where
         SelfEncoder::<_, OPT, _>::new,
         SelfDecoder::<_, OPT, _>::new,
         IntoReader::into_reader,
+        Reader,
     );
 }
 
@@ -1,3 +1,4 @@
+use crate::context::ErrorKind;
 use crate::int::continuation as c;
 use crate::int::zigzag as zig;
 use crate::int::{Signed, Unsigned};
@@ -34,7 +35,10 @@ where
             let value = zig::decode(value);
 
             let Ok(value) = T::try_from(value) else {
-                return Err(cx.message(format_args!("Unsigned value outside of signed range")));
+                return Err(cx.message_with_kind(
+                    ErrorKind::NumberOverflow,
+                    "Unsigned value outside of signed range",
+                ));
             };
 
             Ok(value)
@@ -68,7 +72,10 @@ where
     let tag = Tag::from_byte(reader.read_byte(cx)?);
 
     if tag.kind() != Kind::Number {
-        return Err(cx.message(format_args!("Expected {:?}, got {tag:?}", Kind::Number)));
+        return Err(cx.message_with_kind(
+            ErrorKind::InvalidTag,
+            format_args!("Expected {:?}, got {tag:?}", Kind::Number),
+        ));
     }
 
     let kind = tag.number_kind();
@@ -103,7 +110,10 @@ where
         NumberKind::Signed => Ok(zig::decode(value)),
         NumberKind::Unsigned => {
             let Ok(value) = T::try_from(value) else {
-                return Err(cx.message(format_args!("Unsigned value outside of signed range")));
+                return Err(cx.message_with_kind(
+                    ErrorKind::NumberOverflow,
+                    "Unsigned value outside of signed range",
+                ));
             };
 
             Ok(value)
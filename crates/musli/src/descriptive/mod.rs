@@ -116,7 +116,9 @@ pub use self::encoding::to_vec;
 #[doc(inline)]
 pub use self::encoding::to_writer;
 #[doc(inline)]
-pub use self::encoding::{decode, encode, from_slice, to_fixed_bytes, Encoding, DEFAULT, OPTIONS};
+pub use self::encoding::{
+    decode, encode, from_slice, from_slice_exact, to_fixed_bytes, Encoding, DEFAULT, OPTIONS,
+};
 #[doc(inline)]
 pub use self::error::Error;
 
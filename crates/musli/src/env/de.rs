@@ -0,0 +1,963 @@
+use core::fmt;
+
+use crate::alloc::Vec;
+use crate::de::{
+    Decode, DecodeUnsized, Decoder, EntriesDecoder, EntryDecoder, MapDecoder, SequenceDecoder,
+    Skip, UnsizedVisitor,
+};
+#[cfg(not(feature = "parse-full"))]
+use crate::json::parser::integer::{
+    parse_signed_base as parse_signed, parse_unsigned_base as parse_unsigned,
+};
+#[cfg(feature = "parse-full")]
+use crate::json::parser::integer::{
+    parse_signed_full as parse_signed, parse_unsigned_full as parse_unsigned,
+};
+use crate::json::parser::{Parser, SliceParser};
+use crate::Context;
+
+/// The separator between the names of nested fields.
+const SEPARATOR: &[u8] = b"__";
+
+/// The location of a variable name and its value.
+#[derive(Clone, Copy)]
+struct Var {
+    start: usize,
+    mid: usize,
+    end: usize,
+}
+
+/// A collection of environment variables which matched a prefix.
+pub struct Vars<'a, C>
+where
+    C: ?Sized + Context,
+{
+    cx: &'a C,
+    /// The uppercase prefix used in error messages, followed by lowercase
+    /// names with the prefix stripped and their values stored back to back.
+    data: Vec<'a, u8, C::Allocator>,
+    vars: Vec<'a, Var, C::Allocator>,
+    /// The length of the prefix stored at the start of data.
+    prefix: usize,
+    /// The delimiter used to split sequences.
+    delimiter: u8,
+}
+
+impl<'a, C> Vars<'a, C>
+where
+    C: ?Sized + Context,
+{
+    /// Collect every variable which has the given prefix.
+    pub(crate) fn new<I, K, V>(
+        cx: &'a C,
+        prefix: &str,
+        delimiter: u8,
+        vars: I,
+    ) -> Result<Self, C::Error>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let mut this = Self {
+            cx,
+            data: Vec::new_in(cx.alloc()),
+            vars: Vec::new_in(cx.alloc()),
+            prefix: 0,
+            delimiter,
+        };
+
+        let prefix = prefix.as_bytes();
+
+        if !prefix.is_empty() {
+            for &b in prefix {
+                this.push(b.to_ascii_uppercase())?;
+            }
+
+            this.push(b'_')?;
+            this.prefix = this.data.len();
+        }
+
+        for (name, value) in vars {
+            let name = name.as_ref().as_bytes();
+
+            let name = if prefix.is_empty() {
+                name
+            } else {
+                match name.split_at_checked(prefix.len()) {
+                    Some((head, [b'_', rest @ ..])) if head.eq_ignore_ascii_case(prefix) => rest,
+                    _ => continue,
+                }
+            };
+
+            if name.is_empty() {
+                continue;
+            }
+
+            let start = this.data.len();
+
+            for &b in name {
+                this.push(b.to_ascii_lowercase())?;
+            }
+
+            let mid = this.data.len();
+
+            if !this.data.write(value.as_ref().as_bytes()) {
+                return Err(cx.message("Scratch buffer overflow"));
+            }
+
+            let var = Var {
+                start,
+                mid,
+                end: this.data.len(),
+            };
+
+            if !this.vars.push(var) {
+                return Err(cx.message("Scratch buffer overflow"));
+            }
+        }
+
+        Ok(this)
+    }
+
+    /// Construct the root decoder.
+    #[inline]
+    pub(crate) fn decoder(&self) -> EnvDecoder<'a, '_, C> {
+        EnvDecoder {
+            cx: self.cx,
+            vars: self,
+            path: &[],
+        }
+    }
+
+    #[inline]
+    fn push(&mut self, b: u8) -> Result<(), C::Error> {
+        if !self.data.push(b) {
+            return Err(self.cx.message("Scratch buffer overflow"));
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn name(&self, index: usize) -> &[u8] {
+        let var = self.vars.as_slice()[index];
+        &self.data.as_slice()[var.start..var.mid]
+    }
+
+    #[inline]
+    fn value(&self, index: usize) -> &[u8] {
+        let var = self.vars.as_slice()[index];
+        &self.data.as_slice()[var.mid..var.end]
+    }
+
+    /// Find the value of the variable with exactly the given name.
+    #[inline]
+    fn find(&self, path: &[u8]) -> Option<&[u8]> {
+        let index = (0..self.vars.len()).find(|&n| self.name(n) == path)?;
+        Some(self.value(index))
+    }
+
+    /// Get the name of the field nested under `path` which the variable at
+    /// `index` belongs to, and the full path of that field.
+    #[inline]
+    fn child(&self, path: &[u8], index: usize) -> Option<(&[u8], &[u8])> {
+        let name = self.name(index);
+
+        let offset = if path.is_empty() {
+            0
+        } else {
+            name.strip_prefix(path)?.strip_prefix(SEPARATOR)?;
+            path.len() + SEPARATOR.len()
+        };
+
+        let rest = &name[offset..];
+
+        let len = rest
+            .windows(SEPARATOR.len())
+            .position(|w| w == SEPARATOR)
+            .unwrap_or(rest.len());
+
+        if len == 0 {
+            return None;
+        }
+
+        Some((&rest[..len], &name[..offset + len]))
+    }
+
+    /// Format the variable name of the given path for diagnostics.
+    #[inline]
+    fn display<'b>(&'b self, path: &'b [u8]) -> VarName<'b> {
+        VarName {
+            prefix: &self.data.as_slice()[..self.prefix],
+            path,
+        }
+    }
+}
+
+/// The decoder for the value at a given path, which is either a variable or a
+/// collection of nested variables.
+pub struct EnvDecoder<'a, 'b, C>
+where
+    C: ?Sized + Context,
+{
+    cx: &'a C,
+    vars: &'b Vars<'a, C>,
+    path: &'b [u8],
+}
+
+impl<'a, 'b, C> EnvDecoder<'a, 'b, C>
+where
+    C: ?Sized + Context,
+{
+    /// Get a decoder for the value of the variable at the current path.
+    #[inline]
+    fn scalar(self) -> Result<ScalarDecoder<'a, 'b, C>, C::Error> {
+        let Some(value) = self.vars.find(self.path) else {
+            return Err(self.cx.message(format_args!(
+                "Expected a value in variable {}",
+                self.vars.display(self.path)
+            )));
+        };
+
+        Ok(ScalarDecoder { cx: self.cx, value })
+    }
+}
+
+/// Decoder for the fields nested under a path.
+pub struct Fields<'a, 'b, C>
+where
+    C: ?Sized + Context,
+{
+    cx: &'a C,
+    vars: &'b Vars<'a, C>,
+    path: &'b [u8],
+    /// The index of the next variable to consider as an entry.
+    index: usize,
+    /// The path of the entry whose key was most recently decoded.
+    current: &'b [u8],
+}
+
+impl<'b, C> Fields<'_, 'b, C>
+where
+    C: ?Sized + Context,
+{
+    /// Advance to the next field which hasn't been seen before.
+    #[inline]
+    fn next_entry(&mut self) -> Option<(&'b [u8], &'b [u8])> {
+        while self.index < self.vars.vars.len() {
+            let index = self.index;
+            self.index += 1;
+
+            let Some((key, path)) = self.vars.child(self.path, index) else {
+                continue;
+            };
+
+            let seen = (0..index).any(|n| {
+                self.vars
+                    .child(self.path, n)
+                    .is_some_and(|(_, other)| other == path)
+            });
+
+            if !seen {
+                self.current = path;
+                return Some((key, path));
+            }
+        }
+
+        None
+    }
+}
+
+/// Decoder for a single field.
+pub struct FieldDecoder<'a, 'b, C>
+where
+    C: ?Sized + Context,
+{
+    cx: &'a C,
+    vars: &'b Vars<'a, C>,
+    key: &'b [u8],
+    path: &'b [u8],
+}
+
+/// Decoder for the name of a field.
+pub struct KeyDecoder<'a, 'b, C: ?Sized> {
+    cx: &'a C,
+    key: &'b [u8],
+}
+
+/// Decoder for the delimited values of a variable.
+pub struct ValuesDecoder<'a, 'b, C: ?Sized> {
+    cx: &'a C,
+    /// The name of the variable, used in diagnostics.
+    name: VarName<'b>,
+    rest: Option<&'b [u8]>,
+    delimiter: u8,
+}
+
+/// Decoder for a single value.
+pub struct ScalarDecoder<'a, 'b, C: ?Sized> {
+    cx: &'a C,
+    value: &'b [u8],
+}
+
+impl<'a, 'b, C> ScalarDecoder<'a, 'b, C>
+where
+    C: ?Sized + Context,
+{
+    #[inline]
+    fn as_str(&self) -> Result<&'b str, C::Error> {
+        crate::str::from_utf8(self.value).map_err(crate::str::map_utf8_error(self.cx))
+    }
+
+    /// Parse a number which must span the whole value.
+    #[inline]
+    fn number<T>(
+        self,
+        parse: impl FnOnce(&C, &mut SliceParser<'b>) -> Result<T, C::Error>,
+    ) -> Result<T, C::Error> {
+        let mut parser = SliceParser::new(self.value);
+        let value = parse(self.cx, &mut parser)?;
+
+        if parser.index != self.value.len() {
+            return Err(self.cx.message(format_args!(
+                "Expected number, but found {:?}",
+                Printable(self.value)
+            )));
+        }
+
+        Ok(value)
+    }
+}
+
+#[crate::decoder(crate)]
+impl<'a, 'b, 'de, C> Decoder<'de> for EnvDecoder<'a, 'b, C>
+where
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = C::Mode;
+    type DecodeSome = Self;
+    type DecodeSequence = ValuesDecoder<'a, 'b, C>;
+    type DecodeMap = Fields<'a, 'b, C>;
+    type DecodeMapEntries = Fields<'a, 'b, C>;
+
+    #[inline]
+    fn cx(&self) -> &Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "value that can be decoded from environment variables")
+    }
+
+    #[inline]
+    fn decode<T>(self) -> Result<T, Self::Error>
+    where
+        T: Decode<'de, Self::Mode>,
+    {
+        self.cx.decode(self)
+    }
+
+    #[inline]
+    fn decode_unsized<T, F, O>(self, f: F) -> Result<O, Self::Error>
+    where
+        T: ?Sized + DecodeUnsized<'de, Self::Mode>,
+        F: FnOnce(&T) -> Result<O, Self::Error>,
+    {
+        self.cx.decode_unsized(self, f)
+    }
+
+    #[inline]
+    fn skip(self) -> Result<(), C::Error> {
+        Ok(())
+    }
+
+    #[inline]
+    fn try_skip(self) -> Result<Skip, C::Error> {
+        Ok(Skip::Skipped)
+    }
+
+    #[inline]
+    fn decode_empty(self) -> Result<(), C::Error> {
+        self.scalar()?.decode_empty()
+    }
+
+    #[inline]
+    fn decode_bool(self) -> Result<bool, C::Error> {
+        self.scalar()?.decode_bool()
+    }
+
+    #[inline]
+    fn decode_char(self) -> Result<char, C::Error> {
+        self.scalar()?.decode_char()
+    }
+
+    #[inline]
+    fn decode_u8(self) -> Result<u8, C::Error> {
+        self.scalar()?.decode_u8()
+    }
+
+    #[inline]
+    fn decode_u16(self) -> Result<u16, C::Error> {
+        self.scalar()?.decode_u16()
+    }
+
+    #[inline]
+    fn decode_u32(self) -> Result<u32, C::Error> {
+        self.scalar()?.decode_u32()
+    }
+
+    #[inline]
+    fn decode_u64(self) -> Result<u64, C::Error> {
+        self.scalar()?.decode_u64()
+    }
+
+    #[inline]
+    fn decode_u128(self) -> Result<u128, C::Error> {
+        self.scalar()?.decode_u128()
+    }
+
+    #[inline]
+    fn decode_i8(self) -> Result<i8, C::Error> {
+        self.scalar()?.decode_i8()
+    }
+
+    #[inline]
+    fn decode_i16(self) -> Result<i16, C::Error> {
+        self.scalar()?.decode_i16()
+    }
+
+    #[inline]
+    fn decode_i32(self) -> Result<i32, C::Error> {
+        self.scalar()?.decode_i32()
+    }
+
+    #[inline]
+    fn decode_i64(self) -> Result<i64, C::Error> {
+        self.scalar()?.decode_i64()
+    }
+
+    #[inline]
+    fn decode_i128(self) -> Result<i128, C::Error> {
+        self.scalar()?.decode_i128()
+    }
+
+    #[inline]
+    fn decode_usize(self) -> Result<usize, C::Error> {
+        self.scalar()?.decode_usize()
+    }
+
+    #[inline]
+    fn decode_isize(self) -> Result<isize, C::Error> {
+        self.scalar()?.decode_isize()
+    }
+
+    #[inline]
+    fn decode_f32(self) -> Result<f32, C::Error> {
+        self.scalar()?.decode_f32()
+    }
+
+    #[inline]
+    fn decode_f64(self) -> Result<f64, C::Error> {
+        self.scalar()?.decode_f64()
+    }
+
+    #[inline]
+    fn decode_string<V>(self, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: UnsizedVisitor<'de, C, str>,
+    {
+        self.scalar()?.decode_string(visitor)
+    }
+
+    #[inline]
+    fn decode_option(self) -> Result<Option<Self::DecodeSome>, C::Error> {
+        // An empty variable is treated as being unset.
+        if self
+            .vars
+            .find(self.path)
+            .is_some_and(|value| value.is_empty())
+        {
+            return Ok(None);
+        }
+
+        Ok(Some(self))
+    }
+
+    #[inline]
+    fn decode_sequence<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeSequence) -> Result<O, C::Error>,
+    {
+        let cx = self.cx;
+        let delimiter = self.vars.delimiter;
+        let name = self.vars.display(self.path);
+        let value = self.scalar()?.value;
+
+        f(&mut ValuesDecoder {
+            cx,
+            name,
+            rest: (!value.is_empty()).then_some(value),
+            delimiter,
+        })
+    }
+
+    #[inline]
+    fn decode_map<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeMap) -> Result<O, C::Error>,
+    {
+        f(&mut Fields {
+            cx: self.cx,
+            vars: self.vars,
+            path: self.path,
+            index: 0,
+            current: &[],
+        })
+    }
+
+    #[inline]
+    fn decode_map_entries<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeMapEntries) -> Result<O, C::Error>,
+    {
+        self.decode_map(f)
+    }
+}
+
+impl<'a, 'b, 'de, C> MapDecoder<'de> for Fields<'a, 'b, C>
+where
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeEntry<'this> = FieldDecoder<'a, 'b, C> where Self: 'this;
+    type DecodeRemainingEntries<'this> = &'this mut Self where Self: 'this;
+
+    #[inline]
+    fn decode_entry(&mut self) -> Result<Option<Self::DecodeEntry<'_>>, C::Error> {
+        let Some((key, path)) = self.next_entry() else {
+            return Ok(None);
+        };
+
+        Ok(Some(FieldDecoder {
+            cx: self.cx,
+            vars: self.vars,
+            key,
+            path,
+        }))
+    }
+
+    #[inline]
+    fn decode_remaining_entries(&mut self) -> Result<Self::DecodeRemainingEntries<'_>, C::Error> {
+        Ok(self)
+    }
+}
+
+impl<'a, 'b, 'de, C> EntriesDecoder<'de> for Fields<'a, 'b, C>
+where
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeEntryKey<'this> = KeyDecoder<'a, 'b, C> where Self: 'this;
+    type DecodeEntryValue<'this> = EnvDecoder<'a, 'b, C> where Self: 'this;
+
+    #[inline]
+    fn decode_entry_key(&mut self) -> Result<Option<Self::DecodeEntryKey<'_>>, C::Error> {
+        let Some((key, _)) = self.next_entry() else {
+            return Ok(None);
+        };
+
+        Ok(Some(KeyDecoder { cx: self.cx, key }))
+    }
+
+    #[inline]
+    fn decode_entry_value(&mut self) -> Result<Self::DecodeEntryValue<'_>, C::Error> {
+        Ok(EnvDecoder {
+            cx: self.cx,
+            vars: self.vars,
+            path: self.current,
+        })
+    }
+
+    #[inline]
+    fn end_entries(self) -> Result<(), C::Error> {
+        Ok(())
+    }
+}
+
+impl<'a, 'b, 'c, 'de, C> EntriesDecoder<'de> for &'c mut Fields<'a, 'b, C>
+where
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeEntryKey<'this> = KeyDecoder<'a, 'b, C> where Self: 'this;
+    type DecodeEntryValue<'this> = EnvDecoder<'a, 'b, C> where Self: 'this;
+
+    #[inline]
+    fn decode_entry_key(&mut self) -> Result<Option<Self::DecodeEntryKey<'_>>, C::Error> {
+        (**self).decode_entry_key()
+    }
+
+    #[inline]
+    fn decode_entry_value(&mut self) -> Result<Self::DecodeEntryValue<'_>, C::Error> {
+        (**self).decode_entry_value()
+    }
+
+    #[inline]
+    fn end_entries(self) -> Result<(), C::Error> {
+        Ok(())
+    }
+}
+
+impl<'a, 'b, 'de, C> EntryDecoder<'de> for FieldDecoder<'a, 'b, C>
+where
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeKey<'this> = KeyDecoder<'a, 'b, C> where Self: 'this;
+    type DecodeValue = EnvDecoder<'a, 'b, C>;
+
+    #[inline]
+    fn decode_key(&mut self) -> Result<Self::DecodeKey<'_>, C::Error> {
+        Ok(KeyDecoder {
+            cx: self.cx,
+            key: self.key,
+        })
+    }
+
+    #[inline]
+    fn decode_value(self) -> Result<Self::DecodeValue, C::Error> {
+        Ok(EnvDecoder {
+            cx: self.cx,
+            vars: self.vars,
+            path: self.path,
+        })
+    }
+}
+
+#[crate::decoder(crate)]
+impl<'a, 'b, 'de, C> Decoder<'de> for KeyDecoder<'a, 'b, C>
+where
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = C::Mode;
+
+    #[inline]
+    fn cx(&self) -> &Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "field name that can be decoded from an environment variable"
+        )
+    }
+
+    #[inline]
+    fn decode<T>(self) -> Result<T, Self::Error>
+    where
+        T: Decode<'de, Self::Mode>,
+    {
+        self.cx.decode(self)
+    }
+
+    #[inline]
+    fn decode_unsized<T, F, O>(self, f: F) -> Result<O, Self::Error>
+    where
+        T: ?Sized + DecodeUnsized<'de, Self::Mode>,
+        F: FnOnce(&T) -> Result<O, Self::Error>,
+    {
+        self.cx.decode_unsized(self, f)
+    }
+
+    #[inline]
+    fn skip(self) -> Result<(), C::Error> {
+        Ok(())
+    }
+
+    #[inline]
+    fn try_skip(self) -> Result<Skip, C::Error> {
+        Ok(Skip::Skipped)
+    }
+
+    #[inline]
+    fn decode_string<V>(self, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: UnsizedVisitor<'de, C, str>,
+    {
+        let string =
+            crate::str::from_utf8(self.key).map_err(crate::str::map_utf8_error(self.cx))?;
+        visitor.visit_ref(self.cx, string)
+    }
+}
+
+impl<'a, 'b, 'de, C> SequenceDecoder<'de> for ValuesDecoder<'a, 'b, C>
+where
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeNext<'this> = ScalarDecoder<'a, 'b, C> where Self: 'this;
+
+    #[inline]
+    fn decode_next(&mut self) -> Result<Self::DecodeNext<'_>, C::Error> {
+        let Some(decoder) = self.try_decode_next()? else {
+            return Err(self.cx.message("Expected another value"));
+        };
+
+        Ok(decoder)
+    }
+
+    #[inline]
+    fn try_decode_next(&mut self) -> Result<Option<Self::DecodeNext<'_>>, C::Error> {
+        let Some(rest) = self.rest.take() else {
+            return Ok(None);
+        };
+
+        let value = match rest.iter().position(|&b| b == self.delimiter) {
+            Some(n) => {
+                self.rest = Some(&rest[n + 1..]);
+                &rest[..n]
+            }
+            None => rest,
+        };
+
+        if value.is_empty() {
+            return Err(self
+                .cx
+                .message(format_args!("Empty list element in variable {}", self.name)));
+        }
+
+        Ok(Some(ScalarDecoder { cx: self.cx, value }))
+    }
+}
+
+#[crate::decoder(crate)]
+impl<'a, 'b, 'de, C> Decoder<'de> for ScalarDecoder<'a, 'b, C>
+where
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = C::Mode;
+    type WithContext<'this, U> = ScalarDecoder<'this, 'b, U> where U: 'this + Context;
+    type DecodeSome = Self;
+
+    #[inline]
+    fn cx(&self) -> &Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn with_context<U>(self, cx: &U) -> Result<Self::WithContext<'_, U>, C::Error>
+    where
+        U: Context,
+    {
+        Ok(ScalarDecoder {
+            cx,
+            value: self.value,
+        })
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "value that can be decoded from an environment variable")
+    }
+
+    #[inline]
+    fn decode<T>(self) -> Result<T, Self::Error>
+    where
+        T: Decode<'de, Self::Mode>,
+    {
+        self.cx.decode(self)
+    }
+
+    #[inline]
+    fn decode_unsized<T, F, O>(self, f: F) -> Result<O, Self::Error>
+    where
+        T: ?Sized + DecodeUnsized<'de, Self::Mode>,
+        F: FnOnce(&T) -> Result<O, Self::Error>,
+    {
+        self.cx.decode_unsized(self, f)
+    }
+
+    #[inline]
+    fn skip(self) -> Result<(), C::Error> {
+        Ok(())
+    }
+
+    #[inline]
+    fn try_skip(self) -> Result<Skip, C::Error> {
+        Ok(Skip::Skipped)
+    }
+
+    #[inline]
+    fn decode_empty(self) -> Result<(), C::Error> {
+        if !self.value.is_empty() {
+            return Err(self.cx.message("Expected empty value"));
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn decode_bool(self) -> Result<bool, C::Error> {
+        match self.value {
+            b"true" | b"1" => Ok(true),
+            b"false" | b"0" => Ok(false),
+            value => Err(self.cx.message(format_args!(
+                "Expected boolean, but found {:?}",
+                Printable(value)
+            ))),
+        }
+    }
+
+    #[inline]
+    fn decode_char(self) -> Result<char, C::Error> {
+        let mut chars = self.as_str()?.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(self.cx.message(format_args!(
+                "Expected character, but found {:?}",
+                Printable(self.value)
+            ))),
+        }
+    }
+
+    #[inline]
+    fn decode_u8(self) -> Result<u8, C::Error> {
+        self.number(|cx, p| parse_unsigned(cx, p))
+    }
+
+    #[inline]
+    fn decode_u16(self) -> Result<u16, C::Error> {
+        self.number(|cx, p| parse_unsigned(cx, p))
+    }
+
+    #[inline]
+    fn decode_u32(self) -> Result<u32, C::Error> {
+        self.number(|cx, p| parse_unsigned(cx, p))
+    }
+
+    #[inline]
+    fn decode_u64(self) -> Result<u64, C::Error> {
+        self.number(|cx, p| parse_unsigned(cx, p))
+    }
+
+    #[inline]
+    fn decode_u128(self) -> Result<u128, C::Error> {
+        self.number(|cx, p| parse_unsigned(cx, p))
+    }
+
+    #[inline]
+    fn decode_i8(self) -> Result<i8, C::Error> {
+        self.number(|cx, p| parse_signed(cx, p))
+    }
+
+    #[inline]
+    fn decode_i16(self) -> Result<i16, C::Error> {
+        self.number(|cx, p| parse_signed(cx, p))
+    }
+
+    #[inline]
+    fn decode_i32(self) -> Result<i32, C::Error> {
+        self.number(|cx, p| parse_signed(cx, p))
+    }
+
+    #[inline]
+    fn decode_i64(self) -> Result<i64, C::Error> {
+        self.number(|cx, p| parse_signed(cx, p))
+    }
+
+    #[inline]
+    fn decode_i128(self) -> Result<i128, C::Error> {
+        self.number(|cx, p| parse_signed(cx, p))
+    }
+
+    #[inline]
+    fn decode_usize(self) -> Result<usize, C::Error> {
+        self.number(|cx, p| parse_unsigned(cx, p))
+    }
+
+    #[inline]
+    fn decode_isize(self) -> Result<isize, C::Error> {
+        self.number(|cx, p| parse_signed(cx, p))
+    }
+
+    #[inline]
+    fn decode_f32(self) -> Result<f32, C::Error> {
+        self.number(|cx, p| p.parse_f32(cx))
+    }
+
+    #[inline]
+    fn decode_f64(self) -> Result<f64, C::Error> {
+        self.number(|cx, p| p.parse_f64(cx))
+    }
+
+    #[inline]
+    fn decode_string<V>(self, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: UnsizedVisitor<'de, C, str>,
+    {
+        let string = self.as_str()?;
+        visitor.visit_ref(self.cx, string)
+    }
+
+    #[inline]
+    fn decode_option(self) -> Result<Option<Self::DecodeSome>, C::Error> {
+        if self.value.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(self))
+    }
+}
+
+/// Helper to display the name of an environment variable in errors.
+struct VarName<'a> {
+    prefix: &'a [u8],
+    path: &'a [u8],
+}
+
+impl fmt::Display for VarName<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let prefix = match self.path {
+            [] => self.prefix.strip_suffix(b"_").unwrap_or(self.prefix),
+            _ => self.prefix,
+        };
+
+        for part in [prefix, self.path] {
+            match core::str::from_utf8(part) {
+                Ok(string) => {
+                    for c in string.chars() {
+                        write!(f, "{}", c.to_ascii_uppercase())?;
+                    }
+                }
+                Err(..) => write!(f, "{part:?}")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Helper to display bytes which are most likely a string in errors.
+struct Printable<'a>(&'a [u8]);
+
+impl fmt::Debug for Printable<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match core::str::from_utf8(self.0) {
+            Ok(string) => string.fmt(f),
+            Err(..) => self.0.fmt(f),
+        }
+    }
+}
@@ -0,0 +1,299 @@
+//! Module that defines [`Encoding`] whith allows for customization of the
+//! encoding format, and the [`DEFAULT`] encoding configuration.
+
+use core::marker;
+
+use crate::de::DecodeOwned;
+use crate::mode::Text;
+use crate::Context;
+
+use super::de::Vars;
+use super::error::Error;
+
+/// The default configuration.
+pub const DEFAULT: Encoding = Encoding::new();
+
+/// Decode the given type `T` from the given variables using the [`DEFAULT`]
+/// [`Encoding`].
+///
+/// Only variables whose name starts with `prefix` followed by an underscore
+/// are considered.
+///
+/// # Examples
+///
+/// ```
+/// use musli::{Decode, Encode};
+/// use musli::env;
+/// # use musli::env::Error;
+///
+/// #[derive(Decode, Encode)]
+/// struct Database {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// #[derive(Decode, Encode)]
+/// struct Config {
+///     workers: u32,
+///     database: Database,
+/// }
+///
+/// let vars = [
+///     ("APP_WORKERS", "4"),
+///     ("APP_DATABASE__HOST", "localhost"),
+///     ("APP_DATABASE__PORT", "5432"),
+///     ("PATH", "/usr/bin"),
+/// ];
+///
+/// let config: Config = env::from_vars("APP", vars)?;
+/// assert_eq!(config.workers, 4);
+/// assert_eq!(config.database.host, "localhost");
+/// assert_eq!(config.database.port, 5432);
+/// # Ok::<(), Error>(())
+/// ```
+#[inline]
+pub fn from_vars<T, I, K, V>(prefix: &str, vars: I) -> Result<T, Error>
+where
+    T: DecodeOwned<Text>,
+    I: IntoIterator<Item = (K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    DEFAULT.from_vars(prefix, vars)
+}
+
+/// Decode the given type `T` from the environment of the current process
+/// using the [`DEFAULT`] [`Encoding`].
+///
+/// Variables whose name or value is not valid Unicode are ignored.
+///
+/// # Examples
+///
+/// ```no_run
+/// use musli::{Decode, Encode};
+/// use musli::env;
+/// # use musli::env::Error;
+///
+/// #[derive(Decode, Encode)]
+/// struct Config {
+///     #[musli(default)]
+///     workers: Option<u32>,
+/// }
+///
+/// let config: Config = env::from_env("APP")?;
+/// # Ok::<(), Error>(())
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+#[inline]
+pub fn from_env<T>(prefix: &str) -> Result<T, Error>
+where
+    T: DecodeOwned<Text>,
+{
+    DEFAULT.from_env(prefix)
+}
+
+/// Setting up encoding with parameters.
+pub struct Encoding<const D: u8 = b',', M = Text>
+where
+    M: 'static,
+{
+    _marker: marker::PhantomData<M>,
+}
+
+impl Default for Encoding<b',', Text> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Encoding<b',', Text> {
+    /// Construct a new [`Encoding`].
+    ///
+    /// You can modify this using the available factory methods:
+    ///
+    /// ```
+    /// use musli::{Encode, Decode};
+    /// use musli::env::Encoding;
+    /// # use musli::env::Error;
+    ///
+    /// const CONFIG: Encoding<b':'> = Encoding::new().with_delimiter();
+    ///
+    /// #[derive(Debug, PartialEq, Encode, Decode)]
+    /// struct Config {
+    ///     search_path: Vec<String>,
+    /// }
+    ///
+    /// let config: Config = CONFIG.from_vars("APP", [("APP_SEARCH_PATH", "/usr/bin:/bin")])?;
+    /// assert_eq!(config.search_path, ["/usr/bin", "/bin"]);
+    /// # Ok::<(), Error>(())
+    /// ```
+    #[inline]
+    pub const fn new() -> Self {
+        Encoding {
+            _marker: marker::PhantomData,
+        }
+    }
+}
+
+impl<const D: u8, M> Encoding<D, M>
+where
+    M: 'static,
+{
+    /// Change the mode of the encoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::env::Encoding;
+    ///
+    /// enum Custom {}
+    ///
+    /// const CONFIG: Encoding<b',', Custom> = Encoding::new().with_mode();
+    /// ```
+    pub const fn with_mode<T>(self) -> Encoding<D, T> {
+        Encoding {
+            _marker: marker::PhantomData,
+        }
+    }
+
+    /// Change the delimiter used to split sequences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::env::Encoding;
+    ///
+    /// const CONFIG: Encoding<b';'> = Encoding::new().with_delimiter();
+    /// ```
+    pub const fn with_delimiter<const U: u8>(self) -> Encoding<U, M> {
+        Encoding {
+            _marker: marker::PhantomData,
+        }
+    }
+
+    /// Decode the given type `T` from the given variables using the current
+    /// [`Encoding`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Decode, Encode};
+    /// use musli::env;
+    /// # use musli::env::Error;
+    ///
+    /// const ENCODING: env::Encoding = env::Encoding::new();
+    ///
+    /// #[derive(Decode, Encode)]
+    /// struct Config {
+    ///     verbose: bool,
+    ///     ports: Vec<u16>,
+    /// }
+    ///
+    /// let config: Config = ENCODING.from_vars("", [("VERBOSE", "1"), ("PORTS", "80,443")])?;
+    /// assert!(config.verbose);
+    /// assert_eq!(config.ports, [80, 443]);
+    /// # Ok::<(), Error>(())
+    /// ```
+    #[inline]
+    pub fn from_vars<T, I, K, V>(self, prefix: &str, vars: I) -> Result<T, Error>
+    where
+        T: DecodeOwned<M>,
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        crate::alloc::default!(|alloc| {
+            let cx = crate::context::Same::with_alloc(alloc);
+            self.from_vars_with(&cx, prefix, vars)
+        })
+    }
+
+    /// Decode the given type `T` from the given variables using the current
+    /// [`Encoding`].
+    ///
+    /// This is the same as [`Encoding::from_vars`] but allows for using a
+    /// configurable [`Context`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Decode, Encode};
+    /// use musli::env;
+    /// use musli::context::Same;
+    /// # use musli::env::Error;
+    ///
+    /// const ENCODING: env::Encoding = env::Encoding::new();
+    ///
+    /// #[derive(Decode, Encode)]
+    /// struct Config {
+    ///     workers: u32,
+    /// }
+    ///
+    /// let cx = Same::new();
+    ///
+    /// let config: Config = ENCODING.from_vars_with(&cx, "APP", [("APP_WORKERS", "4")])?;
+    /// assert_eq!(config.workers, 4);
+    /// # Ok::<(), Error>(())
+    /// ```
+    #[inline]
+    pub fn from_vars_with<C, T, I, K, V>(self, cx: &C, prefix: &str, vars: I) -> Result<T, C::Error>
+    where
+        C: ?Sized + Context<Mode = M>,
+        T: DecodeOwned<M>,
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        cx.clear();
+        let vars = Vars::new(cx, prefix, D, vars)?;
+        T::decode(cx, vars.decoder())
+    }
+
+    /// Decode the given type `T` from the environment of the current process
+    /// using the current [`Encoding`].
+    ///
+    /// Variables whose name or value is not valid Unicode are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use musli::{Decode, Encode};
+    /// use musli::env;
+    /// # use musli::env::Error;
+    ///
+    /// const ENCODING: env::Encoding<b':'> = env::Encoding::new().with_delimiter();
+    ///
+    /// #[derive(Decode, Encode)]
+    /// struct Config {
+    ///     #[musli(default)]
+    ///     search_path: Vec<String>,
+    /// }
+    ///
+    /// let config: Config = ENCODING.from_env("APP")?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    #[inline]
+    pub fn from_env<T>(self, prefix: &str) -> Result<T, Error>
+    where
+        T: DecodeOwned<M>,
+    {
+        let vars = std::env::vars_os().filter_map(|(name, value)| {
+            Some((name.into_string().ok()?, value.into_string().ok()?))
+        });
+
+        self.from_vars(prefix, vars)
+    }
+}
+
+impl<const D: u8, M> Clone for Encoding<D, M> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<const D: u8, M> Copy for Encoding<D, M> {}
@@ -0,0 +1,21 @@
+crate::macros::error_type! {
+    /// Error raised when decoding environment variables.
+    pub struct Error;
+
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::Decode;
+    /// use musli::context::ErrorKind;
+    ///
+    /// #[derive(Debug, Decode)]
+    /// struct Config {
+    ///     port: u16,
+    /// }
+    ///
+    /// let result: Result<Config, _> = musli::env::from_vars("APP", [("APP_HOST", "localhost")]);
+    /// assert_eq!(result.unwrap_err().kind(), ErrorKind::MissingField);
+    /// ```
+    pub fn kind;
+}
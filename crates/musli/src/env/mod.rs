@@ -0,0 +1,87 @@
+//! Support for decoding configuration from environment variables.
+//!
+//! Each field of a struct is read from a variable named after the field, where
+//! fields of nested structs are separated by a double underscore. With the
+//! prefix `APP` the field `port` of the field `database` is read from the
+//! variable `APP_DATABASE__PORT`. Variable names are matched without regard to
+//! ASCII case, since they are lowercased before being matched against the
+//! field names of the [`Text`] mode.
+//!
+//! Numbers are parsed the same way as in [`musli::json`], booleans are either
+//! `true`, `false`, `1` or `0`, and sequences such as `Vec` are split on a
+//! delimiter which defaults to `,`. An empty variable decodes as `None` if the
+//! field is optional.
+//!
+//! * ✔ Can tolerate missing fields if they are annotated with
+//!   `#[musli(default)]`.
+//! * ✔ Can skip over unknown variables.
+//!
+//! Since decoding uses the same derived [`Decode`] implementations as every
+//! other format, the same types can be used to read a configuration file and
+//! to read overrides from the environment.
+//!
+//! Only decoding is supported.
+//!
+//! [`Text`]: crate::mode::Text
+//! [`Decode`]: crate::Decode
+//! [`musli::json`]: crate::json
+//!
+//! ```
+//! use musli::{Encode, Decode};
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! struct Database {
+//!     url: String,
+//!     #[musli(default)]
+//!     max_connections: Option<u32>,
+//! }
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! struct Config {
+//!     log_level: String,
+//!     #[musli(default)]
+//!     features: Vec<String>,
+//!     database: Database,
+//! }
+//!
+//! let vars = [
+//!     ("MYAPP_LOG_LEVEL", "debug"),
+//!     ("MYAPP_FEATURES", "metrics,tracing"),
+//!     ("MYAPP_DATABASE__URL", "postgres://localhost/app"),
+//!     ("MYAPP_DATABASE__MAX_CONNECTIONS", "16"),
+//! ];
+//!
+//! let config: Config = musli::env::from_vars("MYAPP", vars)?;
+//!
+//! assert_eq!(config, Config {
+//!     log_level: String::from("debug"),
+//!     features: vec![String::from("metrics"), String::from("tracing")],
+//!     database: Database {
+//!         url: String::from("postgres://localhost/app"),
+//!         max_connections: Some(16),
+//!     },
+//! });
+//! # Ok::<_, musli::env::Error>(())
+//! ```
+
+#![cfg(feature = "env")]
+#![cfg_attr(doc_cfg, doc(cfg(feature = "env")))]
+
+#[cfg(test)]
+mod tests;
+
+mod de;
+mod encoding;
+mod error;
+
+/// Convenient result alias for use with `musli::env`.
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+#[doc(inline)]
+pub use self::encoding::from_env;
+#[doc(inline)]
+pub use self::encoding::{from_vars, Encoding, DEFAULT};
+#[doc(inline)]
+pub use self::error::Error;
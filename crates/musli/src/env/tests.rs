@@ -0,0 +1,165 @@
+use rust_alloc::collections::BTreeMap;
+use rust_alloc::string::{String, ToString};
+use rust_alloc::vec;
+use rust_alloc::vec::Vec;
+
+use crate::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+enum Level {
+    Info,
+    Debug,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Config {
+    level: Level,
+    verbose: bool,
+    ratio: f64,
+    server: Server,
+    #[musli(default)]
+    workers: Option<u32>,
+    #[musli(default)]
+    allowed_hosts: Vec<String>,
+}
+
+#[test]
+fn nested() {
+    let vars = [
+        ("APP_LEVEL", "Debug"),
+        ("APP_VERBOSE", "true"),
+        ("APP_RATIO", "-1.5e1"),
+        ("APP_SERVER__HOST", "localhost"),
+        ("app_server__port", "8080"),
+        ("APP_ALLOWED_HOSTS", "a.example,b.example"),
+        ("APP_UNKNOWN__FIELD", "ignored"),
+        ("OTHER_LEVEL", "Info"),
+        ("APP", "ignored"),
+        ("APPLEVEL", "ignored"),
+    ];
+
+    let config: Config = super::from_vars("app", vars).unwrap();
+
+    assert_eq!(
+        config,
+        Config {
+            level: Level::Debug,
+            verbose: true,
+            ratio: -15.0,
+            server: Server {
+                host: "localhost".to_string(),
+                port: 8080,
+            },
+            workers: None,
+            allowed_hosts: vec!["a.example".to_string(), "b.example".to_string()],
+        }
+    );
+}
+
+#[test]
+fn options_and_sequences() {
+    let base = [
+        ("LEVEL", "Info"),
+        ("VERBOSE", "0"),
+        ("RATIO", "1"),
+        ("SERVER__HOST", ""),
+        ("SERVER__PORT", "1"),
+    ];
+
+    let config: Config = super::from_vars(
+        "",
+        base.into_iter()
+            .chain([("WORKERS", ""), ("ALLOWED_HOSTS", "")]),
+    )
+    .unwrap();
+
+    assert_eq!(config.workers, None);
+    assert!(config.allowed_hosts.is_empty());
+    assert_eq!(config.server.host, "");
+
+    let config: Config = super::Encoding::new()
+        .with_delimiter::<b' '>()
+        .from_vars(
+            "",
+            base.into_iter()
+                .chain([("WORKERS", "8"), ("ALLOWED_HOSTS", "a b,c")]),
+        )
+        .unwrap();
+
+    assert_eq!(config.workers, Some(8));
+    assert_eq!(config.allowed_hosts, ["a", "b,c"]);
+}
+
+#[test]
+fn maps() {
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    struct Limits {
+        limits: BTreeMap<String, BTreeMap<String, u32>>,
+    }
+
+    let vars = [
+        ("X_LIMITS__API__READ", "10"),
+        ("X_LIMITS__WEB__READ", "30"),
+        ("X_LIMITS__API__WRITE", "20"),
+    ];
+
+    let value: Limits = super::from_vars("X", vars).unwrap();
+
+    let mut api = BTreeMap::new();
+    api.insert("read".to_string(), 10);
+    api.insert("write".to_string(), 20);
+
+    let mut web = BTreeMap::new();
+    web.insert("read".to_string(), 30);
+
+    let mut limits = BTreeMap::new();
+    limits.insert("api".to_string(), api);
+    limits.insert("web".to_string(), web);
+
+    assert_eq!(value, Limits { limits });
+}
+
+#[test]
+fn errors() {
+    #[derive(Debug, Decode)]
+    struct Port {
+        #[allow(unused)]
+        port: u16,
+    }
+
+    #[derive(Debug, Decode)]
+    struct Nested {
+        #[allow(unused)]
+        inner: Port,
+    }
+
+    let error = super::from_vars::<Port, _, _, _>("APP", [("APP_PORT", "80x")]).unwrap_err();
+    assert_eq!(error.to_string(), "Expected number, but found \"80x\"");
+
+    let error =
+        super::from_vars::<Nested, _, _, _>("app", [("APP_INNER__PORT__X", "1")]).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Expected a value in variable APP_INNER__PORT"
+    );
+
+    let error = super::from_vars::<Port, _, _, _>("", [("PORT", "yes")]);
+    assert!(error.is_err());
+
+    #[derive(Debug, Decode)]
+    struct Xs {
+        #[allow(unused)]
+        xs: Vec<u32>,
+    }
+
+    for value in ["1,,2", "1,2,", ","] {
+        let error = super::from_vars::<Xs, _, _, _>("APP", [("APP_XS", value)]).unwrap_err();
+        assert_eq!(error.to_string(), "Empty list element in variable APP_XS");
+    }
+}
@@ -0,0 +1,133 @@
+use rust_alloc::vec::Vec;
+
+use crate::context::Same;
+use crate::mode::Binary;
+use crate::{Context, Options};
+
+use super::error::Error;
+use super::framing::{Framing, OPTIONS};
+
+/// An incremental decoder of frames which can be fed data as it arrives.
+///
+/// # Examples
+///
+/// ```
+/// use musli::framing::{self, FrameDecoder};
+///
+/// let mut out = Vec::new();
+/// framing::DEFAULT.write_frame(&mut out, b"first")?;
+/// framing::DEFAULT.write_frame(&mut out, b"second")?;
+///
+/// let mut decoder = FrameDecoder::new();
+/// let mut frames = Vec::new();
+///
+/// for chunk in out.chunks(4) {
+///     decoder.extend_from_slice(chunk);
+///
+///     while let Some(frame) = decoder.next_frame()? {
+///         frames.push(frame.to_vec());
+///     }
+/// }
+///
+/// assert_eq!(frames, [&b"first"[..], &b"second"[..]]);
+/// assert!(decoder.is_empty());
+/// # Ok::<_, framing::Error>(())
+/// ```
+pub struct FrameDecoder<const OPT: Options = OPTIONS> {
+    framing: Framing<OPT>,
+    buffer: Vec<u8>,
+    /// The number of bytes at the start of the buffer which have already been
+    /// returned as frames.
+    read: usize,
+}
+
+impl FrameDecoder<OPTIONS> {
+    /// Construct a new decoder using the [`DEFAULT`] framing configuration.
+    ///
+    /// [`DEFAULT`]: super::DEFAULT
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_framing(Framing::new())
+    }
+}
+
+impl Default for FrameDecoder<OPTIONS> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const OPT: Options> FrameDecoder<OPT> {
+    /// Construct a new decoder using the given framing configuration.
+    #[inline]
+    pub fn with_framing(framing: Framing<OPT>) -> Self {
+        Self {
+            framing,
+            buffer: Vec::new(),
+            read: 0,
+        }
+    }
+
+    /// Add data to the decoder.
+    #[inline]
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+        if self.read > 0 {
+            self.buffer.drain(..self.read);
+            self.read = 0;
+        }
+
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Get the next complete frame, or `None` if more data is needed.
+    ///
+    /// An error is returned if the length prefix of the next frame is invalid
+    /// or exceeds the maximum frame size, after which the stream can't be
+    /// recovered.
+    #[inline]
+    pub fn next_frame(&mut self) -> Result<Option<&[u8]>, Error> {
+        crate::alloc::default!(|alloc| {
+            let cx = Same::<Binary, Error, _>::with_alloc(alloc);
+            self.next_frame_with(&cx)
+        })
+    }
+
+    /// Get the next complete frame, or `None` if more data is needed.
+    ///
+    /// This is the same as [`FrameDecoder::next_frame`] but allows for using a
+    /// configurable [`Context`].
+    pub fn next_frame_with<C>(&mut self, cx: &C) -> Result<Option<&[u8]>, C::Error>
+    where
+        C: ?Sized + Context,
+    {
+        let bytes = &self.buffer[self.read..];
+
+        let Some((frame, len)) = self.framing.split(cx, bytes)? else {
+            return Ok(None);
+        };
+
+        self.read += len;
+        Ok(Some(frame))
+    }
+
+    /// Get the number of buffered bytes which have not been returned as
+    /// frames.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.buffer.len() - self.read
+    }
+
+    /// Test if there are no buffered bytes.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Clear all buffered bytes.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.read = 0;
+    }
+}
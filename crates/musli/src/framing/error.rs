@@ -0,0 +1,17 @@
+crate::macros::error_type! {
+    /// Error raised when reading or writing frames.
+    pub struct Error;
+
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::context::ErrorKind;
+    /// use musli::framing;
+    ///
+    /// let mut frames = framing::DEFAULT.frames(b"\x03a");
+    /// let error = frames.next().unwrap().unwrap_err();
+    /// assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    /// ```
+    pub fn kind;
+}
@@ -0,0 +1,82 @@
+use crate::context::{ErrorKind, Same};
+use crate::mode::Binary;
+use crate::{Context, Options};
+
+use super::error::Error;
+use super::framing::Framing;
+
+/// An iterator over the frames in a byte slice.
+///
+/// See [`Framing::frames`].
+pub struct Frames<'de, const OPT: Options> {
+    framing: Framing<OPT>,
+    bytes: &'de [u8],
+    failed: bool,
+}
+
+impl<'de, const OPT: Options> Frames<'de, OPT> {
+    #[inline]
+    pub(super) fn new(framing: Framing<OPT>, bytes: &'de [u8]) -> Self {
+        Self {
+            framing,
+            bytes,
+            failed: false,
+        }
+    }
+
+    /// Get the bytes which have not been consumed as frames.
+    ///
+    /// If the iterator produced an error, these start with the frame which
+    /// could not be read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::framing;
+    ///
+    /// let mut frames = framing::DEFAULT.frames(b"\x01a\x03bc");
+    /// assert_eq!(frames.next().transpose()?, Some(&b"a"[..]));
+    /// assert!(frames.next().unwrap().is_err());
+    /// assert_eq!(frames.remaining(), b"\x03bc");
+    /// # Ok::<_, framing::Error>(())
+    /// ```
+    #[inline]
+    pub fn remaining(&self) -> &'de [u8] {
+        self.bytes
+    }
+}
+
+impl<'de, const OPT: Options> Iterator for Frames<'de, OPT> {
+    type Item = Result<&'de [u8], Error>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.bytes.is_empty() {
+            return None;
+        }
+
+        let result = crate::alloc::default!(|alloc| {
+            let cx = Same::<Binary, Error, _>::with_alloc(alloc);
+
+            match self.framing.split(&cx, self.bytes) {
+                Ok(Some(frame)) => Ok(frame),
+                Ok(None) => Err(cx.message_with_kind(
+                    ErrorKind::UnexpectedEof,
+                    "Unexpected end of input in the middle of a frame",
+                )),
+                Err(error) => Err(error),
+            }
+        });
+
+        match result {
+            Ok((frame, len)) => {
+                self.bytes = &self.bytes[len..];
+                Some(Ok(frame))
+            }
+            Err(error) => {
+                self.failed = true;
+                Some(Err(error))
+            }
+        }
+    }
+}
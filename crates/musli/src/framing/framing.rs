@@ -0,0 +1,285 @@
+//! Module that defines [`Framing`] which allows for customization of how
+//! frames are delimited, and the [`DEFAULT`] framing configuration.
+
+use crate::context::{ErrorKind, Same};
+use crate::mode::Binary;
+use crate::options::{self, Integer, Options, Width};
+use crate::reader::SliceReader;
+use crate::{Context, Writer};
+
+use super::error::Error;
+use super::frames::Frames;
+
+/// The default options used by the [`DEFAULT`] configuration, which prefixes
+/// frames with a variable-length encoded length.
+pub const OPTIONS: Options = options::new().build();
+
+/// The default maximum size of a frame, which is 8 MiB.
+pub const MAX_FRAME_SIZE: usize = 8 * 1024 * 1024;
+
+/// The default configuration.
+pub const DEFAULT: Framing = Framing::new();
+
+/// The largest number of bytes a variable-length encoded `usize` can occupy.
+const MAX_VARIABLE_PREFIX: usize = (usize::BITS as usize).div_ceil(7);
+
+/// Setting up framing with parameters.
+///
+/// The length prefix of each frame is encoded the same way as lengths in the
+/// [`storage`] and [`wire`] formats, so it is governed by the
+/// [`Builder::with_length`], [`Builder::with_length_width`] and
+/// [`Builder::with_byte_order`] options.
+///
+/// [`storage`]: crate::storage
+/// [`wire`]: crate::wire
+/// [`Builder::with_length`]: crate::options::Builder::with_length
+/// [`Builder::with_length_width`]: crate::options::Builder::with_length_width
+/// [`Builder::with_byte_order`]: crate::options::Builder::with_byte_order
+pub struct Framing<const OPT: Options = OPTIONS> {
+    max_frame_size: usize,
+}
+
+impl Default for Framing<OPTIONS> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Framing<OPTIONS> {
+    /// Construct a new [`Framing`] instance with the [`OPTIONS`] configuration
+    /// and a maximum frame size of [`MAX_FRAME_SIZE`].
+    ///
+    /// You can modify this using the available factory methods:
+    ///
+    /// ```
+    /// use musli::framing::Framing;
+    /// use musli::options::{self, ByteOrder, Integer, Options, Width};
+    /// # use musli::framing::Error;
+    ///
+    /// const OPTIONS: Options = options::new()
+    ///     .with_length(Integer::Fixed)
+    ///     .with_length_width(Width::U32)
+    ///     .with_byte_order(ByteOrder::Big)
+    ///     .build();
+    ///
+    /// const FRAMING: Framing<OPTIONS> = Framing::new().with_options().with_max_frame_size(1024);
+    ///
+    /// let mut out = Vec::new();
+    /// FRAMING.write_frame(&mut out, b"hello")?;
+    /// assert_eq!(out, b"\x00\x00\x00\x05hello");
+    ///
+    /// assert!(FRAMING.write_frame(&mut out, &[0; 2048]).is_err());
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[inline]
+    pub const fn new() -> Self {
+        Framing {
+            max_frame_size: MAX_FRAME_SIZE,
+        }
+    }
+}
+
+impl<const OPT: Options> Framing<OPT> {
+    /// Change the options used to encode the length prefix of frames.
+    #[inline]
+    pub const fn with_options<const U: Options>(self) -> Framing<U> {
+        Framing {
+            max_frame_size: self.max_frame_size,
+        }
+    }
+
+    /// Change the maximum size of a frame, not counting its length prefix.
+    ///
+    /// Writing a larger frame is an error, and so is reading the length prefix
+    /// of one. This means that a peer can't make us buffer an arbitrary amount
+    /// of data.
+    #[inline]
+    pub const fn with_max_frame_size(self, max_frame_size: usize) -> Self {
+        Self { max_frame_size }
+    }
+
+    /// Get the maximum size of a frame.
+    #[inline]
+    pub const fn max_frame_size(&self) -> usize {
+        self.max_frame_size
+    }
+
+    /// Write a single frame to the given [`Writer`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Decode, Encode};
+    /// use musli::framing;
+    /// # use musli::framing::Error;
+    ///
+    /// #[derive(Debug, PartialEq, Encode, Decode)]
+    /// struct Ping {
+    ///     id: u32,
+    /// }
+    ///
+    /// let mut out = Vec::new();
+    ///
+    /// for id in 0..3 {
+    ///     let message = musli::wire::to_vec(&Ping { id }).unwrap();
+    ///     framing::DEFAULT.write_frame(&mut out, &message)?;
+    /// }
+    ///
+    /// let mut pings = Vec::new();
+    ///
+    /// for frame in framing::DEFAULT.frames(&out) {
+    ///     let ping: Ping = musli::wire::from_slice(frame?).unwrap();
+    ///     pings.push(ping.id);
+    /// }
+    ///
+    /// assert_eq!(pings, [0, 1, 2]);
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[inline]
+    pub fn write_frame<W>(self, writer: W, frame: &[u8]) -> Result<(), Error>
+    where
+        W: Writer,
+    {
+        crate::alloc::default!(|alloc| {
+            let cx = Same::<Binary, Error, _>::with_alloc(alloc);
+            self.write_frame_with(&cx, writer, frame)
+        })
+    }
+
+    /// Write a single frame to the given [`Writer`].
+    ///
+    /// This is the same as [`Framing::write_frame`] but allows for using a
+    /// configurable [`Context`].
+    #[inline]
+    pub fn write_frame_with<C, W>(self, cx: &C, mut writer: W, frame: &[u8]) -> Result<(), C::Error>
+    where
+        C: ?Sized + Context,
+        W: Writer,
+    {
+        self.write_header(cx, &mut writer, frame.len())?;
+        writer.write_bytes(cx, frame)
+    }
+
+    /// Write the length prefix of a frame of the given length.
+    pub(crate) fn write_header<C, W>(self, cx: &C, writer: W, len: usize) -> Result<(), C::Error>
+    where
+        C: ?Sized + Context,
+        W: Writer,
+    {
+        self.check_size(cx, len)?;
+        crate::int::encode_usize::<_, _, OPT>(cx, writer, len)
+    }
+
+    /// Iterate over the frames in the given byte slice.
+    ///
+    /// If the slice ends with an incomplete frame, the iterator produces an
+    /// error. Use a [`FrameDecoder`] to deal with data which arrives in
+    /// pieces.
+    ///
+    /// [`FrameDecoder`]: super::FrameDecoder
+    #[inline]
+    pub fn frames(self, bytes: &[u8]) -> Frames<'_, OPT> {
+        Frames::new(self, bytes)
+    }
+
+    /// Split a single frame off the start of the given bytes.
+    ///
+    /// Returns the frame and the number of bytes it occupies including its
+    /// length prefix, or `None` if the bytes don't contain a complete frame.
+    pub(crate) fn split<'de, C>(
+        self,
+        cx: &C,
+        bytes: &'de [u8],
+    ) -> Result<Option<(&'de [u8], usize)>, C::Error>
+    where
+        C: ?Sized + Context,
+    {
+        let Some((prefix, len)) = self.header(cx, bytes)? else {
+            return Ok(None);
+        };
+
+        let Some(frame) = bytes[prefix..].get(..len) else {
+            return Ok(None);
+        };
+
+        Ok(Some((frame, prefix + len)))
+    }
+
+    /// Read the length prefix at the start of the given bytes.
+    ///
+    /// Returns the size of the length prefix and the size of the frame which
+    /// follows it, or `None` if the length prefix is incomplete.
+    pub(crate) fn header<C>(self, cx: &C, bytes: &[u8]) -> Result<Option<(usize, usize)>, C::Error>
+    where
+        C: ?Sized + Context,
+    {
+        let Some(prefix) = prefix_len::<C, OPT>(cx, bytes)? else {
+            return Ok(None);
+        };
+
+        let len = crate::int::decode_usize::<_, _, OPT>(cx, SliceReader::new(&bytes[..prefix]))?;
+        self.check_size(cx, len)?;
+        Ok(Some((prefix, len)))
+    }
+
+    #[inline]
+    fn check_size<C>(self, cx: &C, len: usize) -> Result<(), C::Error>
+    where
+        C: ?Sized + Context,
+    {
+        if len > self.max_frame_size {
+            return Err(cx.message_with_kind(
+                ErrorKind::LimitExceeded,
+                format_args!(
+                    "Frame of {len} bytes exceeds the maximum frame size of {} bytes",
+                    self.max_frame_size
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl<const OPT: Options> Clone for Framing<OPT> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<const OPT: Options> Copy for Framing<OPT> {}
+
+/// Get the number of bytes occupied by the length prefix at the start of the
+/// given bytes, or `None` if it's incomplete.
+fn prefix_len<C, const OPT: Options>(cx: &C, bytes: &[u8]) -> Result<Option<usize>, C::Error>
+where
+    C: ?Sized + Context,
+{
+    let len = match options::length::<OPT>() {
+        Integer::Variable => {
+            let Some(n) = bytes.iter().position(|b| b & 0b1000_0000 == 0) else {
+                if bytes.len() >= MAX_VARIABLE_PREFIX {
+                    return Err(cx.message("Frame length prefix is too long"));
+                }
+
+                return Ok(None);
+            };
+
+            n + 1
+        }
+        _ => match options::length_width::<OPT>() {
+            Width::U8 => 1,
+            Width::U16 => 2,
+            Width::U32 => 4,
+            Width::U64 => 8,
+        },
+    };
+
+    if len > bytes.len() {
+        return Ok(None);
+    }
+
+    Ok(Some(len))
+}
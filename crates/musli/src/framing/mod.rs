@@ -0,0 +1,78 @@
+//! Length-delimited framing for streams of messages.
+//!
+//! Binary formats like [`wire`] and [`storage`] don't record where a message
+//! ends, so when messages are written back to back over a stream like a TCP
+//! connection each of them needs to be delimited. This module does so by
+//! prefixing each frame with its length, which is either variable-length or
+//! fixed-width encoded depending on the [`Options`] used.
+//!
+//! A maximum frame size is enforced both when writing and reading frames,
+//! which defaults to [`MAX_FRAME_SIZE`].
+//!
+//! Frames can be read from a byte slice using [`Framing::frames`], or
+//! incrementally as data arrives using a [`FrameDecoder`] which requires the
+//! `alloc` feature.
+//!
+//! [`wire`]: crate::wire
+//! [`storage`]: crate::storage
+//! [`Options`]: crate::Options
+//!
+//! ```
+//! use musli::{Decode, Encode};
+//! use musli::framing::{self, FrameDecoder};
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! enum Message {
+//!     Ping { id: u32 },
+//!     Pong { id: u32 },
+//! }
+//!
+//! # fn main() -> Result<(), Box<dyn core::error::Error>> {
+//! let mut stream = Vec::new();
+//!
+//! for message in [Message::Ping { id: 1 }, Message::Pong { id: 1 }] {
+//!     let bytes = musli::wire::to_vec(&message)?;
+//!     framing::DEFAULT.write_frame(&mut stream, &bytes)?;
+//! }
+//!
+//! let mut decoder = FrameDecoder::new();
+//! let mut messages = Vec::new();
+//!
+//! // Simulate data arriving one byte at a time.
+//! for b in stream {
+//!     decoder.extend_from_slice(&[b]);
+//!
+//!     while let Some(frame) = decoder.next_frame()? {
+//!         messages.push(musli::wire::from_slice::<Message>(frame)?);
+//!     }
+//! }
+//!
+//! assert_eq!(messages, [Message::Ping { id: 1 }, Message::Pong { id: 1 }]);
+//! # Ok(()) }
+//! ```
+
+#![cfg(feature = "framing")]
+#![cfg_attr(doc_cfg, doc(cfg(feature = "framing")))]
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "alloc")]
+mod decoder;
+mod error;
+mod frames;
+mod framing;
+
+/// Convenient result alias for use with `musli::framing`.
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[doc(inline)]
+pub use self::decoder::FrameDecoder;
+#[doc(inline)]
+pub use self::error::Error;
+#[doc(inline)]
+pub use self::frames::Frames;
+#[doc(inline)]
+pub use self::framing::{Framing, DEFAULT, MAX_FRAME_SIZE, OPTIONS};
@@ -0,0 +1,129 @@
+use rust_alloc::string::ToString;
+use rust_alloc::vec::Vec;
+
+use crate::options::{self, ByteOrder, Integer, Options, Width};
+
+use super::{FrameDecoder, Framing, DEFAULT};
+
+const FIXED: Options = options::new()
+    .with_length(Integer::Fixed)
+    .with_length_width(Width::U16)
+    .with_byte_order(ByteOrder::Big)
+    .build();
+
+#[test]
+fn variable_prefix() {
+    let mut out = Vec::new();
+    DEFAULT.write_frame(&mut out, b"a").unwrap();
+    DEFAULT.write_frame(&mut out, &[7; 200]).unwrap();
+    DEFAULT.write_frame(&mut out, b"").unwrap();
+
+    assert_eq!(&out[..2], b"\x01a");
+    assert_eq!(&out[2..4], b"\xc8\x01");
+    assert_eq!(out.len(), 2 + 2 + 200 + 1);
+
+    let frames = DEFAULT.frames(&out).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(frames, [&b"a"[..], &[7; 200][..], &b""[..]]);
+}
+
+#[test]
+fn fixed_prefix() {
+    const FRAMING: Framing<FIXED> = Framing::new().with_options();
+
+    let mut out = Vec::new();
+    FRAMING.write_frame(&mut out, b"abc").unwrap();
+    assert_eq!(out, b"\x00\x03abc");
+
+    // The length doesn't fit in the prefix.
+    let error = FRAMING.write_frame(&mut out, &[0; 0x10000]).unwrap_err();
+    assert_eq!(error.to_string(), "Size type out of bounds for value type");
+
+    let frames = FRAMING.frames(&out).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(frames, [b"abc"]);
+}
+
+#[test]
+fn max_frame_size() {
+    const FRAMING: Framing = Framing::new().with_max_frame_size(4);
+
+    let mut out = Vec::new();
+    FRAMING.write_frame(&mut out, b"abcd").unwrap();
+
+    let error = FRAMING.write_frame(&mut out, b"abcde").unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "Frame of 5 bytes exceeds the maximum frame size of 4 bytes"
+    );
+
+    // The length prefix is rejected before the frame itself has arrived.
+    let mut decoder = FrameDecoder::with_framing(FRAMING);
+    decoder.extend_from_slice(b"\x05");
+
+    let error = decoder.next_frame().unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "Frame of 5 bytes exceeds the maximum frame size of 4 bytes"
+    );
+}
+
+#[test]
+fn truncated() {
+    let mut frames = DEFAULT.frames(b"\x02ab\x03a");
+    assert_eq!(frames.next().unwrap().unwrap(), b"ab");
+
+    let error = frames.next().unwrap().unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "Unexpected end of input in the middle of a frame"
+    );
+
+    assert!(frames.next().is_none());
+    assert_eq!(frames.remaining(), b"\x03a");
+
+    let error = DEFAULT.frames(&[0xff; 16]).next().unwrap().unwrap_err();
+    assert_eq!(error.to_string(), "Frame length prefix is too long");
+}
+
+#[test]
+fn incremental() {
+    const FRAMING: Framing<FIXED> = Framing::new().with_options();
+
+    let mut out = Vec::new();
+
+    for n in 0..16u8 {
+        FRAMING
+            .write_frame(&mut out, &[n; 300][..usize::from(n) * 20])
+            .unwrap();
+    }
+
+    for chunk in [1, 2, 3, 7, 64, 1024] {
+        let mut decoder = FrameDecoder::with_framing(FRAMING);
+        let mut frames = Vec::new();
+
+        for bytes in out.chunks(chunk) {
+            decoder.extend_from_slice(bytes);
+
+            while let Some(frame) = decoder.next_frame().unwrap() {
+                frames.push(frame.to_vec());
+            }
+        }
+
+        assert!(decoder.is_empty());
+        assert_eq!(frames.len(), 16);
+
+        for (n, frame) in frames.iter().enumerate() {
+            assert_eq!(frame.len(), n * 20);
+            assert!(frame.iter().all(|&b| usize::from(b) == n));
+        }
+    }
+
+    let mut decoder = FrameDecoder::new();
+    decoder.extend_from_slice(b"\x03ab");
+    assert_eq!(decoder.next_frame().unwrap(), None);
+    assert_eq!(decoder.len(), 3);
+    decoder.clear();
+    assert!(decoder.is_empty());
+}
@@ -1,6 +1,7 @@
 //! A variable-length 7-bit encoder where each bit indicates if there is a
 //! continuation of the sequence or not.
 
+use crate::context::ErrorKind;
 use crate::int;
 use crate::reader::Reader;
 use crate::writer::Writer;
@@ -30,7 +31,7 @@ where
         shift += 7;
 
         if shift >= T::BITS {
-            return Err(cx.message("Bits overflow"));
+            return Err(cx.message_with_kind(ErrorKind::NumberOverflow, "Bits overflow"));
         }
 
         b = r.read_byte(cx)?;
@@ -1,3 +1,4 @@
+use crate::context::ErrorKind;
 use crate::int::continuation as c;
 use crate::int::zigzag as zig;
 use crate::int::{Signed, Unsigned, UnsignedOps};
@@ -110,7 +111,10 @@ where
             macro_rules! fixed {
                 ($ty:ty) => {{
                     let Ok(value) = <$ty>::try_from(value) else {
-                        return Err(cx.message("Size type out of bounds for value type"));
+                        return Err(cx.message_with_kind(
+                            ErrorKind::NumberOverflow,
+                            "Size type out of bounds for value type",
+                        ));
                     };
 
                     <$ty as UnsignedOps>::write_bytes(value, cx, writer, bo)
@@ -143,7 +147,10 @@ where
                     let Ok(value) =
                         usize::try_from(<$ty as UnsignedOps>::read_bytes(cx, reader, bo)?)
                     else {
-                        return Err(cx.message("Value type out of bounds for usize"));
+                        return Err(cx.message_with_kind(
+                            ErrorKind::NumberOverflow,
+                            "Value type out of bounds for usize",
+                        ));
                     };
 
                     Ok(value)
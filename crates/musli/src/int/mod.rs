@@ -10,8 +10,19 @@
     feature = "storage",
     feature = "wire",
     feature = "descriptive",
-    feature = "value"
+    feature = "value",
+    feature = "framing"
 ))]
+// Framing only uses the length encoding.
+#![cfg_attr(
+    not(any(
+        feature = "storage",
+        feature = "wire",
+        feature = "descriptive",
+        feature = "value"
+    )),
+    allow(dead_code, unused_imports)
+)]
 
 pub(crate) mod continuation;
 mod encoding;
@@ -116,8 +116,8 @@ where
     type DecodeBuffer = crate::value::AsValueDecoder<'a, BUFFER_OPTIONS, C>;
     type DecodePack = JsonSequenceDecoder<'a, P, C>;
     type DecodeSequence = JsonSequenceDecoder<'a, P, C>;
-    type DecodeMap = JsonObjectDecoder<'a, P, C>;
-    type DecodeMapEntries = JsonObjectDecoder<'a, P, C>;
+    type DecodeMap = JsonObjectDecoder<'a, 'de, P, C>;
+    type DecodeMapEntries = JsonObjectDecoder<'a, 'de, P, C>;
     type DecodeSome = JsonDecoder<'a, P, C>;
     type DecodeVariant = JsonVariantDecoder<'a, P, C>;
 
@@ -193,9 +193,10 @@ where
                 self.parse_false()?;
                 Ok(false)
             }
-            actual => Err(self
-                .cx
-                .message(format_args!("Expected boolean, was {actual}"))),
+            actual => Err(self.cx.marked_message(
+                self.cx.mark(),
+                format_args!("Expected boolean, was {actual}"),
+            )),
         }
     }
 
@@ -347,8 +348,14 @@ where
         let mut scratch = Vec::new_in(self.cx.alloc());
 
         match self.parser.parse_string(self.cx, true, &mut scratch)? {
-            StringReference::Borrowed(borrowed) => visitor.visit_borrowed(self.cx, borrowed),
-            StringReference::Scratch(string) => visitor.visit_ref(self.cx, string),
+            StringReference::Borrowed(borrowed) => {
+                self.cx.check_size(borrowed.len())?;
+                visitor.visit_borrowed(self.cx, borrowed)
+            }
+            StringReference::Scratch(string) => {
+                self.cx.check_size(string.len())?;
+                visitor.visit_ref(self.cx, string)
+            }
         }
     }
 
@@ -367,9 +374,12 @@ where
     where
         F: FnOnce(&mut Self::DecodePack) -> Result<O, C::Error>,
     {
-        let mut decoder = JsonSequenceDecoder::new(self.cx, None, self.parser)?;
+        let cx = self.cx;
+        cx.enter_nested()?;
+        let mut decoder = JsonSequenceDecoder::new(cx, None, self.parser)?;
         let output = f(&mut decoder)?;
         decoder.skip_sequence_remaining()?;
+        cx.leave_nested();
         Ok(output)
     }
 
@@ -378,9 +388,12 @@ where
     where
         F: FnOnce(&mut Self::DecodeSequence) -> Result<O, C::Error>,
     {
-        let mut decoder = JsonSequenceDecoder::new(self.cx, None, self.parser)?;
+        let cx = self.cx;
+        cx.enter_nested()?;
+        let mut decoder = JsonSequenceDecoder::new(cx, None, self.parser)?;
         let output = f(&mut decoder)?;
         decoder.skip_sequence_remaining()?;
+        cx.leave_nested();
         Ok(output)
     }
 
@@ -389,9 +402,12 @@ where
     where
         F: FnOnce(&mut Self::DecodeSequence) -> Result<O, C::Error>,
     {
-        let mut decoder = JsonSequenceDecoder::new(self.cx, Some(hint.size), self.parser)?;
+        let cx = self.cx;
+        cx.enter_nested()?;
+        let mut decoder = JsonSequenceDecoder::new(cx, Some(hint.size), self.parser)?;
         let output = f(&mut decoder)?;
         decoder.skip_sequence_remaining()?;
+        cx.leave_nested();
         Ok(output)
     }
 
@@ -400,9 +416,12 @@ where
     where
         F: FnOnce(&mut Self::DecodeMap) -> Result<O, C::Error>,
     {
-        let mut decoder = JsonObjectDecoder::new(self.cx, None, self.parser)?;
+        let cx = self.cx;
+        cx.enter_nested()?;
+        let mut decoder = JsonObjectDecoder::new(cx, None, self.parser)?;
         let output = f(&mut decoder)?;
         decoder.skip_object_remaining()?;
+        cx.leave_nested();
         Ok(output)
     }
 
@@ -411,9 +430,12 @@ where
     where
         F: FnOnce(&mut Self::DecodeMap) -> Result<O, C::Error>,
     {
-        let mut decoder = JsonObjectDecoder::new(self.cx, Some(hint.size), self.parser)?;
+        let cx = self.cx;
+        cx.enter_nested()?;
+        let mut decoder = JsonObjectDecoder::new(cx, Some(hint.size), self.parser)?;
         let output = f(&mut decoder)?;
         decoder.skip_object_remaining()?;
+        cx.leave_nested();
         Ok(output)
     }
 
@@ -430,9 +452,12 @@ where
     where
         F: FnOnce(&mut Self::DecodeVariant) -> Result<O, C::Error>,
     {
-        let mut decoder = JsonVariantDecoder::new(self.cx, self.parser)?;
+        let cx = self.cx;
+        cx.enter_nested()?;
+        let mut decoder = JsonVariantDecoder::new(cx, self.parser)?;
         let output = f(&mut decoder)?;
         decoder.end()?;
+        cx.leave_nested();
         Ok(output)
     }
 
@@ -473,7 +498,9 @@ where
                 self.parse_false()?;
                 visitor.visit_bool(cx, false)
             }
-            token => Err(cx.message(format_args!("Expected value, found {token:?}"))),
+            token => {
+                Err(cx.marked_message(cx.mark(), format_args!("Expected value, found {token:?}")))
+            }
         }
     }
 }
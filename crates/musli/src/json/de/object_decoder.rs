@@ -7,15 +7,21 @@ use crate::Context;
 use super::{JsonDecoder, JsonKeyDecoder, JsonObjectPairDecoder};
 
 #[must_use = "Must call skip_object_remaining to complete decoding"]
-pub(crate) struct JsonObjectDecoder<'a, P, C: ?Sized> {
+pub(crate) struct JsonObjectDecoder<'a, 'de, P, C>
+where
+    C: ?Sized + Context,
+{
     cx: &'a C,
     first: bool,
     len: Option<usize>,
+    count: usize,
     parser: P,
     finalized: bool,
+    /// Where the last entry started, used to recover from errors.
+    entry: Option<(&'de [u8], C::Mark)>,
 }
 
-impl<'a, 'de, P, C> JsonObjectDecoder<'a, P, C>
+impl<'a, 'de, P, C> JsonObjectDecoder<'a, 'de, P, C>
 where
     P: Parser<'de>,
     C: ?Sized + Context,
@@ -30,8 +36,10 @@ where
             cx,
             first,
             len,
+            count: 0,
             parser,
             finalized: false,
+            entry: None,
         })
     }
 
@@ -40,7 +48,10 @@ where
         let actual = parser.lex(cx);
 
         if !matches!(actual, Token::OpenBrace) {
-            return Err(cx.message(format_args!("Expected opening brace, was {actual}")));
+            return Err(cx.marked_message(
+                cx.mark(),
+                format_args!("Expected opening brace, was {actual}"),
+            ));
         }
 
         parser.skip(cx, 1)?;
@@ -49,8 +60,10 @@ where
             cx,
             first: true,
             len,
+            count: 0,
             parser,
             finalized: false,
+            entry: None,
         })
     }
 
@@ -66,6 +79,8 @@ where
 
             match token {
                 Token::String => {
+                    self.count += 1;
+                    self.cx.check_length(self.count)?;
                     return Ok(true);
                 }
                 Token::Comma if !first => {
@@ -110,7 +125,7 @@ where
     }
 }
 
-impl<'a, 'de, P, C> MapDecoder<'de> for JsonObjectDecoder<'a, P, C>
+impl<'a, 'de, P, C> MapDecoder<'de> for JsonObjectDecoder<'a, 'de, P, C>
 where
     P: Parser<'de>,
     C: ?Sized + Context,
@@ -119,7 +134,7 @@ where
     type DecodeEntry<'this> = JsonObjectPairDecoder<'a, P::Mut<'this>, C>
     where
         Self: 'this;
-    type DecodeRemainingEntries<'this> = JsonObjectDecoder<'a, P::Mut<'this>, C>
+    type DecodeRemainingEntries<'this> = JsonObjectDecoder<'a, 'de, P::Mut<'this>, C>
     where
         Self: 'this;
 
@@ -128,18 +143,59 @@ where
         SizeHint::from(self.len)
     }
 
+    #[inline]
+    fn count_remaining(&mut self) -> Result<Option<usize>, C::Error> {
+        let checkpoint = self.parser.checkpoint();
+        let mark = self.cx.mark();
+        let (first, count, entry) = (self.first, self.count, self.entry.take());
+
+        let mut len = 0;
+
+        while let Some(mut pair) = self.decode_entry()? {
+            pair.decode_key()?.skip()?;
+            pair.decode_value()?.skip()?;
+            len += 1;
+        }
+
+        self.parser.rewind(checkpoint);
+        self.cx.restore(mark);
+        self.first = first;
+        self.count = count;
+        self.entry = entry;
+        Ok(Some(len))
+    }
+
     #[inline]
     fn decode_entry(&mut self) -> Result<Option<Self::DecodeEntry<'_>>, C::Error> {
         if !self.parse_map_key()? {
             return Ok(None);
         }
 
+        if self.cx.is_lenient() {
+            self.entry = Some((self.parser.checkpoint(), self.cx.mark()));
+        }
+
         Ok(Some(JsonObjectPairDecoder::new(
             self.cx,
             self.parser.borrow_mut(),
         )))
     }
 
+    #[inline]
+    fn try_recover(&mut self) -> Result<bool, C::Error> {
+        let Some((checkpoint, mark)) = self.entry.take() else {
+            return Ok(false);
+        };
+
+        self.parser.rewind(checkpoint);
+        self.cx.restore(mark);
+
+        let mut entry = JsonObjectPairDecoder::new(self.cx, self.parser.borrow_mut());
+        entry.decode_key()?.skip()?;
+        entry.decode_value()?.skip()?;
+        Ok(true)
+    }
+
     #[inline]
     fn decode_remaining_entries(
         &mut self,
@@ -154,7 +210,7 @@ where
     }
 }
 
-impl<'a, 'de, P, C> EntriesDecoder<'de> for JsonObjectDecoder<'a, P, C>
+impl<'a, 'de, P, C> EntriesDecoder<'de> for JsonObjectDecoder<'a, 'de, P, C>
 where
     P: Parser<'de>,
     C: ?Sized + Context,
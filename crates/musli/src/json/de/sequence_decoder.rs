@@ -11,6 +11,7 @@ pub(crate) struct JsonSequenceDecoder<'a, P, C: ?Sized> {
     cx: &'a C,
     len: Option<usize>,
     first: bool,
+    count: usize,
     parser: P,
     finalized: bool,
 }
@@ -25,7 +26,10 @@ where
         let actual = parser.lex(cx);
 
         if !matches!(actual, Token::OpenBracket) {
-            return Err(cx.message(format_args!("Expected opening bracket, was {actual}")));
+            return Err(cx.marked_message(
+                cx.mark(),
+                format_args!("Expected opening bracket, was {actual}"),
+            ));
         }
 
         parser.skip(cx, 1)?;
@@ -34,6 +38,7 @@ where
             cx,
             len,
             first: true,
+            count: 0,
             parser,
             finalized: false,
         })
@@ -46,6 +51,8 @@ where
             let token = self.parser.lex(self.cx);
 
             if token.is_value() {
+                self.count += 1;
+                self.cx.check_length(self.count)?;
                 return Ok(true);
             }
 
@@ -104,6 +111,26 @@ where
         SizeHint::from(self.len)
     }
 
+    #[inline]
+    fn count_remaining(&mut self) -> Result<Option<usize>, C::Error> {
+        let checkpoint = self.parser.checkpoint();
+        let mark = self.cx.mark();
+        let (first, count) = (self.first, self.count);
+
+        let mut len = 0;
+
+        while let Some(decoder) = self.try_decode_next()? {
+            decoder.skip()?;
+            len += 1;
+        }
+
+        self.parser.rewind(checkpoint);
+        self.cx.restore(mark);
+        self.first = first;
+        self.count = count;
+        Ok(Some(len))
+    }
+
     #[inline]
     fn try_decode_next(&mut self) -> Result<Option<Self::DecodeNext<'_>>, C::Error> {
         if !self.parse_next_value()? {
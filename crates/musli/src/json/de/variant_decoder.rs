@@ -19,7 +19,10 @@ where
         let actual = parser.lex(cx);
 
         if !matches!(actual, Token::OpenBrace) {
-            return Err(cx.message(format_args!("Expected open brace, was {actual}")));
+            return Err(cx.marked_message(
+                cx.mark(),
+                format_args!("Expected open brace, was {actual}"),
+            ));
         }
 
         parser.skip(cx, 1)?;
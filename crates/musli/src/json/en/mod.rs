@@ -71,6 +71,11 @@ where
         write!(f, "value that can be encoded to JSON")
     }
 
+    #[inline]
+    fn is_size_required(&self) -> bool {
+        false
+    }
+
     #[inline]
     fn encode<T>(self, value: T) -> Result<Self::Ok, Self::Error>
     where
@@ -9,14 +9,14 @@ use rust_alloc::string::String;
 use rust_alloc::vec::Vec;
 
 use crate::mode::Text;
-use crate::Decode;
 #[cfg(feature = "alloc")]
-use crate::{Context, Encode};
+use crate::Encode;
+use crate::{Context, Decode};
 
 use super::de::JsonDecoder;
 use super::en::JsonEncoder;
 use super::error::Error;
-use super::parser::IntoParser;
+use super::parser::{IntoParser, Parser};
 
 /// The default configuration.
 pub const DEFAULT: Encoding = Encoding::new();
@@ -174,7 +174,9 @@ where
         json,
         JsonEncoder::new,
         JsonDecoder::new,
-        IntoParser::into_parser
+        IntoParser::into_parser,
+        Parser,
+        trailing = skip_trailing_whitespace,
     );
 
     /// Encode the given value to the given value to a [`String`] using the
@@ -275,3 +277,13 @@ impl<M> Clone for Encoding<M> {
 }
 
 impl<M> Copy for Encoding<M> {}
+
+/// Skip over any whitespace which follows a value, since it's permitted at the
+/// end of a JSON document.
+#[inline]
+fn skip_trailing_whitespace<C>(cx: &C, bytes: &mut &[u8])
+where
+    C: ?Sized + Context,
+{
+    IntoParser::into_parser(bytes).skip_whitespace(cx);
+}
@@ -5,14 +5,32 @@ use rust_alloc::boxed::Box;
 #[cfg(feature = "alloc")]
 use rust_alloc::string::ToString;
 
-use crate::context::ContextError;
+use crate::context::{ContextError, ErrorKind};
 
 /// Error raised during json encoding.
 #[derive(Debug)]
 pub struct Error {
+    kind: ErrorKind,
     err: ErrorImpl,
 }
 
+impl Error {
+    /// Get the kind of the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::context::ErrorKind;
+    ///
+    /// let error = musli::json::from_slice::<u8>(b"1000").unwrap_err();
+    /// assert_eq!(error.kind(), ErrorKind::NumberOverflow);
+    /// ```
+    #[inline]
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+}
+
 impl fmt::Display for Error {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -51,12 +69,21 @@ impl ContextError for Error {
     }
 
     #[inline]
-    #[allow(unused_variables)]
     fn message<T>(message: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        Self::message_with_kind(ErrorKind::Other, message)
+    }
+
+    #[inline]
+    #[allow(unused_variables)]
+    fn message_with_kind<T>(kind: ErrorKind, message: T) -> Self
     where
         T: fmt::Display,
     {
         Self {
+            kind,
             #[cfg(feature = "alloc")]
             err: ErrorImpl::Message(message.to_string().into()),
             #[cfg(not(feature = "alloc"))]
@@ -91,6 +118,15 @@ pub(crate) enum IntegerError {
     InvalidNumeric,
 }
 
+impl IntegerError {
+    pub(crate) fn kind(&self) -> ErrorKind {
+        match self {
+            IntegerError::IntegerOverflow => ErrorKind::NumberOverflow,
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
 impl fmt::Display for IntegerError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
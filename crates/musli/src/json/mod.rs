@@ -36,6 +36,37 @@
 //! });
 //! # Ok::<_, musli::json::Error>(())
 //! ```
+//!
+//! <br>
+//!
+//! ## Reporting error positions
+//!
+//! The errors returned by functions like [`from_str`] only carry a message. To
+//! point users at the exact location of an error, such as in a configuration
+//! file, decode with a [`DefaultContext`] and render its report against the
+//! input using [`Report::with_source`]:
+//!
+//! ```
+//! use musli::{Decode, Encode};
+//! use musli::context;
+//!
+//! #[derive(Decode, Encode)]
+//! struct Config {
+//!     threads: u8,
+//! }
+//!
+//! let input = "{\n  \"threads\": \"many\"\n}";
+//!
+//! let cx = context::new();
+//! let result = musli::json::DEFAULT.from_str_with::<_, Config>(&cx, input);
+//! assert!(result.is_err());
+//!
+//! let report = cx.report().with_source(input.as_bytes()).to_string();
+//! assert!(report.starts_with(".threads: Invalid numeric (at line 2, column 14)"));
+//! ```
+//!
+//! [`DefaultContext`]: crate::context::DefaultContext
+//! [`Report::with_source`]: crate::context::Report::with_source
 
 #![cfg(feature = "json")]
 #![cfg_attr(doc_cfg, doc(cfg(feature = "json")))]
@@ -44,7 +75,7 @@ mod de;
 mod en;
 mod encoding;
 mod error;
-mod parser;
+pub(crate) mod parser;
 
 #[cfg(feature = "test")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "test")))]
@@ -59,7 +90,9 @@ pub type Result<T, E = Error> = core::result::Result<T, E>;
 #[doc(inline)]
 pub use self::encoding::to_writer;
 #[doc(inline)]
-pub use self::encoding::{decode, encode, from_slice, from_str, to_fixed_bytes, Encoding, DEFAULT};
+pub use self::encoding::{
+    decode, encode, from_slice, from_slice_exact, from_str, to_fixed_bytes, Encoding, DEFAULT,
+};
 #[cfg(feature = "alloc")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 #[doc(inline)]
@@ -1,3 +1,4 @@
+use crate::context::ErrorKind;
 use crate::json::error::IntegerError;
 use crate::json::parser::Parser;
 use crate::Context;
@@ -291,7 +292,7 @@ where
 
     match decode_unsigned_full(cx, p, start)?.compute() {
         Ok(value) => Ok(value),
-        Err(error) => Err(cx.marked_message(start, error)),
+        Err(error) => Err(cx.marked_message_with_kind(start, error.kind(), error)),
     }
 }
 
@@ -375,7 +376,7 @@ where
 
     match decode_signed_base(cx, p)?.compute() {
         Ok(value) => Ok(value),
-        Err(error) => Err(cx.marked_message(start, error)),
+        Err(error) => Err(cx.marked_message_with_kind(start, error.kind(), error)),
     }
 }
 
@@ -394,7 +395,7 @@ where
 
     match decode_signed_full_inner(cx, p)?.compute() {
         Ok(value) => Ok(value),
-        Err(error) => Err(cx.marked_message(start, error)),
+        Err(error) => Err(cx.marked_message_with_kind(start, error.kind(), error)),
     }
 }
 
@@ -461,7 +462,11 @@ where
                 m.value = match m.value.checked_pow10(zeros as u32) {
                     Some(mantissa) => mantissa,
                     None => {
-                        return Err(cx.marked_message(start, IntegerError::IntegerOverflow));
+                        return Err(cx.marked_message_with_kind(
+                            start,
+                            ErrorKind::NumberOverflow,
+                            IntegerError::IntegerOverflow,
+                        ));
                     }
                 };
             }
@@ -509,7 +514,11 @@ where
 
     match if is_negative { e.negate() } else { e.signed() } {
         Some(value) => Ok(value),
-        None => Err(cx.marked_message(start, IntegerError::IntegerOverflow)),
+        None => Err(cx.marked_message_with_kind(
+            start,
+            ErrorKind::NumberOverflow,
+            IntegerError::IntegerOverflow,
+        )),
     }
 }
 
@@ -522,7 +531,11 @@ where
     C: ?Sized + Context,
 {
     let Some(out) = out.checked_mul10() else {
-        return Err(cx.marked_message(start, IntegerError::IntegerOverflow));
+        return Err(cx.marked_message_with_kind(
+            start,
+            ErrorKind::NumberOverflow,
+            IntegerError::IntegerOverflow,
+        ));
     };
 
     Ok(out + T::from_byte(p.read_byte(cx)? - b'0'))
@@ -1,4 +1,5 @@
 use crate::alloc::{Allocator, Vec};
+use crate::context::ErrorKind;
 use crate::json::error::ErrorMessage;
 use crate::json::parser::{Parser, StringReference, Token};
 use crate::reader::SliceUnderflow;
@@ -79,9 +80,13 @@ impl<'de> Parser<'de> for MutSliceParser<'_, 'de> {
         C: ?Sized + Context,
     {
         if self.slice.len() < n {
-            return Err(cx.custom(SliceUnderflow::new(n, self.slice.len())));
+            return Err(cx.message_with_kind(
+                ErrorKind::UnexpectedEof,
+                SliceUnderflow::new(n, self.slice.len()),
+            ));
         }
 
+        cx.check_input(n)?;
         *self.slice = &self.slice[n..];
         cx.advance(n);
         Ok(())
@@ -93,9 +98,13 @@ impl<'de> Parser<'de> for MutSliceParser<'_, 'de> {
         C: ?Sized + Context,
     {
         if self.slice.len() < buf.len() {
-            return Err(cx.custom(SliceUnderflow::new(buf.len(), self.slice.len())));
+            return Err(cx.message_with_kind(
+                ErrorKind::UnexpectedEof,
+                SliceUnderflow::new(buf.len(), self.slice.len()),
+            ));
         }
 
+        cx.check_input(buf.len())?;
         let (head, tail) = self.slice.split_at(buf.len());
         *self.slice = tail;
         buf.copy_from_slice(head);
@@ -131,6 +140,18 @@ impl<'de> Parser<'de> for MutSliceParser<'_, 'de> {
         self.slice.first().copied()
     }
 
+    #[inline]
+    fn checkpoint(&self) -> &'de [u8] {
+        self.slice
+    }
+
+    #[inline]
+    fn rewind(&mut self, checkpoint: &'de [u8]) {
+        // Checkpoints are always taken between tokens, so this keeps the slice
+        // valid UTF-8.
+        *self.slice = checkpoint;
+    }
+
     fn parse_f32<C>(&mut self, cx: &C) -> Result<f32, C::Error>
     where
         C: ?Sized + Context,
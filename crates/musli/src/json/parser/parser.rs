@@ -96,6 +96,19 @@ pub trait Parser<'de>: private::Sealed {
     #[doc(hidden)]
     fn peek(&mut self) -> Option<u8>;
 
+    /// Get the remaining input, which can be used to [`rewind`] the parser.
+    ///
+    /// [`rewind`]: Parser::rewind
+    #[doc(hidden)]
+    fn checkpoint(&self) -> &'de [u8];
+
+    /// Rewind the parser to a checkpoint previously returned by
+    /// [`checkpoint`].
+    ///
+    /// [`checkpoint`]: Parser::checkpoint
+    #[doc(hidden)]
+    fn rewind(&mut self, checkpoint: &'de [u8]);
+
     #[doc(hidden)]
     fn lex<C>(&mut self, cx: &C) -> Token
     where
@@ -260,6 +273,16 @@ where
         (**self).peek()
     }
 
+    #[inline(always)]
+    fn checkpoint(&self) -> &'de [u8] {
+        (**self).checkpoint()
+    }
+
+    #[inline(always)]
+    fn rewind(&mut self, checkpoint: &'de [u8]) {
+        (**self).rewind(checkpoint)
+    }
+
     #[inline(always)]
     fn lex<C>(&mut self, cx: &C) -> Token
     where
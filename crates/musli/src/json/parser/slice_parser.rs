@@ -1,4 +1,5 @@
 use crate::alloc::{Allocator, Vec};
+use crate::context::ErrorKind;
 use crate::json::error::ErrorMessage;
 use crate::json::parser::{Parser, StringReference, Token};
 use crate::reader::SliceUnderflow;
@@ -73,9 +74,13 @@ impl<'de> Parser<'de> for SliceParser<'de> {
         let outcome = self.index.wrapping_add(n);
 
         if outcome > self.slice.len() || outcome < self.index {
-            return Err(cx.custom(SliceUnderflow::new(n, self.slice.len() - self.index)));
+            return Err(cx.message_with_kind(
+                ErrorKind::UnexpectedEof,
+                SliceUnderflow::new(n, self.slice.len() - self.index),
+            ));
         }
 
+        cx.check_input(n)?;
         self.index = outcome;
         cx.advance(n);
         Ok(())
@@ -89,12 +94,13 @@ impl<'de> Parser<'de> for SliceParser<'de> {
         let outcome = self.index.wrapping_add(buf.len());
 
         if outcome > self.slice.len() || outcome < self.index {
-            return Err(cx.custom(SliceUnderflow::new(
-                buf.len(),
-                self.slice.len() - self.index,
-            )));
+            return Err(cx.message_with_kind(
+                ErrorKind::UnexpectedEof,
+                SliceUnderflow::new(buf.len(), self.slice.len() - self.index),
+            ));
         }
 
+        cx.check_input(buf.len())?;
         buf.copy_from_slice(&self.slice[self.index..outcome]);
         self.index = outcome;
         cx.advance(buf.len());
@@ -120,6 +126,16 @@ impl<'de> Parser<'de> for SliceParser<'de> {
         self.slice.get(self.index).copied()
     }
 
+    #[inline]
+    fn checkpoint(&self) -> &'de [u8] {
+        self.slice.get(self.index..).unwrap_or_default()
+    }
+
+    #[inline]
+    fn rewind(&mut self, checkpoint: &'de [u8]) {
+        self.index = self.slice.len().saturating_sub(checkpoint.len());
+    }
+
     fn parse_f32<C>(&mut self, cx: &C) -> Result<f32, C::Error>
     where
         C: ?Sized + Context,
@@ -1,6 +1,7 @@
 #![allow(clippy::zero_prefixed_literal)]
 
 use crate::alloc::{Allocator, Vec};
+use crate::context::ErrorKind;
 use crate::Context;
 
 // Copied and adapter form the serde-json project under the MIT and Apache 2.0
@@ -65,7 +66,9 @@ where
     #[inline]
     fn next(&mut self) -> Result<u8, C::Error> {
         let Some(b) = self.slice.get(self.index) else {
-            return Err(self.cx.message("End of input"));
+            return Err(self
+                .cx
+                .message_with_kind(ErrorKind::UnexpectedEof, "End of input"));
         };
 
         self.cx.advance(1);
@@ -298,7 +301,9 @@ where
             }
 
             if self.index == self.slice.len() {
-                return Err(self.cx.message("End of input"));
+                return Err(self
+                    .cx
+                    .message_with_kind(ErrorKind::UnexpectedEof, "End of input"));
             }
 
             match self.slice[self.index] {
@@ -321,7 +326,7 @@ where
                         self.check_utf8(slice, start)?;
 
                         if !scratch.write(slice) {
-                            return Err(self.cx.message("Scratch buffer overflow"));
+                            return Err(self.cx.alloc_failed());
                         }
 
                         self.index = self.index.wrapping_add(1);
@@ -337,14 +342,14 @@ where
                     self.check_utf8(slice, start)?;
 
                     if !scratch.write(slice) {
-                        return Err(self.cx.message("Scratch buffer overflow"));
+                        return Err(self.cx.alloc_failed());
                     }
 
                     self.index = self.index.wrapping_add(1);
                     self.cx.advance(1);
 
                     if !self.parse_escape(validate, scratch)? {
-                        return Err(self.cx.marked_message(open_mark, "Buffer overflow"));
+                        return Err(self.cx.alloc_failed());
                     }
 
                     open = self.index;
@@ -396,7 +401,11 @@ where
     #[inline]
     fn check_utf8(&self, bytes: &[u8], start: C::Mark) -> Result<(), C::Error> {
         if crate::str::from_utf8(bytes).is_err() {
-            Err(self.cx.marked_message(start, "Invalid unicode string"))
+            Err(self.cx.marked_message_with_kind(
+                start,
+                ErrorKind::InvalidUtf8,
+                "Invalid unicode string",
+            ))
         } else {
             Ok(())
         }
@@ -176,7 +176,14 @@
 //! | [`musli::storage`]                    | ✔ | ✔ | ✗ | ✗ |
 //! | [`musli::wire`]                       | ✔ | ✔ | ✔ | ✗ |
 //! | [`musli::descriptive`]                | ✔ | ✔ | ✔ | ✔ |
+//! | [`musli::cbor`]                       | ✔ | ✔ | ✔ | ✔ |
+//! | [`musli::msgpack`]                    | ✔ | ✔ | ✔ | ✔ |
+//! | [`musli::protobuf`]                   | ✔ | ✔ | ✔ | ✗ |
 //! | [`musli::json`] [^json]               | ✔ | ✔ | ✔ | ✔ |
+//! | [`musli::csv`]                        | ✔ | ✔ | ✔ | ✗ |
+//! | [`musli::urlencoded`]                 | ✔ | ✔ | ✔ | ✗ |
+//! | [`musli::toml`]                       | ✔ | ✔ | ✔ | ✔ |
+//! | [`musli::env`] [^env]                 | ✔ | ✔ | ✔ | ✗ |
 //!
 //! `reorder` determines whether fields must occur in exactly the order in which
 //! they are specified in their type. Reordering fields in such a type would
@@ -212,6 +219,9 @@
 //! as a litmus test to ensure that Müsli has the necessary framework features
 //! to support it. Luckily, the implementation is also quite good!
 //!
+//! [^env]: Only supports decoding, since it reads configuration from environment
+//! variables.
+//!
 //! <br>
 //!
 //! ## Upgrade stability
@@ -370,8 +380,15 @@
 //! [`derives`]: <https://docs.rs/musli/latest/musli/help/derives/index.html>
 //! [`Encode`]: <https://docs.rs/musli/latest/musli/en/trait.Encode.html>
 //! [`Encoder`]: <https://docs.rs/musli/latest/musli/trait.Encoder.html>
+//! [`musli::cbor`]: <https://docs.rs/musli/latest/musli/cbor/index.html>
+//! [`musli::msgpack`]: <https://docs.rs/musli/latest/musli/msgpack/index.html>
+//! [`musli::protobuf`]: <https://docs.rs/musli/latest/musli/protobuf/index.html>
 //! [`musli::descriptive`]: <https://docs.rs/musli/latest/musli/descriptive/index.html>
 //! [`musli::json`]: <https://docs.rs/musli/latest/musli/json/index.html>
+//! [`musli::csv`]: <https://docs.rs/musli/latest/musli/csv/index.html>
+//! [`musli::urlencoded`]: <https://docs.rs/musli/latest/musli/urlencoded/index.html>
+//! [`musli::toml`]: <https://docs.rs/musli/latest/musli/toml/index.html>
+//! [`musli::env`]: <https://docs.rs/musli/latest/musli/env/index.html>
 //! [`musli::serde`]: <https://docs.rs/musli/latest/musli/serde/index.html>
 //! [`musli::storage`]: <https://docs.rs/musli/latest/musli/storage/index.html>
 //! [`musli::value`]: <https://docs.rs/musli/latest/musli/value/index.html>
@@ -561,10 +578,19 @@ pub use musli_core::__priv;
 
 pub mod alloc;
 
+pub mod cbor;
+pub mod csv;
 pub mod descriptive;
+pub mod env;
+pub mod framing;
 pub mod json;
+pub mod msgpack;
+pub mod protobuf;
 pub mod serde;
 pub mod storage;
+pub mod tokio;
+pub mod toml;
+pub mod urlencoded;
 pub mod value;
 pub mod wire;
 
@@ -586,6 +612,10 @@ pub use self::reader::{IntoReader, Reader};
 
 pub mod wrap;
 
+mod transcode;
+#[doc(inline)]
+pub use self::transcode::transcode;
+
 pub mod writer;
 #[doc(inline)]
 pub use self::writer::Writer;
@@ -0,0 +1,123 @@
+//! Helper macro to define the error type of a format.
+
+/// Define the `Error` type of a format.
+///
+/// The error keeps track of its [`ErrorKind`], and custom errors are retained
+/// so that they can be accessed through [`Error::source`] if the `alloc`
+/// feature is enabled.
+///
+/// The first set of attributes is used for the error type, and the second for
+/// its `kind` method.
+///
+/// [`ErrorKind`]: crate::context::ErrorKind
+/// [`Error::source`]: core::error::Error::source
+macro_rules! error_type {
+    (
+        $(#[$meta:meta])*
+        pub struct Error;
+
+        $(#[$kind_meta:meta])*
+        pub fn kind;
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug)]
+        pub struct Error {
+            kind: $crate::context::ErrorKind,
+            err: ErrorImpl,
+        }
+
+        impl Error {
+            /// Get the kind of the error.
+            $(#[$kind_meta])*
+            #[inline]
+            pub fn kind(&self) -> $crate::context::ErrorKind {
+                self.kind
+            }
+        }
+
+        impl ::core::fmt::Display for Error {
+            #[inline]
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Display::fmt(&self.err, f)
+            }
+        }
+
+        #[derive(Debug)]
+        enum ErrorImpl {
+            #[cfg(feature = "alloc")]
+            Message(rust_alloc::boxed::Box<str>),
+            #[cfg(feature = "alloc")]
+            Custom(rust_alloc::boxed::Box<dyn 'static + Send + Sync + ::core::error::Error>),
+            #[cfg(not(feature = "alloc"))]
+            Empty,
+        }
+
+        impl ::core::fmt::Display for ErrorImpl {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match self {
+                    #[cfg(feature = "alloc")]
+                    ErrorImpl::Message(message) => ::core::fmt::Display::fmt(message, f),
+                    #[cfg(feature = "alloc")]
+                    ErrorImpl::Custom(message) => ::core::fmt::Display::fmt(message, f),
+                    #[cfg(not(feature = "alloc"))]
+                    ErrorImpl::Empty => write!(f, "Message error (see diagnostics)"),
+                }
+            }
+        }
+
+        impl ::core::error::Error for Error {
+            #[inline]
+            fn source(&self) -> Option<&(dyn ::core::error::Error + 'static)> {
+                match &self.err {
+                    #[cfg(feature = "alloc")]
+                    ErrorImpl::Custom(err) => Some(&**err),
+                    _ => None,
+                }
+            }
+        }
+
+        impl $crate::context::ContextError for Error {
+            #[inline]
+            #[allow(unused_variables)]
+            fn custom<T>(error: T) -> Self
+            where
+                T: 'static + Send + Sync + ::core::error::Error,
+            {
+                Self {
+                    kind: $crate::context::ErrorKind::Other,
+                    #[cfg(feature = "alloc")]
+                    err: ErrorImpl::Custom(rust_alloc::boxed::Box::new(error)),
+                    #[cfg(not(feature = "alloc"))]
+                    err: ErrorImpl::Empty,
+                }
+            }
+
+            #[inline]
+            fn message<T>(message: T) -> Self
+            where
+                T: ::core::fmt::Display,
+            {
+                Self::message_with_kind($crate::context::ErrorKind::Other, message)
+            }
+
+            #[inline]
+            #[allow(unused_variables)]
+            fn message_with_kind<T>(kind: $crate::context::ErrorKind, message: T) -> Self
+            where
+                T: ::core::fmt::Display,
+            {
+                Self {
+                    kind,
+                    #[cfg(feature = "alloc")]
+                    err: ErrorImpl::Message(
+                        rust_alloc::string::ToString::to_string(&message).into(),
+                    ),
+                    #[cfg(not(feature = "alloc"))]
+                    err: ErrorImpl::Empty,
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use error_type;
@@ -1,7 +1,34 @@
 //! Helper macros for use with Musli.
 
+/// Select between two lines of documentation depending on whether decoding
+/// consumes the whole input, as indicated by `to_end = true` being passed to
+/// the encoding macros.
+macro_rules! doc_select {
+    ([], $default:literal, $to_end:literal) => {
+        $default
+    };
+
+    ([$($tt:tt)+], $default:literal, $to_end:literal) => {
+        $to_end
+    };
+}
+
+pub(crate) use doc_select;
+
+/// Only emit the given items if decoding doesn't consume the whole input, as
+/// indicated by `to_end = true` being passed to the encoding macros.
+macro_rules! unless_to_end {
+    ([], $($item:item)*) => {
+        $($item)*
+    };
+
+    ([$($tt:tt)+], $($item:item)*) => {};
+}
+
+pub(crate) use unless_to_end;
+
 macro_rules! bare_encoding {
-    ($mode:ident, $default:ident, $what:ident, $reader_trait:ident) => {
+    ($mode:ident, $default:ident, $what:ident, $reader_trait:ident $(, name_type = $name_type:ty, names = [$name:literal, $age:literal])? $(, to_end = $to_end:literal)?) => {
         /// Encode the given value to the given [`Writer`] using the [`DEFAULT`]
         /// [`Encoding`].
         ///
@@ -15,8 +42,11 @@ macro_rules! bare_encoding {
         #[doc = concat!("# use musli::", stringify!($what), "::Error;")]
         ///
         /// #[derive(Decode, Encode)]
+        $(#[doc = concat!(" #[musli(name_type = ", stringify!($name_type), ")]")])?
         /// struct Person {
+        $(#[doc = concat!("     #[musli(name = ", stringify!($name), ")]")])?
         ///     name: String,
+        $(#[doc = concat!("     #[musli(name = ", stringify!($age), ")]")])?
         ///     age: u32,
         /// }
         ///
@@ -54,8 +84,11 @@ macro_rules! bare_encoding {
         #[doc = concat!("# use musli::", stringify!($what), "::Error;")]
         ///
         /// #[derive(Decode, Encode)]
+        $(#[doc = concat!(" #[musli(name_type = ", stringify!($name_type), ")]")])?
         /// struct Person {
+        $(#[doc = concat!("     #[musli(name = ", stringify!($name), ")]")])?
         ///     name: String,
+        $(#[doc = concat!("     #[musli(name = ", stringify!($age), ")]")])?
         ///     age: u32,
         /// }
         ///
@@ -88,8 +121,11 @@ macro_rules! bare_encoding {
         #[doc = concat!("# use musli::", stringify!($what), "::Error;")]
         ///
         /// #[derive(Decode, Encode)]
+        $(#[doc = concat!(" #[musli(name_type = ", stringify!($name_type), ")]")])?
         /// struct Person {
+        $(#[doc = concat!("     #[musli(name = ", stringify!($name), ")]")])?
         ///     name: String,
+        $(#[doc = concat!("     #[musli(name = ", stringify!($age), ")]")])?
         ///     age: u32,
         /// }
         ///
@@ -124,8 +160,11 @@ macro_rules! bare_encoding {
         #[doc = concat!("# use musli::", stringify!($what), "::Error;")]
         ///
         /// #[derive(Decode, Encode)]
+        $(#[doc = concat!(" #[musli(name_type = ", stringify!($name_type), ")]")])?
         /// struct Person {
+        $(#[doc = concat!("     #[musli(name = ", stringify!($name), ")]")])?
         ///     name: String,
+        $(#[doc = concat!("     #[musli(name = ", stringify!($age), ")]")])?
         ///     age: u32,
         /// }
         ///
@@ -165,8 +204,11 @@ macro_rules! bare_encoding {
         #[doc = concat!("# use musli::", stringify!($what), "::Error;")]
         ///
         /// #[derive(Decode, Encode)]
+        $(#[doc = concat!(" #[musli(name_type = ", stringify!($name_type), ")]")])?
         /// struct Person {
+        $(#[doc = concat!("     #[musli(name = ", stringify!($name), ")]")])?
         ///     name: String,
+        $(#[doc = concat!("     #[musli(name = ", stringify!($age), ")]")])?
         ///     age: u32,
         /// }
         ///
@@ -175,14 +217,14 @@ macro_rules! bare_encoding {
         ///     age: 61,
         /// })?;
         ///
-        /// // Add some extra data which will be ignored during decoding.
-        /// data.extend_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+        #[doc = $crate::macros::doc_select!([$($to_end)?], " // Add some extra data which will be ignored during decoding.", " // The value extends until the end of the input, so no extra data")]
+        #[doc = $crate::macros::doc_select!([$($to_end)?], " data.extend_from_slice(&[0xde, 0xad, 0xbe, 0xef]);", " // can follow it.")]
         ///
         /// // Note: A slice implements `musli::Reader`.
         /// let mut slice = &data[..];
         ///
         #[doc = concat!("let person: Person = ", stringify!($what), "::decode(&mut slice)?;")]
-        /// assert_eq!(slice, &[0xde, 0xad, 0xbe, 0xef]);
+        #[doc = $crate::macros::doc_select!([$($to_end)?], " assert_eq!(slice, &[0xde, 0xad, 0xbe, 0xef]);", " assert!(slice.is_empty());")]
         /// assert_eq!(person.name, "Aristotle");
         /// assert_eq!(person.age, 61);
         /// # Ok::<(), Error>(())
@@ -207,8 +249,11 @@ macro_rules! bare_encoding {
         #[doc = concat!("# use musli::", stringify!($what), "::Error;")]
         ///
         /// #[derive(Decode, Encode)]
+        $(#[doc = concat!(" #[musli(name_type = ", stringify!($name_type), ")]")])?
         /// struct Person {
+        $(#[doc = concat!("     #[musli(name = ", stringify!($name), ")]")])?
         ///     name: String,
+        $(#[doc = concat!("     #[musli(name = ", stringify!($age), ")]")])?
         ///     age: u32,
         /// }
         ///
@@ -229,6 +274,23 @@ macro_rules! bare_encoding {
         {
             $default.from_slice(bytes)
         }
+
+        $crate::macros::unless_to_end! {
+            [$($to_end)?],
+
+            /// Decode the given type `T` from the given slice using the
+            /// [`DEFAULT`] [`Encoding`], raising an error if any bytes remain
+            /// after the value.
+            ///
+            /// See [`Encoding::from_slice_exact`] for more.
+            #[inline]
+            pub fn from_slice_exact<'de, T>(bytes: &'de [u8]) -> Result<T, Error>
+            where
+                T: $crate::Decode<'de, $mode>,
+            {
+                $default.from_slice_exact(bytes)
+            }
+        }
     };
 }
 
@@ -236,7 +298,7 @@ pub(crate) use bare_encoding;
 
 /// Generate all public encoding helpers.
 macro_rules! encoding_impls {
-    ($mode:ident, $what:ident, $encoder_new:path, $decoder_new:path, $reader_trait:ident :: $into_reader:ident $(,)?) => {
+    ($mode:ident, $what:ident, $encoder_new:path, $decoder_new:path, $reader_trait:ident :: $into_reader:ident, $reader_assoc:ident $(, name_type = $name_type:ty, names = [$name:literal, $age:literal])? $(, to_end = $to_end:literal)? $(, trailing = $trailing:path)? $(,)?) => {
         /// Encode the given value to the given [`Writer`] using the current
         /// [`Encoding`].
         ///
@@ -252,8 +314,11 @@ macro_rules! encoding_impls {
         /// const ENCODING: Encoding = Encoding::new();
         ///
         /// #[derive(Decode, Encode)]
+        $(#[doc = concat!(" #[musli(name_type = ", stringify!($name_type), ")]")])?
         /// struct Person {
+        $(#[doc = concat!("     #[musli(name = ", stringify!($name), ")]")])?
         ///     name: String,
+        $(#[doc = concat!("     #[musli(name = ", stringify!($age), ")]")])?
         ///     age: u32,
         /// }
         ///
@@ -295,8 +360,11 @@ macro_rules! encoding_impls {
         /// const ENCODING: Encoding = Encoding::new();
         ///
         /// #[derive(Decode, Encode)]
+        $(#[doc = concat!(" #[musli(name_type = ", stringify!($name_type), ")]")])?
         /// struct Person {
+        $(#[doc = concat!("     #[musli(name = ", stringify!($name), ")]")])?
         ///     name: String,
+        $(#[doc = concat!("     #[musli(name = ", stringify!($age), ")]")])?
         ///     age: u32,
         /// }
         ///
@@ -335,8 +403,11 @@ macro_rules! encoding_impls {
         /// const ENCODING: Encoding = Encoding::new();
         ///
         /// #[derive(Decode, Encode)]
+        $(#[doc = concat!(" #[musli(name_type = ", stringify!($name_type), ")]")])?
         /// struct Person {
+        $(#[doc = concat!("     #[musli(name = ", stringify!($name), ")]")])?
         ///     name: String,
+        $(#[doc = concat!("     #[musli(name = ", stringify!($age), ")]")])?
         ///     age: u32,
         /// }
         ///
@@ -379,8 +450,11 @@ macro_rules! encoding_impls {
         /// const ENCODING: Encoding = Encoding::new();
         ///
         /// #[derive(Decode, Encode)]
+        $(#[doc = concat!(" #[musli(name_type = ", stringify!($name_type), ")]")])?
         /// struct Person {
+        $(#[doc = concat!("     #[musli(name = ", stringify!($name), ")]")])?
         ///     name: String,
+        $(#[doc = concat!("     #[musli(name = ", stringify!($age), ")]")])?
         ///     age: u32,
         /// }
         ///
@@ -422,8 +496,11 @@ macro_rules! encoding_impls {
         /// const ENCODING: Encoding = Encoding::new();
         ///
         /// #[derive(Decode, Encode)]
+        $(#[doc = concat!(" #[musli(name_type = ", stringify!($name_type), ")]")])?
         /// struct Person {
+        $(#[doc = concat!("     #[musli(name = ", stringify!($name), ")]")])?
         ///     name: String,
+        $(#[doc = concat!("     #[musli(name = ", stringify!($age), ")]")])?
         ///     age: u32,
         /// }
         ///
@@ -432,14 +509,14 @@ macro_rules! encoding_impls {
         ///     age: 61,
         /// })?;
         ///
-        /// // Add some extra data which will be ignored during decoding.
-        /// data.extend_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+        #[doc = $crate::macros::doc_select!([$($to_end)?], " // Add some extra data which will be ignored during decoding.", " // The value extends until the end of the input, so no extra data")]
+        #[doc = $crate::macros::doc_select!([$($to_end)?], " data.extend_from_slice(&[0xde, 0xad, 0xbe, 0xef]);", " // can follow it.")]
         ///
         /// // Note: A slice implements `musli::Reader`.
         /// let mut slice = &data[..];
         /// let person: Person = ENCODING.decode(&mut slice)?;
         ///
-        /// assert_eq!(slice, &[0xde, 0xad, 0xbe, 0xef]);
+        #[doc = $crate::macros::doc_select!([$($to_end)?], " assert_eq!(slice, &[0xde, 0xad, 0xbe, 0xef]);", " assert!(slice.is_empty());")]
         /// assert_eq!(person.name, "Aristotle");
         /// assert_eq!(person.age, 61);
         /// # Ok::<(), Error>(())
@@ -469,8 +546,11 @@ macro_rules! encoding_impls {
         /// const ENCODING: Encoding = Encoding::new();
         ///
         /// #[derive(Decode, Encode)]
+        $(#[doc = concat!(" #[musli(name_type = ", stringify!($name_type), ")]")])?
         /// struct Person {
+        $(#[doc = concat!("     #[musli(name = ", stringify!($name), ")]")])?
         ///     name: String,
+        $(#[doc = concat!("     #[musli(name = ", stringify!($age), ")]")])?
         ///     age: u32,
         /// }
         ///
@@ -508,6 +588,131 @@ macro_rules! encoding_impls {
             self.from_slice(string.as_bytes())
         }
 
+        $crate::macros::unless_to_end! {
+            [$($to_end)?],
+
+            /// Decode the given type `T` from the given slice using the current
+            /// [`Encoding`], raising an error if any bytes remain after the
+            /// value.
+            ///
+            /// Unlike [`Encoding::from_slice`], this makes sure that a value
+            /// which was followed by garbage or by another value isn't
+            /// silently accepted. The error is marked at the first trailing
+            /// byte, so a context which tracks positions like
+            /// [`DefaultContext`] reports its offset.
+            ///
+            /// [`DefaultContext`]: crate::context::DefaultContext
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use musli::{Decode, Encode};
+            #[doc = concat!("use musli::", stringify!($what), "::Encoding;")]
+            #[doc = concat!("# use musli::", stringify!($what), "::Error;")]
+            ///
+            /// const ENCODING: Encoding = Encoding::new();
+            ///
+            /// #[derive(Decode, Encode)]
+            $(#[doc = concat!(" #[musli(name_type = ", stringify!($name_type), ")]")])?
+            /// struct Person {
+            $(#[doc = concat!("     #[musli(name = ", stringify!($name), ")]")])?
+            ///     name: String,
+            $(#[doc = concat!("     #[musli(name = ", stringify!($age), ")]")])?
+            ///     age: u32,
+            /// }
+            ///
+            /// let mut data = ENCODING.to_vec(&Person {
+            ///     name: "Aristotle".to_string(),
+            ///     age: 61,
+            /// })?;
+            ///
+            /// let person: Person = ENCODING.from_slice_exact(&data[..])?;
+            /// assert_eq!(person.name, "Aristotle");
+            /// assert_eq!(person.age, 61);
+            ///
+            /// data.extend_from_slice(b"garbage");
+            ///
+            /// let error = ENCODING.from_slice_exact::<Person>(&data[..]).err().unwrap();
+            /// assert_eq!(error.to_string(), "Expected end of input, but found 7 trailing bytes");
+            /// # Ok::<(), Error>(())
+            /// ```
+            #[inline]
+            pub fn from_slice_exact<'de, T>(self, bytes: &'de [u8]) -> Result<T, Error>
+            where
+                T: $crate::Decode<'de, $mode>,
+            {
+                $crate::alloc::default!(|alloc| {
+                    let cx = $crate::context::Same::with_alloc(alloc);
+                    self.from_slice_exact_with(&cx, bytes)
+                })
+            }
+        }
+
+        $crate::macros::unless_to_end! {
+            [$($to_end)?],
+
+            /// Decode values of type `T` which have been concatenated in the
+            /// given slice using the current [`Encoding`], until the slice is
+            /// exhausted.
+            ///
+            /// The same context is used to decode every value, and once a
+            /// value fails to decode the iterator is exhausted.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use musli::{Decode, Encode};
+            #[doc = concat!("use musli::", stringify!($what), "::Encoding;")]
+            #[doc = concat!("# use musli::", stringify!($what), "::Error;")]
+            ///
+            /// const ENCODING: Encoding = Encoding::new();
+            ///
+            /// #[derive(Decode, Encode)]
+            $(#[doc = concat!(" #[musli(name_type = ", stringify!($name_type), ")]")])?
+            /// struct Person {
+            $(#[doc = concat!("     #[musli(name = ", stringify!($name), ")]")])?
+            ///     name: String,
+            $(#[doc = concat!("     #[musli(name = ", stringify!($age), ")]")])?
+            ///     age: u32,
+            /// }
+            ///
+            /// let mut data = Vec::new();
+            ///
+            /// for (name, age) in [("Aristotle", 61), ("Plato", 80)] {
+            ///     ENCODING.encode(&mut data, &Person { name: name.to_string(), age })?;
+            /// }
+            ///
+            /// let people = ENCODING.decode_iter::<Person>(&data).collect::<Result<Vec<_>, _>>()?;
+            /// assert_eq!(people.len(), 2);
+            /// assert_eq!(people[1].name, "Plato");
+            /// assert_eq!(people[1].age, 80);
+            /// # Ok::<(), Error>(())
+            /// ```
+            #[inline]
+            pub fn decode_iter<'de, T>(
+                self,
+                bytes: &'de [u8],
+            ) -> $crate::de::DecodeIter<'de, impl FnMut(&mut &'de [u8]) -> Result<T, Error>>
+            where
+                T: $crate::Decode<'de, $mode>,
+            {
+                $crate::de::DecodeIter {
+                    bytes,
+                    len: bytes.len(),
+                    offset: 0,
+                    done: false,
+                    decode: move |bytes: &mut &'de [u8]| {
+                        $crate::alloc::default!(|alloc| {
+                            let cx = $crate::context::Same::<$mode, Error, _>::with_alloc(alloc);
+                            $crate::de::decode_next(&cx, bytes, |reader| {
+                                T::decode(&cx, $decoder_new(&cx, $reader_trait::$into_reader(reader)))
+                            })
+                        })
+                    },
+                }
+            }
+        }
+
         /// Encode the given value to the given [`Writer`] using the current
         /// [`Encoding`].
         ///
@@ -529,8 +734,11 @@ macro_rules! encoding_impls {
         /// const ENCODING: Encoding = Encoding::new();
         ///
         /// #[derive(Decode, Encode)]
+        $(#[doc = concat!(" #[musli(name_type = ", stringify!($name_type), ")]")])?
         /// struct Person {
+        $(#[doc = concat!("     #[musli(name = ", stringify!($name), ")]")])?
         ///     name: String,
+        $(#[doc = concat!("     #[musli(name = ", stringify!($age), ")]")])?
         ///     age: u32,
         /// }
         ///
@@ -579,8 +787,11 @@ macro_rules! encoding_impls {
         /// const ENCODING: Encoding = Encoding::new();
         ///
         /// #[derive(Decode, Encode)]
+        $(#[doc = concat!(" #[musli(name_type = ", stringify!($name_type), ")]")])?
         /// struct Person {
+        $(#[doc = concat!("     #[musli(name = ", stringify!($name), ")]")])?
         ///     name: String,
+        $(#[doc = concat!("     #[musli(name = ", stringify!($age), ")]")])?
         ///     age: u32,
         /// }
         ///
@@ -628,8 +839,11 @@ macro_rules! encoding_impls {
         /// const ENCODING: Encoding = Encoding::new();
         ///
         /// #[derive(Decode, Encode)]
+        $(#[doc = concat!(" #[musli(name_type = ", stringify!($name_type), ")]")])?
         /// struct Person {
+        $(#[doc = concat!("     #[musli(name = ", stringify!($name), ")]")])?
         ///     name: String,
+        $(#[doc = concat!("     #[musli(name = ", stringify!($age), ")]")])?
         ///     age: u32,
         /// }
         ///
@@ -677,8 +891,11 @@ macro_rules! encoding_impls {
         /// const ENCODING: Encoding = Encoding::new();
         ///
         /// #[derive(Decode, Encode)]
+        $(#[doc = concat!(" #[musli(name_type = ", stringify!($name_type), ")]")])?
         /// struct Person {
+        $(#[doc = concat!("     #[musli(name = ", stringify!($name), ")]")])?
         ///     name: String,
+        $(#[doc = concat!("     #[musli(name = ", stringify!($age), ")]")])?
         ///     age: u32,
         /// }
         ///
@@ -729,8 +946,11 @@ macro_rules! encoding_impls {
         /// const ENCODING: Encoding = Encoding::new();
         ///
         /// #[derive(Decode, Encode)]
+        $(#[doc = concat!(" #[musli(name_type = ", stringify!($name_type), ")]")])?
         /// struct Person {
+        $(#[doc = concat!("     #[musli(name = ", stringify!($name), ")]")])?
         ///     name: String,
+        $(#[doc = concat!("     #[musli(name = ", stringify!($age), ")]")])?
         ///     age: u32,
         /// }
         ///
@@ -779,8 +999,11 @@ macro_rules! encoding_impls {
         /// const ENCODING: Encoding = Encoding::new();
         ///
         /// #[derive(Decode, Encode)]
+        $(#[doc = concat!(" #[musli(name_type = ", stringify!($name_type), ")]")])?
         /// struct Person {
+        $(#[doc = concat!("     #[musli(name = ", stringify!($name), ")]")])?
         ///     name: String,
+        $(#[doc = concat!("     #[musli(name = ", stringify!($age), ")]")])?
         ///     age: u32,
         /// }
         ///
@@ -805,6 +1028,30 @@ macro_rules! encoding_impls {
             self.decode_with(cx, bytes)
         }
 
+        /// Decode the given type `T` from the given slice using the current
+        /// [`Encoding`], allocating any memory it needs from `alloc`.
+        ///
+        /// The decoded value is allowed to borrow the allocator, so this can be
+        /// used to decode collections like [`alloc::Vec`] and
+        /// [`alloc::String`] through [`DecodeIn`]. Together with the [`Slice`]
+        /// allocator, this makes it possible to decode variable-length data
+        /// without the `alloc` feature. See [`DecodeIn`] for an example.
+        ///
+        /// [`alloc::Vec`]: crate::alloc::Vec
+        /// [`alloc::String`]: crate::alloc::String
+        /// [`DecodeIn`]: crate::de::DecodeIn
+        /// [`Slice`]: crate::alloc::Slice
+        #[inline]
+        pub fn from_slice_in<'de, 'a, A, T>(self, alloc: &'a A, bytes: &'de [u8]) -> Result<T, Error>
+        where
+            A: ?Sized + $crate::alloc::Allocator,
+            T: $crate::de::DecodeIn<'de, 'a, $mode, A>,
+        {
+            let cx = $crate::context::Same::<$mode, Error, _>::with_alloc(alloc);
+            let reader = $reader_trait::$into_reader(bytes);
+            T::decode_in(&cx, $decoder_new(&cx, reader), alloc)
+        }
+
         /// Decode the given type `T` from the given string using the current
         /// [`Encoding`].
         ///
@@ -823,6 +1070,172 @@ macro_rules! encoding_impls {
         {
             self.from_slice_with(cx, string.as_bytes())
         }
+
+        $crate::macros::unless_to_end! {
+            [$($to_end)?],
+
+            /// Decode the given type `T` from the given slice using the current
+            /// [`Encoding`], raising an error if any bytes remain after the
+            /// value.
+            ///
+            /// This is the same as [`Encoding::from_slice_exact`] but allows
+            /// for using a configurable [`Context`].
+            ///
+            /// [`Context`]: crate::Context
+            #[inline]
+            pub fn from_slice_exact_with<'de, C, T>(self, cx: &C, bytes: &'de [u8]) -> Result<T, C::Error>
+            where
+                C: ?Sized + $crate::Context<Mode = $mode>,
+                T: $crate::Decode<'de, $mode>,
+            {
+                cx.clear();
+
+                let mut rest = bytes;
+                let reader = $reader_trait::$into_reader(&mut rest);
+                let value = T::decode(cx, $decoder_new(cx, reader))?;
+                $($trailing(cx, &mut rest);)?
+
+                if !rest.is_empty() {
+                    let mark = cx.mark();
+                    let (len, unit) = match rest.len() {
+                        1 => (1, "byte"),
+                        len => (len, "bytes"),
+                    };
+
+                    return Err(cx.marked_message(
+                        mark,
+                        format_args!("Expected end of input, but found {len} trailing {unit}"),
+                    ));
+                }
+
+                Ok(value)
+            }
+        }
+
+        $crate::macros::unless_to_end! {
+            [$($to_end)?],
+
+            /// Decode values of type `T` which have been concatenated in the
+            /// given slice using the current [`Encoding`], until the slice is
+            /// exhausted.
+            ///
+            /// This is the same as [`Encoding::decode_iter`] but allows for
+            /// using a configurable [`Context`].
+            ///
+            /// The context is cleared once before decoding the first value.
+            /// Contexts which track the position of errors, like
+            /// [`DefaultContext`], therefore report them as offsets into the
+            /// whole slice.
+            ///
+            /// [`Context`]: crate::Context
+            /// [`DefaultContext`]: crate::context::DefaultContext
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use musli::{Decode, Encode};
+            /// use musli::context;
+            #[doc = concat!("use musli::", stringify!($what), "::Encoding;")]
+            #[doc = concat!("# use musli::", stringify!($what), "::Error;")]
+            ///
+            /// const ENCODING: Encoding = Encoding::new();
+            ///
+            /// #[derive(Decode, Encode)]
+            $(#[doc = concat!(" #[musli(name_type = ", stringify!($name_type), ")]")])?
+            /// struct Person {
+            $(#[doc = concat!("     #[musli(name = ", stringify!($name), ")]")])?
+            ///     name: String,
+            $(#[doc = concat!("     #[musli(name = ", stringify!($age), ")]")])?
+            ///     age: u32,
+            /// }
+            ///
+            /// let mut data = Vec::new();
+            ///
+            /// for (name, age) in [("Aristotle", 61), ("Plato", 80)] {
+            ///     ENCODING.encode(&mut data, &Person { name: name.to_string(), age })?;
+            /// }
+            ///
+            /// let cx = context::new();
+            /// let mut iter = ENCODING.decode_iter_with::<_, Person>(&cx, &data);
+            ///
+            /// while let Some(person) = iter.next() {
+            ///     let Ok(person) = person else {
+            ///         panic!("Failed to decode value at byte {}: {}", iter.offset(), cx.report());
+            ///     };
+            ///
+            ///     assert!(person.age > 60);
+            /// }
+            /// # Ok::<(), Error>(())
+            /// ```
+            #[inline]
+            pub fn decode_iter_with<'a, 'de, C, T>(
+                self,
+                cx: &'a C,
+                bytes: &'de [u8],
+            ) -> $crate::de::DecodeIter<'de, impl FnMut(&mut &'de [u8]) -> Result<T, C::Error> + 'a>
+            where
+                C: ?Sized + $crate::Context<Mode = $mode>,
+                T: $crate::Decode<'de, $mode>,
+            {
+                cx.clear();
+
+                $crate::de::DecodeIter {
+                    bytes,
+                    len: bytes.len(),
+                    offset: 0,
+                    done: false,
+                    decode: move |bytes: &mut &'de [u8]| {
+                        $crate::de::decode_next(cx, bytes, |reader| {
+                            T::decode(cx, $decoder_new(cx, $reader_trait::$into_reader(reader)))
+                        })
+                    },
+                }
+            }
+        }
+
+        /// Construct an [`Encoder`] writing to the given [`Writer`] using the
+        /// current [`Encoding`] and context `C`.
+        ///
+        /// This is primarily useful in combination with [`transcode`].
+        ///
+        /// [`Encoder`]: crate::Encoder
+        /// [`Writer`]: crate::Writer
+        /// [`transcode`]: crate::transcode
+        #[inline]
+        pub fn encoder_with<'a, C, W>(
+            self,
+            cx: &'a C,
+            writer: W,
+        ) -> impl $crate::Encoder<Cx = C, Ok = (), Error = C::Error, Mode = C::Mode> + 'a
+        where
+            C: ?Sized + $crate::Context<Mode = $mode>,
+            W: 'a + $crate::Writer,
+        {
+            $encoder_new(cx, writer)
+        }
+
+        /// Construct a [`Decoder`] reading from the given [`Reader`] using the
+        /// current [`Encoding`] and context `C`.
+        ///
+        /// This is primarily useful in combination with [`transcode`].
+        ///
+        /// [`Decoder`]: crate::Decoder
+        /// [`Reader`]: crate::Reader
+        /// [`transcode`]: crate::transcode
+        #[inline]
+        pub fn decoder_with<'a, 'de, C, R>(
+            self,
+            cx: &'a C,
+            reader: R,
+        ) -> impl $crate::Decoder<'de, Cx = C, Error = C::Error, Mode = C::Mode> + 'a
+        where
+            C: ?Sized + $crate::Context<Mode = $mode>,
+            R: $reader_trait<'de>,
+            R::$reader_assoc: 'a,
+        {
+            let reader = $reader_trait::$into_reader(reader);
+            $decoder_new(cx, reader)
+        }
     };
 }
 
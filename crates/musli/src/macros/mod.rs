@@ -13,7 +13,30 @@ mod internal;
     feature = "descriptive",
     feature = "value"
 ))]
-pub(crate) use self::internal::{bare_encoding, encoding_impls};
+pub(crate) use self::internal::{bare_encoding, doc_select, encoding_impls, unless_to_end};
+
+#[cfg(any(
+    feature = "cbor",
+    feature = "msgpack",
+    feature = "protobuf",
+    feature = "csv",
+    feature = "urlencoded",
+    feature = "env",
+    feature = "toml",
+    feature = "framing"
+))]
+mod error;
+#[cfg(any(
+    feature = "cbor",
+    feature = "msgpack",
+    feature = "protobuf",
+    feature = "csv",
+    feature = "urlencoded",
+    feature = "env",
+    feature = "toml",
+    feature = "framing"
+))]
+pub(crate) use self::error::error_type;
 
 #[cfg(all(
     feature = "test",
@@ -191,6 +191,63 @@ macro_rules! test_fns {
                 }
             })
         }
+
+        /// Assert that the given value encodes into the expected bytes.
+        #[doc(hidden)]
+        #[track_caller]
+        pub fn encodes<T>(value: T, expected: &[u8])
+        where
+            T: $crate::en::Encode<$crate::mode::$mode>,
+            T: ::core::fmt::Debug,
+        {
+            const WHAT: &str = $what;
+
+            let out = to_vec::<_, $crate::mode::$mode>(&value);
+            assert_eq!(out, expected, "{WHAT}: encoding does not match\nValue: {value:?}");
+        }
+
+        /// Assert that the given bytes decode into the expected value.
+        #[doc(hidden)]
+        #[track_caller]
+        pub fn decodes<T>(bytes: &[u8], expected: T)
+        where
+            T: $crate::de::DecodeOwned<$crate::mode::$mode>,
+            T: ::core::fmt::Debug + ::core::cmp::PartialEq,
+        {
+            const WHAT: &str = $what;
+
+            let encoding = super::Encoding::new().with_mode::<$crate::mode::$mode>();
+
+            use ::core::any::type_name;
+
+            $crate::alloc::default!(|alloc| {
+                let mut cx = $crate::context::with_alloc(alloc);
+                cx.include_type();
+
+                let actual: T = match encoding.from_slice_with(&cx, bytes) {
+                    Ok(decoded) => decoded,
+                    Err(..) => {
+                        let error = cx.report();
+                        panic!("{WHAT}: {}: failed to decode:\nBytes: {bytes:02x?}\n{error}", type_name::<T>())
+                    }
+                };
+
+                assert_eq!(actual, expected, "{WHAT}: decoded value does not match expected\nBytes: {bytes:02x?}");
+            })
+        }
+
+        /// Assert that the given value encodes into the expected bytes, and
+        /// that they decode back into the same value.
+        #[doc(hidden)]
+        #[track_caller]
+        pub fn roundtrips<T>(value: T, expected: &[u8])
+        where
+            T: $crate::en::Encode<$crate::mode::$mode> + $crate::de::DecodeOwned<$crate::mode::$mode>,
+            T: ::core::fmt::Debug + ::core::cmp::PartialEq,
+        {
+            encodes(&value, expected);
+            decodes(expected, value);
+        }
     }
 }
 
@@ -386,6 +443,8 @@ macro_rules! __test_matrix {
         $call!(storage, Binary);
         $call!(wire, Binary);
         $call!(descriptive, Binary);
+        $call!(cbor, Binary);
+        $call!(msgpack, Binary);
         $call!(json, Text);
     };
 
@@ -393,6 +452,8 @@ macro_rules! __test_matrix {
         $call!(storage, Text);
         $call!(wire, Text);
         $call!(descriptive, Text);
+        $call!(cbor, Text);
+        $call!(msgpack, Text);
         $call!(json, Text);
     };
 
@@ -400,6 +461,8 @@ macro_rules! __test_matrix {
         $call!(storage, Binary);
         $call!(wire, Binary);
         $call!(descriptive, Binary);
+        $call!(cbor, Binary);
+        $call!(msgpack, Binary);
         $call!(json, Binary);
     };
 
@@ -407,10 +470,13 @@ macro_rules! __test_matrix {
         $call!(storage, Binary);
         $call!(wire, Binary);
         $call!(descriptive, Binary);
+        $call!(cbor, Binary);
+        $call!(msgpack, Binary);
     };
 
     (descriptive, $call:path) => {
         $call!(descriptive, Binary);
+        $call!(cbor, Binary);
         $call!(json, Text);
     };
 
@@ -421,6 +487,8 @@ macro_rules! __test_matrix {
     (upgrade_stable, $call:path) => {
         $call!(wire, Binary);
         $call!(descriptive, Binary);
+        $call!(cbor, Binary);
+        $call!(msgpack, Binary);
         $call!(json, Text);
     };
 }
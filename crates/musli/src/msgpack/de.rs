@@ -0,0 +1,1071 @@
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use rust_alloc::vec::Vec;
+
+use crate::de::{
+    Decode, DecodeUnsized, Decoder, EntriesDecoder, EntryDecoder, MapDecoder, SequenceDecoder,
+    SizeHint, Skip, UnsizedVisitor, VariantDecoder, Visitor,
+};
+#[cfg(feature = "value")]
+use crate::options;
+use crate::Context;
+use crate::{Options, Reader};
+
+use super::marker::{
+    Kind, Marker, ARRAY16, ARRAY32, BIN16, BIN32, BIN8, EXT16, EXT32, EXT8, FALSE, FIXEXT1,
+    FIXEXT16, FIXEXT2, FIXEXT4, FIXEXT8, FLOAT32, FLOAT64, INT16, INT32, INT64, INT8, MAP16, MAP32,
+    NEGATIVE_FIXINT_MIN, NIL, POSITIVE_FIXINT_MAX, STR16, STR32, STR8, TRUE, UINT16, UINT32,
+    UINT64, UINT8,
+};
+
+#[cfg(feature = "value")]
+const BUFFER_OPTIONS: Options = options::new().build();
+
+/// A decoded integer, before it has been converted into its final type.
+#[derive(Clone, Copy)]
+enum Integer {
+    /// An integer stored with an unsigned marker.
+    Unsigned(u64),
+    /// An integer stored with a signed marker.
+    Signed(i64),
+}
+
+/// A MessagePack decoder.
+pub struct MsgPackDecoder<'a, R, const OPT: Options, C: ?Sized> {
+    cx: &'a C,
+    reader: R,
+}
+
+impl<'a, R, const OPT: Options, C: ?Sized> MsgPackDecoder<'a, R, OPT, C> {
+    /// Construct a new MessagePack decoder.
+    #[inline]
+    pub(crate) fn new(cx: &'a C, reader: R) -> Self {
+        Self { cx, reader }
+    }
+}
+
+impl<'a, 'de, R, const OPT: Options, C> MsgPackDecoder<'a, R, OPT, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    #[inline]
+    fn read_u8(&mut self) -> Result<u8, C::Error> {
+        self.reader.read_byte(self.cx)
+    }
+
+    #[inline]
+    fn read_u16(&mut self) -> Result<u16, C::Error> {
+        Ok(u16::from_be_bytes(self.reader.read_array(self.cx)?))
+    }
+
+    #[inline]
+    fn read_u32(&mut self) -> Result<u32, C::Error> {
+        Ok(u32::from_be_bytes(self.reader.read_array(self.cx)?))
+    }
+
+    #[inline]
+    fn read_len32(&mut self) -> Result<usize, C::Error> {
+        let len = self.read_u32()?;
+
+        match usize::try_from(len) {
+            Ok(len) => Ok(len),
+            Err(..) => Err(self.cx.message(format_args!("Length {len} out of bounds"))),
+        }
+    }
+
+    /// Skip over a single value, including any values nested in it.
+    fn skip_item(&mut self) -> Result<(), C::Error> {
+        let mut remaining = 1usize;
+
+        while remaining > 0 {
+            remaining -= 1;
+
+            let pos = self.cx.mark();
+            let marker = self.read_u8()?;
+
+            let (skip, items) = match marker {
+                0x00..=POSITIVE_FIXINT_MAX | NEGATIVE_FIXINT_MIN..=0xff | NIL | FALSE | TRUE => {
+                    (0, 0)
+                }
+                0x80..=0x8f => (0, usize::from(marker & 0x0f) * 2),
+                0x90..=0x9f => (0, usize::from(marker & 0x0f)),
+                0xa0..=0xbf => (usize::from(marker & 0x1f), 0),
+                UINT8 | INT8 => (1, 0),
+                UINT16 | INT16 => (2, 0),
+                UINT32 | INT32 | FLOAT32 => (4, 0),
+                UINT64 | INT64 | FLOAT64 => (8, 0),
+                BIN8 | STR8 => (usize::from(self.read_u8()?), 0),
+                BIN16 | STR16 => (usize::from(self.read_u16()?), 0),
+                BIN32 | STR32 => (self.read_len32()?, 0),
+                ARRAY16 => (0, usize::from(self.read_u16()?)),
+                ARRAY32 => (0, self.read_len32()?),
+                MAP16 => (0, usize::from(self.read_u16()?) * 2),
+                MAP32 => (0, self.read_len32()?.saturating_mul(2)),
+                _ => match self.decode_ext_header(marker)? {
+                    Some((_, len)) => (len, 0),
+                    None => {
+                        return Err(self.cx.marked_message(
+                            pos,
+                            format_args!("Cannot skip over {:?}", Marker(marker)),
+                        ));
+                    }
+                },
+            };
+
+            if skip > 0 {
+                self.reader.skip(self.cx, skip)?;
+            }
+
+            let Some(next) = remaining.checked_add(items) else {
+                return Err(self.cx.marked_message(pos, "Too many items to skip"));
+            };
+
+            remaining = next;
+        }
+
+        Ok(())
+    }
+
+    /// Decode the length of a value of the given kind from an already read
+    /// marker.
+    fn decode_len(&mut self, kind: Kind, marker: u8, mark: C::Mark) -> Result<usize, C::Error> {
+        let (fixed, m8, m16, m32) = kind.markers();
+
+        if let Some((base, max)) = fixed {
+            if marker & !(max as u8) == base {
+                return Ok(usize::from(marker & max as u8));
+            }
+        }
+
+        if Some(marker) == m8 {
+            return Ok(usize::from(self.read_u8()?));
+        }
+
+        if marker == m16 {
+            return Ok(usize::from(self.read_u16()?));
+        }
+
+        if marker == m32 {
+            return self.read_len32();
+        }
+
+        Err(self.cx.marked_message(
+            mark,
+            Expected {
+                expected: kind,
+                actual: Marker(marker),
+            },
+        ))
+    }
+
+    /// Read a marker and decode the length of a value of the given kind.
+    #[inline]
+    fn decode_prefix(&mut self, kind: Kind, mark: C::Mark) -> Result<usize, C::Error> {
+        let marker = self.read_u8()?;
+        self.decode_len(kind, marker, mark)
+    }
+
+    /// Decode the type and payload length of an extension value if the marker
+    /// refers to one.
+    fn decode_ext_header(&mut self, marker: u8) -> Result<Option<(i8, usize)>, C::Error> {
+        let len = match marker {
+            FIXEXT1 => 1,
+            FIXEXT2 => 2,
+            FIXEXT4 => 4,
+            FIXEXT8 => 8,
+            FIXEXT16 => 16,
+            EXT8 => usize::from(self.read_u8()?),
+            EXT16 => usize::from(self.read_u16()?),
+            EXT32 => self.read_len32()?,
+            _ => return Ok(None),
+        };
+
+        let ty = self.read_u8()? as i8;
+        Ok(Some((ty, len)))
+    }
+
+    /// Decode the payload length of a binary or extension value.
+    #[inline]
+    fn decode_bytes_len(&mut self, mark: C::Mark) -> Result<usize, C::Error> {
+        let marker = self.read_u8()?;
+
+        if let Some((_, len)) = self.decode_ext_header(marker)? {
+            return Ok(len);
+        }
+
+        self.decode_len(Kind::Bytes, marker, mark)
+    }
+
+    // Standard function for decoding a pair sequence.
+    #[inline]
+    fn shared_decode_map(mut self) -> Result<RemainingMsgPackDecoder<'a, R, OPT, C>, C::Error> {
+        let pos = self.cx.mark();
+        let len = self.decode_prefix(Kind::Map, pos)?;
+        self.cx.check_length(len)?;
+        Ok(RemainingMsgPackDecoder::new(self.cx, self.reader, len))
+    }
+
+    // Standard function for decoding a sequence.
+    #[inline]
+    fn shared_decode_sequence(
+        mut self,
+    ) -> Result<RemainingMsgPackDecoder<'a, R, OPT, C>, C::Error> {
+        let pos = self.cx.mark();
+        let len = self.decode_prefix(Kind::Array, pos)?;
+        self.cx.check_length(len)?;
+        Ok(RemainingMsgPackDecoder::new(self.cx, self.reader, len))
+    }
+
+    /// Decode an integer stored with any of the integer markers.
+    #[inline]
+    fn decode_integer(&mut self) -> Result<Integer, C::Error> {
+        let pos = self.cx.mark();
+        let marker = self.read_u8()?;
+
+        let integer = match marker {
+            0x00..=POSITIVE_FIXINT_MAX => Integer::Unsigned(marker.into()),
+            NEGATIVE_FIXINT_MIN..=0xff => Integer::Signed((marker as i8).into()),
+            UINT8 => Integer::Unsigned(self.read_u8()?.into()),
+            UINT16 => Integer::Unsigned(self.read_u16()?.into()),
+            UINT32 => Integer::Unsigned(self.read_u32()?.into()),
+            UINT64 => Integer::Unsigned(u64::from_be_bytes(self.reader.read_array(self.cx)?)),
+            INT8 => Integer::Signed((self.read_u8()? as i8).into()),
+            INT16 => Integer::Signed(i16::from_be_bytes(self.reader.read_array(self.cx)?).into()),
+            INT32 => Integer::Signed(i32::from_be_bytes(self.reader.read_array(self.cx)?).into()),
+            INT64 => Integer::Signed(i64::from_be_bytes(self.reader.read_array(self.cx)?)),
+            marker => {
+                return Err(self.cx.marked_message(
+                    pos,
+                    format_args!("Expected integer, but got {:?}", Marker(marker)),
+                ));
+            }
+        };
+
+        Ok(integer)
+    }
+
+    /// Decode the bytes of a 128-bit integer if it's stored as 16 big-endian
+    /// bytes, which is interpreted according to the type being decoded.
+    #[inline]
+    fn decode_wide(&mut self) -> Result<Option<[u8; 16]>, C::Error> {
+        if !matches!(self.reader.peek(), Some(BIN8 | BIN16 | BIN32)) {
+            return Ok(None);
+        }
+
+        let pos = self.cx.mark();
+        let len = self.decode_prefix(Kind::Bytes, pos)?;
+
+        if len != 16 {
+            return Err(self.cx.marked_message(
+                pos,
+                format_args!("Expected 16 bytes for 128-bit integer, but got {len}"),
+            ));
+        }
+
+        Ok(Some(self.reader.read_array(self.cx)?))
+    }
+
+    #[inline]
+    fn decode_unsigned<T>(mut self) -> Result<T, C::Error>
+    where
+        T: TryFrom<u128>,
+    {
+        let pos = self.cx.mark();
+
+        let value = match self.decode_wide()? {
+            Some(bytes) => Some(u128::from_be_bytes(bytes)),
+            None => match self.decode_integer()? {
+                Integer::Unsigned(value) => Some(value.into()),
+                Integer::Signed(value) => u128::try_from(value).ok(),
+            },
+        };
+
+        let Some(value) = value.and_then(|value| T::try_from(value).ok()) else {
+            return Err(self.cx.marked_message(pos, "Integer out of range"));
+        };
+
+        Ok(value)
+    }
+
+    #[inline]
+    fn decode_signed<T>(mut self) -> Result<T, C::Error>
+    where
+        T: TryFrom<i128>,
+    {
+        let pos = self.cx.mark();
+
+        let value = match self.decode_wide()? {
+            Some(bytes) => i128::from_be_bytes(bytes),
+            None => match self.decode_integer()? {
+                Integer::Unsigned(value) => value.into(),
+                Integer::Signed(value) => value.into(),
+            },
+        };
+
+        let Ok(value) = T::try_from(value) else {
+            return Err(self.cx.marked_message(pos, "Integer out of range"));
+        };
+
+        Ok(value)
+    }
+}
+
+/// A length-prefixed decode wrapper.
+#[doc(hidden)]
+pub struct RemainingMsgPackDecoder<'a, R, const OPT: Options, C: ?Sized> {
+    cx: &'a C,
+    reader: R,
+    remaining: usize,
+}
+
+impl<'a, 'de, R, const OPT: Options, C> RemainingMsgPackDecoder<'a, R, OPT, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    #[inline]
+    fn new(cx: &'a C, reader: R, remaining: usize) -> Self {
+        Self {
+            cx,
+            reader,
+            remaining,
+        }
+    }
+
+    #[inline]
+    fn skip_sequence_remaining(mut self) -> Result<(), C::Error> {
+        while let Some(item) = self.try_decode_next()? {
+            item.skip()?;
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn skip_map_remaining(mut self) -> Result<(), C::Error> {
+        loop {
+            let Some(key) = self.decode_entry_key()? else {
+                break;
+            };
+
+            key.skip()?;
+            self.decode_entry_value()?.skip()?;
+        }
+
+        Ok(())
+    }
+}
+
+#[crate::decoder(crate)]
+impl<'a, 'de, R, const OPT: Options, C> Decoder<'de> for MsgPackDecoder<'a, R, OPT, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = C::Mode;
+    type WithContext<'this, U> = MsgPackDecoder<'this, R, OPT, U> where U: 'this + Context;
+    #[cfg(feature = "value")]
+    type DecodeBuffer = crate::value::AsValueDecoder<'a, BUFFER_OPTIONS, C>;
+    type DecodePack = RemainingMsgPackDecoder<'a, R, OPT, C>;
+    type DecodeSome = Self;
+    type DecodeSequence = RemainingMsgPackDecoder<'a, R, OPT, C>;
+    type DecodeMap = RemainingMsgPackDecoder<'a, R, OPT, C>;
+    type DecodeMapEntries = RemainingMsgPackDecoder<'a, R, OPT, C>;
+    type DecodeVariant = Self;
+
+    #[inline]
+    fn cx(&self) -> &C {
+        self.cx
+    }
+
+    #[inline]
+    fn with_context<U>(self, cx: &U) -> Result<Self::WithContext<'_, U>, C::Error>
+    where
+        U: Context,
+    {
+        Ok(MsgPackDecoder::new(cx, self.reader))
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "type supported by the MessagePack decoder")
+    }
+
+    #[inline]
+    fn decode<T>(self) -> Result<T, Self::Error>
+    where
+        T: Decode<'de, Self::Mode>,
+    {
+        self.cx.decode(self)
+    }
+
+    #[inline]
+    fn decode_unsized<T, F, O>(self, f: F) -> Result<O, Self::Error>
+    where
+        T: ?Sized + DecodeUnsized<'de, Self::Mode>,
+        F: FnOnce(&T) -> Result<O, Self::Error>,
+    {
+        self.cx.decode_unsized(self, f)
+    }
+
+    #[inline]
+    fn skip(mut self) -> Result<(), C::Error> {
+        self.skip_item()
+    }
+
+    #[inline]
+    fn try_skip(self) -> Result<Skip, C::Error> {
+        self.skip()?;
+        Ok(Skip::Skipped)
+    }
+
+    #[cfg(feature = "value")]
+    #[inline]
+    fn decode_buffer(self) -> Result<Self::DecodeBuffer, C::Error> {
+        let cx = self.cx;
+        let value = self.decode::<crate::value::Value>()?;
+        Ok(value.into_value_decoder(cx))
+    }
+
+    #[inline]
+    fn decode_empty(self) -> Result<(), C::Error> {
+        self.skip()
+    }
+
+    #[inline]
+    fn decode_pack<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodePack) -> Result<O, C::Error>,
+    {
+        let cx = self.cx;
+        cx.enter_nested()?;
+        let mut decoder = self.shared_decode_sequence()?;
+        let output = f(&mut decoder)?;
+        decoder.skip_sequence_remaining()?;
+        cx.leave_nested();
+        Ok(output)
+    }
+
+    #[inline]
+    fn decode_array<const N: usize>(mut self) -> Result<[u8; N], C::Error> {
+        let pos = self.cx.mark();
+        let len = self.decode_bytes_len(pos)?;
+
+        if len != N {
+            return Err(self.cx.marked_message(
+                pos,
+                format_args! {
+                    "Bad length, got {len} but expect {N}"
+                },
+            ));
+        }
+
+        self.reader.read_array(self.cx)
+    }
+
+    #[inline]
+    fn decode_bytes<V>(mut self, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: UnsizedVisitor<'de, C, [u8]>,
+    {
+        let pos = self.cx.mark();
+        let len = self.decode_bytes_len(pos)?;
+        self.cx.check_size(len)?;
+        self.reader.read_bytes(self.cx, len, visitor)
+    }
+
+    #[inline]
+    fn decode_extension<V>(mut self, code: i64, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: UnsizedVisitor<'de, C, [u8]>,
+    {
+        let pos = self.cx.mark();
+        let marker = self.read_u8()?;
+
+        let Some((ty, len)) = self.decode_ext_header(marker)? else {
+            return Err(self.cx.marked_message(
+                pos,
+                format_args!("Expected extension type {code}, but got {:?}", Marker(marker)),
+            ));
+        };
+
+        if i64::from(ty) != code {
+            return Err(self.cx.marked_message(
+                pos,
+                format_args!("Expected extension type {code}, but got extension type {ty}"),
+            ));
+        }
+
+        self.cx.check_size(len)?;
+        self.reader.read_bytes(self.cx, len, visitor)
+    }
+
+    #[inline]
+    fn decode_string<V>(mut self, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: UnsizedVisitor<'de, C, str>,
+    {
+        struct Visitor<V>(V);
+
+        impl<'de, C, V> UnsizedVisitor<'de, C, [u8]> for Visitor<V>
+        where
+            C: ?Sized + Context,
+            V: UnsizedVisitor<'de, C, str>,
+        {
+            type Ok = V::Ok;
+
+            #[inline]
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.expecting(f)
+            }
+
+            #[cfg(feature = "alloc")]
+            #[inline]
+            fn visit_owned(self, cx: &C, bytes: Vec<u8>) -> Result<Self::Ok, C::Error> {
+                let string =
+                    crate::str::from_utf8_owned(bytes).map_err(crate::str::map_utf8_error(cx))?;
+                self.0.visit_owned(cx, string)
+            }
+
+            #[inline]
+            fn visit_borrowed(self, cx: &C, bytes: &'de [u8]) -> Result<Self::Ok, C::Error> {
+                let string =
+                    crate::str::from_utf8(bytes).map_err(crate::str::map_utf8_error(cx))?;
+                self.0.visit_borrowed(cx, string)
+            }
+
+            #[inline]
+            fn visit_ref(self, cx: &C, bytes: &[u8]) -> Result<Self::Ok, C::Error> {
+                let string =
+                    crate::str::from_utf8(bytes).map_err(crate::str::map_utf8_error(cx))?;
+                self.0.visit_ref(cx, string)
+            }
+        }
+
+        let pos = self.cx.mark();
+        let len = self.decode_prefix(Kind::String, pos)?;
+        self.cx.check_size(len)?;
+        self.reader.read_bytes(self.cx, len, Visitor(visitor))
+    }
+
+    #[inline]
+    fn decode_bool(mut self) -> Result<bool, C::Error> {
+        let pos = self.cx.mark();
+
+        match self.read_u8()? {
+            FALSE => Ok(false),
+            TRUE => Ok(true),
+            marker => Err(self.cx.marked_message(
+                pos,
+                format_args! {
+                    "Expected boolean, got {:?}", Marker(marker)
+                },
+            )),
+        }
+    }
+
+    #[inline]
+    fn decode_char(mut self) -> Result<char, C::Error> {
+        struct Visitor;
+
+        impl<C> UnsizedVisitor<'_, C, str> for Visitor
+        where
+            C: ?Sized + Context,
+        {
+            type Ok = char;
+
+            #[inline]
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "string with a single character")
+            }
+
+            #[inline]
+            fn visit_ref(self, cx: &C, string: &str) -> Result<Self::Ok, C::Error> {
+                let mut it = string.chars();
+
+                match (it.next(), it.next()) {
+                    (Some(c), None) => Ok(c),
+                    _ => Err(cx.message("Expected string with a single character")),
+                }
+            }
+        }
+
+        let cx = self.cx;
+        let pos = cx.mark();
+
+        // Permit decoding characters from their numerical code point as well.
+        if matches!(
+            self.reader.peek(),
+            Some(0x00..=POSITIVE_FIXINT_MAX | UINT8 | UINT16 | UINT32 | UINT64)
+        ) {
+            let Some(c) = char::from_u32(self.decode_u32()?) else {
+                return Err(cx.marked_message(pos, "Bad character"));
+            };
+
+            return Ok(c);
+        }
+
+        self.decode_string(Visitor)
+    }
+
+    #[inline]
+    fn decode_number<V>(mut self, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: Visitor<'de, C>,
+    {
+        let cx = self.cx;
+
+        match self.reader.peek() {
+            Some(FLOAT32) => {
+                let value = self.decode_f32()?;
+                visitor.visit_f32(cx, value)
+            }
+            Some(FLOAT64) => {
+                let value = self.decode_f64()?;
+                visitor.visit_f64(cx, value)
+            }
+            _ => match self.decode_integer()? {
+                Integer::Unsigned(value) => visitor.visit_u64(cx, value),
+                Integer::Signed(value) => visitor.visit_i64(cx, value),
+            },
+        }
+    }
+
+    #[inline]
+    fn decode_u8(self) -> Result<u8, C::Error> {
+        self.decode_unsigned()
+    }
+
+    #[inline]
+    fn decode_u16(self) -> Result<u16, C::Error> {
+        self.decode_unsigned()
+    }
+
+    #[inline]
+    fn decode_u32(self) -> Result<u32, C::Error> {
+        self.decode_unsigned()
+    }
+
+    #[inline]
+    fn decode_u64(self) -> Result<u64, C::Error> {
+        self.decode_unsigned()
+    }
+
+    #[inline]
+    fn decode_u128(self) -> Result<u128, C::Error> {
+        self.decode_unsigned()
+    }
+
+    #[inline]
+    fn decode_i8(self) -> Result<i8, C::Error> {
+        self.decode_signed()
+    }
+
+    #[inline]
+    fn decode_i16(self) -> Result<i16, C::Error> {
+        self.decode_signed()
+    }
+
+    #[inline]
+    fn decode_i32(self) -> Result<i32, C::Error> {
+        self.decode_signed()
+    }
+
+    #[inline]
+    fn decode_i64(self) -> Result<i64, C::Error> {
+        self.decode_signed()
+    }
+
+    #[inline]
+    fn decode_i128(self) -> Result<i128, C::Error> {
+        self.decode_signed()
+    }
+
+    #[inline]
+    fn decode_usize(self) -> Result<usize, C::Error> {
+        self.decode_unsigned()
+    }
+
+    #[inline]
+    fn decode_isize(self) -> Result<isize, C::Error> {
+        self.decode_signed()
+    }
+
+    /// Decode a 32-bit floating point value, which truncates double-precision
+    /// floats.
+    #[inline]
+    fn decode_f32(mut self) -> Result<f32, C::Error> {
+        let pos = self.cx.mark();
+
+        match self.read_u8()? {
+            FLOAT32 => Ok(f32::from_be_bytes(self.reader.read_array(self.cx)?)),
+            FLOAT64 => Ok(f64::from_be_bytes(self.reader.read_array(self.cx)?) as f32),
+            marker => Err(self.cx.marked_message(
+                pos,
+                format_args!("Expected float, got {:?}", Marker(marker)),
+            )),
+        }
+    }
+
+    /// Decode a 64-bit floating point value, which also accepts
+    /// single-precision floats.
+    #[inline]
+    fn decode_f64(mut self) -> Result<f64, C::Error> {
+        let pos = self.cx.mark();
+
+        match self.read_u8()? {
+            FLOAT32 => Ok(f32::from_be_bytes(self.reader.read_array(self.cx)?).into()),
+            FLOAT64 => Ok(f64::from_be_bytes(self.reader.read_array(self.cx)?)),
+            marker => Err(self.cx.marked_message(
+                pos,
+                format_args!("Expected float, got {:?}", Marker(marker)),
+            )),
+        }
+    }
+
+    #[inline]
+    fn decode_option(mut self) -> Result<Option<Self::DecodeSome>, C::Error> {
+        // Options are encoded as nil, or the value itself.
+        if self.reader.peek() == Some(NIL) {
+            self.reader.skip(self.cx, 1)?;
+            return Ok(None);
+        }
+
+        Ok(Some(self))
+    }
+
+    #[inline]
+    fn decode_sequence<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeSequence) -> Result<O, C::Error>,
+    {
+        let cx = self.cx;
+        cx.enter_nested()?;
+        let mut decoder = self.shared_decode_sequence()?;
+        let output = f(&mut decoder)?;
+        decoder.skip_sequence_remaining()?;
+        cx.leave_nested();
+        Ok(output)
+    }
+
+    #[inline]
+    fn decode_map<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeMap) -> Result<O, C::Error>,
+    {
+        let cx = self.cx;
+        cx.enter_nested()?;
+        let mut decoder = self.shared_decode_map()?;
+        let output = f(&mut decoder)?;
+        decoder.skip_map_remaining()?;
+        cx.leave_nested();
+        Ok(output)
+    }
+
+    #[inline]
+    fn decode_map_entries<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeMapEntries) -> Result<O, C::Error>,
+    {
+        let cx = self.cx;
+        cx.enter_nested()?;
+        let mut decoder = self.shared_decode_map()?;
+        let output = f(&mut decoder)?;
+        decoder.skip_map_remaining()?;
+        cx.leave_nested();
+        Ok(output)
+    }
+
+    #[inline]
+    fn decode_variant<F, O>(mut self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeVariant) -> Result<O, C::Error>,
+    {
+        // Variants are encoded as maps with a single entry.
+        let pos = self.cx.mark();
+
+        match self.decode_prefix(Kind::Map, pos)? {
+            1 => {
+                self.cx.enter_nested()?;
+                let output = f(&mut self)?;
+                self.cx.leave_nested();
+                Ok(output)
+            }
+            len => Err(self.cx.marked_message(
+                pos,
+                format_args!("Expected variant to contain a single entry, but got {len}"),
+            )),
+        }
+    }
+
+    #[inline]
+    fn decode_any<V>(mut self, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: Visitor<'de, C>,
+    {
+        let cx = self.cx;
+
+        let Some(marker) = self.reader.peek() else {
+            return Err(cx.message("Expected marker in input"));
+        };
+
+        match marker {
+            0x00..=POSITIVE_FIXINT_MAX => {
+                self.reader.skip(cx, 1)?;
+                visitor.visit_u8(cx, marker)
+            }
+            NEGATIVE_FIXINT_MIN..=0xff => {
+                self.reader.skip(cx, 1)?;
+                visitor.visit_i8(cx, marker as i8)
+            }
+            UINT8 => {
+                let value = self.decode_u8()?;
+                visitor.visit_u8(cx, value)
+            }
+            UINT16 => {
+                let value = self.decode_u16()?;
+                visitor.visit_u16(cx, value)
+            }
+            UINT32 => {
+                let value = self.decode_u32()?;
+                visitor.visit_u32(cx, value)
+            }
+            UINT64 => {
+                let value = self.decode_u64()?;
+                visitor.visit_u64(cx, value)
+            }
+            INT8 => {
+                let value = self.decode_i8()?;
+                visitor.visit_i8(cx, value)
+            }
+            INT16 => {
+                let value = self.decode_i16()?;
+                visitor.visit_i16(cx, value)
+            }
+            INT32 => {
+                let value = self.decode_i32()?;
+                visitor.visit_i32(cx, value)
+            }
+            INT64 => {
+                let value = self.decode_i64()?;
+                visitor.visit_i64(cx, value)
+            }
+            NIL => {
+                self.reader.skip(cx, 1)?;
+                visitor.visit_empty(cx)
+            }
+            FALSE | TRUE => {
+                let value = self.decode_bool()?;
+                visitor.visit_bool(cx, value)
+            }
+            FLOAT32 => {
+                let value = self.decode_f32()?;
+                visitor.visit_f32(cx, value)
+            }
+            FLOAT64 => {
+                let value = self.decode_f64()?;
+                visitor.visit_f64(cx, value)
+            }
+            0xa0..=0xbf | STR8 | STR16 | STR32 => {
+                let hint = match marker {
+                    0xa0..=0xbf => SizeHint::exact(usize::from(marker & 0x1f)),
+                    _ => SizeHint::any(),
+                };
+
+                let visitor = visitor.visit_string(cx, hint)?;
+                self.decode_string(visitor)
+            }
+            0x90..=0x9f | ARRAY16 | ARRAY32 => {
+                cx.enter_nested()?;
+                let mut sequence = self.shared_decode_sequence()?;
+                let output = visitor.visit_sequence(cx, &mut sequence)?;
+                sequence.skip_sequence_remaining()?;
+                cx.leave_nested();
+                Ok(output)
+            }
+            0x80..=0x8f | MAP16 | MAP32 => {
+                cx.enter_nested()?;
+                let mut map = self.shared_decode_map()?;
+                let output = visitor.visit_map(cx, &mut map)?;
+                map.skip_map_remaining()?;
+                cx.leave_nested();
+                Ok(output)
+            }
+            // Binary values and the payload of extension types.
+            _ => {
+                let pos = cx.mark();
+                let len = self.decode_bytes_len(pos)?;
+                cx.check_size(len)?;
+                let visitor = visitor.visit_bytes(cx, SizeHint::exact(len))?;
+                self.reader.read_bytes(cx, len, visitor)
+            }
+        }
+    }
+}
+
+impl<'a, 'de, R, const OPT: Options, C> SequenceDecoder<'de>
+    for RemainingMsgPackDecoder<'a, R, OPT, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeNext<'this> = MsgPackDecoder<'a, R::Mut<'this>, OPT, C> where Self: 'this;
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::exact(self.remaining)
+    }
+
+    #[inline]
+    fn try_decode_next(&mut self) -> Result<Option<Self::DecodeNext<'_>>, C::Error> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        self.remaining -= 1;
+        Ok(Some(MsgPackDecoder::new(self.cx, self.reader.borrow_mut())))
+    }
+
+    #[inline]
+    fn decode_next(&mut self) -> Result<Self::DecodeNext<'_>, <Self::Cx as Context>::Error> {
+        let cx = self.cx;
+
+        let Some(decoder) = self.try_decode_next()? else {
+            return Err(cx.message("No remaining elements"));
+        };
+
+        Ok(decoder)
+    }
+}
+
+impl<'a, 'de, R, const OPT: Options, C> MapDecoder<'de> for RemainingMsgPackDecoder<'a, R, OPT, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeEntry<'this> = MsgPackDecoder<'a, R::Mut<'this>, OPT, C> where Self: 'this;
+    type DecodeRemainingEntries<'this>
+        = RemainingMsgPackDecoder<'a, R::Mut<'this>, OPT, C>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::exact(self.remaining)
+    }
+
+    #[inline]
+    fn decode_entry(&mut self) -> Result<Option<Self::DecodeEntry<'_>>, C::Error> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        self.remaining -= 1;
+        Ok(Some(MsgPackDecoder::new(self.cx, self.reader.borrow_mut())))
+    }
+
+    #[inline]
+    fn decode_remaining_entries(&mut self) -> Result<Self::DecodeRemainingEntries<'_>, C::Error> {
+        Ok(RemainingMsgPackDecoder::new(
+            self.cx,
+            self.reader.borrow_mut(),
+            core::mem::take(&mut self.remaining),
+        ))
+    }
+}
+
+impl<'a, 'de, R, const OPT: Options, C> EntriesDecoder<'de>
+    for RemainingMsgPackDecoder<'a, R, OPT, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeEntryKey<'this> = MsgPackDecoder<'a, R::Mut<'this>, OPT, C> where Self: 'this;
+    type DecodeEntryValue<'this> = MsgPackDecoder<'a, R::Mut<'this>, OPT, C> where Self: 'this;
+
+    #[inline]
+    fn decode_entry_key(&mut self) -> Result<Option<Self::DecodeEntryKey<'_>>, C::Error> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        self.remaining -= 1;
+        Ok(Some(MsgPackDecoder::new(self.cx, self.reader.borrow_mut())))
+    }
+
+    #[inline]
+    fn decode_entry_value(&mut self) -> Result<Self::DecodeEntryValue<'_>, C::Error> {
+        Ok(MsgPackDecoder::new(self.cx, self.reader.borrow_mut()))
+    }
+
+    #[inline]
+    fn end_entries(self) -> Result<(), <Self::Cx as Context>::Error> {
+        self.skip_map_remaining()?;
+        Ok(())
+    }
+}
+
+impl<'a, 'de, R, const OPT: Options, C> EntryDecoder<'de> for MsgPackDecoder<'a, R, OPT, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeKey<'this> = MsgPackDecoder<'a, R::Mut<'this>, OPT, C> where Self: 'this;
+    type DecodeValue = Self;
+
+    #[inline]
+    fn decode_key(&mut self) -> Result<Self::DecodeKey<'_>, C::Error> {
+        Ok(MsgPackDecoder::new(self.cx, self.reader.borrow_mut()))
+    }
+
+    #[inline]
+    fn decode_value(self) -> Result<Self::DecodeValue, C::Error> {
+        Ok(self)
+    }
+}
+
+impl<'a, 'de, R, const OPT: Options, C> VariantDecoder<'de> for MsgPackDecoder<'a, R, OPT, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeTag<'this> = MsgPackDecoder<'a, R::Mut<'this>, OPT, C> where Self: 'this;
+    type DecodeValue<'this> = MsgPackDecoder<'a, R::Mut<'this>, OPT, C> where Self: 'this;
+
+    #[inline]
+    fn decode_tag(&mut self) -> Result<Self::DecodeTag<'_>, C::Error> {
+        Ok(MsgPackDecoder::new(self.cx, self.reader.borrow_mut()))
+    }
+
+    #[inline]
+    fn decode_value(&mut self) -> Result<Self::DecodeValue<'_>, C::Error> {
+        Ok(MsgPackDecoder::new(self.cx, self.reader.borrow_mut()))
+    }
+}
+
+struct Expected {
+    expected: Kind,
+    actual: Marker,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let expected = match self.expected {
+            Kind::String => "str",
+            Kind::Bytes => "bin",
+            Kind::Array => "array",
+            Kind::Map => "map",
+        };
+
+        write!(f, "Expected {expected} but was {:?}", self.actual)
+    }
+}
@@ -0,0 +1,591 @@
+use core::fmt;
+
+use crate::en::{
+    Encoder, EntriesEncoder, EntryEncoder, MapEncoder, SequenceEncoder, VariantEncoder,
+};
+use crate::hint::{MapHint, SequenceHint};
+use crate::writer::BufWriter;
+use crate::{Context, Encode, Options, Writer};
+
+use super::marker::{
+    Kind, EXT16, EXT32, EXT8, FALSE, FIXEXT1, FIXEXT16, FIXEXT2, FIXEXT4, FIXEXT8, FLOAT32,
+    FLOAT64, INT16, INT32, INT64, INT8, NIL, TRUE, UINT16, UINT32, UINT64, UINT8,
+};
+
+/// A MessagePack encoder.
+pub struct MsgPackEncoder<'a, W, const OPT: Options, C: ?Sized> {
+    cx: &'a C,
+    writer: W,
+}
+
+impl<'a, W, const OPT: Options, C: ?Sized> MsgPackEncoder<'a, W, OPT, C> {
+    /// Construct a new MessagePack encoder.
+    #[inline]
+    pub(crate) fn new(cx: &'a C, writer: W) -> Self {
+        Self { cx, writer }
+    }
+}
+
+/// Encoder for packed values, which are stored in an array.
+///
+/// Since the number of elements is not known up front, they are buffered
+/// until the pack is finished.
+pub struct MsgPackPackEncoder<'a, W, const OPT: Options, C>
+where
+    C: ?Sized + Context,
+{
+    cx: &'a C,
+    writer: W,
+    buffer: BufWriter<'a, C::Allocator>,
+    len: usize,
+}
+
+impl<'a, W, const OPT: Options, C> MsgPackPackEncoder<'a, W, OPT, C>
+where
+    C: ?Sized + Context,
+{
+    #[inline]
+    pub(crate) fn new(cx: &'a C, writer: W) -> Self {
+        Self {
+            cx,
+            writer,
+            buffer: BufWriter::new(cx.alloc()),
+            len: 0,
+        }
+    }
+}
+
+#[crate::encoder(crate)]
+impl<'a, W, const OPT: Options, C> Encoder for MsgPackEncoder<'a, W, OPT, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Ok = ();
+    type Mode = C::Mode;
+    type WithContext<'this, U> = MsgPackEncoder<'this, W, OPT, U> where U: 'this + Context;
+    type EncodePack = MsgPackPackEncoder<'a, W, OPT, C>;
+    type EncodeSome = Self;
+    type EncodeSequence = Self;
+    type EncodeMap = Self;
+    type EncodeMapEntries = Self;
+    type EncodeVariant = Self;
+    type EncodeSequenceVariant = Self;
+    type EncodeMapVariant = Self;
+
+    #[inline]
+    fn cx(&self) -> &Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn with_context<U>(self, cx: &U) -> Result<Self::WithContext<'_, U>, C::Error>
+    where
+        U: Context,
+    {
+        Ok(MsgPackEncoder::new(cx, self.writer))
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "type supported by the MessagePack encoder")
+    }
+
+    #[inline]
+    fn encode<T>(self, value: T) -> Result<Self::Ok, C::Error>
+    where
+        T: Encode<Self::Mode>,
+    {
+        value.encode(self.cx, self)
+    }
+
+    #[inline]
+    fn encode_empty(mut self) -> Result<Self::Ok, C::Error> {
+        self.writer.write_byte(self.cx, NIL)
+    }
+
+    #[inline]
+    fn encode_pack(self) -> Result<Self::EncodePack, C::Error> {
+        Ok(MsgPackPackEncoder::new(self.cx, self.writer))
+    }
+
+    #[inline]
+    fn encode_array<const N: usize>(self, array: &[u8; N]) -> Result<Self::Ok, C::Error> {
+        self.encode_bytes(array)
+    }
+
+    #[inline]
+    fn encode_bytes(mut self, bytes: &[u8]) -> Result<Self::Ok, C::Error> {
+        encode_len(self.cx, self.writer.borrow_mut(), Kind::Bytes, bytes.len())?;
+        self.writer.write_bytes(self.cx, bytes)
+    }
+
+    #[inline]
+    fn encode_bytes_vectored<I>(mut self, len: usize, vectors: I) -> Result<Self::Ok, C::Error>
+    where
+        I: IntoIterator<Item: AsRef<[u8]>>,
+    {
+        encode_len(self.cx, self.writer.borrow_mut(), Kind::Bytes, len)?;
+
+        for bytes in vectors {
+            self.writer.write_bytes(self.cx, bytes.as_ref())?;
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn encode_extension(mut self, code: i64, payload: &[u8]) -> Result<Self::Ok, C::Error> {
+        let Ok(code) = i8::try_from(code) else {
+            return Err(self.cx.message(format_args!(
+                "Extension type {code} is out of bounds for MessagePack"
+            )));
+        };
+
+        match payload.len() {
+            1 => self.writer.write_byte(self.cx, FIXEXT1)?,
+            2 => self.writer.write_byte(self.cx, FIXEXT2)?,
+            4 => self.writer.write_byte(self.cx, FIXEXT4)?,
+            8 => self.writer.write_byte(self.cx, FIXEXT8)?,
+            16 => self.writer.write_byte(self.cx, FIXEXT16)?,
+            len => {
+                if let Ok(len) = u8::try_from(len) {
+                    self.writer.write_bytes(self.cx, &[EXT8, len])?;
+                } else if let Ok(len) = u16::try_from(len) {
+                    self.writer.write_byte(self.cx, EXT16)?;
+                    self.writer.write_bytes(self.cx, &len.to_be_bytes())?;
+                } else if let Ok(len) = u32::try_from(len) {
+                    self.writer.write_byte(self.cx, EXT32)?;
+                    self.writer.write_bytes(self.cx, &len.to_be_bytes())?;
+                } else {
+                    return Err(self.cx.message(format_args!(
+                        "Length {len} is too large for MessagePack"
+                    )));
+                }
+            }
+        }
+
+        self.writer.write_byte(self.cx, code as u8)?;
+        self.writer.write_bytes(self.cx, payload)
+    }
+
+    #[inline]
+    fn encode_string(mut self, string: &str) -> Result<Self::Ok, C::Error> {
+        encode_len(
+            self.cx,
+            self.writer.borrow_mut(),
+            Kind::String,
+            string.len(),
+        )?;
+        self.writer.write_bytes(self.cx, string.as_bytes())
+    }
+
+    #[inline]
+    fn collect_string<T>(self, value: &T) -> Result<Self::Ok, <Self::Cx as Context>::Error>
+    where
+        T: ?Sized + fmt::Display,
+    {
+        let buf = self.cx.collect_string(value)?;
+        self.encode_string(buf.as_ref())
+    }
+
+    #[inline]
+    fn encode_bool(mut self, value: bool) -> Result<Self::Ok, C::Error> {
+        self.writer
+            .write_byte(self.cx, if value { TRUE } else { FALSE })
+    }
+
+    #[inline]
+    fn encode_char(self, value: char) -> Result<Self::Ok, C::Error> {
+        self.encode_string(value.encode_utf8(&mut [0, 0, 0, 0]))
+    }
+
+    #[inline]
+    fn encode_u8(mut self, value: u8) -> Result<Self::Ok, C::Error> {
+        encode_unsigned(self.cx, self.writer.borrow_mut(), value.into())
+    }
+
+    #[inline]
+    fn encode_u16(mut self, value: u16) -> Result<Self::Ok, C::Error> {
+        encode_unsigned(self.cx, self.writer.borrow_mut(), value.into())
+    }
+
+    #[inline]
+    fn encode_u32(mut self, value: u32) -> Result<Self::Ok, C::Error> {
+        encode_unsigned(self.cx, self.writer.borrow_mut(), value.into())
+    }
+
+    #[inline]
+    fn encode_u64(mut self, value: u64) -> Result<Self::Ok, C::Error> {
+        encode_unsigned(self.cx, self.writer.borrow_mut(), value)
+    }
+
+    #[inline]
+    fn encode_u128(mut self, value: u128) -> Result<Self::Ok, C::Error> {
+        // MessagePack has no 128-bit integers, so like other implementations
+        // values which don't fit in 64 bits are stored as 16 big-endian bytes.
+        match u64::try_from(value) {
+            Ok(value) => encode_unsigned(self.cx, self.writer.borrow_mut(), value),
+            Err(..) => self.encode_bytes(&value.to_be_bytes()),
+        }
+    }
+
+    #[inline]
+    fn encode_usize(mut self, value: usize) -> Result<Self::Ok, C::Error> {
+        encode_unsigned(self.cx, self.writer.borrow_mut(), value as u64)
+    }
+
+    #[inline]
+    fn encode_i8(mut self, value: i8) -> Result<Self::Ok, C::Error> {
+        encode_signed(self.cx, self.writer.borrow_mut(), value.into())
+    }
+
+    #[inline]
+    fn encode_i16(mut self, value: i16) -> Result<Self::Ok, C::Error> {
+        encode_signed(self.cx, self.writer.borrow_mut(), value.into())
+    }
+
+    #[inline]
+    fn encode_i32(mut self, value: i32) -> Result<Self::Ok, C::Error> {
+        encode_signed(self.cx, self.writer.borrow_mut(), value.into())
+    }
+
+    #[inline]
+    fn encode_i64(mut self, value: i64) -> Result<Self::Ok, C::Error> {
+        encode_signed(self.cx, self.writer.borrow_mut(), value)
+    }
+
+    #[inline]
+    fn encode_i128(mut self, value: i128) -> Result<Self::Ok, C::Error> {
+        match i64::try_from(value) {
+            Ok(value) => encode_signed(self.cx, self.writer.borrow_mut(), value),
+            Err(..) => self.encode_bytes(&value.to_be_bytes()),
+        }
+    }
+
+    #[inline]
+    fn encode_isize(mut self, value: isize) -> Result<Self::Ok, C::Error> {
+        encode_signed(self.cx, self.writer.borrow_mut(), value as i64)
+    }
+
+    #[inline]
+    fn encode_f32(mut self, value: f32) -> Result<Self::Ok, C::Error> {
+        self.writer.write_byte(self.cx, FLOAT32)?;
+        self.writer.write_bytes(self.cx, &value.to_be_bytes())
+    }
+
+    #[inline]
+    fn encode_f64(mut self, value: f64) -> Result<Self::Ok, C::Error> {
+        self.writer.write_byte(self.cx, FLOAT64)?;
+        self.writer.write_bytes(self.cx, &value.to_be_bytes())
+    }
+
+    #[inline]
+    fn encode_some(self) -> Result<Self::EncodeSome, C::Error> {
+        Ok(self)
+    }
+
+    #[inline]
+    fn encode_none(self) -> Result<Self::Ok, C::Error> {
+        self.encode_empty()
+    }
+
+    #[inline]
+    fn encode_sequence(mut self, hint: &SequenceHint) -> Result<Self::EncodeSequence, C::Error> {
+        encode_len(self.cx, self.writer.borrow_mut(), Kind::Array, hint.size)?;
+        Ok(self)
+    }
+
+    #[inline]
+    fn encode_map(mut self, hint: &MapHint) -> Result<Self::EncodeMap, C::Error> {
+        encode_len(self.cx, self.writer.borrow_mut(), Kind::Map, hint.size)?;
+        Ok(self)
+    }
+
+    #[inline]
+    fn encode_map_entries(mut self, hint: &MapHint) -> Result<Self::EncodeMapEntries, C::Error> {
+        encode_len(self.cx, self.writer.borrow_mut(), Kind::Map, hint.size)?;
+        Ok(self)
+    }
+
+    #[inline]
+    fn encode_variant(mut self) -> Result<Self::EncodeVariant, C::Error> {
+        encode_len(self.cx, self.writer.borrow_mut(), Kind::Map, 1)?;
+        Ok(self)
+    }
+
+    #[inline]
+    fn encode_sequence_variant<T>(
+        mut self,
+        tag: &T,
+        hint: &SequenceHint,
+    ) -> Result<Self::EncodeSequenceVariant, C::Error>
+    where
+        T: ?Sized + Encode<C::Mode>,
+    {
+        encode_len(self.cx, self.writer.borrow_mut(), Kind::Map, 1)?;
+        MsgPackEncoder::<_, OPT, _>::new(self.cx, self.writer.borrow_mut()).encode(tag)?;
+        self.encode_sequence(hint)
+    }
+
+    #[inline]
+    fn encode_map_variant<T>(
+        mut self,
+        tag: &T,
+        hint: &MapHint,
+    ) -> Result<Self::EncodeMapVariant, C::Error>
+    where
+        T: ?Sized + Encode<C::Mode>,
+    {
+        encode_len(self.cx, self.writer.borrow_mut(), Kind::Map, 1)?;
+        MsgPackEncoder::<_, OPT, _>::new(self.cx, self.writer.borrow_mut()).encode(tag)?;
+        self.encode_map(hint)
+    }
+}
+
+impl<'a, W, const OPT: Options, C> SequenceEncoder for MsgPackPackEncoder<'a, W, OPT, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Ok = ();
+    type EncodeNext<'this>
+        = MsgPackEncoder<'a, &'this mut BufWriter<'a, C::Allocator>, OPT, C>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn encode_next(&mut self) -> Result<Self::EncodeNext<'_>, C::Error> {
+        self.len += 1;
+        Ok(MsgPackEncoder::new(self.cx, &mut self.buffer))
+    }
+
+    #[inline]
+    fn finish_sequence(mut self) -> Result<Self::Ok, C::Error> {
+        let buffer = self.buffer.into_inner();
+        encode_len(self.cx, self.writer.borrow_mut(), Kind::Array, self.len)?;
+        self.writer.extend(self.cx, buffer)?;
+        Ok(())
+    }
+}
+
+impl<'a, W, const OPT: Options, C> SequenceEncoder for MsgPackEncoder<'a, W, OPT, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Ok = ();
+    type EncodeNext<'this> = MsgPackEncoder<'a, W::Mut<'this>, OPT, C> where Self: 'this;
+
+    #[inline]
+    fn encode_next(&mut self) -> Result<Self::EncodeNext<'_>, C::Error> {
+        Ok(MsgPackEncoder::new(self.cx, self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn finish_sequence(self) -> Result<Self::Ok, C::Error> {
+        Ok(())
+    }
+}
+
+impl<'a, W, const OPT: Options, C> MapEncoder for MsgPackEncoder<'a, W, OPT, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Ok = ();
+    type EncodeEntry<'this> = MsgPackEncoder<'a, W::Mut<'this>, OPT, C> where Self: 'this;
+
+    #[inline]
+    fn encode_entry(&mut self) -> Result<Self::EncodeEntry<'_>, C::Error> {
+        Ok(MsgPackEncoder::new(self.cx, self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn finish_map(self) -> Result<Self::Ok, C::Error> {
+        Ok(())
+    }
+}
+
+impl<'a, W, const OPT: Options, C> EntryEncoder for MsgPackEncoder<'a, W, OPT, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Ok = ();
+    type EncodeKey<'this> = MsgPackEncoder<'a, W::Mut<'this>, OPT, C> where Self: 'this;
+    type EncodeValue<'this> = MsgPackEncoder<'a, W::Mut<'this>, OPT, C> where Self: 'this;
+
+    #[inline]
+    fn encode_key(&mut self) -> Result<Self::EncodeKey<'_>, C::Error> {
+        Ok(MsgPackEncoder::new(self.cx, self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn encode_value(&mut self) -> Result<Self::EncodeValue<'_>, C::Error> {
+        Ok(MsgPackEncoder::new(self.cx, self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn finish_entry(self) -> Result<Self::Ok, C::Error> {
+        Ok(())
+    }
+}
+
+impl<'a, W, const OPT: Options, C> EntriesEncoder for MsgPackEncoder<'a, W, OPT, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Ok = ();
+    type EncodeEntryKey<'this> = MsgPackEncoder<'a, W::Mut<'this>, OPT, C> where Self: 'this;
+    type EncodeEntryValue<'this> = MsgPackEncoder<'a, W::Mut<'this>, OPT, C> where Self: 'this;
+
+    #[inline]
+    fn encode_entry_key(&mut self) -> Result<Self::EncodeEntryKey<'_>, C::Error> {
+        Ok(MsgPackEncoder::new(self.cx, self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn encode_entry_value(&mut self) -> Result<Self::EncodeEntryValue<'_>, C::Error> {
+        Ok(MsgPackEncoder::new(self.cx, self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn finish_entries(self) -> Result<Self::Ok, C::Error> {
+        Ok(())
+    }
+}
+
+impl<'a, W, const OPT: Options, C> VariantEncoder for MsgPackEncoder<'a, W, OPT, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Ok = ();
+    type EncodeTag<'this> = MsgPackEncoder<'a, W::Mut<'this>, OPT, C> where Self: 'this;
+    type EncodeData<'this> = MsgPackEncoder<'a, W::Mut<'this>, OPT, C> where Self: 'this;
+
+    #[inline]
+    fn encode_tag(&mut self) -> Result<Self::EncodeTag<'_>, C::Error> {
+        Ok(MsgPackEncoder::new(self.cx, self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn encode_data(&mut self) -> Result<Self::EncodeData<'_>, C::Error> {
+        Ok(MsgPackEncoder::new(self.cx, self.writer.borrow_mut()))
+    }
+
+    #[inline]
+    fn finish_variant(self) -> Result<Self::Ok, C::Error> {
+        Ok(())
+    }
+}
+
+/// Encode the marker and length of a length-prefixed value using the
+/// smallest marker available for its kind.
+pub(crate) fn encode_len<C, W>(
+    cx: &C,
+    mut writer: W,
+    kind: Kind,
+    len: usize,
+) -> Result<(), C::Error>
+where
+    C: ?Sized + Context,
+    W: Writer,
+{
+    let (fixed, m8, m16, m32) = kind.markers();
+
+    if let Some((base, max)) = fixed {
+        if len <= max {
+            return writer.write_byte(cx, base | len as u8);
+        }
+    }
+
+    if let (Some(m8), Ok(len)) = (m8, u8::try_from(len)) {
+        return writer.write_bytes(cx, &[m8, len]);
+    }
+
+    if let Ok(len) = u16::try_from(len) {
+        writer.write_byte(cx, m16)?;
+        return writer.write_bytes(cx, &len.to_be_bytes());
+    }
+
+    let Ok(len) = u32::try_from(len) else {
+        return Err(cx.message(format_args!("Length {len} is too large for MessagePack")));
+    };
+
+    writer.write_byte(cx, m32)?;
+    writer.write_bytes(cx, &len.to_be_bytes())
+}
+
+/// Encode an unsigned integer in its smallest form.
+#[inline]
+fn encode_unsigned<C, W>(cx: &C, mut writer: W, value: u64) -> Result<(), C::Error>
+where
+    C: ?Sized + Context,
+    W: Writer,
+{
+    if let Ok(value) = u8::try_from(value) {
+        if value <= super::marker::POSITIVE_FIXINT_MAX {
+            return writer.write_byte(cx, value);
+        }
+
+        return writer.write_bytes(cx, &[UINT8, value]);
+    }
+
+    if let Ok(value) = u16::try_from(value) {
+        writer.write_byte(cx, UINT16)?;
+        return writer.write_bytes(cx, &value.to_be_bytes());
+    }
+
+    if let Ok(value) = u32::try_from(value) {
+        writer.write_byte(cx, UINT32)?;
+        return writer.write_bytes(cx, &value.to_be_bytes());
+    }
+
+    writer.write_byte(cx, UINT64)?;
+    writer.write_bytes(cx, &value.to_be_bytes())
+}
+
+/// Encode a signed integer in its smallest form. Non-negative values use the
+/// unsigned markers.
+#[inline]
+fn encode_signed<C, W>(cx: &C, mut writer: W, value: i64) -> Result<(), C::Error>
+where
+    C: ?Sized + Context,
+    W: Writer,
+{
+    if value >= 0 {
+        return encode_unsigned(cx, writer, value as u64);
+    }
+
+    if value >= -32 {
+        return writer.write_byte(cx, value as u8);
+    }
+
+    if let Ok(value) = i8::try_from(value) {
+        return writer.write_bytes(cx, &[INT8, value as u8]);
+    }
+
+    if let Ok(value) = i16::try_from(value) {
+        writer.write_byte(cx, INT16)?;
+        return writer.write_bytes(cx, &value.to_be_bytes());
+    }
+
+    if let Ok(value) = i32::try_from(value) {
+        writer.write_byte(cx, INT32)?;
+        return writer.write_bytes(cx, &value.to_be_bytes());
+    }
+
+    writer.write_byte(cx, INT64)?;
+    writer.write_bytes(cx, &value.to_be_bytes())
+}
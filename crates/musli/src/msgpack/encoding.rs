@@ -0,0 +1,134 @@
+//! Module that defines [`Encoding`] whith allows for customization of the
+//! encoding format, and the [`DEFAULT`] encoding configuration.
+
+use core::marker;
+
+use crate::mode::Binary;
+use crate::options;
+use crate::{IntoReader, Options};
+
+use super::de::MsgPackDecoder;
+use super::en::MsgPackEncoder;
+use super::error::Error;
+
+/// The default flavor used by the [`DEFAULT`] configuration.
+pub const OPTIONS: options::Options = options::new().build();
+
+/// The default configuration.
+///
+/// MessagePack always uses big-endian, shortest-form integers, so the options
+/// currently have no effect on the encoding.
+pub const DEFAULT: Encoding = Encoding::new();
+
+crate::macros::bare_encoding!(Binary, DEFAULT, msgpack, IntoReader);
+
+/// Setting up encoding with parameters.
+pub struct Encoding<const OPT: Options = OPTIONS, M = Binary>
+where
+    M: 'static,
+{
+    _marker: marker::PhantomData<M>,
+}
+
+impl Default for Encoding<OPTIONS, Binary> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Encoding<OPTIONS, Binary> {
+    /// Construct a new [`Encoding`] instance.
+    ///
+    /// ```
+    /// use musli::{Encode, Decode};
+    /// use musli::msgpack::Encoding;
+    /// # use musli::msgpack::Error;
+    ///
+    /// const CONFIG: Encoding = Encoding::new();
+    ///
+    /// #[derive(Debug, PartialEq, Encode, Decode)]
+    /// struct Person<'a> {
+    ///     name: &'a str,
+    ///     age: u32,
+    /// }
+    ///
+    /// let mut out = Vec::new();
+    ///
+    /// let expected = Person {
+    ///     name: "Aristotle",
+    ///     age: 61,
+    /// };
+    ///
+    /// CONFIG.encode(&mut out, &expected)?;
+    /// let actual = CONFIG.decode(&out[..])?;
+    ///
+    /// assert_eq!(expected, actual);
+    /// # Ok::<_, Error>(())
+    /// ```
+    pub const fn new() -> Self {
+        Encoding {
+            _marker: marker::PhantomData,
+        }
+    }
+}
+
+impl<const OPT: Options, M> Encoding<OPT, M>
+where
+    M: 'static,
+{
+    /// Change the mode of the encoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::msgpack::{OPTIONS, Encoding};
+    ///
+    /// enum Custom {}
+    ///
+    /// const CONFIG: Encoding<OPTIONS, Custom> = Encoding::new().with_mode();
+    /// ```
+    pub const fn with_mode<T>(self) -> Encoding<OPT, T> {
+        Encoding {
+            _marker: marker::PhantomData,
+        }
+    }
+
+    /// Change the options of the encoding.
+    ///
+    /// MessagePack has a fixed integer encoding, so this is only provided for
+    /// parity with other formats.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::options::{self, Options, Integer};
+    /// use musli::msgpack::Encoding;
+    ///
+    /// const OPTIONS: Options = options::new().with_integer(Integer::Fixed).build();
+    /// const CONFIG: Encoding<OPTIONS> = Encoding::new().with_options();
+    /// ```
+    pub const fn with_options<const U: Options>(self) -> Encoding<U, M> {
+        Encoding {
+            _marker: marker::PhantomData,
+        }
+    }
+
+    crate::macros::encoding_impls!(
+        M,
+        msgpack,
+        MsgPackEncoder::<_, OPT, _>::new,
+        MsgPackDecoder::<_, OPT, _>::new,
+        IntoReader::into_reader,
+        Reader,
+    );
+}
+
+impl<const OPT: Options, M> Clone for Encoding<OPT, M> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<const OPT: Options, M> Copy for Encoding<OPT, M> {}
@@ -0,0 +1,100 @@
+//! Markers used by `musli::msgpack`.
+//!
+//! Every value in MessagePack starts with a single marker byte. Some families
+//! of markers such as fixint, fixstr, fixarray and fixmap embed a small value
+//! or length in the marker itself.
+
+/// The upper bound of positive fixints.
+pub(crate) const POSITIVE_FIXINT_MAX: u8 = 0x7f;
+/// The base of fixmaps, with the length stored in the low 4 bits.
+pub(crate) const FIXMAP: u8 = 0x80;
+/// The base of fixarrays, with the length stored in the low 4 bits.
+pub(crate) const FIXARRAY: u8 = 0x90;
+/// The base of fixstrs, with the length stored in the low 5 bits.
+pub(crate) const FIXSTR: u8 = 0xa0;
+/// The lower bound of negative fixints.
+pub(crate) const NEGATIVE_FIXINT_MIN: u8 = 0xe0;
+
+pub(crate) const NIL: u8 = 0xc0;
+pub(crate) const FALSE: u8 = 0xc2;
+pub(crate) const TRUE: u8 = 0xc3;
+pub(crate) const BIN8: u8 = 0xc4;
+pub(crate) const BIN16: u8 = 0xc5;
+pub(crate) const BIN32: u8 = 0xc6;
+pub(crate) const EXT8: u8 = 0xc7;
+pub(crate) const EXT16: u8 = 0xc8;
+pub(crate) const EXT32: u8 = 0xc9;
+pub(crate) const FLOAT32: u8 = 0xca;
+pub(crate) const FLOAT64: u8 = 0xcb;
+pub(crate) const UINT8: u8 = 0xcc;
+pub(crate) const UINT16: u8 = 0xcd;
+pub(crate) const UINT32: u8 = 0xce;
+pub(crate) const UINT64: u8 = 0xcf;
+pub(crate) const INT8: u8 = 0xd0;
+pub(crate) const INT16: u8 = 0xd1;
+pub(crate) const INT32: u8 = 0xd2;
+pub(crate) const INT64: u8 = 0xd3;
+pub(crate) const FIXEXT1: u8 = 0xd4;
+pub(crate) const FIXEXT2: u8 = 0xd5;
+pub(crate) const FIXEXT4: u8 = 0xd6;
+pub(crate) const FIXEXT8: u8 = 0xd7;
+pub(crate) const FIXEXT16: u8 = 0xd8;
+pub(crate) const STR8: u8 = 0xd9;
+pub(crate) const STR16: u8 = 0xda;
+pub(crate) const STR32: u8 = 0xdb;
+pub(crate) const ARRAY16: u8 = 0xdc;
+pub(crate) const ARRAY32: u8 = 0xdd;
+pub(crate) const MAP16: u8 = 0xde;
+pub(crate) const MAP32: u8 = 0xdf;
+
+/// The kind of a length-prefixed value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Kind {
+    String,
+    Bytes,
+    Array,
+    Map,
+}
+
+impl Kind {
+    /// Get the markers used for this kind, in order of the fixed marker with
+    /// its maximum length, followed by the 8, 16 and 32-bit markers. Kinds
+    /// without a marker for a given width use `None`.
+    #[inline]
+    pub(crate) const fn markers(self) -> (Option<(u8, usize)>, Option<u8>, u8, u8) {
+        match self {
+            Kind::String => (Some((FIXSTR, 31)), Some(STR8), STR16, STR32),
+            Kind::Bytes => (None, Some(BIN8), BIN16, BIN32),
+            Kind::Array => (Some((FIXARRAY, 15)), None, ARRAY16, ARRAY32),
+            Kind::Map => (Some((FIXMAP, 15)), None, MAP16, MAP32),
+        }
+    }
+}
+
+/// Debug a marker byte.
+pub(crate) struct Marker(pub(crate) u8);
+
+impl core::fmt::Debug for Marker {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let name = match self.0 {
+            0x00..=POSITIVE_FIXINT_MAX => "positive fixint",
+            0x80..=0x8f => "fixmap",
+            0x90..=0x9f => "fixarray",
+            0xa0..=0xbf => "fixstr",
+            NIL => "nil",
+            FALSE | TRUE => "bool",
+            BIN8 | BIN16 | BIN32 => "bin",
+            EXT8 | EXT16 | EXT32 | FIXEXT1 | FIXEXT2 | FIXEXT4 | FIXEXT8 | FIXEXT16 => "ext",
+            FLOAT32 | FLOAT64 => "float",
+            UINT8 | UINT16 | UINT32 | UINT64 => "uint",
+            INT8 | INT16 | INT32 | INT64 => "int",
+            STR8 | STR16 | STR32 => "str",
+            ARRAY16 | ARRAY32 => "array",
+            MAP16 | MAP32 => "map",
+            NEGATIVE_FIXINT_MIN..=0xff => "negative fixint",
+            _ => "reserved",
+        };
+
+        write!(f, "{name} ({:#04x})", self.0)
+    }
+}
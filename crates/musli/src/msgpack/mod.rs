@@ -0,0 +1,158 @@
+//! Support for [MessagePack], an efficient binary serialization format which
+//! is widely supported across languages.
+//!
+//! MessagePack is a self-descriptive format, and this implementation is fully
+//! upgrade stable:
+//!
+//! * ✔ Can tolerate missing fields if they are annotated with
+//!   `#[musli(default)]`.
+//! * ✔ Can skip over unknown fields.
+//! * ✔ Can be decoded into dynamic containers such as the [`Value`] type.
+//! * ✔ Can handle coercion from different types of primitive types, such as
+//!   signed to unsigned integers. So primitive field types can be assuming they
+//!   only inhabit compatible values.
+//!
+//! [MessagePack]: https://github.com/msgpack/msgpack/blob/master/spec.md
+//! [`Value`]: crate::value
+//!
+//! ```
+//! use musli::{Encode, Decode};
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! struct Version1 {
+//!     name: String,
+//! }
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! struct Version2 {
+//!     name: String,
+//!     #[musli(default)]
+//!     age: Option<u32>,
+//! }
+//!
+//! let version2 = musli::msgpack::to_vec(&Version2 {
+//!     name: String::from("Aristotle"),
+//!     age: Some(61),
+//! })?;
+//!
+//! let version1: Version1 = musli::msgpack::from_slice(version2.as_slice())?;
+//!
+//! assert_eq!(version1, Version1 {
+//!     name: String::from("Aristotle"),
+//! });
+//! # Ok::<_, musli::msgpack::Error>(())
+//! ```
+//!
+//! <br>
+//!
+//! ## Struct layouts
+//!
+//! Structs are encoded as maps by default. Packed structs are encoded as
+//! arrays of their fields instead, which is the layout that is commonly used
+//! by other MessagePack implementations such as `rmp-serde`. By only packing
+//! a struct in a particular [mode], both layouts can be selected through the
+//! [`Encoding`] in use:
+//!
+//! [mode]: crate::mode
+//!
+//! ```
+//! use musli::{Encode, Decode};
+//! use musli::msgpack::{Encoding, OPTIONS};
+//!
+//! enum Packed {}
+//!
+//! const MAP: Encoding = Encoding::new();
+//! const ARRAY: Encoding<OPTIONS, Packed> = Encoding::new().with_mode();
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! #[musli(name_all = "name")]
+//! #[musli(mode = Packed, packed)]
+//! struct Person {
+//!     name: String,
+//!     age: u32,
+//! }
+//!
+//! let person = Person {
+//!     name: String::from("Aristotle"),
+//!     age: 61,
+//! };
+//!
+//! let bytes = MAP.to_vec(&person)?;
+//! assert_eq!(bytes, b"\x82\xa4name\xa9Aristotle\xa3age\x3d");
+//!
+//! let bytes = ARRAY.to_vec(&person)?;
+//! assert_eq!(bytes, b"\x92\xa9Aristotle\x3d");
+//! assert_eq!(ARRAY.from_slice::<Person>(&bytes)?, person);
+//! # Ok::<_, musli::msgpack::Error>(())
+//! ```
+//!
+//! <br>
+//!
+//! ## Implementation details
+//!
+//! Values are mapped onto the MessagePack data model like this:
+//!
+//! * Integers are encoded in their shortest form, using the fixint families
+//!   where possible. 128-bit integers which do not fit in 64 bits are encoded
+//!   as 16 big-endian bytes in a `bin`, which is also how other implementations
+//!   represent them.
+//! * Floats are encoded with their own precision.
+//! * Strings and bytes are `str` and `bin` values. Characters are encoded as
+//!   strings.
+//! * Sequences and maps are arrays and maps, and structs are maps keyed by
+//!   their field names.
+//! * Packed values are arrays.
+//! * Variants are maps with a single entry, where the key is the variant tag.
+//! * `None` and empty values are encoded as `nil`, while `Some` is encoded as
+//!   the value itself.
+//!
+//! Since 128-bit integers which do not fit in 64 bits are stored as binary
+//! values, they are decoded as bytes when the type is not known, such as when
+//! decoding into [`Value`] or when buffering internally tagged enums.
+//!
+//! Extension types are supported through [`Encoder::encode_extension`] and
+//! [`Decoder::decode_extension`], which is how the [`Timestamp`] type
+//! implements the timestamp extension. When the expected extension type is not
+//! known, such as when decoding into [`Value`], they are treated as binary
+//! values containing their payload.
+//!
+//! [`Encoder::encode_extension`]: crate::Encoder::encode_extension
+//! [`Decoder::decode_extension`]: crate::Decoder::decode_extension
+
+#![cfg(feature = "msgpack")]
+#![cfg_attr(doc_cfg, doc(cfg(feature = "msgpack")))]
+
+#[cfg(test)]
+mod tests;
+
+mod de;
+mod en;
+mod encoding;
+mod error;
+mod marker;
+mod timestamp;
+
+#[cfg(feature = "test")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "test")))]
+#[doc(hidden)]
+pub mod test;
+
+/// Convenient result alias for use with `musli::msgpack`.
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[doc(inline)]
+pub use self::encoding::to_vec;
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+#[doc(inline)]
+pub use self::encoding::to_writer;
+#[doc(inline)]
+pub use self::encoding::{
+    decode, encode, from_slice, from_slice_exact, to_fixed_bytes, Encoding, DEFAULT, OPTIONS,
+};
+#[doc(inline)]
+pub use self::error::Error;
+#[doc(inline)]
+pub use self::timestamp::Timestamp;
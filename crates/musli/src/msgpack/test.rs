@@ -0,0 +1,3 @@
+//! Helpers for writing tests.
+
+crate::macros::test_fns!(Binary, "msgpack");
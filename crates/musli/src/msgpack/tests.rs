@@ -0,0 +1,298 @@
+//! Test vectors follow the MessagePack specification.
+
+use rust_alloc::string::String;
+use rust_alloc::vec;
+use rust_alloc::vec::Vec;
+
+use crate::compat::Bytes;
+use crate::value::Value;
+use crate::{Decode, Encode};
+
+use super::test::{decodes, roundtrips};
+use super::{Encoding, Timestamp, OPTIONS};
+
+#[test]
+fn integers() {
+    roundtrips(0u8, b"\x00");
+    roundtrips(127u8, b"\x7f");
+    roundtrips(128u8, b"\xcc\x80");
+    roundtrips(255u8, b"\xcc\xff");
+    roundtrips(256u16, b"\xcd\x01\x00");
+    roundtrips(65536u32, b"\xce\x00\x01\x00\x00");
+    roundtrips(u64::MAX, b"\xcf\xff\xff\xff\xff\xff\xff\xff\xff");
+    roundtrips(-1i8, b"\xff");
+    roundtrips(-32i8, b"\xe0");
+    roundtrips(-33i8, b"\xd0\xdf");
+    roundtrips(-129i16, b"\xd1\xff\x7f");
+    roundtrips(-32769i32, b"\xd2\xff\xff\x7f\xff");
+    roundtrips(i64::MIN, b"\xd3\x80\x00\x00\x00\x00\x00\x00\x00");
+
+    // Non-negative signed integers use the unsigned markers.
+    roundtrips(100i32, b"\x64");
+    roundtrips(200i64, b"\xcc\xc8");
+
+    // Values which are coerced between signed and unsigned types.
+    decodes(b"\xd0\x05", 5u8);
+    decodes(b"\xcf\x00\x00\x00\x00\x00\x00\x00\x2a", 42i8);
+    assert!(super::from_slice::<u8>(b"\xcd\x01\x00").is_err());
+    assert!(super::from_slice::<u32>(b"\xff").is_err());
+    assert!(super::from_slice::<i64>(b"\xcf\xff\xff\xff\xff\xff\xff\xff\xff").is_err());
+}
+
+#[test]
+fn wide_integers() {
+    roundtrips(u128::MAX, &[&[0xc4, 0x10][..], &[0xff; 16]].concat());
+    roundtrips(i128::MIN, &[&[0xc4, 0x10, 0x80][..], &[0; 15]].concat());
+    roundtrips(42u128, b"\x2a");
+    roundtrips(-33i128, b"\xd0\xdf");
+
+    // Wide integers are coerced into smaller types if they fit.
+    decodes(&[&[0xc4, 0x10][..], &[0; 15], &[1]].concat(), 1u8);
+    decodes(&[&[0xc4, 0x10][..], &[0xff; 16]].concat(), -1i32);
+    assert!(super::from_slice::<u128>(b"\xc4\x01\x00").is_err());
+}
+
+#[test]
+fn floats() {
+    roundtrips(1.5f32, b"\xca\x3f\xc0\x00\x00");
+    roundtrips(1.1f64, b"\xcb\x3f\xf1\x99\x99\x99\x99\x99\x9a");
+    decodes(b"\xca\x3f\xc0\x00\x00", 1.5f64);
+    decodes(b"\xcb\x3f\xf8\x00\x00\x00\x00\x00\x00", 1.5f32);
+    assert!(super::from_slice::<f32>(b"\x01").is_err());
+}
+
+#[test]
+fn simple_values() {
+    roundtrips(false, b"\xc2");
+    roundtrips(true, b"\xc3");
+    roundtrips(None::<u32>, b"\xc0");
+    roundtrips(Some(1u32), b"\x01");
+    roundtrips((), b"\xc0");
+    assert!(super::from_slice::<bool>(b"\xc1").is_err());
+}
+
+#[test]
+fn strings() {
+    roundtrips(String::new(), b"\xa0");
+    roundtrips(String::from("a"), b"\xa1a");
+    roundtrips('\u{6c34}', b"\xa3\xe6\xb0\xb4");
+    decodes(b"\x61", 'a');
+
+    let string = "a".repeat(31);
+    roundtrips(string.clone(), &[b"\xbf", string.as_bytes()].concat());
+    let string = "a".repeat(32);
+    roundtrips(string.clone(), &[b"\xd9\x20", string.as_bytes()].concat());
+    let string = "a".repeat(256);
+    roundtrips(
+        string.clone(),
+        &[b"\xda\x01\x00", string.as_bytes()].concat(),
+    );
+    decodes(b"\xdb\x00\x00\x00\x01a", String::from("a"));
+
+    roundtrips(Bytes(Vec::<u8>::new()), b"\xc4\x00");
+    roundtrips(Bytes(vec![1u8, 2, 3]), b"\xc4\x03\x01\x02\x03");
+    roundtrips(Bytes([1u8, 2, 3]), b"\xc4\x03\x01\x02\x03");
+    decodes(b"\xc5\x00\x01\x2a", Bytes(vec![42u8]));
+    decodes(b"\xc6\x00\x00\x00\x01\x2a", Bytes(vec![42u8]));
+
+    assert!(super::from_slice::<String>(b"\xa2\xc3\x28").is_err());
+    assert!(super::from_slice::<String>(b"\xc4\x01a").is_err());
+}
+
+#[test]
+fn arrays_and_maps() {
+    roundtrips(Vec::<u32>::new(), b"\x90");
+    roundtrips(vec![1u32, 2, 3], b"\x93\x01\x02\x03");
+
+    let values = (1u32..=16).collect::<Vec<_>>();
+    let mut expected = b"\xdc\x00\x10".to_vec();
+    expected.extend(1u8..=16);
+    roundtrips(values, &expected);
+
+    let mut map = rust_alloc::collections::BTreeMap::new();
+    map.insert(1u32, 2u32);
+    map.insert(3u32, 4u32);
+    roundtrips(map.clone(), b"\x82\x01\x02\x03\x04");
+    decodes(b"\xde\x00\x02\x01\x02\x03\x04", map.clone());
+    decodes(b"\xdf\x00\x00\x00\x02\x01\x02\x03\x04", map);
+    decodes(b"\xdd\x00\x00\x00\x01\x2a", vec![42u32]);
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate, name_all = "name")]
+struct Person {
+    name: String,
+    age: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate, name_all = "name")]
+enum Animal {
+    Cat,
+    #[musli(name_all = "name")]
+    Dog {
+        name: String,
+    },
+}
+
+#[test]
+fn structs_and_variants() {
+    roundtrips(
+        Person {
+            name: String::from("Aristotle"),
+            age: 61,
+        },
+        b"\x82\xa4name\xa9Aristotle\xa3age\x3d",
+    );
+
+    roundtrips(Animal::Cat, b"\x81\xa3Cat\x80");
+    roundtrips(
+        Animal::Dog {
+            name: String::from("Fido"),
+        },
+        b"\x81\xa3Dog\x81\xa4name\xa4Fido",
+    );
+
+    assert!(super::from_slice::<Animal>(b"\x80").is_err());
+    assert!(super::from_slice::<Animal>(b"\x82\xa3Cat\x80\xa3Cat\x80").is_err());
+}
+
+#[test]
+fn tagged_variants() {
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    #[musli(crate, name_all = "name", tag = "type")]
+    enum Internal {
+        #[musli(name_all = "name")]
+        Point { x: i32, y: i32 },
+    }
+
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    #[musli(crate, name_all = "name", tag = "type", content = "data")]
+    enum Adjacent {
+        #[musli(name_all = "name")]
+        Point { x: i32, y: i32 },
+    }
+
+    roundtrips(
+        Internal::Point { x: 1, y: -1 },
+        b"\x83\xa4type\xa5Point\xa1x\x01\xa1y\xff",
+    );
+    decodes(
+        b"\x83\xa1x\x01\xa1y\xff\xa4type\xa5Point",
+        Internal::Point { x: 1, y: -1 },
+    );
+    roundtrips(
+        Adjacent::Point { x: 1, y: -1 },
+        b"\x82\xa4type\xa5Point\xa4data\x82\xa1x\x01\xa1y\xff",
+    );
+}
+
+#[test]
+fn packed_layout() {
+    enum Packed {}
+
+    const ARRAY: Encoding<OPTIONS, Packed> = Encoding::new().with_mode();
+
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    #[musli(crate, mode = Packed, packed)]
+    struct Point {
+        x: i32,
+        y: i32,
+        label: Option<String>,
+    }
+
+    let point = Point {
+        x: 1,
+        y: -1,
+        label: None,
+    };
+
+    let bytes = ARRAY.to_vec(&point).expect("failed to encode");
+    assert_eq!(bytes, b"\x93\x01\xff\xc0");
+    assert_eq!(ARRAY.from_slice::<Point>(&bytes).unwrap(), point);
+
+    // Trailing elements written by newer versions are skipped, while missing
+    // elements are an error.
+    assert_eq!(
+        ARRAY.from_slice::<Point>(b"\x94\x01\xff\xc0\x2a").unwrap(),
+        point
+    );
+    assert!(ARRAY.from_slice::<Point>(b"\x92\x01\xff").is_err());
+}
+
+#[test]
+fn skip_unknown() {
+    // Unknown fields containing extension values, floats and nested
+    // containers are skipped over.
+    decodes(
+        b"\x84\xa4name\xa9Aristotle\xa5extra\xd6\xff\x00\x00\x00\x01\xa3age\x3d\xa4more\x82\xa1a\xcb\x3f\xf0\x00\x00\x00\x00\x00\x00\xa1b\x92\xc7\x02\x05\x01\x02\xc3",
+        Person {
+            name: String::from("Aristotle"),
+            age: 61,
+        },
+    );
+}
+
+#[test]
+fn timestamps() {
+    let timestamp = |seconds, nanoseconds| Timestamp {
+        seconds,
+        nanoseconds,
+    };
+
+    decodes(b"\xd6\xff\x00\x00\x00\x2a", timestamp(42, 0));
+    decodes(
+        b"\xd7\xff\x00\x00\x00\x04\x00\x00\x00\x2a",
+        timestamp(42, 1),
+    );
+    decodes(
+        b"\xc7\x0c\xff\x00\x00\x00\x01\xff\xff\xff\xff\xff\xff\xff\xff",
+        timestamp(-1, 1),
+    );
+
+    roundtrips(timestamp(42, 0), b"\xd6\xff\x00\x00\x00\x2a");
+    roundtrips(
+        timestamp(42, 1),
+        b"\xd7\xff\x00\x00\x00\x04\x00\x00\x00\x2a",
+    );
+    roundtrips(
+        timestamp(-1, 1),
+        b"\xc7\x0c\xff\x00\x00\x00\x01\xff\xff\xff\xff\xff\xff\xff\xff",
+    );
+
+    // The extension type must be the one reserved for timestamps.
+    assert!(super::from_slice::<Timestamp>(b"\xd6\x01\x00\x00\x00\x2a").is_err());
+    assert!(super::from_slice::<Timestamp>(b"\xc4\x04\x00\x00\x00\x2a").is_err());
+
+    // Nanoseconds must be less than one second.
+    assert!(
+        super::from_slice::<Timestamp>(b"\xc7\x0c\xff\x3b\x9a\xca\x00\0\0\0\0\0\0\0\0").is_err()
+    );
+    assert!(super::from_slice::<Timestamp>(b"\xd4\xff\x00").is_err());
+}
+
+#[test]
+fn decode_any() {
+    let value: Value = super::from_slice(
+        b"\x84\xa1a\xff\xa1b\x92\x02\xcd\x01\x00\xa1c\xc4\x02\x01\x02\xa1d\xd4\x05\x2a",
+    )
+    .expect("failed to decode");
+
+    let string = |s: &str| Value::String(String::from(s));
+
+    assert_eq!(
+        value,
+        Value::Map(vec![
+            (string("a"), Value::Number((-1i8).into())),
+            (
+                string("b"),
+                Value::Sequence(vec![
+                    Value::Number(2u8.into()),
+                    Value::Number(256u16.into()),
+                ])
+            ),
+            (string("c"), Value::Bytes(vec![1, 2])),
+            (string("d"), Value::Bytes(vec![42])),
+        ])
+    );
+}
@@ -0,0 +1,138 @@
+use core::fmt;
+
+use crate::de::{Decode, Decoder, UnsizedVisitor};
+use crate::en::{Encode, Encoder};
+use crate::Context;
+
+/// The extension type reserved for timestamps.
+const EXTENSION_TYPE: i64 = -1;
+
+/// A point in time as defined by the MessagePack [timestamp extension type].
+///
+/// With [`musli::msgpack`] this is encoded as an extension value of type `-1`,
+/// using the smallest of the 32, 64 and 96-bit forms which can represent it.
+/// All forms are accepted when decoding, but the type of the extension value
+/// must match. Formats without extension types store the payload as bytes.
+///
+/// [timestamp extension type]: https://github.com/msgpack/msgpack/blob/master/spec.md#timestamp-extension-type
+/// [`musli::msgpack`]: crate::msgpack
+///
+/// # Examples
+///
+/// ```
+/// use musli::msgpack::Timestamp;
+///
+/// // A timestamp extension value using the 32-bit form.
+/// let bytes = b"\xd6\xff\x00\x00\x00\x2a";
+/// let timestamp: Timestamp = musli::msgpack::from_slice(bytes)?;
+///
+/// assert_eq!(timestamp, Timestamp { seconds: 42, nanoseconds: 0 });
+/// # Ok::<_, musli::msgpack::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp {
+    /// Seconds since the unix epoch.
+    pub seconds: i64,
+    /// Nanoseconds within the second, which must be less than one billion.
+    pub nanoseconds: u32,
+}
+
+impl Timestamp {
+    /// Parse a timestamp from the payload of a timestamp extension value.
+    fn from_payload(bytes: &[u8]) -> Option<Self> {
+        let timestamp = match *bytes {
+            [a, b, c, d] => Self {
+                seconds: u32::from_be_bytes([a, b, c, d]).into(),
+                nanoseconds: 0,
+            },
+            [a, b, c, d, e, f, g, h] => {
+                let value = u64::from_be_bytes([a, b, c, d, e, f, g, h]);
+
+                Self {
+                    seconds: (value & 0x3_ffff_ffff) as i64,
+                    nanoseconds: (value >> 34) as u32,
+                }
+            }
+            [a, b, c, d, ref rest @ ..] if rest.len() == 8 => {
+                let mut seconds = [0; 8];
+                seconds.copy_from_slice(rest);
+
+                Self {
+                    seconds: i64::from_be_bytes(seconds),
+                    nanoseconds: u32::from_be_bytes([a, b, c, d]),
+                }
+            }
+            _ => return None,
+        };
+
+        if timestamp.nanoseconds >= 1_000_000_000 {
+            return None;
+        }
+
+        Some(timestamp)
+    }
+
+    /// Write the payload of the smallest timestamp extension form which can
+    /// represent this value, returning the number of bytes used.
+    fn to_payload(self, out: &mut [u8; 12]) -> usize {
+        if self.seconds >> 34 == 0 {
+            let value = (u64::from(self.nanoseconds) << 34) | self.seconds as u64;
+
+            if let Ok(value) = u32::try_from(value) {
+                out[..4].copy_from_slice(&value.to_be_bytes());
+                return 4;
+            }
+
+            out[..8].copy_from_slice(&value.to_be_bytes());
+            return 8;
+        }
+
+        out[..4].copy_from_slice(&self.nanoseconds.to_be_bytes());
+        out[4..].copy_from_slice(&self.seconds.to_be_bytes());
+        12
+    }
+}
+
+impl<M> Encode<M> for Timestamp {
+    #[inline]
+    fn encode<E>(&self, _: &E::Cx, encoder: E) -> Result<E::Ok, E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        let mut payload = [0; 12];
+        let len = self.to_payload(&mut payload);
+        encoder.encode_extension(EXTENSION_TYPE, &payload[..len])
+    }
+}
+
+impl<'de, M> Decode<'de, M> for Timestamp {
+    #[inline]
+    fn decode<D>(_: &D::Cx, decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = M>,
+    {
+        struct Visitor;
+
+        impl<C> UnsizedVisitor<'_, C, [u8]> for Visitor
+        where
+            C: ?Sized + Context,
+        {
+            type Ok = Timestamp;
+
+            #[inline]
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "timestamp extension")
+            }
+
+            #[inline]
+            fn visit_ref(self, cx: &C, bytes: &[u8]) -> Result<Self::Ok, C::Error> {
+                match Timestamp::from_payload(bytes) {
+                    Some(timestamp) => Ok(timestamp),
+                    None => Err(cx.message("Bad timestamp extension payload")),
+                }
+            }
+        }
+
+        decoder.decode_extension(EXTENSION_TYPE, Visitor)
+    }
+}
@@ -12,6 +12,48 @@ pub const fn new() -> Builder {
     Builder(DEFAULT)
 }
 
+/// Start building options which makes the [`storage`] encoding byte-compatible
+/// with [bincode] 1.x, as produced by `bincode::serialize`.
+///
+/// Structs, enum variants, tuples and arrays need to be annotated with
+/// `#[musli(packed)]` since bincode doesn't encode any field tags or lengths
+/// for them.
+///
+/// [`storage`]: crate::storage
+/// [bincode]: https://docs.rs/bincode/1
+pub const fn bincode() -> Builder {
+    new()
+        .with_integer(Integer::Fixed)
+        .with_float(Float::Fixed)
+        .with_byte_order(ByteOrder::Little)
+        .with_length_width(Width::U64)
+        .with_variant_tag(VariantTag::U32)
+        .with_char(Char::Utf8)
+        .with_size_integers(true)
+        .with_empty_omitted(true)
+}
+
+/// Start building options which makes the [`storage`] encoding byte-compatible
+/// with [postcard].
+///
+/// Structs, enum variants, tuples and arrays need to be annotated with
+/// `#[musli(packed)]` since postcard doesn't encode any field tags or lengths
+/// for them.
+///
+/// [`storage`]: crate::storage
+/// [postcard]: https://docs.rs/postcard
+pub const fn postcard() -> Builder {
+    new()
+        .with_integer(Integer::Variable)
+        .with_float(Float::Fixed)
+        .with_byte_order(ByteOrder::Little)
+        .with_length(Integer::Variable)
+        .with_variant_tag(VariantTag::U32)
+        .with_char(Char::String)
+        .with_size_integers(true)
+        .with_empty_omitted(true)
+}
+
 /// Type encapsulating a static options for an encoding.
 ///
 /// Note: despite being made up of a primitive type, this cannot be serialized
@@ -30,6 +72,10 @@ const LENGTH_BIT: Options = 2;
 const MAP_KEYS_AS_NUMBERS_BIT: Options = 3;
 const FLOAT_BIT: Options = 8;
 const LENGTH_WIDTH_BIT: Options = 16;
+const VARIANT_TAG_BIT: Options = 20;
+const CHAR_BIT: Options = 21;
+const SIZE_INTEGERS_BIT: Options = 24;
+const EMPTY_OMITTED_BIT: Options = 25;
 
 impl Builder {
     /// Indicates if an integer serialization should be variable.
@@ -76,6 +122,38 @@ impl Builder {
         Self((this.0 & !MASK) | ((width as Options) << LENGTH_WIDTH_BIT))
     }
 
+    /// Specify how the index of an enum variant should be serialized.
+    #[inline(always)]
+    pub const fn with_variant_tag(self, tag: VariantTag) -> Self {
+        const MASK: Options = 0b1 << VARIANT_TAG_BIT;
+        Self((self.0 & !MASK) | ((tag as Options) << VARIANT_TAG_BIT))
+    }
+
+    /// Specify how characters should be serialized.
+    #[inline(always)]
+    pub const fn with_char(self, char: Char) -> Self {
+        const MASK: Options = 0b11 << CHAR_BIT;
+        Self((self.0 & !MASK) | ((char as Options) << CHAR_BIT))
+    }
+
+    /// Serialize `usize` and `isize` as 64-bit integers using the integer
+    /// configuration, rather than using the length configuration.
+    #[inline(always)]
+    pub const fn with_size_integers(self, value: bool) -> Self {
+        const MASK: Options = 0b1 << SIZE_INTEGERS_BIT;
+        let value = if value { 1 } else { 0 };
+        Self((self.0 & !MASK) | (value << SIZE_INTEGERS_BIT))
+    }
+
+    /// Don't serialize anything for empty values such as `()` and unit
+    /// structs, rather than serializing them as empty sequences.
+    #[inline(always)]
+    pub const fn with_empty_omitted(self, value: bool) -> Self {
+        const MASK: Options = 0b1 << EMPTY_OMITTED_BIT;
+        let value = if value { 1 } else { 0 };
+        Self((self.0 & !MASK) | (value << EMPTY_OMITTED_BIT))
+    }
+
     /// Build a flavor.
     #[inline(always)]
     pub const fn build(self) -> Options {
@@ -88,8 +166,19 @@ impl Builder {
     feature = "wire",
     feature = "descriptive",
     feature = "json",
-    feature = "value"
+    feature = "value",
+    feature = "framing"
 ))]
+#[cfg_attr(
+    not(any(
+        feature = "storage",
+        feature = "wire",
+        feature = "descriptive",
+        feature = "json",
+        feature = "value"
+    )),
+    allow(dead_code)
+)]
 #[inline(always)]
 pub(crate) const fn integer<const OPT: Options>() -> Integer {
     match (OPT >> INTEGER_BIT) & 0b1 {
@@ -98,7 +187,13 @@ pub(crate) const fn integer<const OPT: Options>() -> Integer {
     }
 }
 
-#[cfg(test)]
+#[cfg(any(
+    test,
+    feature = "storage",
+    feature = "wire",
+    feature = "descriptive",
+    feature = "value"
+))]
 #[inline(always)]
 pub(crate) const fn float<const OPT: Options>() -> Float {
     match (OPT >> FLOAT_BIT) & 0b11 {
@@ -113,7 +208,8 @@ pub(crate) const fn float<const OPT: Options>() -> Float {
     feature = "wire",
     feature = "descriptive",
     feature = "json",
-    feature = "value"
+    feature = "value",
+    feature = "framing"
 ))]
 #[inline(always)]
 pub(crate) const fn length<const OPT: Options>() -> Integer {
@@ -128,7 +224,8 @@ pub(crate) const fn length<const OPT: Options>() -> Integer {
     feature = "wire",
     feature = "descriptive",
     feature = "json",
-    feature = "value"
+    feature = "value",
+    feature = "framing"
 ))]
 #[inline(always)]
 pub(crate) const fn length_width<const OPT: Options>() -> Width {
@@ -145,7 +242,8 @@ pub(crate) const fn length_width<const OPT: Options>() -> Width {
     feature = "wire",
     feature = "descriptive",
     feature = "json",
-    feature = "value"
+    feature = "value",
+    feature = "framing"
 ))]
 #[inline(always)]
 pub(crate) const fn byteorder<const OPT: Options>() -> ByteOrder {
@@ -155,6 +253,57 @@ pub(crate) const fn byteorder<const OPT: Options>() -> ByteOrder {
     }
 }
 
+#[cfg(any(
+    feature = "storage",
+    feature = "wire",
+    feature = "descriptive",
+    feature = "value"
+))]
+#[inline(always)]
+pub(crate) const fn variant_tag<const OPT: Options>() -> VariantTag {
+    match (OPT >> VARIANT_TAG_BIT) & 0b1 {
+        0 => VariantTag::Length,
+        _ => VariantTag::U32,
+    }
+}
+
+#[cfg(any(
+    feature = "storage",
+    feature = "wire",
+    feature = "descriptive",
+    feature = "value"
+))]
+#[inline(always)]
+pub(crate) const fn char<const OPT: Options>() -> Char {
+    match (OPT >> CHAR_BIT) & 0b11 {
+        0 => Char::Integer,
+        1 => Char::Utf8,
+        _ => Char::String,
+    }
+}
+
+#[cfg(any(
+    feature = "storage",
+    feature = "wire",
+    feature = "descriptive",
+    feature = "value"
+))]
+#[inline(always)]
+pub(crate) const fn is_size_integers<const OPT: Options>() -> bool {
+    ((OPT >> SIZE_INTEGERS_BIT) & 0b1) == 1
+}
+
+#[cfg(any(
+    feature = "storage",
+    feature = "wire",
+    feature = "descriptive",
+    feature = "value"
+))]
+#[inline(always)]
+pub(crate) const fn is_empty_omitted<const OPT: Options>() -> bool {
+    ((OPT >> EMPTY_OMITTED_BIT) & 0b1) == 1
+}
+
 #[cfg(all(feature = "alloc", feature = "value"))]
 #[inline(always)]
 pub(crate) const fn is_map_keys_as_numbers<const OPT: Options>() -> bool {
@@ -186,6 +335,31 @@ pub enum Float {
     Fixed = 2,
 }
 
+/// How the index of an enum variant is serialized.
+#[cfg_attr(test, derive(Debug, PartialEq))]
+#[repr(u8)]
+#[non_exhaustive]
+pub enum VariantTag {
+    /// Serialize the index in the same way as lengths.
+    Length = 0,
+    /// Serialize the index as a `u32` using the integer configuration.
+    U32 = 1,
+}
+
+/// Character serialization mode.
+#[cfg_attr(test, derive(Debug, PartialEq))]
+#[repr(u8)]
+#[non_exhaustive]
+pub enum Char {
+    /// Serialize the character as its `u32` scalar value using the integer
+    /// configuration.
+    Integer = 0,
+    /// Serialize the character as its UTF-8 encoding without a length prefix.
+    Utf8 = 1,
+    /// Serialize the character as a length-prefixed UTF-8 string.
+    String = 2,
+}
+
 /// Byte order to use when encoding numbers.
 ///
 /// By default, this is the [`ByteOrder::NATIVE`] byte order of the target
@@ -227,7 +401,8 @@ impl ByteOrder {
     feature = "storage",
     feature = "wire",
     feature = "descriptive",
-    feature = "value"
+    feature = "value",
+    feature = "framing"
 ))]
 macro_rules! width_arm {
     ($width:expr, $macro:path) => {
@@ -252,7 +427,8 @@ macro_rules! width_arm {
     feature = "storage",
     feature = "wire",
     feature = "descriptive",
-    feature = "value"
+    feature = "value",
+    feature = "framing"
 ))]
 pub(crate) use width_arm;
 
@@ -304,6 +480,10 @@ fn test_builds() {
             $(length = $length:expr,)?
             $(length_width = $length_width:expr,)?
             $(is_map_keys_as_numbers = $is_map_keys_as_numbers:expr,)?
+            $(variant_tag = $variant_tag:expr,)?
+            $(char = $char:expr,)?
+            $(is_size_integers = $is_size_integers:expr,)?
+            $(is_empty_omitted = $is_empty_omitted:expr,)?
         }) => {{
             const O: Options = $expr.build();
             assert_or_default!($expr, byteorder::<O>(), ByteOrder::NATIVE, ($($byteorder)?));
@@ -311,6 +491,10 @@ fn test_builds() {
             assert_or_default!($expr, float::<O>(), Float::Integer, ($($float)?));
             assert_or_default!($expr, length::<O>(), Integer::Variable, ($($length)?));
             assert_or_default!($expr, is_map_keys_as_numbers::<O>(), false, ($($is_map_keys_as_numbers)?));
+            assert_or_default!($expr, variant_tag::<O>(), VariantTag::Length, ($($variant_tag)?));
+            assert_or_default!($expr, char::<O>(), Char::Integer, ($($char)?));
+            assert_or_default!($expr, is_size_integers::<O>(), false, ($($is_size_integers)?));
+            assert_or_default!($expr, is_empty_omitted::<O>(), false, ($($is_empty_omitted)?));
         }}
     }
 
@@ -380,4 +564,59 @@ fn test_builds() {
             length_width = Width::U64,
         }
     }
+
+    test_case! {
+        self::new().with_variant_tag(VariantTag::U32) => {
+            variant_tag = VariantTag::U32,
+        }
+    }
+
+    test_case! {
+        self::new().with_char(Char::Utf8) => {
+            char = Char::Utf8,
+        }
+    }
+
+    test_case! {
+        self::new().with_char(Char::String) => {
+            char = Char::String,
+        }
+    }
+
+    test_case! {
+        self::new().with_size_integers(true) => {
+            is_size_integers = true,
+        }
+    }
+
+    test_case! {
+        self::new().with_empty_omitted(true) => {
+            is_empty_omitted = true,
+        }
+    }
+
+    test_case! {
+        self::bincode() => {
+            byteorder = ByteOrder::Little,
+            integer = Integer::Fixed,
+            float = Float::Fixed,
+            length = Integer::Fixed,
+            length_width = Width::U64,
+            variant_tag = VariantTag::U32,
+            char = Char::Utf8,
+            is_size_integers = true,
+            is_empty_omitted = true,
+        }
+    }
+
+    test_case! {
+        self::postcard() => {
+            byteorder = ByteOrder::Little,
+            float = Float::Fixed,
+            variant_tag = VariantTag::U32,
+            char = Char::String,
+            is_size_integers = true,
+            is_empty_omitted = true,
+        }
+    }
 }
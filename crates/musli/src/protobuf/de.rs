@@ -0,0 +1,1386 @@
+use core::fmt;
+use core::mem::{replace, take};
+
+#[cfg(feature = "alloc")]
+use rust_alloc::vec::Vec;
+
+use crate::de::{
+    Decode, DecodeUnsized, Decoder, EntriesDecoder, EntryDecoder, MapDecoder, SequenceDecoder,
+    SizeHint, Skip, UnsizedVisitor, VariantDecoder, Visitor,
+};
+use crate::{Context, Options, Reader};
+
+use super::tag::{self, Tag, WireType};
+
+/// State shared between a message and the fields being decoded from it.
+///
+/// Nested messages are read from the same reader as the message containing
+/// them, so the length of the message is tracked here instead of by limiting
+/// the reader.
+struct State {
+    /// A tag which has been read ahead while looking for further occurrences
+    /// of a repeated field.
+    pending: Option<Tag>,
+    /// The number of bytes remaining in a packed repeated field.
+    packed: usize,
+    /// The number of bytes remaining in the message, or `None` for the
+    /// top-level message which continues until the end of the input.
+    remaining: Option<usize>,
+}
+
+impl State {
+    #[inline]
+    fn new(remaining: Option<usize>) -> Self {
+        Self {
+            pending: None,
+            packed: 0,
+            remaining,
+        }
+    }
+
+    /// Test if the message has ended.
+    #[inline]
+    fn at_end<'de, R>(&self, reader: &mut R) -> bool
+    where
+        R: Reader<'de>,
+    {
+        match self.remaining {
+            Some(remaining) => remaining == 0,
+            None => reader.peek().is_none(),
+        }
+    }
+
+    /// Account for `n` bytes being read from a value of the given kind.
+    #[inline]
+    fn consume<C>(&mut self, cx: &C, kind: Kind, n: usize) -> Result<(), C::Error>
+    where
+        C: ?Sized + Context,
+    {
+        if kind == Kind::Packed {
+            let Some(packed) = self.packed.checked_sub(n) else {
+                return Err(cx.message("Packed field ended unexpectedly"));
+            };
+
+            self.packed = packed;
+        }
+
+        if let Some(remaining) = &mut self.remaining {
+            let Some(n) = remaining.checked_sub(n) else {
+                return Err(cx.message("Message ended unexpectedly"));
+            };
+
+            *remaining = n;
+        }
+
+        Ok(())
+    }
+
+    fn read_varint<'de, C, R>(&mut self, cx: &C, mut reader: R, kind: Kind) -> Result<u64, C::Error>
+    where
+        C: ?Sized + Context,
+        R: Reader<'de>,
+    {
+        let mut value = 0u64;
+        let mut shift = 0u32;
+
+        loop {
+            self.consume(cx, kind, 1)?;
+            let b = reader.read_byte(cx)?;
+            value |= u64::from(b & 0x7f) << shift;
+
+            if b & 0x80 == 0 {
+                return Ok(value);
+            }
+
+            shift += 7;
+
+            if shift >= 64 {
+                return Err(cx.message("Varint is too long"));
+            }
+        }
+    }
+
+    #[inline]
+    fn read_len<'de, C, R>(&mut self, cx: &C, reader: R) -> Result<usize, C::Error>
+    where
+        C: ?Sized + Context,
+        R: Reader<'de>,
+    {
+        let len = self.read_varint(cx, reader, Kind::Field)?;
+
+        match usize::try_from(len) {
+            Ok(len) => Ok(len),
+            Err(..) => Err(cx.message(format_args!("Length {len} is out of range"))),
+        }
+    }
+
+    #[inline]
+    fn read_tag<'de, C, R>(&mut self, cx: &C, reader: R) -> Result<Tag, C::Error>
+    where
+        C: ?Sized + Context,
+        R: Reader<'de>,
+    {
+        let tag = self.read_varint(cx, reader, Kind::Field)?;
+        tag::decode(cx, tag)
+    }
+
+    /// Skip over the value of a field with the given wire type.
+    fn skip<'de, C, R>(&mut self, cx: &C, mut reader: R, wire: WireType) -> Result<(), C::Error>
+    where
+        C: ?Sized + Context,
+        R: Reader<'de>,
+    {
+        let len = match wire {
+            WireType::Varint => {
+                self.read_varint(cx, reader, Kind::Field)?;
+                return Ok(());
+            }
+            WireType::I64 => 8,
+            WireType::Len => self.read_len(cx, reader.borrow_mut())?,
+            WireType::I32 => 4,
+        };
+
+        self.consume(cx, Kind::Field, len)?;
+        reader.skip(cx, len)
+    }
+}
+
+/// How a field value is being decoded.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    /// A singular field.
+    Field,
+    /// An occurrence of a repeated field.
+    Element,
+    /// An element inside of a packed repeated field, which has no tag.
+    Packed,
+}
+
+/// A protobuf decoder for the top-level message.
+pub struct ProtobufDecoder<'a, R, const OPT: Options, C: ?Sized> {
+    cx: &'a C,
+    reader: R,
+}
+
+impl<'a, R, const OPT: Options, C: ?Sized> ProtobufDecoder<'a, R, OPT, C> {
+    /// Construct a new protobuf decoder.
+    #[inline]
+    pub(crate) fn new(cx: &'a C, reader: R) -> Self {
+        Self { cx, reader }
+    }
+}
+
+/// Decoder for the fields of a message.
+pub struct MessageDecoder<'a, R, const OPT: Options, C: ?Sized> {
+    cx: &'a C,
+    reader: R,
+    state: State,
+    /// The tag of the field most recently decoded as a key.
+    current: Option<Tag>,
+    /// The field number of the last element in a packed message.
+    next: u32,
+}
+
+impl<'a, 'de, R, const OPT: Options, C> MessageDecoder<'a, R, OPT, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    #[inline]
+    fn new(cx: &'a C, reader: R, state: State) -> Self {
+        Self {
+            cx,
+            reader,
+            state,
+            current: None,
+            next: 0,
+        }
+    }
+
+    /// Read the tag of the next field, or `None` if the message has ended.
+    #[inline]
+    fn next_tag(&mut self) -> Result<Option<Tag>, C::Error> {
+        if let Some(tag) = self.state.pending.take() {
+            return Ok(Some(tag));
+        }
+
+        if self.state.at_end(&mut self.reader) {
+            return Ok(None);
+        }
+
+        Ok(Some(
+            self.state.read_tag(self.cx, self.reader.borrow_mut())?,
+        ))
+    }
+
+    #[inline]
+    fn value(&mut self) -> Result<FieldDecoder<'a, '_, R::Mut<'_>, OPT, C>, C::Error> {
+        let Some(tag) = self.current.take() else {
+            return Err(self.cx.message("Missing field number"));
+        };
+
+        Ok(FieldDecoder::new(
+            self.cx,
+            self.reader.borrow_mut(),
+            tag,
+            Kind::Field,
+            &mut self.state,
+        ))
+    }
+
+    fn skip_remaining(mut self) -> Result<(), C::Error> {
+        while let Some(tag) = self.next_tag()? {
+            self.state
+                .skip(self.cx, self.reader.borrow_mut(), tag.wire)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Decoder for a single field in a message.
+pub struct FieldEntryDecoder<'a, 'b, R, const OPT: Options, C: ?Sized> {
+    cx: &'a C,
+    reader: R,
+    tag: Tag,
+    state: &'b mut State,
+}
+
+/// Decoder for a field number.
+pub struct KeyDecoder<'a, C: ?Sized> {
+    cx: &'a C,
+    field: u32,
+}
+
+impl<'a, C> KeyDecoder<'a, C>
+where
+    C: ?Sized + Context,
+{
+    #[inline]
+    fn new(cx: &'a C, field: u32) -> Self {
+        Self { cx, field }
+    }
+
+    #[inline]
+    fn get<T>(self) -> Result<T, C::Error>
+    where
+        T: TryFrom<u32>,
+    {
+        match T::try_from(self.field) {
+            Ok(value) => Ok(value),
+            Err(..) => Err(self
+                .cx
+                .message(format_args!("Field number {} is out of range", self.field))),
+        }
+    }
+}
+
+/// Decoder for the value of a field.
+pub struct FieldDecoder<'a, 'b, R, const OPT: Options, C: ?Sized> {
+    cx: &'a C,
+    reader: R,
+    tag: Tag,
+    kind: Kind,
+    state: &'b mut State,
+}
+
+impl<'a, 'b, 'de, R, const OPT: Options, C> FieldDecoder<'a, 'b, R, OPT, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    #[inline]
+    fn new(cx: &'a C, reader: R, tag: Tag, kind: Kind, state: &'b mut State) -> Self {
+        Self {
+            cx,
+            reader,
+            tag,
+            kind,
+            state,
+        }
+    }
+
+    #[inline]
+    fn consume(&mut self, n: usize) -> Result<(), C::Error> {
+        self.state.consume(self.cx, self.kind, n)
+    }
+
+    #[inline]
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], C::Error> {
+        self.consume(N)?;
+        self.reader.read_array(self.cx)
+    }
+
+    #[inline]
+    fn read_bytes<V>(&mut self, len: usize, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: UnsizedVisitor<'de, C, [u8]>,
+    {
+        self.cx.check_size(len)?;
+        self.consume(len)?;
+        self.reader.read_bytes(self.cx, len, visitor)
+    }
+
+    #[inline]
+    fn read_varint(&mut self) -> Result<u64, C::Error> {
+        self.state
+            .read_varint(self.cx, self.reader.borrow_mut(), self.kind)
+    }
+
+    #[inline]
+    fn read_len(&mut self) -> Result<usize, C::Error> {
+        if self.kind == Kind::Packed || self.tag.wire != WireType::Len {
+            return Err(self.cx.message(format_args!(
+                "Expected length-delimited value, but field {} is {}",
+                self.tag.field,
+                self.wire()
+            )));
+        }
+
+        self.state.read_len(self.cx, self.reader.borrow_mut())
+    }
+
+    /// Describe the wire type of the value being decoded.
+    #[inline]
+    fn wire(&self) -> &'static str {
+        match (self.kind, self.tag.wire) {
+            (Kind::Packed, _) => "packed",
+            (_, WireType::Varint) => "varint",
+            (_, WireType::I64) => "i64",
+            (_, WireType::Len) => "length-delimited",
+            (_, WireType::I32) => "i32",
+        }
+    }
+
+    /// Get the wire type to use for a scalar value whose natural encoding in
+    /// a packed field is `natural`.
+    ///
+    /// If a length-delimited occurrence of a repeated field is decoded as a
+    /// scalar, it is a packed field and its elements follow.
+    #[inline]
+    fn scalar(&mut self, natural: WireType) -> Result<WireType, C::Error> {
+        match (self.kind, self.tag.wire) {
+            (Kind::Packed, _) => Ok(natural),
+            (Kind::Element, WireType::Len) => {
+                self.state.packed = self.state.read_len(self.cx, self.reader.borrow_mut())?;
+                self.kind = Kind::Packed;
+                Ok(natural)
+            }
+            (_, wire) => Ok(wire),
+        }
+    }
+
+    fn decode_unsigned<T>(mut self) -> Result<T, C::Error>
+    where
+        T: TryFrom<u64>,
+    {
+        let value = match self.scalar(WireType::Varint)? {
+            WireType::Varint => self.read_varint()?,
+            WireType::I32 => u32::from_le_bytes(self.read_array()?).into(),
+            WireType::I64 => u64::from_le_bytes(self.read_array()?),
+            WireType::Len => return Err(self.unexpected("integer")),
+        };
+
+        match T::try_from(value) {
+            Ok(value) => Ok(value),
+            Err(..) => Err(self.cx.message(format_args!(
+                "Value {value} in field {} is out of range",
+                self.tag.field
+            ))),
+        }
+    }
+
+    fn decode_signed<T>(mut self) -> Result<T, C::Error>
+    where
+        T: TryFrom<i64>,
+    {
+        let value = match self.scalar(WireType::Varint)? {
+            WireType::Varint => self.read_varint()? as i64,
+            WireType::I32 => i32::from_le_bytes(self.read_array()?).into(),
+            WireType::I64 => i64::from_le_bytes(self.read_array()?),
+            WireType::Len => return Err(self.unexpected("integer")),
+        };
+
+        match T::try_from(value) {
+            Ok(value) => Ok(value),
+            Err(..) => Err(self.cx.message(format_args!(
+                "Value {value} in field {} is out of range",
+                self.tag.field
+            ))),
+        }
+    }
+
+    #[inline]
+    fn unexpected(&self, what: &str) -> C::Error {
+        self.cx.message(format_args!(
+            "Expected {what}, but field {} is {}",
+            self.tag.field,
+            self.wire()
+        ))
+    }
+
+    #[inline]
+    fn into_message(mut self) -> Result<MessageDecoder<'a, R, OPT, C>, C::Error> {
+        let len = self.read_len()?;
+        // The nested message is accounted for up front in the message
+        // containing it, which means that it can use the same reader.
+        self.consume(len)?;
+        Ok(MessageDecoder::new(
+            self.cx,
+            self.reader,
+            State::new(Some(len)),
+        ))
+    }
+}
+
+/// Decoder for the occurrences of a repeated field.
+///
+/// Consecutive occurrences of the same field are decoded as a single sequence,
+/// where each occurrence is either a single element or a packed field
+/// containing any number of scalar elements.
+pub struct RepeatedDecoder<'a, 'b, R, const OPT: Options, C: ?Sized> {
+    cx: &'a C,
+    reader: R,
+    tag: Tag,
+    state: &'b mut State,
+    first: bool,
+    done: bool,
+    /// The number of elements decoded so far.
+    count: usize,
+}
+
+impl<'a, 'b, 'de, R, const OPT: Options, C> RepeatedDecoder<'a, 'b, R, OPT, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    /// Advance to the next element, returning how it should be decoded.
+    fn advance(&mut self) -> Result<Option<Kind>, C::Error> {
+        if self.done {
+            return Ok(None);
+        }
+
+        if self.state.packed > 0 {
+            return Ok(Some(Kind::Packed));
+        }
+
+        if take(&mut self.first) {
+            return Ok(Some(Kind::Element));
+        }
+
+        if self.state.at_end(&mut self.reader) {
+            self.done = true;
+            return Ok(None);
+        }
+
+        let tag = self.state.read_tag(self.cx, self.reader.borrow_mut())?;
+
+        if tag.field != self.tag.field {
+            self.done = true;
+            self.ensure_consecutive(tag)?;
+            self.state.pending = Some(tag);
+            return Ok(None);
+        }
+
+        self.tag = tag;
+        Ok(Some(Kind::Element))
+    }
+
+    /// Protobuf requires occurrences of a repeated field which are not
+    /// consecutive to be merged, which can't be done while decoding a single
+    /// sequence. So instead we scan the rest of the message starting at the
+    /// field with the tag `next`, and error if the repeated field occurs
+    /// again.
+    fn ensure_consecutive(&mut self, next: Tag) -> Result<(), C::Error> {
+        let checkpoint = self.reader.checkpoint();
+        let remaining = self.state.remaining;
+        let mut wire = next.wire;
+
+        loop {
+            self.state.skip(self.cx, self.reader.borrow_mut(), wire)?;
+
+            if self.state.at_end(&mut self.reader) {
+                break;
+            }
+
+            let tag = self.state.read_tag(self.cx, self.reader.borrow_mut())?;
+
+            if tag.field == self.tag.field {
+                return Err(self.cx.message(format_args!(
+                    "Occurrences of repeated field {} are not consecutive",
+                    tag.field
+                )));
+            }
+
+            wire = tag.wire;
+        }
+
+        self.reader.rewind(checkpoint);
+        self.state.remaining = remaining;
+        Ok(())
+    }
+
+    fn skip_remaining(mut self) -> Result<(), C::Error> {
+        while let Some(kind) = self.advance()? {
+            if kind == Kind::Packed {
+                let len = self.state.packed;
+                self.state.consume(self.cx, Kind::Packed, len)?;
+                self.reader.skip(self.cx, len)?;
+            } else {
+                self.state
+                    .skip(self.cx, self.reader.borrow_mut(), self.tag.wire)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[crate::decoder(crate)]
+impl<'a, 'de, R, const OPT: Options, C> Decoder<'de> for ProtobufDecoder<'a, R, OPT, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = C::Mode;
+    type WithContext<'this, U> = ProtobufDecoder<'this, R, OPT, U> where U: 'this + Context;
+    type DecodePack = MessageDecoder<'a, R, OPT, C>;
+    type DecodeMap = MessageDecoder<'a, R, OPT, C>;
+    type DecodeMapEntries = MessageDecoder<'a, R, OPT, C>;
+    type DecodeVariant = MessageDecoder<'a, R, OPT, C>;
+
+    #[inline]
+    fn cx(&self) -> &C {
+        self.cx
+    }
+
+    #[inline]
+    fn with_context<U>(self, cx: &U) -> Result<Self::WithContext<'_, U>, C::Error>
+    where
+        U: Context,
+    {
+        Ok(ProtobufDecoder::new(cx, self.reader))
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "message supported by the protobuf decoder")
+    }
+
+    #[inline]
+    fn decode<T>(self) -> Result<T, Self::Error>
+    where
+        T: Decode<'de, Self::Mode>,
+    {
+        self.cx.decode(self)
+    }
+
+    #[inline]
+    fn decode_unsized<T, F, O>(self, f: F) -> Result<O, Self::Error>
+    where
+        T: ?Sized + DecodeUnsized<'de, Self::Mode>,
+        F: FnOnce(&T) -> Result<O, Self::Error>,
+    {
+        self.cx.decode_unsized(self, f)
+    }
+
+    #[inline]
+    fn skip(self) -> Result<(), C::Error> {
+        MessageDecoder::<_, OPT, _>::new(self.cx, self.reader, State::new(None)).skip_remaining()
+    }
+
+    #[inline]
+    fn try_skip(self) -> Result<Skip, C::Error> {
+        self.skip()?;
+        Ok(Skip::Skipped)
+    }
+
+    #[inline]
+    fn decode_empty(self) -> Result<(), C::Error> {
+        self.skip()
+    }
+
+    #[inline]
+    fn decode_pack<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodePack) -> Result<O, C::Error>,
+    {
+        let cx = self.cx;
+        cx.enter_nested()?;
+        let mut decoder = MessageDecoder::new(self.cx, self.reader, State::new(None));
+        let output = f(&mut decoder)?;
+        decoder.skip_remaining()?;
+        cx.leave_nested();
+        Ok(output)
+    }
+
+    #[inline]
+    fn decode_map<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeMap) -> Result<O, C::Error>,
+    {
+        let cx = self.cx;
+        cx.enter_nested()?;
+        let mut decoder = MessageDecoder::new(self.cx, self.reader, State::new(None));
+        let output = f(&mut decoder)?;
+        decoder.skip_remaining()?;
+        cx.leave_nested();
+        Ok(output)
+    }
+
+    #[inline]
+    fn decode_map_entries<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeMapEntries) -> Result<O, C::Error>,
+    {
+        let cx = self.cx;
+        cx.enter_nested()?;
+        let mut decoder = MessageDecoder::new(self.cx, self.reader, State::new(None));
+        let output = f(&mut decoder)?;
+        decoder.skip_remaining()?;
+        cx.leave_nested();
+        Ok(output)
+    }
+
+    #[inline]
+    fn decode_variant<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeVariant) -> Result<O, C::Error>,
+    {
+        let cx = self.cx;
+        cx.enter_nested()?;
+        let mut decoder = MessageDecoder::new(self.cx, self.reader, State::new(None));
+        let output = f(&mut decoder)?;
+        decoder.skip_remaining()?;
+        cx.leave_nested();
+        Ok(output)
+    }
+
+    #[inline]
+    fn decode_any<V>(self, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: Visitor<'de, C>,
+    {
+        let cx = self.cx;
+        let mut decoder = MessageDecoder::<_, OPT, _>::new(cx, self.reader, State::new(None));
+        let output = visitor.visit_map(cx, &mut decoder)?;
+        decoder.skip_remaining()?;
+        Ok(output)
+    }
+}
+
+#[crate::decoder(crate)]
+impl<'a, 'b, 'de, R, const OPT: Options, C> Decoder<'de> for FieldDecoder<'a, 'b, R, OPT, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = C::Mode;
+    type WithContext<'this, U> = FieldDecoder<'this, 'b, R, OPT, U> where U: 'this + Context;
+    type DecodePack = MessageDecoder<'a, R, OPT, C>;
+    type DecodeSome = Self;
+    type DecodeSequence = RepeatedDecoder<'a, 'b, R, OPT, C>;
+    type DecodeMap = MessageDecoder<'a, R, OPT, C>;
+    type DecodeMapEntries = MessageDecoder<'a, R, OPT, C>;
+    type DecodeVariant = MessageDecoder<'a, R, OPT, C>;
+
+    #[inline]
+    fn cx(&self) -> &C {
+        self.cx
+    }
+
+    #[inline]
+    fn with_context<U>(self, cx: &U) -> Result<Self::WithContext<'_, U>, C::Error>
+    where
+        U: Context,
+    {
+        Ok(FieldDecoder::new(
+            cx,
+            self.reader,
+            self.tag,
+            self.kind,
+            self.state,
+        ))
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "type supported by the protobuf decoder")
+    }
+
+    #[inline]
+    fn decode<T>(self) -> Result<T, Self::Error>
+    where
+        T: Decode<'de, Self::Mode>,
+    {
+        self.cx.decode(self)
+    }
+
+    #[inline]
+    fn decode_unsized<T, F, O>(self, f: F) -> Result<O, Self::Error>
+    where
+        T: ?Sized + DecodeUnsized<'de, Self::Mode>,
+        F: FnOnce(&T) -> Result<O, Self::Error>,
+    {
+        self.cx.decode_unsized(self, f)
+    }
+
+    #[inline]
+    fn skip(mut self) -> Result<(), C::Error> {
+        if self.kind == Kind::Packed {
+            // The size of an element in a packed field depends on its type,
+            // so the rest of the packed field is skipped instead.
+            let len = self.state.packed;
+            self.consume(len)?;
+            return self.reader.skip(self.cx, len);
+        }
+
+        self.state.skip(self.cx, self.reader, self.tag.wire)
+    }
+
+    #[inline]
+    fn try_skip(self) -> Result<Skip, C::Error> {
+        self.skip()?;
+        Ok(Skip::Skipped)
+    }
+
+    #[inline]
+    fn decode_empty(self) -> Result<(), C::Error> {
+        self.skip()
+    }
+
+    #[inline]
+    fn decode_pack<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodePack) -> Result<O, C::Error>,
+    {
+        let cx = self.cx;
+        cx.enter_nested()?;
+        let mut decoder = self.into_message()?;
+        let output = f(&mut decoder)?;
+        decoder.skip_remaining()?;
+        cx.leave_nested();
+        Ok(output)
+    }
+
+    #[inline]
+    fn decode_array<const N: usize>(mut self) -> Result<[u8; N], C::Error> {
+        let len = self.read_len()?;
+
+        if len != N {
+            return Err(self.cx.message(format_args! {
+                "Bad length, got {len} but expect {N}"
+            }));
+        }
+
+        self.read_array()
+    }
+
+    #[inline]
+    fn decode_fixed<const N: usize>(mut self) -> Result<[u8; N], C::Error> {
+        let Some(natural) = WireType::fixed(N) else {
+            return self.decode_array();
+        };
+
+        match (self.scalar(natural)?, natural) {
+            (wire, natural) if wire == natural => self.read_array(),
+            (_, WireType::I32) => Err(self.unexpected("fixed32")),
+            _ => Err(self.unexpected("fixed64")),
+        }
+    }
+
+    #[inline]
+    fn decode_bytes<V>(mut self, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: UnsizedVisitor<'de, C, [u8]>,
+    {
+        let len = self.read_len()?;
+        self.read_bytes(len, visitor)
+    }
+
+    #[inline]
+    fn decode_string<V>(mut self, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: UnsizedVisitor<'de, C, str>,
+    {
+        struct Visitor<V>(V);
+
+        impl<'de, C, V> UnsizedVisitor<'de, C, [u8]> for Visitor<V>
+        where
+            C: ?Sized + Context,
+            V: UnsizedVisitor<'de, C, str>,
+        {
+            type Ok = V::Ok;
+
+            #[inline]
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.expecting(f)
+            }
+
+            #[cfg(feature = "alloc")]
+            #[inline]
+            fn visit_owned(self, cx: &C, bytes: Vec<u8>) -> Result<Self::Ok, C::Error> {
+                let string =
+                    crate::str::from_utf8_owned(bytes).map_err(crate::str::map_utf8_error(cx))?;
+                self.0.visit_owned(cx, string)
+            }
+
+            #[inline]
+            fn visit_borrowed(self, cx: &C, bytes: &'de [u8]) -> Result<Self::Ok, C::Error> {
+                let string =
+                    crate::str::from_utf8(bytes).map_err(crate::str::map_utf8_error(cx))?;
+                self.0.visit_borrowed(cx, string)
+            }
+
+            #[inline]
+            fn visit_ref(self, cx: &C, bytes: &[u8]) -> Result<Self::Ok, C::Error> {
+                let string =
+                    crate::str::from_utf8(bytes).map_err(crate::str::map_utf8_error(cx))?;
+                self.0.visit_ref(cx, string)
+            }
+        }
+
+        let len = self.read_len()?;
+        self.read_bytes(len, Visitor(visitor))
+    }
+
+    #[inline]
+    fn decode_bool(self) -> Result<bool, C::Error> {
+        Ok(self.decode_unsigned::<u64>()? != 0)
+    }
+
+    #[inline]
+    fn decode_char(self) -> Result<char, C::Error> {
+        struct Visitor;
+
+        impl<C> UnsizedVisitor<'_, C, str> for Visitor
+        where
+            C: ?Sized + Context,
+        {
+            type Ok = char;
+
+            #[inline]
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "string with a single character")
+            }
+
+            #[inline]
+            fn visit_ref(self, cx: &C, string: &str) -> Result<Self::Ok, C::Error> {
+                let mut it = string.chars();
+
+                match (it.next(), it.next()) {
+                    (Some(c), None) => Ok(c),
+                    _ => Err(cx.message("Expected string with a single character")),
+                }
+            }
+        }
+
+        self.decode_string(Visitor)
+    }
+
+    #[inline]
+    fn decode_number<V>(self, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: Visitor<'de, C>,
+    {
+        self.decode_any(visitor)
+    }
+
+    #[inline]
+    fn decode_u8(self) -> Result<u8, C::Error> {
+        self.decode_unsigned()
+    }
+
+    #[inline]
+    fn decode_u16(self) -> Result<u16, C::Error> {
+        self.decode_unsigned()
+    }
+
+    #[inline]
+    fn decode_u32(self) -> Result<u32, C::Error> {
+        self.decode_unsigned()
+    }
+
+    #[inline]
+    fn decode_u64(self) -> Result<u64, C::Error> {
+        self.decode_unsigned()
+    }
+
+    #[inline]
+    fn decode_usize(self) -> Result<usize, C::Error> {
+        self.decode_unsigned()
+    }
+
+    #[inline]
+    fn decode_i8(self) -> Result<i8, C::Error> {
+        self.decode_signed()
+    }
+
+    #[inline]
+    fn decode_i16(self) -> Result<i16, C::Error> {
+        self.decode_signed()
+    }
+
+    #[inline]
+    fn decode_i32(self) -> Result<i32, C::Error> {
+        self.decode_signed()
+    }
+
+    #[inline]
+    fn decode_i64(self) -> Result<i64, C::Error> {
+        self.decode_signed()
+    }
+
+    #[inline]
+    fn decode_isize(self) -> Result<isize, C::Error> {
+        self.decode_signed()
+    }
+
+    #[inline]
+    fn decode_f32(mut self) -> Result<f32, C::Error> {
+        match self.scalar(WireType::I32)? {
+            WireType::I32 => Ok(f32::from_le_bytes(self.read_array()?)),
+            WireType::I64 => Ok(f64::from_le_bytes(self.read_array()?) as f32),
+            _ => Err(self.unexpected("float")),
+        }
+    }
+
+    #[inline]
+    fn decode_f64(mut self) -> Result<f64, C::Error> {
+        match self.scalar(WireType::I64)? {
+            WireType::I64 => Ok(f64::from_le_bytes(self.read_array()?)),
+            WireType::I32 => Ok(f32::from_le_bytes(self.read_array()?).into()),
+            _ => Err(self.unexpected("double")),
+        }
+    }
+
+    #[inline]
+    fn decode_option(self) -> Result<Option<Self::DecodeSome>, C::Error> {
+        // Absent fields are never decoded, so a field which is present is
+        // always `Some`.
+        Ok(Some(self))
+    }
+
+    #[inline]
+    fn decode_sequence<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeSequence) -> Result<O, C::Error>,
+    {
+        if self.kind != Kind::Field {
+            return Err(self.cx.message("Nested repeated fields are not supported"));
+        }
+
+        let cx = self.cx;
+        cx.enter_nested()?;
+
+        let mut decoder = RepeatedDecoder {
+            cx,
+            reader: self.reader,
+            tag: self.tag,
+            state: self.state,
+            first: true,
+            done: false,
+            count: 0,
+        };
+
+        let output = f(&mut decoder)?;
+        decoder.skip_remaining()?;
+        cx.leave_nested();
+        Ok(output)
+    }
+
+    #[inline]
+    fn decode_map<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeMap) -> Result<O, C::Error>,
+    {
+        let cx = self.cx;
+        cx.enter_nested()?;
+        let mut decoder = self.into_message()?;
+        let output = f(&mut decoder)?;
+        decoder.skip_remaining()?;
+        cx.leave_nested();
+        Ok(output)
+    }
+
+    #[inline]
+    fn decode_map_entries<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeMapEntries) -> Result<O, C::Error>,
+    {
+        let cx = self.cx;
+        cx.enter_nested()?;
+        let mut decoder = self.into_message()?;
+        let output = f(&mut decoder)?;
+        decoder.skip_remaining()?;
+        cx.leave_nested();
+        Ok(output)
+    }
+
+    #[inline]
+    fn decode_variant<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeVariant) -> Result<O, C::Error>,
+    {
+        let cx = self.cx;
+        cx.enter_nested()?;
+        let mut decoder = self.into_message()?;
+        let output = f(&mut decoder)?;
+        decoder.skip_remaining()?;
+        cx.leave_nested();
+        Ok(output)
+    }
+
+    #[inline]
+    fn decode_any<V>(mut self, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: Visitor<'de, C>,
+    {
+        let cx = self.cx;
+
+        // Without a schema the wire type is all we have to go on, so values
+        // are decoded in their most general form.
+        let wire = if self.kind != Kind::Packed && self.tag.wire == WireType::Len {
+            WireType::Len
+        } else {
+            self.scalar(WireType::Varint)?
+        };
+
+        match wire {
+            WireType::Varint => {
+                let value = self.read_varint()?;
+                visitor.visit_u64(cx, value)
+            }
+            WireType::I32 => {
+                let value = u32::from_le_bytes(self.read_array()?);
+                visitor.visit_u32(cx, value)
+            }
+            WireType::I64 => {
+                let value = u64::from_le_bytes(self.read_array()?);
+                visitor.visit_u64(cx, value)
+            }
+            WireType::Len => {
+                let len = self.read_len()?;
+                cx.check_size(len)?;
+                let visitor = visitor.visit_bytes(cx, SizeHint::exact(len))?;
+                self.read_bytes(len, visitor)
+            }
+        }
+    }
+}
+
+#[crate::decoder(crate)]
+impl<'a, 'de, C> Decoder<'de> for KeyDecoder<'a, C>
+where
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = C::Mode;
+    type WithContext<'this, U> = KeyDecoder<'this, U> where U: 'this + Context;
+
+    #[inline]
+    fn cx(&self) -> &C {
+        self.cx
+    }
+
+    #[inline]
+    fn with_context<U>(self, cx: &U) -> Result<Self::WithContext<'_, U>, C::Error>
+    where
+        U: Context,
+    {
+        Ok(KeyDecoder::new(cx, self.field))
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "integer field number")
+    }
+
+    #[inline]
+    fn decode<T>(self) -> Result<T, Self::Error>
+    where
+        T: Decode<'de, Self::Mode>,
+    {
+        self.cx.decode(self)
+    }
+
+    #[inline]
+    fn skip(self) -> Result<(), C::Error> {
+        Ok(())
+    }
+
+    #[inline]
+    fn try_skip(self) -> Result<Skip, C::Error> {
+        Ok(Skip::Skipped)
+    }
+
+    #[inline]
+    fn decode_u8(self) -> Result<u8, C::Error> {
+        self.get()
+    }
+
+    #[inline]
+    fn decode_u16(self) -> Result<u16, C::Error> {
+        self.get()
+    }
+
+    #[inline]
+    fn decode_u32(self) -> Result<u32, C::Error> {
+        self.get()
+    }
+
+    #[inline]
+    fn decode_u64(self) -> Result<u64, C::Error> {
+        self.get()
+    }
+
+    #[inline]
+    fn decode_usize(self) -> Result<usize, C::Error> {
+        self.get()
+    }
+
+    #[inline]
+    fn decode_i8(self) -> Result<i8, C::Error> {
+        self.get()
+    }
+
+    #[inline]
+    fn decode_i16(self) -> Result<i16, C::Error> {
+        self.get()
+    }
+
+    #[inline]
+    fn decode_i32(self) -> Result<i32, C::Error> {
+        self.get()
+    }
+
+    #[inline]
+    fn decode_i64(self) -> Result<i64, C::Error> {
+        self.get()
+    }
+
+    #[inline]
+    fn decode_isize(self) -> Result<isize, C::Error> {
+        self.get()
+    }
+
+    #[inline]
+    fn decode_number<V>(self, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: Visitor<'de, C>,
+    {
+        visitor.visit_u32(self.cx, self.field)
+    }
+
+    #[inline]
+    fn decode_any<V>(self, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: Visitor<'de, C>,
+    {
+        visitor.visit_u32(self.cx, self.field)
+    }
+}
+
+impl<'a, 'de, R, const OPT: Options, C> MapDecoder<'de> for MessageDecoder<'a, R, OPT, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeEntry<'this> = FieldEntryDecoder<'a, 'this, R::Mut<'this>, OPT, C> where Self: 'this;
+    type DecodeRemainingEntries<'this>
+        = MessageDecoder<'a, R::Mut<'this>, OPT, C>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn decode_entry(&mut self) -> Result<Option<Self::DecodeEntry<'_>>, C::Error> {
+        let Some(tag) = self.next_tag()? else {
+            return Ok(None);
+        };
+
+        Ok(Some(FieldEntryDecoder {
+            cx: self.cx,
+            reader: self.reader.borrow_mut(),
+            tag,
+            state: &mut self.state,
+        }))
+    }
+
+    #[inline]
+    fn decode_remaining_entries(&mut self) -> Result<Self::DecodeRemainingEntries<'_>, C::Error> {
+        // The remaining entries take over the state of the message, which
+        // leaves this decoder at its end.
+        Ok(MessageDecoder::new(
+            self.cx,
+            self.reader.borrow_mut(),
+            replace(&mut self.state, State::new(Some(0))),
+        ))
+    }
+}
+
+impl<'a, 'de, R, const OPT: Options, C> EntriesDecoder<'de> for MessageDecoder<'a, R, OPT, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeEntryKey<'this> = KeyDecoder<'a, C> where Self: 'this;
+    type DecodeEntryValue<'this> = FieldDecoder<'a, 'this, R::Mut<'this>, OPT, C> where Self: 'this;
+
+    #[inline]
+    fn decode_entry_key(&mut self) -> Result<Option<Self::DecodeEntryKey<'_>>, C::Error> {
+        let Some(tag) = self.next_tag()? else {
+            return Ok(None);
+        };
+
+        self.current = Some(tag);
+        Ok(Some(KeyDecoder::new(self.cx, tag.field)))
+    }
+
+    #[inline]
+    fn decode_entry_value(&mut self) -> Result<Self::DecodeEntryValue<'_>, C::Error> {
+        self.value()
+    }
+
+    #[inline]
+    fn end_entries(self) -> Result<(), <Self::Cx as Context>::Error> {
+        self.skip_remaining()
+    }
+}
+
+impl<'a, 'de, R, const OPT: Options, C> SequenceDecoder<'de> for MessageDecoder<'a, R, OPT, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeNext<'this> = FieldDecoder<'a, 'this, R::Mut<'this>, OPT, C> where Self: 'this;
+
+    #[inline]
+    fn try_decode_next(&mut self) -> Result<Option<Self::DecodeNext<'_>>, C::Error> {
+        let Some(tag) = self.next_tag()? else {
+            return Ok(None);
+        };
+
+        // Packed values are numbered from 1 in the order they are decoded.
+        self.next += 1;
+
+        if tag.field != self.next {
+            return Err(self.cx.message(format_args!(
+                "Expected field {}, but got {}",
+                self.next, tag.field
+            )));
+        }
+
+        self.current = Some(tag);
+        Ok(Some(self.value()?))
+    }
+
+    #[inline]
+    fn decode_next(&mut self) -> Result<Self::DecodeNext<'_>, C::Error> {
+        let cx = self.cx;
+
+        let Some(decoder) = self.try_decode_next()? else {
+            return Err(cx.message("No remaining fields"));
+        };
+
+        Ok(decoder)
+    }
+}
+
+impl<'a, 'de, R, const OPT: Options, C> VariantDecoder<'de> for MessageDecoder<'a, R, OPT, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeTag<'this> = KeyDecoder<'a, C> where Self: 'this;
+    type DecodeValue<'this> = FieldDecoder<'a, 'this, R::Mut<'this>, OPT, C> where Self: 'this;
+
+    #[inline]
+    fn decode_tag(&mut self) -> Result<Self::DecodeTag<'_>, C::Error> {
+        let Some(tag) = self.next_tag()? else {
+            return Err(self.cx.message("Expected a field for the variant"));
+        };
+
+        self.current = Some(tag);
+        Ok(KeyDecoder::new(self.cx, tag.field))
+    }
+
+    #[inline]
+    fn decode_value(&mut self) -> Result<Self::DecodeValue<'_>, C::Error> {
+        self.value()
+    }
+}
+
+impl<'a, 'b, 'de, R, const OPT: Options, C> EntryDecoder<'de>
+    for FieldEntryDecoder<'a, 'b, R, OPT, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeKey<'this> = KeyDecoder<'a, C> where Self: 'this;
+    type DecodeValue = FieldDecoder<'a, 'b, R, OPT, C>;
+
+    #[inline]
+    fn decode_key(&mut self) -> Result<Self::DecodeKey<'_>, C::Error> {
+        Ok(KeyDecoder::new(self.cx, self.tag.field))
+    }
+
+    #[inline]
+    fn decode_value(self) -> Result<Self::DecodeValue, C::Error> {
+        Ok(FieldDecoder::new(
+            self.cx,
+            self.reader,
+            self.tag,
+            Kind::Field,
+            self.state,
+        ))
+    }
+}
+
+impl<'a, 'b, 'de, R, const OPT: Options, C> SequenceDecoder<'de>
+    for RepeatedDecoder<'a, 'b, R, OPT, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeNext<'this> = FieldDecoder<'a, 'this, R::Mut<'this>, OPT, C> where Self: 'this;
+
+    #[inline]
+    fn try_decode_next(&mut self) -> Result<Option<Self::DecodeNext<'_>>, C::Error> {
+        let Some(kind) = self.advance()? else {
+            return Ok(None);
+        };
+
+        self.count += 1;
+        self.cx.check_length(self.count)?;
+
+        Ok(Some(FieldDecoder::new(
+            self.cx,
+            self.reader.borrow_mut(),
+            self.tag,
+            kind,
+            self.state,
+        )))
+    }
+
+    #[inline]
+    fn decode_next(&mut self) -> Result<Self::DecodeNext<'_>, C::Error> {
+        let cx = self.cx;
+
+        let Some(decoder) = self.try_decode_next()? else {
+            return Err(cx.message("No remaining elements"));
+        };
+
+        Ok(decoder)
+    }
+}
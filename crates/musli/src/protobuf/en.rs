@@ -0,0 +1,947 @@
+use core::fmt;
+
+use crate::en::{
+    Encoder, EntriesEncoder, EntryEncoder, MapEncoder, SequenceEncoder, VariantEncoder,
+};
+use crate::hint::{MapHint, SequenceHint};
+use crate::int::continuation as c;
+use crate::writer::BufWriter;
+use crate::{Context, Encode, Options, Writer};
+
+use super::tag::{self, WireType, MAX_FIELD};
+
+/// A protobuf encoder for the top-level message.
+pub struct ProtobufEncoder<'a, W, const OPT: Options, C: ?Sized> {
+    cx: &'a C,
+    writer: W,
+}
+
+impl<'a, W, const OPT: Options, C: ?Sized> ProtobufEncoder<'a, W, OPT, C> {
+    /// Construct a new protobuf encoder.
+    #[inline]
+    pub(crate) fn new(cx: &'a C, writer: W) -> Self {
+        Self { cx, writer }
+    }
+}
+
+/// Encoder for the fields of a message.
+///
+/// Since the length of a nested message has to be written before its fields,
+/// they are buffered until the message is finished.
+pub struct MessageEncoder<'a, W, const OPT: Options, C>
+where
+    C: ?Sized + Context,
+{
+    cx: &'a C,
+    writer: W,
+    /// The field number of the message, or `None` for the top-level message
+    /// which is not length-prefixed.
+    field: Option<u32>,
+    buffer: BufWriter<'a, C::Allocator>,
+    /// The field number most recently encoded as a key.
+    key: Option<u32>,
+    /// The field number of the last element in a packed message.
+    next: u32,
+}
+
+impl<'a, W, const OPT: Options, C> MessageEncoder<'a, W, OPT, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    #[inline]
+    fn new(cx: &'a C, writer: W, field: Option<u32>) -> Self {
+        Self {
+            cx,
+            writer,
+            field,
+            buffer: BufWriter::new(cx.alloc()),
+            key: None,
+            next: 0,
+        }
+    }
+
+    #[inline]
+    fn value(
+        &mut self,
+    ) -> Result<FieldEncoder<'a, &mut BufWriter<'a, C::Allocator>, OPT, C>, C::Error> {
+        let Some(field) = self.key.take() else {
+            return Err(self.cx.message("Missing field number"));
+        };
+
+        Ok(FieldEncoder::new(self.cx, &mut self.buffer, field))
+    }
+
+    fn finish(mut self) -> Result<(), C::Error> {
+        let buffer = self.buffer.into_inner();
+
+        if let Some(field) = self.field {
+            tag::encode(self.cx, self.writer.borrow_mut(), field, WireType::Len)?;
+            c::encode(self.cx, self.writer.borrow_mut(), buffer.len())?;
+        }
+
+        self.writer.extend(self.cx, buffer)
+    }
+}
+
+/// Encoder for a single field in a message.
+pub struct FieldEntryEncoder<'a, 'b, const OPT: Options, C>
+where
+    C: ?Sized + Context,
+{
+    cx: &'a C,
+    buffer: &'b mut BufWriter<'a, C::Allocator>,
+    key: Option<u32>,
+}
+
+/// Encoder for a field number.
+pub struct KeyEncoder<'a, 'b, C: ?Sized> {
+    cx: &'a C,
+    field: &'b mut Option<u32>,
+}
+
+impl<'a, 'b, C> KeyEncoder<'a, 'b, C>
+where
+    C: ?Sized + Context,
+{
+    #[inline]
+    fn new(cx: &'a C, field: &'b mut Option<u32>) -> Self {
+        Self { cx, field }
+    }
+
+    #[inline]
+    fn set<T>(self, value: T) -> Result<(), C::Error>
+    where
+        T: Copy + fmt::Display + TryInto<u32>,
+    {
+        match value.try_into() {
+            Ok(field) if (1..=MAX_FIELD).contains(&field) => {
+                *self.field = Some(field);
+                Ok(())
+            }
+            _ => Err(self.cx.message(format_args!(
+                "Field number {value} is not in the range 1 to {MAX_FIELD}"
+            ))),
+        }
+    }
+}
+
+/// Encoder for the value of a field, which is prefixed with its tag.
+pub struct FieldEncoder<'a, W, const OPT: Options, C: ?Sized> {
+    cx: &'a C,
+    writer: W,
+    field: u32,
+}
+
+impl<'a, W, const OPT: Options, C> FieldEncoder<'a, W, OPT, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    #[inline]
+    fn new(cx: &'a C, writer: W, field: u32) -> Self {
+        Self { cx, writer, field }
+    }
+
+    #[inline]
+    fn encode_varint(mut self, value: u64) -> Result<(), C::Error> {
+        tag::encode(
+            self.cx,
+            self.writer.borrow_mut(),
+            self.field,
+            WireType::Varint,
+        )?;
+        c::encode(self.cx, self.writer, value)
+    }
+
+    #[inline]
+    fn write_fixed(mut self, wire: WireType, bytes: &[u8]) -> Result<(), C::Error> {
+        tag::encode(self.cx, self.writer.borrow_mut(), self.field, wire)?;
+        self.writer.write_bytes(self.cx, bytes)
+    }
+
+    #[inline]
+    fn encode_len(&mut self, len: usize) -> Result<(), C::Error> {
+        tag::encode(self.cx, self.writer.borrow_mut(), self.field, WireType::Len)?;
+        c::encode(self.cx, self.writer.borrow_mut(), len)
+    }
+}
+
+/// Encoder for the elements of a repeated field.
+///
+/// Scalar elements are buffered and written as a single packed field once the
+/// sequence is finished, while length-delimited elements are written as one
+/// field each.
+pub struct RepeatedEncoder<'a, W, const OPT: Options, C>
+where
+    C: ?Sized + Context,
+{
+    cx: &'a C,
+    writer: W,
+    field: u32,
+    packed: BufWriter<'a, C::Allocator>,
+}
+
+/// Encoder for an element in a repeated field.
+pub struct ElementEncoder<'a, W, P, const OPT: Options, C: ?Sized> {
+    cx: &'a C,
+    writer: W,
+    packed: P,
+    field: u32,
+}
+
+impl<'a, W, P, const OPT: Options, C> ElementEncoder<'a, W, P, OPT, C>
+where
+    W: Writer,
+    P: Writer,
+    C: ?Sized + Context,
+{
+    #[inline]
+    fn new(cx: &'a C, writer: W, packed: P, field: u32) -> Self {
+        Self {
+            cx,
+            writer,
+            packed,
+            field,
+        }
+    }
+
+    #[inline]
+    fn encode_varint(self, value: u64) -> Result<(), C::Error> {
+        c::encode(self.cx, self.packed, value)
+    }
+
+    #[inline]
+    fn write_fixed(mut self, bytes: &[u8]) -> Result<(), C::Error> {
+        self.packed.write_bytes(self.cx, bytes)
+    }
+
+    #[inline]
+    fn into_field(self) -> FieldEncoder<'a, W, OPT, C> {
+        FieldEncoder::new(self.cx, self.writer, self.field)
+    }
+}
+
+#[crate::encoder(crate)]
+impl<'a, W, const OPT: Options, C> Encoder for ProtobufEncoder<'a, W, OPT, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Ok = ();
+    type Mode = C::Mode;
+    type WithContext<'this, U> = ProtobufEncoder<'this, W, OPT, U> where U: 'this + Context;
+    type EncodePack = MessageEncoder<'a, W, OPT, C>;
+    type EncodeMap = MessageEncoder<'a, W, OPT, C>;
+    type EncodeMapEntries = MessageEncoder<'a, W, OPT, C>;
+    type EncodeVariant = MessageEncoder<'a, W, OPT, C>;
+
+    #[inline]
+    fn cx(&self) -> &Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn with_context<U>(self, cx: &U) -> Result<Self::WithContext<'_, U>, C::Error>
+    where
+        U: Context,
+    {
+        Ok(ProtobufEncoder::new(cx, self.writer))
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "message supported by the protobuf encoder")
+    }
+
+    #[inline]
+    fn encode<T>(self, value: T) -> Result<Self::Ok, C::Error>
+    where
+        T: Encode<Self::Mode>,
+    {
+        value.encode(self.cx, self)
+    }
+
+    #[inline]
+    fn encode_empty(self) -> Result<Self::Ok, C::Error> {
+        Ok(())
+    }
+
+    #[inline]
+    fn encode_pack(self) -> Result<Self::EncodePack, C::Error> {
+        Ok(MessageEncoder::new(self.cx, self.writer, None))
+    }
+
+    #[inline]
+    fn encode_map(self, _: &MapHint) -> Result<Self::EncodeMap, C::Error> {
+        Ok(MessageEncoder::new(self.cx, self.writer, None))
+    }
+
+    #[inline]
+    fn encode_map_entries(self, _: &MapHint) -> Result<Self::EncodeMapEntries, C::Error> {
+        Ok(MessageEncoder::new(self.cx, self.writer, None))
+    }
+
+    #[inline]
+    fn encode_variant(self) -> Result<Self::EncodeVariant, C::Error> {
+        Ok(MessageEncoder::new(self.cx, self.writer, None))
+    }
+}
+
+#[crate::encoder(crate)]
+impl<'a, W, const OPT: Options, C> Encoder for FieldEncoder<'a, W, OPT, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Ok = ();
+    type Mode = C::Mode;
+    type WithContext<'this, U> = FieldEncoder<'this, W, OPT, U> where U: 'this + Context;
+    type EncodePack = MessageEncoder<'a, W, OPT, C>;
+    type EncodeSome = Self;
+    type EncodeSequence = RepeatedEncoder<'a, W, OPT, C>;
+    type EncodeMap = MessageEncoder<'a, W, OPT, C>;
+    type EncodeMapEntries = MessageEncoder<'a, W, OPT, C>;
+    type EncodeVariant = MessageEncoder<'a, W, OPT, C>;
+
+    #[inline]
+    fn cx(&self) -> &Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn with_context<U>(self, cx: &U) -> Result<Self::WithContext<'_, U>, C::Error>
+    where
+        U: Context,
+    {
+        Ok(FieldEncoder::new(cx, self.writer, self.field))
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "type supported by the protobuf encoder")
+    }
+
+    #[inline]
+    fn encode<T>(self, value: T) -> Result<Self::Ok, C::Error>
+    where
+        T: Encode<Self::Mode>,
+    {
+        value.encode(self.cx, self)
+    }
+
+    #[inline]
+    fn encode_empty(mut self) -> Result<Self::Ok, C::Error> {
+        // Empty values are encoded as empty messages, so that they are
+        // distinguishable from absent fields.
+        self.encode_len(0)
+    }
+
+    #[inline]
+    fn encode_pack(self) -> Result<Self::EncodePack, C::Error> {
+        Ok(MessageEncoder::new(self.cx, self.writer, Some(self.field)))
+    }
+
+    #[inline]
+    fn encode_array<const N: usize>(mut self, array: &[u8; N]) -> Result<Self::Ok, C::Error> {
+        self.encode_len(N)?;
+        self.writer.write_bytes(self.cx, array)
+    }
+
+    #[inline]
+    fn encode_fixed<const N: usize>(self, bytes: &[u8; N]) -> Result<Self::Ok, C::Error> {
+        match WireType::fixed(N) {
+            Some(wire) => self.write_fixed(wire, bytes),
+            None => self.encode_array(bytes),
+        }
+    }
+
+    #[inline]
+    fn encode_bytes(mut self, bytes: &[u8]) -> Result<Self::Ok, C::Error> {
+        self.encode_len(bytes.len())?;
+        self.writer.write_bytes(self.cx, bytes)
+    }
+
+    #[inline]
+    fn encode_bytes_vectored<I>(mut self, len: usize, vectors: I) -> Result<Self::Ok, C::Error>
+    where
+        I: IntoIterator<Item: AsRef<[u8]>>,
+    {
+        self.encode_len(len)?;
+
+        for bytes in vectors {
+            self.writer.write_bytes(self.cx, bytes.as_ref())?;
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn encode_string(self, string: &str) -> Result<Self::Ok, C::Error> {
+        self.encode_bytes(string.as_bytes())
+    }
+
+    #[inline]
+    fn collect_string<T>(self, value: &T) -> Result<Self::Ok, <Self::Cx as Context>::Error>
+    where
+        T: ?Sized + fmt::Display,
+    {
+        let buf = self.cx.collect_string(value)?;
+        self.encode_string(buf.as_ref())
+    }
+
+    #[inline]
+    fn encode_bool(self, value: bool) -> Result<Self::Ok, C::Error> {
+        self.encode_varint(value.into())
+    }
+
+    #[inline]
+    fn encode_char(self, value: char) -> Result<Self::Ok, C::Error> {
+        self.encode_string(value.encode_utf8(&mut [0, 0, 0, 0]))
+    }
+
+    #[inline]
+    fn encode_u8(self, value: u8) -> Result<Self::Ok, C::Error> {
+        self.encode_varint(value.into())
+    }
+
+    #[inline]
+    fn encode_u16(self, value: u16) -> Result<Self::Ok, C::Error> {
+        self.encode_varint(value.into())
+    }
+
+    #[inline]
+    fn encode_u32(self, value: u32) -> Result<Self::Ok, C::Error> {
+        self.encode_varint(value.into())
+    }
+
+    #[inline]
+    fn encode_u64(self, value: u64) -> Result<Self::Ok, C::Error> {
+        self.encode_varint(value)
+    }
+
+    #[inline]
+    fn encode_usize(self, value: usize) -> Result<Self::Ok, C::Error> {
+        self.encode_varint(value as u64)
+    }
+
+    #[inline]
+    fn encode_i8(self, value: i8) -> Result<Self::Ok, C::Error> {
+        self.encode_varint(value as i64 as u64)
+    }
+
+    #[inline]
+    fn encode_i16(self, value: i16) -> Result<Self::Ok, C::Error> {
+        self.encode_varint(value as i64 as u64)
+    }
+
+    #[inline]
+    fn encode_i32(self, value: i32) -> Result<Self::Ok, C::Error> {
+        self.encode_varint(value as i64 as u64)
+    }
+
+    #[inline]
+    fn encode_i64(self, value: i64) -> Result<Self::Ok, C::Error> {
+        self.encode_varint(value as u64)
+    }
+
+    #[inline]
+    fn encode_isize(self, value: isize) -> Result<Self::Ok, C::Error> {
+        self.encode_varint(value as i64 as u64)
+    }
+
+    #[inline]
+    fn encode_f32(self, value: f32) -> Result<Self::Ok, C::Error> {
+        self.write_fixed(WireType::I32, &value.to_le_bytes())
+    }
+
+    #[inline]
+    fn encode_f64(self, value: f64) -> Result<Self::Ok, C::Error> {
+        self.write_fixed(WireType::I64, &value.to_le_bytes())
+    }
+
+    #[inline]
+    fn encode_some(self) -> Result<Self::EncodeSome, C::Error> {
+        Ok(self)
+    }
+
+    #[inline]
+    fn encode_none(self) -> Result<Self::Ok, C::Error> {
+        Ok(())
+    }
+
+    #[inline]
+    fn encode_sequence(self, _: &SequenceHint) -> Result<Self::EncodeSequence, C::Error> {
+        Ok(RepeatedEncoder {
+            cx: self.cx,
+            writer: self.writer,
+            field: self.field,
+            packed: BufWriter::new(self.cx.alloc()),
+        })
+    }
+
+    #[inline]
+    fn encode_map(self, _: &MapHint) -> Result<Self::EncodeMap, C::Error> {
+        Ok(MessageEncoder::new(self.cx, self.writer, Some(self.field)))
+    }
+
+    #[inline]
+    fn encode_map_entries(self, _: &MapHint) -> Result<Self::EncodeMapEntries, C::Error> {
+        Ok(MessageEncoder::new(self.cx, self.writer, Some(self.field)))
+    }
+
+    #[inline]
+    fn encode_variant(self) -> Result<Self::EncodeVariant, C::Error> {
+        Ok(MessageEncoder::new(self.cx, self.writer, Some(self.field)))
+    }
+}
+
+#[crate::encoder(crate)]
+impl<'a, W, P, const OPT: Options, C> Encoder for ElementEncoder<'a, W, P, OPT, C>
+where
+    W: Writer,
+    P: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Ok = ();
+    type Mode = C::Mode;
+    type WithContext<'this, U> = ElementEncoder<'this, W, P, OPT, U> where U: 'this + Context;
+    type EncodePack = MessageEncoder<'a, W, OPT, C>;
+    type EncodeMap = MessageEncoder<'a, W, OPT, C>;
+    type EncodeMapEntries = MessageEncoder<'a, W, OPT, C>;
+    type EncodeVariant = MessageEncoder<'a, W, OPT, C>;
+
+    #[inline]
+    fn cx(&self) -> &Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn with_context<U>(self, cx: &U) -> Result<Self::WithContext<'_, U>, C::Error>
+    where
+        U: Context,
+    {
+        Ok(ElementEncoder::new(
+            cx,
+            self.writer,
+            self.packed,
+            self.field,
+        ))
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "type supported in a repeated protobuf field")
+    }
+
+    #[inline]
+    fn encode<T>(self, value: T) -> Result<Self::Ok, C::Error>
+    where
+        T: Encode<Self::Mode>,
+    {
+        value.encode(self.cx, self)
+    }
+
+    #[inline]
+    fn encode_empty(self) -> Result<Self::Ok, C::Error> {
+        self.into_field().encode_empty()
+    }
+
+    #[inline]
+    fn encode_pack(self) -> Result<Self::EncodePack, C::Error> {
+        self.into_field().encode_pack()
+    }
+
+    #[inline]
+    fn encode_array<const N: usize>(self, array: &[u8; N]) -> Result<Self::Ok, C::Error> {
+        self.into_field().encode_array(array)
+    }
+
+    #[inline]
+    fn encode_fixed<const N: usize>(self, bytes: &[u8; N]) -> Result<Self::Ok, C::Error> {
+        match WireType::fixed(N) {
+            Some(..) => self.write_fixed(bytes),
+            None => self.into_field().encode_array(bytes),
+        }
+    }
+
+    #[inline]
+    fn encode_bytes(self, bytes: &[u8]) -> Result<Self::Ok, C::Error> {
+        self.into_field().encode_bytes(bytes)
+    }
+
+    #[inline]
+    fn encode_bytes_vectored<I>(self, len: usize, vectors: I) -> Result<Self::Ok, C::Error>
+    where
+        I: IntoIterator<Item: AsRef<[u8]>>,
+    {
+        self.into_field().encode_bytes_vectored(len, vectors)
+    }
+
+    #[inline]
+    fn encode_string(self, string: &str) -> Result<Self::Ok, C::Error> {
+        self.into_field().encode_string(string)
+    }
+
+    #[inline]
+    fn collect_string<T>(self, value: &T) -> Result<Self::Ok, <Self::Cx as Context>::Error>
+    where
+        T: ?Sized + fmt::Display,
+    {
+        self.into_field().collect_string(value)
+    }
+
+    #[inline]
+    fn encode_bool(self, value: bool) -> Result<Self::Ok, C::Error> {
+        self.encode_varint(value.into())
+    }
+
+    #[inline]
+    fn encode_char(self, value: char) -> Result<Self::Ok, C::Error> {
+        self.into_field().encode_char(value)
+    }
+
+    #[inline]
+    fn encode_u8(self, value: u8) -> Result<Self::Ok, C::Error> {
+        self.encode_varint(value.into())
+    }
+
+    #[inline]
+    fn encode_u16(self, value: u16) -> Result<Self::Ok, C::Error> {
+        self.encode_varint(value.into())
+    }
+
+    #[inline]
+    fn encode_u32(self, value: u32) -> Result<Self::Ok, C::Error> {
+        self.encode_varint(value.into())
+    }
+
+    #[inline]
+    fn encode_u64(self, value: u64) -> Result<Self::Ok, C::Error> {
+        self.encode_varint(value)
+    }
+
+    #[inline]
+    fn encode_usize(self, value: usize) -> Result<Self::Ok, C::Error> {
+        self.encode_varint(value as u64)
+    }
+
+    #[inline]
+    fn encode_i8(self, value: i8) -> Result<Self::Ok, C::Error> {
+        self.encode_varint(value as i64 as u64)
+    }
+
+    #[inline]
+    fn encode_i16(self, value: i16) -> Result<Self::Ok, C::Error> {
+        self.encode_varint(value as i64 as u64)
+    }
+
+    #[inline]
+    fn encode_i32(self, value: i32) -> Result<Self::Ok, C::Error> {
+        self.encode_varint(value as i64 as u64)
+    }
+
+    #[inline]
+    fn encode_i64(self, value: i64) -> Result<Self::Ok, C::Error> {
+        self.encode_varint(value as u64)
+    }
+
+    #[inline]
+    fn encode_isize(self, value: isize) -> Result<Self::Ok, C::Error> {
+        self.encode_varint(value as i64 as u64)
+    }
+
+    #[inline]
+    fn encode_f32(self, value: f32) -> Result<Self::Ok, C::Error> {
+        self.write_fixed(&value.to_le_bytes())
+    }
+
+    #[inline]
+    fn encode_f64(self, value: f64) -> Result<Self::Ok, C::Error> {
+        self.write_fixed(&value.to_le_bytes())
+    }
+
+    #[inline]
+    fn encode_map(self, hint: &MapHint) -> Result<Self::EncodeMap, C::Error> {
+        self.into_field().encode_map(hint)
+    }
+
+    #[inline]
+    fn encode_map_entries(self, hint: &MapHint) -> Result<Self::EncodeMapEntries, C::Error> {
+        self.into_field().encode_map_entries(hint)
+    }
+
+    #[inline]
+    fn encode_variant(self) -> Result<Self::EncodeVariant, C::Error> {
+        self.into_field().encode_variant()
+    }
+}
+
+#[crate::encoder(crate)]
+impl<'a, 'b, C> Encoder for KeyEncoder<'a, 'b, C>
+where
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Ok = ();
+    type Mode = C::Mode;
+    type WithContext<'this, U> = KeyEncoder<'this, 'b, U> where U: 'this + Context;
+
+    #[inline]
+    fn cx(&self) -> &Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn with_context<U>(self, cx: &U) -> Result<Self::WithContext<'_, U>, C::Error>
+    where
+        U: Context,
+    {
+        Ok(KeyEncoder::new(cx, self.field))
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "integer field number")
+    }
+
+    #[inline]
+    fn encode<T>(self, value: T) -> Result<Self::Ok, C::Error>
+    where
+        T: Encode<Self::Mode>,
+    {
+        value.encode(self.cx, self)
+    }
+
+    #[inline]
+    fn encode_u8(self, value: u8) -> Result<Self::Ok, C::Error> {
+        self.set(value)
+    }
+
+    #[inline]
+    fn encode_u16(self, value: u16) -> Result<Self::Ok, C::Error> {
+        self.set(value)
+    }
+
+    #[inline]
+    fn encode_u32(self, value: u32) -> Result<Self::Ok, C::Error> {
+        self.set(value)
+    }
+
+    #[inline]
+    fn encode_u64(self, value: u64) -> Result<Self::Ok, C::Error> {
+        self.set(value)
+    }
+
+    #[inline]
+    fn encode_usize(self, value: usize) -> Result<Self::Ok, C::Error> {
+        self.set(value)
+    }
+
+    #[inline]
+    fn encode_i8(self, value: i8) -> Result<Self::Ok, C::Error> {
+        self.set(value)
+    }
+
+    #[inline]
+    fn encode_i16(self, value: i16) -> Result<Self::Ok, C::Error> {
+        self.set(value)
+    }
+
+    #[inline]
+    fn encode_i32(self, value: i32) -> Result<Self::Ok, C::Error> {
+        self.set(value)
+    }
+
+    #[inline]
+    fn encode_i64(self, value: i64) -> Result<Self::Ok, C::Error> {
+        self.set(value)
+    }
+
+    #[inline]
+    fn encode_isize(self, value: isize) -> Result<Self::Ok, C::Error> {
+        self.set(value)
+    }
+}
+
+impl<'a, W, const OPT: Options, C> MapEncoder for MessageEncoder<'a, W, OPT, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Ok = ();
+    type EncodeEntry<'this> = FieldEntryEncoder<'a, 'this, OPT, C> where Self: 'this;
+
+    #[inline]
+    fn encode_entry(&mut self) -> Result<Self::EncodeEntry<'_>, C::Error> {
+        Ok(FieldEntryEncoder {
+            cx: self.cx,
+            buffer: &mut self.buffer,
+            key: None,
+        })
+    }
+
+    #[inline]
+    fn finish_map(self) -> Result<Self::Ok, C::Error> {
+        self.finish()
+    }
+}
+
+impl<'a, W, const OPT: Options, C> EntriesEncoder for MessageEncoder<'a, W, OPT, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Ok = ();
+    type EncodeEntryKey<'this> = KeyEncoder<'a, 'this, C> where Self: 'this;
+    type EncodeEntryValue<'this>
+        = FieldEncoder<'a, &'this mut BufWriter<'a, C::Allocator>, OPT, C>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn encode_entry_key(&mut self) -> Result<Self::EncodeEntryKey<'_>, C::Error> {
+        Ok(KeyEncoder::new(self.cx, &mut self.key))
+    }
+
+    #[inline]
+    fn encode_entry_value(&mut self) -> Result<Self::EncodeEntryValue<'_>, C::Error> {
+        self.value()
+    }
+
+    #[inline]
+    fn finish_entries(self) -> Result<Self::Ok, C::Error> {
+        self.finish()
+    }
+}
+
+impl<'a, W, const OPT: Options, C> SequenceEncoder for MessageEncoder<'a, W, OPT, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Ok = ();
+    type EncodeNext<'this>
+        = FieldEncoder<'a, &'this mut BufWriter<'a, C::Allocator>, OPT, C>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn encode_next(&mut self) -> Result<Self::EncodeNext<'_>, C::Error> {
+        // Packed values are numbered from 1 in the order they are encoded.
+        self.next += 1;
+        Ok(FieldEncoder::new(self.cx, &mut self.buffer, self.next))
+    }
+
+    #[inline]
+    fn finish_sequence(self) -> Result<Self::Ok, C::Error> {
+        self.finish()
+    }
+}
+
+impl<'a, W, const OPT: Options, C> VariantEncoder for MessageEncoder<'a, W, OPT, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Ok = ();
+    type EncodeTag<'this> = KeyEncoder<'a, 'this, C> where Self: 'this;
+    type EncodeData<'this>
+        = FieldEncoder<'a, &'this mut BufWriter<'a, C::Allocator>, OPT, C>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn encode_tag(&mut self) -> Result<Self::EncodeTag<'_>, C::Error> {
+        Ok(KeyEncoder::new(self.cx, &mut self.key))
+    }
+
+    #[inline]
+    fn encode_data(&mut self) -> Result<Self::EncodeData<'_>, C::Error> {
+        self.value()
+    }
+
+    #[inline]
+    fn finish_variant(self) -> Result<Self::Ok, C::Error> {
+        self.finish()
+    }
+}
+
+impl<'a, 'b, const OPT: Options, C> EntryEncoder for FieldEntryEncoder<'a, 'b, OPT, C>
+where
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Ok = ();
+    type EncodeKey<'this> = KeyEncoder<'a, 'this, C> where Self: 'this;
+    type EncodeValue<'this>
+        = FieldEncoder<'a, &'this mut BufWriter<'a, C::Allocator>, OPT, C>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn encode_key(&mut self) -> Result<Self::EncodeKey<'_>, C::Error> {
+        Ok(KeyEncoder::new(self.cx, &mut self.key))
+    }
+
+    #[inline]
+    fn encode_value(&mut self) -> Result<Self::EncodeValue<'_>, C::Error> {
+        let Some(field) = self.key.take() else {
+            return Err(self.cx.message("Missing field number"));
+        };
+
+        Ok(FieldEncoder::new(self.cx, &mut *self.buffer, field))
+    }
+
+    #[inline]
+    fn finish_entry(self) -> Result<Self::Ok, C::Error> {
+        Ok(())
+    }
+}
+
+impl<'a, W, const OPT: Options, C> SequenceEncoder for RepeatedEncoder<'a, W, OPT, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Ok = ();
+    type EncodeNext<'this>
+        = ElementEncoder<'a, W::Mut<'this>, &'this mut BufWriter<'a, C::Allocator>, OPT, C>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn encode_next(&mut self) -> Result<Self::EncodeNext<'_>, C::Error> {
+        Ok(ElementEncoder::new(
+            self.cx,
+            self.writer.borrow_mut(),
+            &mut self.packed,
+            self.field,
+        ))
+    }
+
+    #[inline]
+    fn finish_sequence(mut self) -> Result<Self::Ok, C::Error> {
+        let packed = self.packed.into_inner();
+
+        if packed.is_empty() {
+            return Ok(());
+        }
+
+        tag::encode(self.cx, self.writer.borrow_mut(), self.field, WireType::Len)?;
+        c::encode(self.cx, self.writer.borrow_mut(), packed.len())?;
+        self.writer.extend(self.cx, packed)
+    }
+}
@@ -0,0 +1,128 @@
+//! Module that defines [`Encoding`] whith allows for customization of the
+//! encoding format, and the [`DEFAULT`] encoding configuration.
+
+use core::marker;
+
+use crate::mode::Binary;
+use crate::options;
+use crate::{IntoReader, Options};
+
+use super::de::ProtobufDecoder;
+use super::en::ProtobufEncoder;
+use super::error::Error;
+
+/// The default flavor used by the [`DEFAULT`] configuration.
+pub const OPTIONS: options::Options = options::new().build();
+
+/// The default configuration.
+///
+/// The protobuf wire format is fixed, so the options currently have no effect
+/// on the encoding.
+pub const DEFAULT: Encoding = Encoding::new();
+
+crate::macros::bare_encoding!(
+    Binary,
+    DEFAULT,
+    protobuf,
+    IntoReader,
+    name_type = u32,
+    names = [1, 2],
+    to_end = true
+);
+
+/// Setting up encoding with parameters.
+pub struct Encoding<const OPT: Options = OPTIONS, M = Binary>
+where
+    M: 'static,
+{
+    _marker: marker::PhantomData<M>,
+}
+
+impl Default for Encoding<OPTIONS, Binary> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Encoding<OPTIONS, Binary> {
+    /// Construct a new [`Encoding`] instance.
+    ///
+    /// ```
+    /// use musli::{Encode, Decode};
+    /// use musli::protobuf::Encoding;
+    /// # use musli::protobuf::Error;
+    ///
+    /// const CONFIG: Encoding = Encoding::new();
+    ///
+    /// #[derive(Debug, PartialEq, Encode, Decode)]
+    /// #[musli(name_type = u32)]
+    /// struct Person<'a> {
+    ///     #[musli(name = 1)]
+    ///     name: &'a str,
+    ///     #[musli(name = 2)]
+    ///     age: u32,
+    /// }
+    ///
+    /// let mut out = Vec::new();
+    ///
+    /// let expected = Person {
+    ///     name: "Aristotle",
+    ///     age: 61,
+    /// };
+    ///
+    /// CONFIG.encode(&mut out, &expected)?;
+    /// let actual = CONFIG.decode(&out[..])?;
+    ///
+    /// assert_eq!(expected, actual);
+    /// # Ok::<_, Error>(())
+    /// ```
+    pub const fn new() -> Self {
+        Encoding {
+            _marker: marker::PhantomData,
+        }
+    }
+}
+
+impl<const OPT: Options, M> Encoding<OPT, M>
+where
+    M: 'static,
+{
+    /// Change the mode of the encoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::protobuf::{OPTIONS, Encoding};
+    ///
+    /// enum Custom {}
+    ///
+    /// const CONFIG: Encoding<OPTIONS, Custom> = Encoding::new().with_mode();
+    /// ```
+    pub const fn with_mode<T>(self) -> Encoding<OPT, T> {
+        Encoding {
+            _marker: marker::PhantomData,
+        }
+    }
+
+    crate::macros::encoding_impls!(
+        M,
+        protobuf,
+        ProtobufEncoder::<_, OPT, _>::new,
+        ProtobufDecoder::<_, OPT, _>::new,
+        IntoReader::into_reader,
+        Reader,
+        name_type = u32,
+        names = [1, 2],
+        to_end = true,
+    );
+}
+
+impl<const OPT: Options, M> Clone for Encoding<OPT, M> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<const OPT: Options, M> Copy for Encoding<OPT, M> {}
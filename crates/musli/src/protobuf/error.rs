@@ -0,0 +1,23 @@
+crate::macros::error_type! {
+    /// Error raised when encoding or decoding protobuf messages.
+    pub struct Error;
+
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::Decode;
+    /// use musli::context::ErrorKind;
+    ///
+    /// #[derive(Debug, Decode)]
+    /// #[musli(name_type = u32)]
+    /// struct Message {
+    ///     #[musli(name = 1)]
+    ///     id: u32,
+    /// }
+    ///
+    /// let error = musli::protobuf::from_slice::<Message>(b"\x08").unwrap_err();
+    /// assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    /// ```
+    pub fn kind;
+}
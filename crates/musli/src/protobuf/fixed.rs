@@ -0,0 +1,51 @@
+//! Encode a field using the `fixed32`, `sfixed32`, `fixed64` or `sfixed64`
+//! protobuf types.
+//!
+//! This is used through the `#[musli(with = musli::protobuf::fixed)]`
+//! attribute, and behaves like wrapping the field in [`Fixed`].
+//!
+//! [`Fixed`]: super::Fixed
+//!
+//! # Examples
+//!
+//! ```
+//! use musli::{Encode, Decode};
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! #[musli(name_type = u32)]
+//! struct Message {
+//!     #[musli(name = 1, with = musli::protobuf::fixed)]
+//!     value: i64,
+//! }
+//!
+//! let bytes = musli::protobuf::to_vec(&Message { value: -1 })?;
+//! assert_eq!(bytes, b"\x09\xff\xff\xff\xff\xff\xff\xff\xff");
+//! assert_eq!(musli::protobuf::from_slice::<Message>(&bytes)?, Message { value: -1 });
+//! # Ok::<_, musli::protobuf::Error>(())
+//! ```
+
+use crate::de::{Decode, Decoder};
+use crate::en::{Encode, Encoder};
+
+use super::Fixed;
+
+/// Encode an integer as fixed-width little-endian bytes.
+#[inline]
+pub fn encode<E, T>(value: &T, _: &E::Cx, encoder: E) -> Result<E::Ok, E::Error>
+where
+    E: Encoder,
+    T: Copy,
+    Fixed<T>: Encode<E::Mode>,
+{
+    encoder.encode(Fixed(*value))
+}
+
+/// Decode an integer from fixed-width little-endian bytes.
+#[inline]
+pub fn decode<'de, D, T>(_: &D::Cx, decoder: D) -> Result<T, D::Error>
+where
+    D: Decoder<'de>,
+    Fixed<T>: Decode<'de, D::Mode>,
+{
+    Ok(decoder.decode::<Fixed<T>>()?.0)
+}
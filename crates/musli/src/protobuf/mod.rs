@@ -0,0 +1,184 @@
+//! Support for the [protobuf] binary wire format, which allows musli types to
+//! communicate with existing protobuf and gRPC services without generated
+//! code.
+//!
+//! Messages are structs whose fields are given numeric names using
+//! `#[musli(name = N)]`, which are used as the protobuf field numbers. Since
+//! the [`Text`] mode uses string names by default, this also requires
+//! `#[musli(name_type = u32)]` on the container. The
+//! wire type of each field is determined by its Rust type, and can be
+//! customized using the [`Sint`] and [`Fixed`] wrappers or the corresponding
+//! [`sint`] and [`fixed`] modules through `#[musli(with = ..)]`.
+//!
+//! This format is upgrade stable in the same way protobuf is:
+//!
+//! * ✔ Can tolerate missing fields if they are annotated with
+//!   `#[musli(default)]`, which is how proto3 represents fields that are set
+//!   to their default value.
+//! * ✔ Can skip over unknown fields.
+//! * ✔ Can be decoded into dynamic containers such as the [`Value`] type,
+//!   although since protobuf is not self-descriptive, fields are decoded as
+//!   integers or bytes depending on their wire type.
+//! * ✔ Can handle coercion between integer types of different widths and
+//!   signedness as long as the value fits.
+//!
+//! [protobuf]: https://protobuf.dev/programming-guides/encoding/
+//! [`Value`]: crate::value
+//! [`Text`]: crate::mode::Text
+//!
+//! ```
+//! use musli::{Encode, Decode};
+//!
+//! // message Person {
+//! //   string name = 1;
+//! //   uint32 age = 2;
+//! //   repeated string emails = 3;
+//! // }
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! #[musli(name_type = u32)]
+//! struct Person {
+//!     #[musli(name = 1)]
+//!     name: String,
+//!     #[musli(name = 2, default)]
+//!     age: u32,
+//!     #[musli(name = 3, default)]
+//!     emails: Vec<String>,
+//! }
+//!
+//! let person = Person {
+//!     name: String::from("Aristotle"),
+//!     age: 61,
+//!     emails: vec![String::from("a@example.com")],
+//! };
+//!
+//! let bytes = musli::protobuf::to_vec(&person)?;
+//! assert_eq!(bytes, b"\x0a\x09Aristotle\x10\x3d\x1a\x0da@example.com");
+//! assert_eq!(musli::protobuf::from_slice::<Person>(&bytes)?, person);
+//! # Ok::<_, musli::protobuf::Error>(())
+//! ```
+//!
+//! <br>
+//!
+//! ## Type mapping
+//!
+//! Rust types are mapped onto protobuf types like this:
+//!
+//! | Rust type                            | Protobuf type            | Wire type        |
+//! |--------------------------------------|--------------------------|------------------|
+//! | `bool`                               | `bool`                   | varint           |
+//! | `u32`, `u64` and smaller             | `uint32`, `uint64`       | varint           |
+//! | `i32`, `i64` and smaller             | `int32`, `int64`         | varint           |
+//! | [`Sint<i32>`], [`Sint<i64>`]         | `sint32`, `sint64`       | varint (zigzag)  |
+//! | [`Fixed<u32>`], [`Fixed<i32>`]       | `fixed32`, `sfixed32`    | i32              |
+//! | [`Fixed<u64>`], [`Fixed<i64>`]       | `fixed64`, `sfixed64`    | i64              |
+//! | `f32`, `f64`                         | `float`, `double`        | i32, i64         |
+//! | `String`, `char`                     | `string`                 | length-delimited |
+//! | [`Bytes`], `#[musli(bytes)] [u8; N]` | `bytes`                  | length-delimited |
+//! | `[u8; N]`                            | `repeated uint32`        | packed           |
+//! | structs                              | messages                 | length-delimited |
+//! | `Vec<T>` and other sequences         | `repeated`               | packed if scalar |
+//! | enums with only unit variants        | `enum`                   | varint           |
+//! | enums with data                      | a message with a `oneof` | length-delimited |
+//!
+//! Note that a plain `[u8; N]` is a sequence like `Vec<u8>`, so its elements
+//! are encoded as packed varints unless it's annotated with `#[musli(bytes)]`.
+//!
+//! [`Fixed`] is implemented using [`Encoder::encode_fixed`], where values of 4
+//! bytes use the i32 and values of 8 bytes use the i64 wire type. Extension
+//! values, like those of MessagePack, are encoded as `bytes`.
+//!
+//! [`Encoder::encode_fixed`]: crate::Encoder::encode_fixed
+//!
+//! Repeated scalar fields are written using the packed encoding, which is the
+//! default in proto3, while both packed and unpacked fields are accepted when
+//! decoding. `None` and absent fields are not written at all.
+//!
+//! Enums which have data are encoded as a message containing a single field,
+//! whose number is the variant name. This is compatible with a message that
+//! wraps a `oneof`, where unit variants correspond to a field of type
+//! `google.protobuf.Empty`:
+//!
+//! ```
+//! use musli::{Encode, Decode};
+//!
+//! // message Shape {
+//! //   oneof shape {
+//! //     Circle circle = 1;
+//! //     google.protobuf.Empty empty = 2;
+//! //   }
+//! // }
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! #[musli(name_type = u32)]
+//! enum Shape {
+//!     #[musli(name = 1, name_type = u32)]
+//!     Circle {
+//!         #[musli(name = 1)]
+//!         radius: f32,
+//!     },
+//!     #[musli(name = 2)]
+//!     Empty,
+//! }
+//!
+//! let bytes = musli::protobuf::to_vec(&Shape::Circle { radius: 1.0 })?;
+//! assert_eq!(bytes, b"\x0a\x05\x0d\x00\x00\x80\x3f");
+//!
+//! let bytes = musli::protobuf::to_vec(&Shape::Empty)?;
+//! assert_eq!(bytes, b"\x12\x00");
+//! # Ok::<_, musli::protobuf::Error>(())
+//! ```
+//!
+//! <br>
+//!
+//! ## Limitations
+//!
+//! * Every field must be given a field number between 1 and 2<sup>29</sup> -
+//!   1, since the default index-based names start at 0.
+//! * Protobuf `map<K, V>` fields are not supported, and neither are nested
+//!   sequences or 128-bit integers.
+//! * The occurrences of a repeated field must be consecutive, which is how all
+//!   conforming encoders write them. Since they can't be merged into a single
+//!   sequence otherwise, decoding a message where they are not is an error.
+//! * The top-level message is decoded until the end of the input, so it has to
+//!   be decoded from a slice.
+//! * Types which require buffering while decoding, such as internally tagged
+//!   enums, are not supported.
+//!
+//! [`Bytes`]: crate::compat::Bytes
+
+#![cfg(feature = "protobuf")]
+#![cfg_attr(doc_cfg, doc(cfg(feature = "protobuf")))]
+
+#[cfg(test)]
+mod tests;
+
+mod de;
+mod en;
+mod encoding;
+mod error;
+pub mod fixed;
+pub mod sint;
+mod tag;
+mod types;
+
+#[cfg(feature = "test")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "test")))]
+#[doc(hidden)]
+pub mod test;
+
+/// Convenient result alias for use with `musli::protobuf`.
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[doc(inline)]
+pub use self::encoding::to_vec;
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+#[doc(inline)]
+pub use self::encoding::to_writer;
+#[doc(inline)]
+pub use self::encoding::{decode, encode, from_slice, to_fixed_bytes, Encoding, DEFAULT, OPTIONS};
+#[doc(inline)]
+pub use self::error::Error;
+#[doc(inline)]
+pub use self::types::{Fixed, Sint};
@@ -0,0 +1,50 @@
+//! Encode a field using the `sint32` or `sint64` protobuf types.
+//!
+//! This is used through the `#[musli(with = musli::protobuf::sint)]`
+//! attribute, and behaves like wrapping the field in [`Sint`].
+//!
+//! [`Sint`]: super::Sint
+//!
+//! # Examples
+//!
+//! ```
+//! use musli::{Encode, Decode};
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! #[musli(name_type = u32)]
+//! struct Message {
+//!     #[musli(name = 1, with = musli::protobuf::sint)]
+//!     value: i64,
+//! }
+//!
+//! let bytes = musli::protobuf::to_vec(&Message { value: -2 })?;
+//! assert_eq!(bytes, b"\x08\x03");
+//! assert_eq!(musli::protobuf::from_slice::<Message>(&bytes)?, Message { value: -2 });
+//! # Ok::<_, musli::protobuf::Error>(())
+//! ```
+
+use crate::de::{Decode, Decoder};
+use crate::en::{Encode, Encoder};
+
+use super::Sint;
+
+/// Encode a signed integer as a zigzag encoded varint.
+#[inline]
+pub fn encode<E, T>(value: &T, _: &E::Cx, encoder: E) -> Result<E::Ok, E::Error>
+where
+    E: Encoder,
+    T: Copy,
+    Sint<T>: Encode<E::Mode>,
+{
+    encoder.encode(Sint(*value))
+}
+
+/// Decode a signed integer from a zigzag encoded varint.
+#[inline]
+pub fn decode<'de, D, T>(_: &D::Cx, decoder: D) -> Result<T, D::Error>
+where
+    D: Decoder<'de>,
+    Sint<T>: Decode<'de, D::Mode>,
+{
+    Ok(decoder.decode::<Sint<T>>()?.0)
+}
@@ -0,0 +1,79 @@
+//! Field tags used by `musli::protobuf`.
+//!
+//! Every field in a message is prefixed with a tag, which is a varint
+//! combining the field number and the wire type of the value that follows.
+
+use crate::int::continuation as c;
+use crate::{Context, Writer};
+
+/// The largest permitted field number.
+pub(crate) const MAX_FIELD: u32 = (1 << 29) - 1;
+
+/// The wire type of a field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum WireType {
+    /// A variable-length integer.
+    Varint = 0,
+    /// A little-endian 64-bit value.
+    I64 = 1,
+    /// A length-delimited value.
+    Len = 2,
+    /// A little-endian 32-bit value.
+    I32 = 5,
+}
+
+impl WireType {
+    /// The wire type used for fixed-width values of the given size.
+    #[inline]
+    pub(crate) fn fixed(size: usize) -> Option<Self> {
+        match size {
+            4 => Some(WireType::I32),
+            8 => Some(WireType::I64),
+            _ => None,
+        }
+    }
+}
+
+/// A decoded field tag.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Tag {
+    pub(crate) field: u32,
+    pub(crate) wire: WireType,
+}
+
+/// Encode the tag of a field.
+#[inline]
+pub(crate) fn encode<C, W>(cx: &C, writer: W, field: u32, wire: WireType) -> Result<(), C::Error>
+where
+    C: ?Sized + Context,
+    W: Writer,
+{
+    c::encode(cx, writer, (field << 3) | wire as u32)
+}
+
+/// Decode the tag of a field from the varint it's stored as.
+pub(crate) fn decode<C>(cx: &C, tag: u64) -> Result<Tag, C::Error>
+where
+    C: ?Sized + Context,
+{
+    let Ok(tag) = u32::try_from(tag) else {
+        return Err(cx.message(format_args!("Tag {tag} is out of range")));
+    };
+
+    let wire = match tag & 0b111 {
+        0 => WireType::Varint,
+        1 => WireType::I64,
+        2 => WireType::Len,
+        5 => WireType::I32,
+        3 | 4 => return Err(cx.message("Groups are not supported")),
+        wire => return Err(cx.message(format_args!("Unsupported wire type {wire}"))),
+    };
+
+    let field = tag >> 3;
+
+    if field == 0 {
+        return Err(cx.message("Field number 0 is not valid"));
+    }
+
+    Ok(Tag { field, wire })
+}
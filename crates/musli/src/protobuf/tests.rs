@@ -0,0 +1,448 @@
+//! Test vectors follow the protobuf encoding guide, and can be reproduced with
+//! `protoc --encode`.
+
+use rust_alloc::boxed::Box;
+use rust_alloc::string::{String, ToString};
+use rust_alloc::vec;
+use rust_alloc::vec::Vec;
+
+use crate::compat::Bytes;
+use crate::mode::{Binary, Text};
+use crate::value::Value;
+use crate::{Decode, Encode};
+
+use super::test::{decodes, roundtrips};
+use super::{Encoding, Fixed, Sint, OPTIONS};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate, name_type = u32)]
+#[musli(mode = Binary, bound = {T: Encode<Binary>}, decode_bound = {T: Decode<'de, Binary>})]
+#[musli(mode = Text, bound = {T: Encode<Text>}, decode_bound = {T: Decode<'de, Text>})]
+struct Field<T> {
+    #[musli(name = 1)]
+    value: T,
+}
+
+fn field<T>(value: T) -> Field<T> {
+    Field { value }
+}
+
+#[test]
+fn varints() {
+    roundtrips(field(150u32), b"\x08\x96\x01");
+    roundtrips(
+        field(u64::MAX),
+        b"\x08\xff\xff\xff\xff\xff\xff\xff\xff\xff\x01",
+    );
+    roundtrips(field(true), b"\x08\x01");
+    roundtrips(field(false), b"\x08\x00");
+
+    // Negative values of int32 and int64 are always ten bytes long.
+    roundtrips(
+        field(-1i32),
+        b"\x08\xff\xff\xff\xff\xff\xff\xff\xff\xff\x01",
+    );
+    roundtrips(
+        field(-2i64),
+        b"\x08\xfe\xff\xff\xff\xff\xff\xff\xff\xff\x01",
+    );
+
+    // Values are coerced between integer types if they fit.
+    decodes(b"\x08\x96\x01", field(150i64));
+    decodes(b"\x08\x96\x01", field(150u8));
+    assert!(super::from_slice::<Field<u8>>(b"\x08\x80\x02").is_err());
+    assert!(
+        super::from_slice::<Field<u32>>(b"\x08\xff\xff\xff\xff\xff\xff\xff\xff\xff\x01").is_err()
+    );
+    assert!(
+        super::from_slice::<Field<u64>>(b"\x08\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\x01")
+            .is_err()
+    );
+}
+
+#[test]
+fn sints() {
+    roundtrips(field(Sint(0i32)), b"\x08\x00");
+    roundtrips(field(Sint(-1i32)), b"\x08\x01");
+    roundtrips(field(Sint(1i32)), b"\x08\x02");
+    roundtrips(field(Sint(-2i64)), b"\x08\x03");
+    roundtrips(field(Sint(i32::MIN)), b"\x08\xff\xff\xff\xff\x0f");
+    roundtrips(
+        field(Sint(i64::MAX)),
+        b"\x08\xfe\xff\xff\xff\xff\xff\xff\xff\xff\x01",
+    );
+
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    #[musli(crate, name_type = u32)]
+    struct Message {
+        #[musli(name = 1, with = super::sint)]
+        value: i32,
+    }
+
+    roundtrips(Message { value: -3 }, b"\x08\x05");
+}
+
+#[test]
+fn fixed() {
+    roundtrips(field(Fixed(1u32)), b"\x0d\x01\x00\x00\x00");
+    roundtrips(field(Fixed(-1i32)), b"\x0d\xff\xff\xff\xff");
+    roundtrips(field(Fixed(1u64)), b"\x09\x01\x00\x00\x00\x00\x00\x00\x00");
+    roundtrips(field(Fixed(-2i64)), b"\x09\xfe\xff\xff\xff\xff\xff\xff\xff");
+    roundtrips(field(1.5f32), b"\x0d\x00\x00\xc0\x3f");
+    roundtrips(field(1.1f64), b"\x09\x9a\x99\x99\x99\x99\x99\xf1\x3f");
+
+    // Fixed-width integers can be decoded into regular integer fields.
+    decodes(b"\x0d\x2a\x00\x00\x00", field(42u32));
+    decodes(b"\x09\xff\xff\xff\xff\xff\xff\xff\xff", field(-1i64));
+    decodes(b"\x0d\x00\x00\xc0\x3f", field(1.5f64));
+
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    #[musli(crate, name_type = u32)]
+    struct Message {
+        #[musli(name = 1, with = super::fixed)]
+        value: u64,
+    }
+
+    roundtrips(
+        Message { value: 2 },
+        b"\x09\x02\x00\x00\x00\x00\x00\x00\x00",
+    );
+    assert!(super::from_slice::<Field<f32>>(b"\x0a\x00").is_err());
+}
+
+#[test]
+fn length_delimited() {
+    roundtrips(field(String::from("testing")), b"\x0a\x07testing");
+    roundtrips(field(String::new()), b"\x0a\x00");
+    roundtrips(field('a'), b"\x0a\x01a");
+    roundtrips(field(Bytes(vec![1u8, 2, 3])), b"\x0a\x03\x01\x02\x03");
+    roundtrips(field([1u8, 2]), b"\x0a\x02\x01\x02");
+    decodes(b"\x0a\x04\x01\x02\x03\x04", field(Bytes([1u8, 2, 3, 4])));
+
+    assert!(super::from_slice::<Field<String>>(b"\x0a\x02\xc3\x28").is_err());
+    assert!(super::from_slice::<Field<String>>(b"\x08\x01").is_err());
+    assert!(super::from_slice::<Field<[u8; 2]>>(b"\x0a\x01\x01").is_err());
+
+    // Byte arrays are bytes regardless of their length, and only fixed-width
+    // integers use the i32 and i64 wire types.
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    #[musli(crate, name_type = u32)]
+    struct Addresses {
+        #[musli(name = 1, bytes)]
+        ip: [u8; 4],
+        #[musli(name = 2, bytes)]
+        mac: [u8; 8],
+        #[musli(name = 3)]
+        ipv4: core::net::Ipv4Addr,
+    }
+
+    roundtrips(
+        Addresses {
+            ip: [127, 0, 0, 1],
+            mac: [1, 2, 3, 4, 5, 6, 7, 8],
+            ipv4: core::net::Ipv4Addr::new(10, 0, 0, 1),
+        },
+        b"\x0a\x04\x7f\x00\x00\x01\x12\x08\x01\x02\x03\x04\x05\x06\x07\x08\x1a\x04\x0a\x00\x00\x01",
+    );
+    assert!(super::from_slice::<Field<Fixed<u32>>>(b"\x0a\x04\x01\x00\x00\x00").is_err());
+
+    // Extension values are encoded as bytes.
+    #[cfg(feature = "msgpack")]
+    roundtrips(
+        field(crate::msgpack::Timestamp {
+            seconds: 42,
+            nanoseconds: 0,
+        }),
+        b"\x0a\x04\x00\x00\x00\x2a",
+    );
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate, name_type = u32)]
+struct Test1 {
+    #[musli(name = 1)]
+    a: i32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate, name_type = u32)]
+struct Test3 {
+    #[musli(name = 3)]
+    c: Test1,
+}
+
+#[test]
+fn nested_messages() {
+    roundtrips(Test1 { a: 150 }, b"\x08\x96\x01");
+    roundtrips(
+        Test3 {
+            c: Test1 { a: 150 },
+        },
+        b"\x1a\x03\x08\x96\x01",
+    );
+    roundtrips(
+        field(Test3 { c: Test1 { a: 1 } }),
+        b"\x0a\x04\x1a\x02\x08\x01",
+    );
+
+    // A nested message must not extend past its length.
+    assert!(super::from_slice::<Test3>(b"\x1a\x02\x08\x96\x01").is_err());
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate, name_type = u32)]
+struct Tree {
+    #[musli(name = 1)]
+    value: u32,
+    #[musli(name = 6, default)]
+    child: Option<Box<Tree>>,
+}
+
+#[test]
+fn recursive_messages() {
+    let tree = Tree {
+        value: 1,
+        child: Some(Box::new(Tree {
+            value: 2,
+            child: Some(Box::new(Tree {
+                value: 3,
+                child: None,
+            })),
+        })),
+    };
+
+    roundtrips(tree, b"\x08\x01\x32\x06\x08\x02\x32\x02\x08\x03");
+
+    // A nested message must not extend past the message containing it.
+    assert!(super::from_slice::<Tree>(b"\x08\x01\x32\x04\x08\x02\x32\x03\x08\x03").is_err());
+}
+
+#[test]
+fn repeated() {
+    // Scalars are packed.
+    roundtrips(
+        field(vec![3u32, 270, 86942]),
+        b"\x0a\x06\x03\x8e\x02\x9e\xa7\x05",
+    );
+    roundtrips(
+        field(vec![1.0f32, 2.0]),
+        b"\x0a\x08\x00\x00\x80\x3f\x00\x00\x00\x40",
+    );
+    roundtrips(field(vec![Sint(-1i32), Sint(1)]), b"\x0a\x02\x01\x02");
+    roundtrips(
+        field(vec![Fixed(1u32), Fixed(2)]),
+        b"\x0a\x08\x01\x00\x00\x00\x02\x00\x00\x00",
+    );
+
+    // Length-delimited values are written as one field each.
+    roundtrips(
+        field(vec![String::from("a"), String::from("b")]),
+        b"\x0a\x01a\x0a\x01b",
+    );
+    roundtrips(
+        field(vec![Test1 { a: 1 }, Test1 { a: 2 }]),
+        b"\x0a\x02\x08\x01\x0a\x02\x08\x02",
+    );
+
+    // Unpacked scalars, and multiple packed fields, are also accepted.
+    decodes(
+        b"\x08\x03\x08\x8e\x02\x08\x9e\xa7\x05",
+        field(vec![3u32, 270, 86942]),
+    );
+    decodes(
+        b"\x0a\x01\x01\x08\x02\x0a\x02\x03\x04",
+        field(vec![1u32, 2, 3, 4]),
+    );
+    decodes(
+        b"\x0d\x01\x00\x00\x00\x0d\x02\x00\x00\x00",
+        field(vec![Fixed(1u32), Fixed(2)]),
+    );
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate, name_type = u32)]
+struct Person {
+    #[musli(name = 1)]
+    name: String,
+    #[musli(name = 2, default)]
+    id: i32,
+    #[musli(name = 3, default)]
+    emails: Vec<String>,
+    #[musli(name = 4, default)]
+    scores: Vec<u32>,
+    #[musli(name = 5, default)]
+    nickname: Option<String>,
+}
+
+#[test]
+fn messages() {
+    let person = Person {
+        name: String::from("Aristotle"),
+        id: 61,
+        emails: vec![String::from("a@b"), String::from("c@d")],
+        scores: vec![1, 2],
+        nickname: None,
+    };
+
+    roundtrips(
+        person,
+        b"\x0a\x09Aristotle\x10\x3d\x1a\x03a@b\x1a\x03c@d\x22\x02\x01\x02",
+    );
+
+    // Fields can appear in any order, and missing fields use their default.
+    decodes(
+        b"\x2a\x03Ari\x0a\x09Aristotle",
+        Person {
+            name: String::from("Aristotle"),
+            id: 0,
+            emails: Vec::new(),
+            scores: Vec::new(),
+            nickname: Some(String::from("Ari")),
+        },
+    );
+
+    // A repeated field followed by the next field is decoded correctly.
+    decodes(
+        b"\x22\x01\x05\x22\x01\x06\x0a\x01A\x10\x01",
+        Person {
+            name: String::from("A"),
+            id: 1,
+            emails: Vec::new(),
+            scores: vec![5, 6],
+            nickname: None,
+        },
+    );
+
+    // Occurrences of a repeated field which are not consecutive can't be
+    // merged.
+    let error = super::from_slice::<Person>(b"\x0a\x01A\x20\x01\x10\x05\x20\x02").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Occurrences of repeated field 4 are not consecutive"
+    );
+
+    // Fields without a default are required.
+    assert!(super::from_slice::<Person>(b"\x10\x01").is_err());
+}
+
+#[test]
+fn skip_unknown() {
+    decodes(
+        b"\x08\x96\x01\x15\x01\x02\x03\x04\x19\x01\x02\x03\x04\x05\x06\x07\x08\x22\x02\x08\x01\x28\xff\x01",
+        Test1 { a: 150 },
+    );
+
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    #[musli(crate, name_type = u32)]
+    struct Single {
+        #[musli(name = 2)]
+        b: u32,
+    }
+
+    // Unknown repeated fields are skipped, including packed ones.
+    decodes(b"\x08\x01\x08\x02\x0a\x02\x03\x04\x10\x07", Single { b: 7 });
+
+    // Groups and invalid wire types are rejected.
+    assert!(super::from_slice::<Single>(b"\x0b\x0c\x10\x07").is_err());
+    assert!(super::from_slice::<Single>(b"\x0e\x10\x07").is_err());
+    assert!(super::from_slice::<Single>(b"\x00\x01").is_err());
+}
+
+#[test]
+fn enums() {
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    #[musli(crate, name_type = u32)]
+    enum Corpus {
+        #[musli(name = 0)]
+        Unspecified,
+        #[musli(name = 5)]
+        Web,
+    }
+
+    roundtrips(field(Corpus::Web), b"\x08\x05");
+    roundtrips(field(Corpus::Unspecified), b"\x08\x00");
+    assert!(super::from_slice::<Field<Corpus>>(b"\x08\x01").is_err());
+
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    #[musli(crate, name_type = u32)]
+    enum OneOf {
+        #[musli(name = 2, name_type = u32)]
+        Test {
+            #[musli(name = 1)]
+            a: i32,
+        },
+        #[musli(name = 3)]
+        Empty,
+    }
+
+    roundtrips(OneOf::Test { a: 1 }, b"\x12\x02\x08\x01");
+    roundtrips(OneOf::Empty, b"\x1a\x00");
+    roundtrips(field(OneOf::Test { a: 1 }), b"\x0a\x04\x12\x02\x08\x01");
+    assert!(super::from_slice::<OneOf>(b"").is_err());
+}
+
+#[test]
+fn field_numbers() {
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    #[musli(crate)]
+    struct Unnamed {
+        value: u32,
+    }
+
+    // Default field names start at zero, which is not a valid field number.
+    assert!(super::to_vec(&Unnamed { value: 1 }).is_err());
+
+    roundtrips(
+        Field {
+            value: Field { value: 1u32 },
+        },
+        b"\x0a\x02\x08\x01",
+    );
+
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    #[musli(crate, name_type = u32)]
+    struct Large {
+        #[musli(name = 536_870_911)]
+        value: u32,
+    }
+
+    roundtrips(Large { value: 1 }, b"\xf8\xff\xff\xff\x0f\x01");
+}
+
+#[test]
+fn packed_layout() {
+    enum Packed {}
+
+    const PACKED: Encoding<OPTIONS, Packed> = Encoding::new().with_mode();
+
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    #[musli(crate, mode = Packed, packed)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    let point = Point { x: 1, y: -1 };
+    let bytes = PACKED.to_vec(&point).expect("failed to encode");
+    assert_eq!(
+        bytes,
+        b"\x08\x01\x10\xff\xff\xff\xff\xff\xff\xff\xff\xff\x01"
+    );
+    assert_eq!(PACKED.from_slice::<Point>(&bytes).unwrap(), point);
+    assert!(PACKED.from_slice::<Point>(b"\x10\x01\x08\x01").is_err());
+}
+
+#[test]
+fn decode_any() {
+    let value: Value =
+        super::from_slice(b"\x08\x96\x01\x12\x02hi\x1d\x01\x00\x00\x00").expect("failed to decode");
+
+    assert_eq!(
+        value,
+        Value::Map(vec![
+            (Value::Number(1u32.into()), Value::Number(150u64.into())),
+            (Value::Number(2u32.into()), Value::Bytes(vec![b'h', b'i'])),
+            (Value::Number(3u32.into()), Value::Number(1u32.into())),
+        ])
+    );
+}
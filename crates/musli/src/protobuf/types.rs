@@ -0,0 +1,119 @@
+use crate::de::{Decode, Decoder};
+use crate::en::{Encode, Encoder};
+use crate::int::zigzag;
+
+/// A signed integer which is encoded using the `sint32` or `sint64` protobuf
+/// types.
+///
+/// These are zigzag encoded varints, which are more compact than `int32` and
+/// `int64` when the value is negative. The same encoding can be selected for a
+/// field with the [`sint`] module.
+///
+/// [`sint`]: super::sint
+///
+/// # Examples
+///
+/// ```
+/// use musli::{Encode, Decode};
+/// use musli::protobuf::Sint;
+///
+/// #[derive(Debug, PartialEq, Encode, Decode)]
+/// #[musli(name_type = u32)]
+/// struct Message {
+///     #[musli(name = 1)]
+///     value: Sint<i32>,
+/// }
+///
+/// let bytes = musli::protobuf::to_vec(&Message { value: Sint(-2) })?;
+/// assert_eq!(bytes, b"\x08\x03");
+/// # Ok::<_, musli::protobuf::Error>(())
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Sint<T>(pub T);
+
+/// An integer which is encoded using the `fixed32`, `sfixed32`, `fixed64` or
+/// `sfixed64` protobuf types.
+///
+/// These are always stored using 4 or 8 little-endian bytes, which is more
+/// compact than a varint for large values. The same encoding can be selected
+/// for a field with the [`fixed`] module.
+///
+/// [`fixed`]: super::fixed
+///
+/// # Examples
+///
+/// ```
+/// use musli::{Encode, Decode};
+/// use musli::protobuf::Fixed;
+///
+/// #[derive(Debug, PartialEq, Encode, Decode)]
+/// #[musli(name_type = u32)]
+/// struct Message {
+///     #[musli(name = 1)]
+///     value: Fixed<u32>,
+/// }
+///
+/// let bytes = musli::protobuf::to_vec(&Message { value: Fixed(1) })?;
+/// assert_eq!(bytes, b"\x0d\x01\x00\x00\x00");
+/// # Ok::<_, musli::protobuf::Error>(())
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Fixed<T>(pub T);
+
+macro_rules! sint {
+    ($ty:ty, $unsigned:ty, $encode:ident, $decode:ident) => {
+        impl<M> Encode<M> for Sint<$ty> {
+            #[inline]
+            fn encode<E>(&self, _: &E::Cx, encoder: E) -> Result<E::Ok, E::Error>
+            where
+                E: Encoder<Mode = M>,
+            {
+                encoder.$encode(zigzag::encode(self.0))
+            }
+        }
+
+        impl<'de, M> Decode<'de, M> for Sint<$ty> {
+            #[inline]
+            fn decode<D>(_: &D::Cx, decoder: D) -> Result<Self, D::Error>
+            where
+                D: Decoder<'de, Mode = M>,
+            {
+                Ok(Sint(zigzag::decode::<$unsigned>(decoder.$decode()?)))
+            }
+        }
+    };
+}
+
+macro_rules! fixed {
+    ($ty:ty) => {
+        impl<M> Encode<M> for Fixed<$ty> {
+            #[inline]
+            fn encode<E>(&self, _: &E::Cx, encoder: E) -> Result<E::Ok, E::Error>
+            where
+                E: Encoder<Mode = M>,
+            {
+                encoder.encode_fixed(&self.0.to_le_bytes())
+            }
+        }
+
+        impl<'de, M> Decode<'de, M> for Fixed<$ty> {
+            #[inline]
+            fn decode<D>(_: &D::Cx, decoder: D) -> Result<Self, D::Error>
+            where
+                D: Decoder<'de, Mode = M>,
+            {
+                Ok(Fixed(<$ty>::from_le_bytes(decoder.decode_fixed()?)))
+            }
+        }
+    };
+}
+
+sint!(i32, u32, encode_u32, decode_u32);
+sint!(i64, u64, encode_u64, decode_u64);
+
+fixed!(u32);
+fixed!(i32);
+fixed!(u64);
+fixed!(i64);
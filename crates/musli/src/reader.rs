@@ -7,6 +7,7 @@ use core::ops::Range;
 use core::ptr;
 use core::slice;
 
+use crate::context::ErrorKind;
 use crate::de::UnsizedVisitor;
 use crate::Context;
 
@@ -39,9 +40,27 @@ pub trait Reader<'de>: self::sealed::Sealed {
     where
         Self: 'this;
 
+    /// A position in the reader which it can be rewound to.
+    #[doc(hidden)]
+    type Checkpoint: Copy;
+
     /// Borrow the current reader.
     fn borrow_mut(&mut self) -> Self::Mut<'_>;
 
+    /// Get the current position of the reader, which can be used to
+    /// [`rewind`] it.
+    ///
+    /// [`rewind`]: Reader::rewind
+    #[doc(hidden)]
+    fn checkpoint(&self) -> Self::Checkpoint;
+
+    /// Rewind the reader to a position previously returned by
+    /// [`checkpoint`].
+    ///
+    /// [`checkpoint`]: Reader::checkpoint
+    #[doc(hidden)]
+    fn rewind(&mut self, checkpoint: Self::Checkpoint);
+
     /// Skip over the given number of bytes.
     fn skip<C>(&mut self, cx: &C, n: usize) -> Result<(), C::Error>
     where
@@ -170,24 +189,39 @@ where
 
 impl<'de> Reader<'de> for &'de [u8] {
     type Mut<'this> = &'this mut &'de [u8] where Self: 'this;
+    type Checkpoint = &'de [u8];
 
     #[inline]
     fn borrow_mut(&mut self) -> Self::Mut<'_> {
         self
     }
 
+    #[inline]
+    fn checkpoint(&self) -> Self::Checkpoint {
+        self
+    }
+
+    #[inline]
+    fn rewind(&mut self, checkpoint: Self::Checkpoint) {
+        *self = checkpoint;
+    }
+
     #[inline]
     fn skip<C>(&mut self, cx: &C, n: usize) -> Result<(), C::Error>
     where
         C: ?Sized + Context,
     {
         if self.len() < n {
-            return Err(cx.message(SliceUnderflow {
-                n,
-                remaining: self.len(),
-            }));
+            return Err(cx.message_with_kind(
+                ErrorKind::UnexpectedEof,
+                SliceUnderflow {
+                    n,
+                    remaining: self.len(),
+                },
+            ));
         }
 
+        cx.check_input(n)?;
         let (_, tail) = self.split_at(n);
         *self = tail;
         cx.advance(n);
@@ -200,9 +234,13 @@ impl<'de> Reader<'de> for &'de [u8] {
         C: ?Sized + Context,
     {
         if self.len() < buf.len() {
-            return Err(cx.custom(SliceUnderflow::new(buf.len(), self.len())));
+            return Err(cx.message_with_kind(
+                ErrorKind::UnexpectedEof,
+                SliceUnderflow::new(buf.len(), self.len()),
+            ));
         }
 
+        cx.check_input(buf.len())?;
         let (head, tail) = self.split_at(buf.len());
         buf.copy_from_slice(head);
         *self = tail;
@@ -217,9 +255,12 @@ impl<'de> Reader<'de> for &'de [u8] {
         V: UnsizedVisitor<'de, C, [u8]>,
     {
         if self.len() < n {
-            return Err(cx.custom(SliceUnderflow::new(n, self.len())));
+            return Err(
+                cx.message_with_kind(ErrorKind::UnexpectedEof, SliceUnderflow::new(n, self.len()))
+            );
         }
 
+        cx.check_input(n)?;
         let (head, tail) = self.split_at(n);
         *self = tail;
         let ok = visitor.visit_borrowed(cx, head)?;
@@ -233,9 +274,12 @@ impl<'de> Reader<'de> for &'de [u8] {
         C: ?Sized + Context,
     {
         let &[first, ref tail @ ..] = *self else {
-            return Err(cx.custom(SliceUnderflow::new(1, self.len())));
+            return Err(
+                cx.message_with_kind(ErrorKind::UnexpectedEof, SliceUnderflow::new(1, self.len()))
+            );
         };
 
+        cx.check_input(1)?;
         *self = tail;
         cx.advance(1);
         Ok(first)
@@ -247,9 +291,12 @@ impl<'de> Reader<'de> for &'de [u8] {
         C: ?Sized + Context,
     {
         if self.len() < N {
-            return Err(cx.custom(SliceUnderflow::new(N, self.len())));
+            return Err(
+                cx.message_with_kind(ErrorKind::UnexpectedEof, SliceUnderflow::new(N, self.len()))
+            );
         }
 
+        cx.check_input(N)?;
         let (head, tail) = self.split_at(N);
         *self = tail;
         cx.advance(N);
@@ -341,18 +388,31 @@ impl<'de> SliceReader<'de> {
 
 impl<'de> Reader<'de> for SliceReader<'de> {
     type Mut<'this> = &'this mut Self where Self: 'this;
+    type Checkpoint = &'de [u8];
 
     #[inline]
     fn borrow_mut(&mut self) -> Self::Mut<'_> {
         self
     }
 
+    #[inline]
+    fn checkpoint(&self) -> Self::Checkpoint {
+        self.as_slice()
+    }
+
+    #[inline]
+    fn rewind(&mut self, checkpoint: Self::Checkpoint) {
+        self.range.start = checkpoint.as_ptr();
+    }
+
     #[inline]
     fn skip<C>(&mut self, cx: &C, n: usize) -> Result<(), C::Error>
     where
         C: ?Sized + Context,
     {
-        self.range.start = bounds_check_add(cx, &self.range, n)?;
+        let outcome = bounds_check_add(cx, &self.range, n)?;
+        cx.check_input(n)?;
+        self.range.start = outcome;
         cx.advance(n);
         Ok(())
     }
@@ -364,6 +424,7 @@ impl<'de> Reader<'de> for SliceReader<'de> {
         V: UnsizedVisitor<'de, C, [u8]>,
     {
         let outcome = bounds_check_add(cx, &self.range, n)?;
+        cx.check_input(n)?;
 
         let ok = unsafe {
             let bytes = slice::from_raw_parts(self.range.start, n);
@@ -391,6 +452,7 @@ impl<'de> Reader<'de> for SliceReader<'de> {
         C: ?Sized + Context,
     {
         let outcome = bounds_check_add(cx, &self.range, buf.len())?;
+        cx.check_input(buf.len())?;
 
         unsafe {
             ptr::copy_nonoverlapping(self.range.start, buf.as_mut_ptr(), buf.len());
@@ -410,10 +472,13 @@ where
     let outcome = range.start.wrapping_add(len);
 
     if outcome > range.end || outcome < range.start {
-        Err(cx.message(SliceUnderflow {
-            n: len,
-            remaining: (range.end as usize).wrapping_sub(range.start as usize),
-        }))
+        Err(cx.message_with_kind(
+            ErrorKind::UnexpectedEof,
+            SliceUnderflow {
+                n: len,
+                remaining: (range.end as usize).wrapping_sub(range.start as usize),
+            },
+        ))
     } else {
         Ok(outcome)
     }
@@ -457,12 +522,24 @@ where
     R: Reader<'de>,
 {
     type Mut<'this> = &'this mut Self where Self: 'this;
+    type Checkpoint = (usize, R::Checkpoint);
 
     #[inline]
     fn borrow_mut(&mut self) -> Self::Mut<'_> {
         self
     }
 
+    #[inline]
+    fn checkpoint(&self) -> Self::Checkpoint {
+        (self.remaining, self.reader.checkpoint())
+    }
+
+    #[inline]
+    fn rewind(&mut self, (remaining, checkpoint): Self::Checkpoint) {
+        self.remaining = remaining;
+        self.reader.rewind(checkpoint);
+    }
+
     #[inline]
     fn skip<C>(&mut self, cx: &C, n: usize) -> Result<(), C::Error>
     where
@@ -526,12 +603,23 @@ where
     R: ?Sized + Reader<'de>,
 {
     type Mut<'this> = &'this mut R where Self: 'this;
+    type Checkpoint = R::Checkpoint;
 
     #[inline]
     fn borrow_mut(&mut self) -> Self::Mut<'_> {
         self
     }
 
+    #[inline]
+    fn checkpoint(&self) -> Self::Checkpoint {
+        (**self).checkpoint()
+    }
+
+    #[inline]
+    fn rewind(&mut self, checkpoint: Self::Checkpoint) {
+        (**self).rewind(checkpoint);
+    }
+
     #[inline]
     fn skip<C>(&mut self, cx: &C, n: usize) -> Result<(), C::Error>
     where
@@ -104,6 +104,7 @@ use self::deserializer::Deserializer;
 use self::serializer::Serializer;
 
 use crate::alloc::{self, String};
+use crate::context::ErrorKind;
 use crate::{Context, Decoder, Encoder};
 
 struct SerdeContext<'a, C>
@@ -114,6 +115,18 @@ where
     inner: &'a C,
 }
 
+impl<'a, C> SerdeContext<'a, C>
+where
+    C: ?Sized + Context,
+{
+    /// Capture an error raised by the wrapped context.
+    #[inline]
+    fn capture(&self, error: C::Error) -> error::SerdeError {
+        *self.error.borrow_mut() = Some(error);
+        error::SerdeError::Captured
+    }
+}
+
 impl<'a, C> Context for SerdeContext<'a, C>
 where
     C: ?Sized + Context,
@@ -137,6 +150,44 @@ where
         self.inner.mark()
     }
 
+    #[inline]
+    fn advance(&self, n: usize) {
+        self.inner.advance(n);
+    }
+
+    #[inline]
+    fn check_input(&self, n: usize) -> Result<(), Self::Error> {
+        self.inner
+            .check_input(n)
+            .map_err(|error| self.capture(error))
+    }
+
+    #[inline]
+    fn check_length(&self, len: usize) -> Result<(), Self::Error> {
+        self.inner
+            .check_length(len)
+            .map_err(|error| self.capture(error))
+    }
+
+    #[inline]
+    fn check_size(&self, len: usize) -> Result<(), Self::Error> {
+        self.inner
+            .check_size(len)
+            .map_err(|error| self.capture(error))
+    }
+
+    #[inline]
+    fn enter_nested(&self) -> Result<(), Self::Error> {
+        self.inner
+            .enter_nested()
+            .map_err(|error| self.capture(error))
+    }
+
+    #[inline]
+    fn leave_nested(&self) {
+        self.inner.leave_nested();
+    }
+
     #[inline]
     fn alloc(&self) -> &Self::Allocator {
         self.inner.alloc()
@@ -167,6 +218,14 @@ where
         *self.error.borrow_mut() = Some(self.inner.message(message));
         error::SerdeError::Captured
     }
+
+    #[inline]
+    fn message_with_kind<T>(&self, kind: ErrorKind, message: T) -> Self::Error
+    where
+        T: fmt::Display,
+    {
+        self.capture(self.inner.message_with_kind(kind, message))
+    }
 }
 
 /// Encode the given serde value `T` to the given [Encoder] using the serde
@@ -3,10 +3,13 @@ use core::fmt;
 #[cfg(feature = "alloc")]
 use rust_alloc::vec::Vec;
 
+use crate::context::ErrorKind;
 use crate::de::{
     DecodeUnsized, Decoder, EntriesDecoder, EntryDecoder, MapDecoder, SequenceDecoder, SizeHint,
     UnsizedVisitor, VariantDecoder,
 };
+use crate::int::UnsignedOps;
+use crate::options::{self, Char, Float, VariantTag};
 use crate::{Context, Decode, Options, Reader};
 
 /// A very simple decoder suitable for storage decoding.
@@ -87,6 +90,10 @@ where
 
     #[inline]
     fn decode_empty(mut self) -> Result<(), C::Error> {
+        if options::is_empty_omitted::<OPT>() {
+            return Ok(());
+        }
+
         let mark = self.cx.mark();
         let count = crate::int::decode_usize::<_, _, OPT>(self.cx, self.reader.borrow_mut())?;
 
@@ -118,6 +125,7 @@ where
         V: UnsizedVisitor<'de, C, [u8]>,
     {
         let len = crate::int::decode_usize::<_, _, OPT>(self.cx, self.reader.borrow_mut())?;
+        self.cx.check_size(len)?;
         self.reader.read_bytes(self.cx, len, visitor)
     }
 
@@ -143,19 +151,22 @@ where
             #[cfg(feature = "alloc")]
             #[inline]
             fn visit_owned(self, cx: &C, bytes: Vec<u8>) -> Result<Self::Ok, C::Error> {
-                let string = crate::str::from_utf8_owned(bytes).map_err(cx.map())?;
+                let string =
+                    crate::str::from_utf8_owned(bytes).map_err(crate::str::map_utf8_error(cx))?;
                 self.0.visit_owned(cx, string)
             }
 
             #[inline]
             fn visit_borrowed(self, cx: &C, bytes: &'de [u8]) -> Result<Self::Ok, C::Error> {
-                let string = crate::str::from_utf8(bytes).map_err(cx.map())?;
+                let string =
+                    crate::str::from_utf8(bytes).map_err(crate::str::map_utf8_error(cx))?;
                 self.0.visit_borrowed(cx, string)
             }
 
             #[inline]
             fn visit_ref(self, cx: &C, bytes: &[u8]) -> Result<Self::Ok, C::Error> {
-                let string = crate::str::from_utf8(bytes).map_err(cx.map())?;
+                let string =
+                    crate::str::from_utf8(bytes).map_err(crate::str::map_utf8_error(cx))?;
                 self.0.visit_ref(cx, string)
             }
         }
@@ -171,19 +182,60 @@ where
         match byte {
             0 => Ok(false),
             1 => Ok(true),
-            b => Err(self.cx.marked_message(mark, BadBoolean { actual: b })),
+            b => Err(self.cx.marked_message_with_kind(
+                mark,
+                ErrorKind::InvalidTag,
+                BadBoolean { actual: b },
+            )),
         }
     }
 
     #[inline]
-    fn decode_char(self) -> Result<char, C::Error> {
+    fn decode_char(mut self) -> Result<char, C::Error> {
         let cx = self.cx;
         let mark = self.cx.mark();
-        let num = self.decode_u32()?;
 
-        match char::from_u32(num) {
-            Some(d) => Ok(d),
-            None => Err(cx.marked_message(mark, BadCharacter { actual: num })),
+        let mut buf = [0; 4];
+
+        let len = match options::char::<OPT>() {
+            Char::Integer => {
+                let num = self.decode_u32()?;
+
+                return match char::from_u32(num) {
+                    Some(d) => Ok(d),
+                    None => Err(cx.marked_message(mark, BadCharacter { actual: num })),
+                };
+            }
+            Char::Utf8 => {
+                buf[0] = self.reader.read_byte(cx)?;
+
+                match buf[0] {
+                    0x00..=0x7f => 1,
+                    0xc0..=0xdf => 2,
+                    0xe0..=0xef => 3,
+                    0xf0..=0xf7 => 4,
+                    b => return Err(cx.marked_message(mark, BadCharacterByte { actual: b })),
+                }
+            }
+            Char::String => {
+                let len = crate::int::decode_usize::<_, _, OPT>(cx, self.reader.borrow_mut())?;
+
+                if len == 0 || len > buf.len() {
+                    return Err(cx.marked_message(mark, BadCharacterLength { actual: len }));
+                }
+
+                buf[0] = self.reader.read_byte(cx)?;
+                len
+            }
+        };
+
+        self.reader.read(cx, &mut buf[1..len])?;
+        let string = crate::str::from_utf8(&buf[..len]).map_err(crate::str::map_utf8_error(cx))?;
+        let mut chars = string.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(cx.marked_message(mark, BadCharacterLength { actual: len })),
         }
     }
 
@@ -239,11 +291,43 @@ where
 
     #[inline]
     fn decode_usize(self) -> Result<usize, C::Error> {
+        if options::is_size_integers::<OPT>() {
+            let cx = self.cx;
+            let mark = cx.mark();
+            let value = self.decode_u64()?;
+
+            let Ok(value) = usize::try_from(value) else {
+                return Err(cx.marked_message_with_kind(
+                    mark,
+                    ErrorKind::NumberOverflow,
+                    "Value type out of bounds for usize",
+                ));
+            };
+
+            return Ok(value);
+        }
+
         crate::int::decode_usize::<_, _, OPT>(self.cx, self.reader)
     }
 
     #[inline]
     fn decode_isize(self) -> Result<isize, C::Error> {
+        if options::is_size_integers::<OPT>() {
+            let cx = self.cx;
+            let mark = cx.mark();
+            let value = self.decode_i64()?;
+
+            let Ok(value) = isize::try_from(value) else {
+                return Err(cx.marked_message_with_kind(
+                    mark,
+                    ErrorKind::NumberOverflow,
+                    "Value type out of bounds for isize",
+                ));
+            };
+
+            return Ok(value);
+        }
+
         Ok(self.decode_usize()? as isize)
     }
 
@@ -251,14 +335,29 @@ where
     /// IEEE 754 encoding byte-by-byte.
     #[inline]
     fn decode_f32(self) -> Result<f32, C::Error> {
-        Ok(f32::from_bits(self.decode_u32()?))
+        let bits = match options::float::<OPT>() {
+            Float::Fixed => {
+                let bo = options::byteorder::<OPT>();
+                u32::read_bytes(self.cx, self.reader, bo)?
+            }
+            _ => self.decode_u32()?,
+        };
+
+        Ok(f32::from_bits(bits))
     }
 
     /// Decode a 64-bit floating point value by reading the 64-bit in-memory
     /// IEEE 754 encoding byte-by-byte.
     #[inline]
     fn decode_f64(self) -> Result<f64, C::Error> {
-        let bits = self.decode_u64()?;
+        let bits = match options::float::<OPT>() {
+            Float::Fixed => {
+                let bo = options::byteorder::<OPT>();
+                u64::read_bytes(self.cx, self.reader, bo)?
+            }
+            _ => self.decode_u64()?,
+        };
+
         Ok(f64::from_bits(bits))
     }
 
@@ -274,6 +373,7 @@ where
         F: FnOnce(&mut Self::DecodeSequence) -> Result<O, C::Error>,
     {
         let cx = self.cx;
+        cx.enter_nested()?;
         let mut decoder = LimitedStorageDecoder::new(self.cx, self.reader)?;
         let output = f(&mut decoder)?;
 
@@ -281,6 +381,7 @@ where
             return Err(cx.message("Caller did not decode all available map entries"));
         }
 
+        cx.leave_nested();
         Ok(output)
     }
 
@@ -290,6 +391,7 @@ where
         F: FnOnce(&mut Self::DecodeMap) -> Result<O, C::Error>,
     {
         let cx = self.cx;
+        cx.enter_nested()?;
         let mut decoder = LimitedStorageDecoder::new(self.cx, self.reader)?;
         let output = f(&mut decoder)?;
 
@@ -297,6 +399,7 @@ where
             return Err(cx.message("Caller did not decode all available map entries"));
         }
 
+        cx.leave_nested();
         Ok(output)
     }
 
@@ -313,7 +416,10 @@ where
     where
         F: FnOnce(&mut Self::DecodeVariant) -> Result<O, C::Error>,
     {
-        f(&mut self)
+        self.cx.enter_nested()?;
+        let output = f(&mut self)?;
+        self.cx.leave_nested();
+        Ok(output)
     }
 }
 
@@ -346,6 +452,7 @@ where
     #[inline]
     fn new(cx: &'a C, mut reader: R) -> Result<Self, C::Error> {
         let remaining = crate::int::decode_usize::<_, _, OPT>(cx, reader.borrow_mut())?;
+        cx.check_length(remaining)?;
 
         Ok(Self {
             cx,
@@ -499,12 +606,12 @@ where
     R: Reader<'de>,
 {
     type Cx = C;
-    type DecodeTag<'this> = StorageDecoder<'a, R::Mut<'this>, OPT, C> where Self: 'this;
+    type DecodeTag<'this> = StorageTagDecoder<'a, R::Mut<'this>, OPT, C> where Self: 'this;
     type DecodeValue<'this> = StorageDecoder<'a, R::Mut<'this>, OPT, C> where Self: 'this;
 
     #[inline]
     fn decode_tag(&mut self) -> Result<Self::DecodeTag<'_>, C::Error> {
-        Ok(StorageDecoder::new(self.cx, self.reader.borrow_mut()))
+        Ok(StorageTagDecoder::new(self.cx, self.reader.borrow_mut()))
     }
 
     #[inline]
@@ -513,6 +620,174 @@ where
     }
 }
 
+/// Decoder for the tags of enum variants.
+///
+/// This behaves like [`StorageDecoder`], except that index-based tags are
+/// decoded according to [`VariantTag`].
+pub struct StorageTagDecoder<'a, R, const OPT: Options, C: ?Sized> {
+    cx: &'a C,
+    reader: R,
+}
+
+impl<'a, R, const OPT: Options, C: ?Sized> StorageTagDecoder<'a, R, OPT, C> {
+    /// Construct a new tag decoder.
+    #[inline]
+    pub fn new(cx: &'a C, reader: R) -> Self {
+        Self { cx, reader }
+    }
+
+    #[inline]
+    fn inner(self) -> StorageDecoder<'a, R, OPT, C> {
+        StorageDecoder::new(self.cx, self.reader)
+    }
+}
+
+#[crate::decoder(crate)]
+impl<'a, 'de, R, const OPT: Options, C: ?Sized + Context> Decoder<'de>
+    for StorageTagDecoder<'a, R, OPT, C>
+where
+    R: Reader<'de>,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = C::Mode;
+    type WithContext<'this, U> = StorageTagDecoder<'this, R, OPT, U> where U: 'this + Context;
+
+    fn cx(&self) -> &C {
+        self.cx
+    }
+
+    #[inline]
+    fn with_context<U>(self, cx: &U) -> Result<Self::WithContext<'_, U>, C::Error>
+    where
+        U: Context,
+    {
+        Ok(StorageTagDecoder::new(cx, self.reader))
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "variant tag supported by the storage decoder")
+    }
+
+    #[inline]
+    fn decode<T>(self) -> Result<T, Self::Error>
+    where
+        T: Decode<'de, Self::Mode>,
+    {
+        self.cx.decode(self)
+    }
+
+    #[inline]
+    fn decode_unsized<T, F, O>(self, f: F) -> Result<O, Self::Error>
+    where
+        T: ?Sized + DecodeUnsized<'de, Self::Mode>,
+        F: FnOnce(&T) -> Result<O, Self::Error>,
+    {
+        self.cx.decode_unsized(self, f)
+    }
+
+    #[inline]
+    fn decode_usize(mut self) -> Result<usize, C::Error> {
+        match options::variant_tag::<OPT>() {
+            VariantTag::Length => {
+                crate::int::decode_usize::<_, _, OPT>(self.cx, self.reader.borrow_mut())
+            }
+            VariantTag::U32 => {
+                let cx = self.cx;
+                let mark = cx.mark();
+                let value = self.inner().decode_u32()?;
+
+                let Ok(value) = usize::try_from(value) else {
+                    return Err(cx.marked_message_with_kind(
+                        mark,
+                        ErrorKind::NumberOverflow,
+                        "Variant tag out of bounds for usize",
+                    ));
+                };
+
+                Ok(value)
+            }
+        }
+    }
+
+    #[inline]
+    fn decode_isize(self) -> Result<isize, C::Error> {
+        self.inner().decode_isize()
+    }
+
+    #[inline]
+    fn decode_u8(self) -> Result<u8, C::Error> {
+        self.inner().decode_u8()
+    }
+
+    #[inline]
+    fn decode_u16(self) -> Result<u16, C::Error> {
+        self.inner().decode_u16()
+    }
+
+    #[inline]
+    fn decode_u32(self) -> Result<u32, C::Error> {
+        self.inner().decode_u32()
+    }
+
+    #[inline]
+    fn decode_u64(self) -> Result<u64, C::Error> {
+        self.inner().decode_u64()
+    }
+
+    #[inline]
+    fn decode_u128(self) -> Result<u128, C::Error> {
+        self.inner().decode_u128()
+    }
+
+    #[inline]
+    fn decode_i8(self) -> Result<i8, C::Error> {
+        self.inner().decode_i8()
+    }
+
+    #[inline]
+    fn decode_i16(self) -> Result<i16, C::Error> {
+        self.inner().decode_i16()
+    }
+
+    #[inline]
+    fn decode_i32(self) -> Result<i32, C::Error> {
+        self.inner().decode_i32()
+    }
+
+    #[inline]
+    fn decode_i64(self) -> Result<i64, C::Error> {
+        self.inner().decode_i64()
+    }
+
+    #[inline]
+    fn decode_i128(self) -> Result<i128, C::Error> {
+        self.inner().decode_i128()
+    }
+
+    #[inline]
+    fn decode_array<const N: usize>(self) -> Result<[u8; N], C::Error> {
+        self.inner().decode_array()
+    }
+
+    #[inline]
+    fn decode_bytes<V>(self, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: UnsizedVisitor<'de, C, [u8]>,
+    {
+        self.inner().decode_bytes(visitor)
+    }
+
+    #[inline]
+    fn decode_string<V>(self, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: UnsizedVisitor<'de, C, str>,
+    {
+        self.inner().decode_string(visitor)
+    }
+}
+
 struct ExpectedEmptySequence {
     actual: usize,
 }
@@ -535,6 +810,28 @@ impl fmt::Display for BadBoolean {
     }
 }
 
+struct BadCharacterByte {
+    actual: u8,
+}
+
+impl fmt::Display for BadCharacterByte {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { actual } = *self;
+        write!(f, "Bad leading character byte 0x{actual:02x}")
+    }
+}
+
+struct BadCharacterLength {
+    actual: usize,
+}
+
+impl fmt::Display for BadCharacterLength {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { actual } = *self;
+        write!(f, "Bad character encoding of length {actual}")
+    }
+}
+
 struct BadCharacter {
     actual: u32,
 }
@@ -4,6 +4,8 @@ use crate::en::{
     Encode, Encoder, EntriesEncoder, EntryEncoder, MapEncoder, SequenceEncoder, VariantEncoder,
 };
 use crate::hint::{MapHint, SequenceHint};
+use crate::int::UnsignedOps;
+use crate::options::{self, Char, Float, VariantTag};
 use crate::{Context, Options, Writer};
 
 /// A vaery simple encoder suitable for storage encoding.
@@ -68,6 +70,10 @@ where
 
     #[inline]
     fn encode_empty(self) -> Result<Self::Ok, C::Error> {
+        if options::is_empty_omitted::<OPT>() {
+            return Ok(());
+        }
+
         static HINT: SequenceHint = SequenceHint::with_size(0);
         self.encode_sequence_fn(&HINT, |_| Ok(()))
     }
@@ -121,11 +127,19 @@ where
 
     #[inline]
     fn encode_usize(mut self, value: usize) -> Result<Self::Ok, C::Error> {
+        if options::is_size_integers::<OPT>() {
+            return self.encode_u64(value as u64);
+        }
+
         crate::int::encode_usize::<_, _, OPT>(self.cx, self.writer.borrow_mut(), value)
     }
 
     #[inline]
     fn encode_isize(self, value: isize) -> Result<Self::Ok, C::Error> {
+        if options::is_size_integers::<OPT>() {
+            return self.encode_i64(value as i64);
+        }
+
         self.encode_usize(value as usize)
     }
 
@@ -135,8 +149,19 @@ where
     }
 
     #[inline]
-    fn encode_char(self, value: char) -> Result<Self::Ok, C::Error> {
-        self.encode_u32(value as u32)
+    fn encode_char(mut self, value: char) -> Result<Self::Ok, C::Error> {
+        match options::char::<OPT>() {
+            Char::Integer => self.encode_u32(value as u32),
+            Char::Utf8 => {
+                let mut buf = [0; 4];
+                let bytes = value.encode_utf8(&mut buf).as_bytes();
+                self.writer.write_bytes(self.cx, bytes)
+            }
+            Char::String => {
+                let mut buf = [0; 4];
+                self.encode_string(value.encode_utf8(&mut buf))
+            }
+        }
     }
 
     #[inline]
@@ -190,13 +215,29 @@ where
     }
 
     #[inline]
-    fn encode_f32(self, value: f32) -> Result<Self::Ok, C::Error> {
-        self.encode_u32(value.to_bits())
+    fn encode_f32(mut self, value: f32) -> Result<Self::Ok, C::Error> {
+        match options::float::<OPT>() {
+            Float::Fixed => {
+                let bo = options::byteorder::<OPT>();
+                value
+                    .to_bits()
+                    .write_bytes(self.cx, self.writer.borrow_mut(), bo)
+            }
+            _ => self.encode_u32(value.to_bits()),
+        }
     }
 
     #[inline]
-    fn encode_f64(self, value: f64) -> Result<Self::Ok, C::Error> {
-        self.encode_u64(value.to_bits())
+    fn encode_f64(mut self, value: f64) -> Result<Self::Ok, C::Error> {
+        match options::float::<OPT>() {
+            Float::Fixed => {
+                let bo = options::byteorder::<OPT>();
+                value
+                    .to_bits()
+                    .write_bytes(self.cx, self.writer.borrow_mut(), bo)
+            }
+            _ => self.encode_u64(value.to_bits()),
+        }
     }
 
     #[inline]
@@ -243,7 +284,7 @@ where
     where
         T: ?Sized + Encode<C::Mode>,
     {
-        StorageEncoder::<_, OPT, _>::new(self.cx, self.writer.borrow_mut()).encode(tag)?;
+        StorageTagEncoder::<_, OPT, _>::new(self.cx, self.writer.borrow_mut()).encode(tag)?;
         crate::int::encode_usize::<_, _, OPT>(self.cx, self.writer.borrow_mut(), hint.size)?;
         Ok(self)
     }
@@ -257,7 +298,7 @@ where
     where
         T: ?Sized + Encode<C::Mode>,
     {
-        StorageEncoder::<_, OPT, _>::new(self.cx, self.writer.borrow_mut()).encode(tag)?;
+        StorageTagEncoder::<_, OPT, _>::new(self.cx, self.writer.borrow_mut()).encode(tag)?;
         crate::int::encode_usize::<_, _, OPT>(self.cx, self.writer.borrow_mut(), hint.size)?;
         Ok(self)
     }
@@ -362,12 +403,12 @@ where
 {
     type Cx = C;
     type Ok = ();
-    type EncodeTag<'this> = StorageEncoder<'a, W::Mut<'this>, OPT, C> where Self: 'this;
+    type EncodeTag<'this> = StorageTagEncoder<'a, W::Mut<'this>, OPT, C> where Self: 'this;
     type EncodeData<'this> = StorageEncoder<'a, W::Mut<'this>, OPT, C> where Self: 'this;
 
     #[inline]
     fn encode_tag(&mut self) -> Result<Self::EncodeTag<'_>, C::Error> {
-        Ok(StorageEncoder::new(self.cx, self.writer.borrow_mut()))
+        Ok(StorageTagEncoder::new(self.cx, self.writer.borrow_mut()))
     }
 
     #[inline]
@@ -380,3 +421,150 @@ where
         Ok(())
     }
 }
+
+/// Encoder for the tags of enum variants.
+///
+/// This behaves like [`StorageEncoder`], except that index-based tags are
+/// encoded according to [`VariantTag`].
+pub struct StorageTagEncoder<'a, W, const OPT: Options, C: ?Sized> {
+    cx: &'a C,
+    writer: W,
+}
+
+impl<'a, W, const OPT: Options, C: ?Sized> StorageTagEncoder<'a, W, OPT, C> {
+    /// Construct a new tag encoder.
+    #[inline]
+    pub fn new(cx: &'a C, writer: W) -> Self {
+        Self { cx, writer }
+    }
+
+    #[inline]
+    fn inner(self) -> StorageEncoder<'a, W, OPT, C> {
+        StorageEncoder::new(self.cx, self.writer)
+    }
+}
+
+#[crate::encoder(crate)]
+impl<'a, W, const OPT: Options, C> Encoder for StorageTagEncoder<'a, W, OPT, C>
+where
+    C: ?Sized + Context,
+    W: Writer,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Ok = ();
+    type Mode = C::Mode;
+    type WithContext<'this, U> = StorageTagEncoder<'this, W, OPT, U> where U: 'this + Context;
+
+    #[inline]
+    fn cx(&self) -> &Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn with_context<U>(self, cx: &U) -> Result<Self::WithContext<'_, U>, C::Error>
+    where
+        U: Context,
+    {
+        Ok(StorageTagEncoder::new(cx, self.writer))
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "variant tag supported by the storage encoder")
+    }
+
+    #[inline]
+    fn encode<T>(self, value: T) -> Result<Self::Ok, Self::Error>
+    where
+        T: Encode<Self::Mode>,
+    {
+        value.encode(self.cx, self)
+    }
+
+    #[inline]
+    fn encode_usize(mut self, value: usize) -> Result<Self::Ok, C::Error> {
+        match options::variant_tag::<OPT>() {
+            VariantTag::Length => {
+                crate::int::encode_usize::<_, _, OPT>(self.cx, self.writer.borrow_mut(), value)
+            }
+            VariantTag::U32 => {
+                let Ok(value) = u32::try_from(value) else {
+                    return Err(self.cx.message("Variant tag out of bounds for u32"));
+                };
+
+                self.inner().encode_u32(value)
+            }
+        }
+    }
+
+    #[inline]
+    fn encode_isize(self, value: isize) -> Result<Self::Ok, C::Error> {
+        self.inner().encode_isize(value)
+    }
+
+    #[inline]
+    fn encode_u8(self, value: u8) -> Result<Self::Ok, C::Error> {
+        self.inner().encode_u8(value)
+    }
+
+    #[inline]
+    fn encode_u16(self, value: u16) -> Result<Self::Ok, C::Error> {
+        self.inner().encode_u16(value)
+    }
+
+    #[inline]
+    fn encode_u32(self, value: u32) -> Result<Self::Ok, C::Error> {
+        self.inner().encode_u32(value)
+    }
+
+    #[inline]
+    fn encode_u64(self, value: u64) -> Result<Self::Ok, C::Error> {
+        self.inner().encode_u64(value)
+    }
+
+    #[inline]
+    fn encode_u128(self, value: u128) -> Result<Self::Ok, C::Error> {
+        self.inner().encode_u128(value)
+    }
+
+    #[inline]
+    fn encode_i8(self, value: i8) -> Result<Self::Ok, C::Error> {
+        self.inner().encode_i8(value)
+    }
+
+    #[inline]
+    fn encode_i16(self, value: i16) -> Result<Self::Ok, C::Error> {
+        self.inner().encode_i16(value)
+    }
+
+    #[inline]
+    fn encode_i32(self, value: i32) -> Result<Self::Ok, C::Error> {
+        self.inner().encode_i32(value)
+    }
+
+    #[inline]
+    fn encode_i64(self, value: i64) -> Result<Self::Ok, C::Error> {
+        self.inner().encode_i64(value)
+    }
+
+    #[inline]
+    fn encode_i128(self, value: i128) -> Result<Self::Ok, C::Error> {
+        self.inner().encode_i128(value)
+    }
+
+    #[inline]
+    fn encode_array<const N: usize>(self, array: &[u8; N]) -> Result<Self::Ok, C::Error> {
+        self.inner().encode_array(array)
+    }
+
+    #[inline]
+    fn encode_bytes(self, bytes: &[u8]) -> Result<Self::Ok, C::Error> {
+        self.inner().encode_bytes(bytes)
+    }
+
+    #[inline]
+    fn encode_string(self, string: &str) -> Result<Self::Ok, C::Error> {
+        self.inner().encode_string(string)
+    }
+}
@@ -126,6 +126,7 @@ where
         StorageEncoder::<_, OPT, _>::new,
         StorageDecoder::<_, OPT, _>::new,
         IntoReader::into_reader,
+        Reader,
     );
 }
 
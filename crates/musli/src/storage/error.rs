@@ -5,14 +5,32 @@ use rust_alloc::boxed::Box;
 #[cfg(feature = "alloc")]
 use rust_alloc::string::ToString;
 
-use crate::context::ContextError;
+use crate::context::{ContextError, ErrorKind};
 
 /// Error raised during storage encoding.
 #[derive(Debug)]
 pub struct Error {
+    kind: ErrorKind,
     err: ErrorImpl,
 }
 
+impl Error {
+    /// Get the kind of the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::context::ErrorKind;
+    ///
+    /// let error = musli::storage::from_slice::<u32>(b"").unwrap_err();
+    /// assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    /// ```
+    #[inline]
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+}
+
 impl fmt::Display for Error {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -51,12 +69,21 @@ impl ContextError for Error {
     }
 
     #[inline]
-    #[allow(unused_variables)]
     fn message<T>(message: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        Self::message_with_kind(ErrorKind::Other, message)
+    }
+
+    #[inline]
+    #[allow(unused_variables)]
+    fn message_with_kind<T>(kind: ErrorKind, message: T) -> Self
     where
         T: fmt::Display,
     {
         Self {
+            kind,
             #[cfg(feature = "alloc")]
             err: ErrorImpl::Message(message.to_string().into()),
             #[cfg(not(feature = "alloc"))]
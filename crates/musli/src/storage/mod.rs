@@ -85,6 +85,60 @@
 //! assert_eq!(expected, actual);
 //! # Ok::<_, musli::storage::Error>(())
 //! ```
+//!
+//! <br>
+//!
+//! ## Compatibility with bincode and postcard
+//!
+//! The [`options::bincode`] and [`options::postcard`] presets configure the
+//! storage encoding so that it reads and writes the same bytes as [bincode] 1.x
+//! and [postcard] respectively. Since neither format encodes field tags or the
+//! lengths of fixed-size containers, structs, enum variants, tuples and arrays
+//! have to be annotated with `#[musli(packed)]`.
+//!
+//! [bincode]: https://docs.rs/bincode/1
+//! [postcard]: https://docs.rs/postcard
+//! [`options::bincode`]: crate::options::bincode
+//! [`options::postcard`]: crate::options::postcard
+//!
+//! ```
+//! use musli::{Encode, Decode};
+//! use musli::options::{self, Options};
+//! use musli::storage::Encoding;
+//!
+//! const OPTIONS: Options = options::bincode().build();
+//! const BINCODE: Encoding<OPTIONS> = Encoding::new().with_options();
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! enum Shape {
+//!     #[musli(packed)]
+//!     Circle(f32),
+//!     #[musli(packed)]
+//!     Square { side: u16 },
+//! }
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! #[musli(packed)]
+//! struct Drawing {
+//!     name: String,
+//!     shape: Option<Shape>,
+//! }
+//!
+//! let drawing = Drawing {
+//!     name: String::from("box"),
+//!     shape: Some(Shape::Square { side: 2 }),
+//! };
+//!
+//! let bytes = BINCODE.to_vec(&drawing)?;
+//!
+//! assert_eq!(bytes, [
+//!     3, 0, 0, 0, 0, 0, 0, 0, b'b', b'o', b'x',
+//!     1, 1, 0, 0, 0, 2, 0,
+//! ]);
+//!
+//! assert_eq!(BINCODE.from_slice::<Drawing>(&bytes)?, drawing);
+//! # Ok::<_, musli::storage::Error>(())
+//! ```
 
 #![cfg(any(
     feature = "storage",
@@ -116,6 +170,8 @@ pub use self::encoding::to_vec;
 #[doc(inline)]
 pub use self::encoding::to_writer;
 #[doc(inline)]
-pub use self::encoding::{decode, encode, from_slice, to_fixed_bytes, Encoding, DEFAULT, OPTIONS};
+pub use self::encoding::{
+    decode, encode, from_slice, from_slice_exact, to_fixed_bytes, Encoding, DEFAULT, OPTIONS,
+};
 #[doc(inline)]
 pub use self::error::Error;
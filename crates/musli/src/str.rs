@@ -16,10 +16,24 @@ use rust_alloc::vec::Vec;
 
 use core::fmt;
 
+use crate::context::ErrorKind;
+use crate::Context;
+
 #[cfg(not(feature = "simdutf8"))]
 #[doc(inline)]
 pub use core::str::from_utf8;
 
+/// Map an error raised when decoding UTF-8 into an error of kind
+/// [`ErrorKind::InvalidUtf8`].
+#[inline]
+pub(crate) fn map_utf8_error<C, E>(cx: &C) -> impl FnOnce(E) -> C::Error + '_
+where
+    C: ?Sized + Context,
+    E: fmt::Display,
+{
+    move |error| cx.message_with_kind(ErrorKind::InvalidUtf8, error)
+}
+
 /// Error raised in case the UTF-8 sequence could not be decoded.
 #[derive(Debug)]
 #[non_exhaustive]
@@ -0,0 +1,52 @@
+use rust_alloc::string::{String, ToString};
+
+use crate::alloc::{Budget, System};
+use crate::context::{self, Same};
+use crate::mode::Text;
+use crate::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+struct Message {
+    id: u32,
+    body: String,
+}
+
+fn message() -> Message {
+    let mut body = String::from("Hello\n");
+    body.extend(core::iter::repeat('x').take(1024));
+    Message { id: 1, body }
+}
+
+#[test]
+fn json() {
+    let input = crate::json::to_vec(&message()).unwrap();
+
+    let alloc = Budget::new(System::new(), 4096);
+    let cx = context::with_alloc(&alloc);
+    let value: Message = crate::json::DEFAULT.from_slice_with(&cx, &input).unwrap();
+    assert_eq!(value, message());
+    drop(cx);
+    assert_eq!(alloc.used(), 0);
+
+    let alloc = Budget::new(System::new(), 512);
+    let cx = context::with_alloc(&alloc);
+    let result = crate::json::DEFAULT.from_slice_with::<_, Message>(&cx, &input);
+    assert!(result.is_err());
+    assert_eq!(
+        cx.report().to_string(),
+        ".body: Failed to allocate (at byte 1047)\n"
+    );
+}
+
+#[test]
+fn same() {
+    let input = crate::json::to_vec(&message()).unwrap();
+
+    let alloc = Budget::new(System::new(), 512);
+    let cx = Same::<Text, crate::json::Error, _>::with_alloc(&alloc);
+    let error = crate::json::DEFAULT
+        .from_slice_with::<_, Message>(&cx, &input)
+        .unwrap_err();
+    assert_eq!(error.to_string(), "Failed to allocate");
+}
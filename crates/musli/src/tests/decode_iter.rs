@@ -0,0 +1,109 @@
+use rust_alloc::string::{String, ToString};
+use rust_alloc::vec::Vec;
+
+use crate::context;
+use crate::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+struct Record {
+    name: String,
+    value: u32,
+}
+
+fn records() -> Vec<Record> {
+    (0..3)
+        .map(|n| Record {
+            name: "x".repeat(n * 10),
+            value: n as u32,
+        })
+        .collect()
+}
+
+#[test]
+fn storage() {
+    let mut data = Vec::new();
+    let mut offsets = Vec::new();
+
+    for record in records() {
+        offsets.push(data.len());
+        crate::storage::encode(&mut data, &record).unwrap();
+    }
+
+    let mut iter = crate::storage::DEFAULT.decode_iter::<Record>(&data);
+    let mut decoded = Vec::new();
+
+    while let Some(record) = iter.next() {
+        decoded.push((iter.offset(), record.unwrap()));
+    }
+
+    let expected = offsets.into_iter().zip(records()).collect::<Vec<_>>();
+    assert_eq!(decoded, expected);
+    assert!(iter.remaining().is_empty());
+
+    assert_eq!(
+        crate::storage::DEFAULT
+            .decode_iter::<Record>(&[])
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap(),
+        []
+    );
+}
+
+#[test]
+fn errors() {
+    let mut data = Vec::new();
+
+    for record in records() {
+        crate::wire::encode(&mut data, &record).unwrap();
+    }
+
+    let complete = data.len();
+    let last = crate::wire::to_vec(&records().pop().unwrap()).unwrap();
+    data.extend_from_slice(&last[..last.len() - 4]);
+
+    let mut iter = crate::wire::DEFAULT.decode_iter::<Record>(&data);
+    assert!(iter.by_ref().take(3).all(|record| record.is_ok()));
+    assert!(iter.next().unwrap().is_err());
+    assert_eq!(iter.offset(), complete);
+    assert_eq!(iter.remaining().len(), last.len() - 4);
+    assert!(iter.next().is_none());
+
+    // The context is shared between records, so errors are reported at their
+    // offset in the whole buffer.
+    let cx = context::new();
+    let mut iter = crate::wire::DEFAULT.decode_iter_with::<_, Record>(&cx, &data);
+    assert!(iter.by_ref().take(3).all(|record| record.is_ok()));
+    assert!(iter.next().unwrap().is_err());
+
+    let report = cx.report().to_string();
+    let (_, at) = report.split_once("(at byte ").unwrap();
+    let at = at
+        .trim_end()
+        .trim_end_matches(')')
+        .parse::<usize>()
+        .unwrap();
+    assert!((complete..data.len()).contains(&at), "{report}");
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate, packed)]
+struct Empty;
+
+#[test]
+fn no_progress() {
+    let mut iter = crate::storage::DEFAULT.decode_iter::<Empty>(&[1, 2, 3]);
+    let error = iter.next().unwrap().unwrap_err();
+    assert_eq!(error.to_string(), "Decoded value did not consume any input");
+    assert_eq!(iter.offset(), 0);
+    assert!(iter.next().is_none());
+
+    let cx = context::new();
+    let mut iter = crate::storage::DEFAULT.decode_iter_with::<_, Empty>(&cx, &[1, 2, 3]);
+    assert!(iter.next().unwrap().is_err());
+    assert!(iter.next().is_none());
+    assert_eq!(
+        cx.report().to_string(),
+        ": Decoded value did not consume any input\n"
+    );
+}
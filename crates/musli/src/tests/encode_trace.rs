@@ -0,0 +1,97 @@
+use rust_alloc::string::ToString;
+use rust_alloc::vec;
+use rust_alloc::vec::Vec;
+
+use crate::context;
+use crate::mode::Binary;
+use crate::{Context, Encode, Encoder};
+
+/// A value which always fails to encode.
+struct Fail;
+
+impl<M> Encode<M> for Fail {
+    fn encode<E>(&self, cx: &E::Cx, _: E) -> Result<E::Ok, E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        Err(cx.message("Boom"))
+    }
+}
+
+#[derive(Encode)]
+#[musli(crate)]
+struct Inner {
+    a: u32,
+    b: Fail,
+}
+
+#[derive(Encode)]
+#[musli(crate)]
+enum Kind {
+    Variant { inner: Inner },
+}
+
+#[derive(Encode)]
+#[musli(crate)]
+struct Tuple(u32, Option<Fail>);
+
+#[test]
+fn derived_fields() {
+    let cx = context::new::<Binary>();
+    let value = Kind::Variant {
+        inner: Inner { a: 1, b: Fail },
+    };
+
+    let result = crate::storage::DEFAULT.to_vec_with(&cx, &value);
+    assert!(result.is_err());
+    assert_eq!(
+        cx.report().to_string(),
+        "Variant { .inner.b }: Boom (at byte 7)\n"
+    );
+
+    let mut cx = context::new::<Binary>();
+    cx.include_type();
+
+    let result = crate::storage::DEFAULT.to_vec_with(&cx, &value);
+    assert!(result.is_err());
+    assert_eq!(
+        cx.report().to_string(),
+        "Kind::Variant { .inner = Inner { .b } }: Boom (at byte 7)\n"
+    );
+
+    let cx = context::new::<Binary>();
+    let result = crate::storage::DEFAULT.to_vec_with(&cx, &Tuple(1, Some(Fail)));
+    assert!(result.is_err());
+    assert_eq!(cx.report().to_string(), ".1: Boom (at byte 5)\n");
+}
+
+#[test]
+fn sequence_index() {
+    let cx = context::new::<Binary>();
+    let result = crate::storage::DEFAULT.to_vec_with(&cx, &[None, Some(Fail)]);
+    assert!(result.is_err());
+    assert_eq!(cx.report().to_string(), "[1]: Boom (at byte 3)\n");
+
+    let cx = context::new::<Binary>();
+    let value: &[Option<Fail>] = &[None, None, Some(Fail)];
+    let result = crate::storage::DEFAULT.to_vec_with(&cx, value);
+    assert!(result.is_err());
+    assert_eq!(cx.report().to_string(), "[2]: Boom (at byte 4)\n");
+
+    let cx = context::new::<Binary>();
+    let result = crate::storage::DEFAULT.to_vec_with(&cx, &vec![None, Some(Fail)]);
+    assert!(result.is_err());
+    assert_eq!(cx.report().to_string(), "[1]: Boom (at byte 3)\n");
+}
+
+#[test]
+fn fixed_bytes_overflow() {
+    let cx = context::new::<Binary>();
+    let value: Vec<u32> = vec![1, 2, 3, 4, 5, 6];
+    let result = crate::storage::DEFAULT.to_fixed_bytes_with::<_, 4, _>(&cx, &value);
+    assert!(result.is_err());
+    assert_eq!(
+        cx.report().to_string(),
+        "[3]: Tried to write 1 bytes at 4 with capacity 4 (at byte 4)\n"
+    );
+}
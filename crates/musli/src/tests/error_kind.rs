@@ -0,0 +1,123 @@
+use rust_alloc::string::{String, ToString};
+use rust_alloc::vec;
+use rust_alloc::vec::Vec;
+
+use crate::compat::Bytes;
+use crate::context::{self, ContextError, ErrorKind, Limits, Same};
+use crate::mode::{Binary, Text};
+use crate::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+struct Person {
+    name: String,
+    age: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+struct Name {
+    name: String,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+enum Animal {
+    Cat,
+    Dog,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+enum Other {
+    Cat,
+    Dog,
+    Fish,
+}
+
+macro_rules! binary {
+    ($($what:ident => $utf8:ident),*) => {
+        $(
+            #[test]
+            fn $what() {
+                let error = crate::$what::from_slice::<u32>(&[]).unwrap_err();
+                assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+
+                let data = crate::$what::to_vec(&Bytes(vec![0xffu8, 0xfe])).unwrap();
+                let error = crate::$what::from_slice::<String>(&data).unwrap_err();
+                assert_eq!(error.kind(), ErrorKind::$utf8);
+
+                let data = crate::$what::to_vec(&Name { name: "Aristotle".to_string() }).unwrap();
+                let error = crate::$what::from_slice::<Person>(&data).unwrap_err();
+                assert_eq!(error.kind(), ErrorKind::MissingField);
+
+                let data = crate::$what::to_vec(&Other::Fish).unwrap();
+                let error = crate::$what::from_slice::<Animal>(&data).unwrap_err();
+                assert_eq!(error.kind(), ErrorKind::UnknownVariant);
+
+                let data = crate::$what::to_vec(&vec![1u32, 2, 3]).unwrap();
+                let cx = Same::<Binary, crate::$what::Error, _>::new()
+                    .with_limits(Limits::new().with_max_length(2));
+                let error = crate::$what::DEFAULT
+                    .from_slice_with::<_, Vec<u32>>(&cx, &data)
+                    .unwrap_err();
+                assert_eq!(error.kind(), ErrorKind::LimitExceeded);
+
+                let error = crate::$what::Error::message("custom");
+                assert_eq!(error.kind(), ErrorKind::Other);
+            }
+        )*
+    };
+}
+
+// Descriptive tags strings and bytes differently, so bytes are rejected before
+// they are validated as UTF-8.
+binary! {
+    storage => InvalidUtf8,
+    wire => InvalidUtf8,
+    descriptive => InvalidTag
+}
+
+#[test]
+fn json() {
+    let error = crate::json::from_slice::<u8>(b"1000").unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::NumberOverflow);
+
+    let error = crate::json::from_slice::<String>(b"\"abc").unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+
+    let error = crate::json::from_slice::<Person>(br#"{"name": "Aristotle"}"#).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::MissingField);
+
+    let error = crate::json::from_slice::<Animal>(br#""Fish""#).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::UnknownVariant);
+
+    let error = crate::json::from_slice::<u32>(b"[]").unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Other);
+}
+
+#[test]
+fn default_context() {
+    let cx = context::new::<Text>();
+    let result = crate::json::DEFAULT
+        .from_slice_with::<_, Person>(&cx, br#"{"name": "Aristotle", "age": 1000000000000}"#);
+    assert!(result.is_err());
+
+    let errors = cx.errors().collect::<Vec<_>>();
+    assert_eq!(errors.len(), 1);
+
+    let error = &errors[0];
+    assert_eq!(error.kind(), ErrorKind::NumberOverflow);
+    assert_eq!(error.range(), 29..39);
+    assert_eq!(error.message(), "Arithmetic overflow");
+    assert_eq!(error.path().to_string(), ".age");
+}
+
+#[test]
+fn io_error() {
+    let cx = Same::<Binary, std::io::Error, _>::new();
+    let error = crate::storage::DEFAULT
+        .from_slice_with::<_, u32>(&cx, &[])
+        .unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::UnexpectedEof);
+}
@@ -0,0 +1,227 @@
+use rust_alloc::string::{String, ToString};
+use rust_alloc::vec::Vec;
+
+use crate::context::{self, Limits};
+use crate::mode::{Binary, Text};
+use crate::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+struct Inner {
+    #[musli(default)]
+    value: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+struct Record {
+    name: String,
+    #[musli(default)]
+    age: u32,
+    inner: Inner,
+    #[musli(default)]
+    flag: bool,
+}
+
+#[derive(Debug, Default, PartialEq, Encode, Decode)]
+#[musli(crate)]
+struct Nested {
+    x: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+struct Outer {
+    #[musli(default)]
+    first: Nested,
+    #[musli(default)]
+    second: Nested,
+    #[musli(default)]
+    flag: bool,
+}
+
+/// A record with the same fields as [`Record`], but of the wrong types.
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+struct Corrupt {
+    name: String,
+    age: String,
+    inner: CorruptInner,
+    flag: String,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+struct CorruptInner {
+    value: String,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+struct Strict {
+    age: u32,
+}
+
+#[test]
+fn recovers_with_paths() {
+    let input = br#"{"name": "x", "age": [1, {"a": 2}], "inner": {"value": "7"}, "flag": 1}"#;
+
+    let mut cx = context::new::<Text>();
+    cx.lenient();
+
+    let record = crate::json::DEFAULT
+        .from_slice_with::<_, Record>(&cx, input)
+        .unwrap();
+
+    assert_eq!(
+        record,
+        Record {
+            name: String::from("x"),
+            age: 0,
+            inner: Inner { value: 0 },
+            flag: false,
+        }
+    );
+
+    let paths = cx
+        .errors()
+        .map(|e| e.path().to_string())
+        .collect::<Vec<_>>();
+
+    assert_eq!(paths, [".age", ".inner.value", ".flag"]);
+}
+
+#[test]
+fn recovers_nested_failure() {
+    let input = br#"{"first": {"x": "bad"}, "second": {"x": "bad"}, "flag": 1}"#;
+
+    // Values which fail to decode must not leave their nesting behind, or
+    // the second field would exceed the limit.
+    let mut cx = context::new::<Text>();
+    cx.lenient();
+    cx.with_limits(Limits::new().with_max_depth(2));
+
+    let outer = crate::json::DEFAULT
+        .from_slice_with::<_, Outer>(&cx, input)
+        .unwrap();
+
+    assert_eq!(
+        outer,
+        Outer {
+            first: Nested { x: 0 },
+            second: Nested { x: 0 },
+            flag: false,
+        }
+    );
+
+    let paths = cx
+        .errors()
+        .map(|e| e.path().to_string())
+        .collect::<Vec<_>>();
+
+    assert_eq!(paths, [".first.x", ".second.x", ".flag"]);
+}
+
+fn corrupt() -> Corrupt {
+    Corrupt {
+        name: String::from("x"),
+        age: String::from("old"),
+        inner: CorruptInner {
+            value: String::from("7"),
+        },
+        flag: String::from("yes"),
+    }
+}
+
+fn recovered() -> Record {
+    Record {
+        name: String::from("x"),
+        age: 0,
+        inner: Inner { value: 0 },
+        flag: false,
+    }
+}
+
+#[test]
+fn recovers_wire() {
+    let data = crate::wire::to_vec(&corrupt()).unwrap();
+
+    let mut cx = context::new::<Binary>();
+    cx.lenient();
+
+    let record = crate::wire::DEFAULT
+        .from_slice_with::<_, Record>(&cx, &data)
+        .unwrap();
+
+    assert_eq!(record, recovered());
+
+    let paths = cx
+        .errors()
+        .map(|e| e.path().to_string())
+        .collect::<Vec<_>>();
+
+    assert_eq!(paths, [".age", ".inner.value", ".flag"]);
+}
+
+#[test]
+fn recovers_descriptive() {
+    let data = crate::descriptive::to_vec(&corrupt()).unwrap();
+
+    let mut cx = context::new::<Binary>();
+    cx.lenient();
+
+    let record = crate::descriptive::DEFAULT
+        .from_slice_with::<_, Record>(&cx, &data)
+        .unwrap();
+
+    assert_eq!(record, recovered());
+
+    let paths = cx
+        .errors()
+        .map(|e| e.path().to_string())
+        .collect::<Vec<_>>();
+
+    assert_eq!(paths, [".age", ".inner.value", ".flag"]);
+}
+
+#[test]
+fn not_lenient() {
+    let input = br#"{"name": "x", "age": "old", "inner": {"value": 1}}"#;
+
+    let cx = context::new::<Text>();
+
+    let result = crate::json::DEFAULT.from_slice_with::<_, Record>(&cx, input);
+    assert!(result.is_err());
+    assert_eq!(cx.errors().count(), 1);
+}
+
+#[test]
+fn without_default() {
+    let mut cx = context::new::<Text>();
+    cx.lenient();
+
+    let result = crate::json::DEFAULT.from_slice_with::<_, Strict>(&cx, br#"{"age": "old"}"#);
+    assert!(result.is_err());
+}
+
+#[test]
+fn malformed_value() {
+    let input = br#"{"name": "x", "age": [1, "#;
+
+    let mut cx = context::new::<Text>();
+    cx.lenient();
+
+    let result = crate::json::DEFAULT.from_slice_with::<_, Record>(&cx, input);
+    assert!(result.is_err());
+}
+
+#[test]
+fn binary_does_not_recover() {
+    let data = crate::storage::to_vec(&Strict { age: 42 }).unwrap();
+
+    let mut cx = context::new::<Binary>();
+    cx.lenient();
+
+    let result = crate::storage::DEFAULT.from_slice_with::<_, Inner>(&cx, &data[..data.len() - 1]);
+    assert!(result.is_err());
+}
@@ -0,0 +1,287 @@
+use rust_alloc::string::{String, ToString};
+use rust_alloc::vec;
+use rust_alloc::vec::Vec;
+
+use crate::context::{self, Limits, Same};
+use crate::mode::{Binary, Text};
+use crate::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+enum Tree {
+    Leaf(u32),
+    Node(Vec<Tree>),
+}
+
+fn tree(depth: usize) -> Tree {
+    if depth == 0 {
+        Tree::Leaf(42)
+    } else {
+        Tree::Node(vec![tree(depth - 1)])
+    }
+}
+
+macro_rules! binary {
+    ($($what:ident),*) => {
+        $(
+            #[test]
+            fn $what() {
+                let limits = Limits::new().with_max_depth(16);
+
+                let data = crate::$what::to_vec(&tree(3)).unwrap();
+                let cx = Same::<Binary, crate::$what::Error, _>::new().with_limits(limits);
+                let value: Tree = crate::$what::DEFAULT.from_slice_with(&cx, &data).unwrap();
+                assert_eq!(value, tree(3));
+
+                // The same context can be used again, since clearing it resets
+                // the tracked depth.
+                let value: Tree = crate::$what::DEFAULT.from_slice_with(&cx, &data).unwrap();
+                assert_eq!(value, tree(3));
+
+                let data = crate::$what::to_vec(&tree(10)).unwrap();
+                let error = crate::$what::DEFAULT
+                    .from_slice_with::<_, Tree>(&cx, &data)
+                    .unwrap_err();
+                assert_eq!(
+                    error.to_string(),
+                    "Values are nested deeper than the maximum depth of 16"
+                );
+
+                let data = crate::$what::to_vec(&vec![1u32; 100]).unwrap();
+                let cx = Same::<Binary, crate::$what::Error, _>::new()
+                    .with_limits(Limits::new().with_max_length(10));
+                let error = crate::$what::DEFAULT
+                    .from_slice_with::<_, Vec<u32>>(&cx, &data)
+                    .unwrap_err();
+                assert_eq!(
+                    error.to_string(),
+                    "Collection of 100 elements exceeds the maximum length of 10"
+                );
+
+                let data = crate::$what::to_vec("x".repeat(100).as_str()).unwrap();
+                let cx = Same::<Binary, crate::$what::Error, _>::new()
+                    .with_limits(Limits::new().with_max_size(10));
+                let error = crate::$what::DEFAULT
+                    .from_slice_with::<_, String>(&cx, &data)
+                    .unwrap_err();
+                assert_eq!(
+                    error.to_string(),
+                    "String or bytes of 100 bytes exceeds the maximum size of 10 bytes"
+                );
+
+                let cx = Same::<Binary, crate::$what::Error, _>::new()
+                    .with_limits(Limits::new().with_max_input(10));
+                let error = crate::$what::DEFAULT
+                    .from_slice_with::<_, String>(&cx, &data)
+                    .unwrap_err();
+                assert_eq!(error.to_string(), "Input exceeds the maximum of 10 bytes");
+
+                let cx = Same::<Binary, crate::$what::Error, _>::new()
+                    .with_limits(Limits::new().with_max_input(data.len()));
+                let value: String = crate::$what::DEFAULT.from_slice_with(&cx, &data).unwrap();
+                assert_eq!(value.len(), 100);
+            }
+        )*
+    };
+}
+
+binary!(storage, wire, descriptive, msgpack);
+
+#[test]
+fn json() {
+    let limits = Limits::new().with_max_depth(16);
+
+    let data = crate::json::to_vec(&tree(3)).unwrap();
+    let cx = Same::<Text, crate::json::Error, _>::new().with_limits(limits);
+    let value: Tree = crate::json::DEFAULT.from_slice_with(&cx, &data).unwrap();
+    assert_eq!(value, tree(3));
+
+    let data = crate::json::to_vec(&tree(10)).unwrap();
+    let error = crate::json::DEFAULT
+        .from_slice_with::<_, Tree>(&cx, &data)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Values are nested deeper than the maximum depth of 16"
+    );
+
+    // Skipping over values is also limited.
+    let data = "[".repeat(100) + &"]".repeat(100);
+    let error = crate::json::DEFAULT
+        .from_slice_with::<_, crate::value::Value>(&cx, data.as_bytes())
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Values are nested deeper than the maximum depth of 16"
+    );
+
+    let cx =
+        Same::<Text, crate::json::Error, _>::new().with_limits(Limits::new().with_max_length(3));
+    let value: Vec<u32> = crate::json::DEFAULT
+        .from_slice_with(&cx, b"[1, 2, 3]")
+        .unwrap();
+    assert_eq!(value, [1, 2, 3]);
+    let error = crate::json::DEFAULT
+        .from_slice_with::<_, Vec<u32>>(&cx, b"[1, 2, 3, 4]")
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Collection of 4 elements exceeds the maximum length of 3"
+    );
+
+    let cx = Same::<Text, crate::json::Error, _>::new().with_limits(Limits::new().with_max_size(3));
+    let error = crate::json::DEFAULT
+        .from_slice_with::<_, String>(&cx, b"\"Hello\"")
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "String or bytes of 5 bytes exceeds the maximum size of 3 bytes"
+    );
+
+    let cx =
+        Same::<Text, crate::json::Error, _>::new().with_limits(Limits::new().with_max_input(8));
+    let error = crate::json::DEFAULT
+        .from_slice_with::<_, Vec<u32>>(&cx, b"[1, 2, 3, 4, 5]")
+        .unwrap_err();
+    assert_eq!(error.to_string(), "Input exceeds the maximum of 8 bytes");
+}
+
+#[test]
+fn cbor_skip() {
+    #[derive(Debug, PartialEq, Decode)]
+    #[musli(crate, name_all = "name")]
+    struct Empty {}
+
+    let cx =
+        Same::<Binary, crate::cbor::Error, _>::new().with_limits(Limits::new().with_max_depth(16));
+
+    // Indefinite length items which are skipped count towards the depth.
+    let data = [&b"\xa1\x61x"[..], &[0x9f; 16], &[0xff; 16]].concat();
+    let value: Empty = crate::cbor::DEFAULT.from_slice_with(&cx, &data).unwrap();
+    assert_eq!(value, Empty {});
+
+    let data = [&b"\xa1\x61x"[..], &[0x9f; 17], &[0xff; 17]].concat();
+    let error = crate::cbor::DEFAULT
+        .from_slice_with::<_, Empty>(&cx, &data)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Values are nested deeper than the maximum depth of 16"
+    );
+
+    let cx =
+        Same::<Binary, crate::cbor::Error, _>::new().with_limits(Limits::new().with_max_length(3));
+    let error = crate::cbor::DEFAULT
+        .from_slice_with::<_, Empty>(&cx, b"\xa1\x61x\x84\x01\x02\x03\x04")
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Collection of 4 elements exceeds the maximum length of 3"
+    );
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate, name_type = u32)]
+struct Message {
+    #[musli(name = 1)]
+    values: Vec<u32>,
+    #[musli(name = 2)]
+    name: String,
+    #[musli(name = 3)]
+    child: Child,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate, name_type = u32)]
+struct Child {
+    #[musli(name = 1)]
+    grandchild: GrandChild,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate, name_type = u32)]
+struct GrandChild {
+    #[musli(name = 1)]
+    value: u32,
+}
+
+fn message() -> Message {
+    Message {
+        values: vec![1, 2, 3],
+        name: String::from("x"),
+        child: Child {
+            grandchild: GrandChild { value: 42 },
+        },
+    }
+}
+
+#[test]
+fn protobuf() {
+    let data = crate::protobuf::to_vec(&message()).unwrap();
+
+    let cx = Same::<Binary, crate::protobuf::Error, _>::new()
+        .with_limits(Limits::new().with_max_depth(3));
+    let value: Message = crate::protobuf::DEFAULT
+        .from_slice_with(&cx, &data)
+        .unwrap();
+    assert_eq!(value, message());
+
+    let cx = Same::<Binary, crate::protobuf::Error, _>::new()
+        .with_limits(Limits::new().with_max_depth(2));
+    let error = crate::protobuf::DEFAULT
+        .from_slice_with::<_, Message>(&cx, &data)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Values are nested deeper than the maximum depth of 2"
+    );
+
+    // Repeated fields don't record their length, so it's checked as elements
+    // are decoded.
+    let data = crate::protobuf::to_vec(&Message {
+        values: vec![1; 100],
+        ..message()
+    })
+    .unwrap();
+    let cx = Same::<Binary, crate::protobuf::Error, _>::new()
+        .with_limits(Limits::new().with_max_length(10));
+    let error = crate::protobuf::DEFAULT
+        .from_slice_with::<_, Message>(&cx, &data)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Collection of 11 elements exceeds the maximum length of 10"
+    );
+
+    let data = crate::protobuf::to_vec(&Message {
+        name: "x".repeat(100),
+        ..message()
+    })
+    .unwrap();
+    let cx = Same::<Binary, crate::protobuf::Error, _>::new()
+        .with_limits(Limits::new().with_max_size(10));
+    let error = crate::protobuf::DEFAULT
+        .from_slice_with::<_, Message>(&cx, &data)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "String or bytes of 100 bytes exceeds the maximum size of 10 bytes"
+    );
+}
+
+#[test]
+fn report() {
+    let data = crate::wire::to_vec(&tree(10)).unwrap();
+
+    let mut cx = context::new();
+    cx.with_limits(Limits::new().with_max_depth(4));
+
+    let result = crate::wire::DEFAULT.from_slice_with::<_, Tree>(&cx, &data);
+    assert!(result.is_err());
+
+    let report = cx.report().to_string();
+    assert_eq!(
+        report,
+        "Node { .0[0] = Node }: Values are nested deeper than the maximum depth of 4 (at byte 7)\n"
+    );
+}
@@ -1,4 +1,15 @@
+mod budget;
+mod decode_iter;
+mod encode_trace;
+mod error_kind;
+mod lenient;
+mod limits;
 mod pack_compat;
+mod profile;
+mod reuse;
+mod source_report;
+mod tracing;
+mod trailing;
 
 #[cfg(loom)]
 mod loom;
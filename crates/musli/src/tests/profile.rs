@@ -0,0 +1,123 @@
+use std::collections::BTreeMap;
+
+use rust_alloc::string::{String, ToString};
+use rust_alloc::vec;
+use rust_alloc::vec::Vec;
+
+use crate::context::{ErrorMarker, Profile};
+use crate::mode::Binary;
+use crate::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+enum Shape {
+    Circle { radius: u32 },
+    Square(u32, u32),
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+struct Drawing {
+    shapes: Vec<Shape>,
+    #[musli(trace)]
+    labels: BTreeMap<String, u32>,
+}
+
+fn drawing() -> Drawing {
+    Drawing {
+        shapes: vec![Shape::Circle { radius: 10 }, Shape::Square(1, 2)],
+        labels: [("a".to_string(), 1), ("b".to_string(), 2)]
+            .into_iter()
+            .collect(),
+    }
+}
+
+fn paths(cx: &Profile<Binary, ErrorMarker, impl Sized>) -> Vec<(String, usize, usize)> {
+    let mut paths = cx
+        .report()
+        .iter()
+        .map(|e| (e.path().to_string(), e.bytes(), e.count()))
+        .collect::<Vec<_>>();
+    paths.sort();
+    paths
+}
+
+#[test]
+fn paths_and_totals() {
+    let cx = Profile::<Binary, ErrorMarker, _>::new();
+    let data = crate::storage::DEFAULT
+        .to_vec_with(&cx, &drawing())
+        .unwrap();
+    assert_eq!(cx.total(), data.len());
+
+    assert_eq!(
+        paths(&cx),
+        [
+            (".labels".to_string(), 8, 1),
+            (".labels{}".to_string(), 6, 2),
+            (".shapes".to_string(), 12, 1),
+            (".shapes[]".to_string(), 10, 2),
+            (".shapes[]::Circle".to_string(), 4, 1),
+            (".shapes[]::Circle.radius".to_string(), 2, 1),
+            (".shapes[]::Square".to_string(), 6, 1),
+            (".shapes[]::Square.0".to_string(), 2, 1),
+            (".shapes[]::Square.1".to_string(), 2, 1),
+        ]
+    );
+}
+
+#[test]
+fn aggregates_and_resets() {
+    let mut cx = Profile::<Binary, ErrorMarker, _>::new();
+
+    let mut buf = [0u8; 128];
+    let mut writer = &mut buf[..];
+
+    crate::storage::DEFAULT
+        .encode_with(&cx, &mut writer, &drawing())
+        .unwrap();
+
+    let first = 128 - writer.len();
+
+    let data = crate::storage::DEFAULT
+        .to_vec_with(&cx, &drawing())
+        .unwrap();
+    assert_eq!(first, data.len());
+    assert_eq!(cx.total(), first * 2);
+
+    let decoded: Drawing = crate::storage::DEFAULT.from_slice_with(&cx, &data).unwrap();
+    assert_eq!(decoded, drawing());
+    assert_eq!(cx.total(), first * 3);
+
+    let report = cx.report();
+    let top = report.iter().next().unwrap();
+    assert_eq!(top.path().to_string(), ".shapes");
+    assert_eq!(top.count(), 3);
+    drop(report);
+
+    cx.reset();
+    assert_eq!(cx.total(), 0);
+    assert_eq!(cx.report().iter().count(), 0);
+    assert_eq!(cx.report().to_string(), "0 100.0%     total\n");
+}
+
+#[test]
+fn recovers_from_errors() {
+    let cx = Profile::<Binary, ErrorMarker, _>::new();
+
+    let mut buf = [0u8; 4];
+    assert!(crate::storage::DEFAULT
+        .encode_with(&cx, &mut buf[..], &drawing())
+        .is_err());
+
+    crate::storage::DEFAULT
+        .to_vec_with(&cx, &drawing())
+        .unwrap();
+
+    let expected = Profile::<Binary, ErrorMarker, _>::new();
+    crate::storage::DEFAULT
+        .to_vec_with(&expected, &drawing())
+        .unwrap();
+
+    assert_eq!(paths(&cx), paths(&expected));
+}
@@ -0,0 +1,69 @@
+use rust_alloc::string::ToString;
+
+use crate::context::{self, Limits};
+use crate::mode::Text;
+use crate::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+struct Person {
+    name: u32,
+}
+
+#[test]
+fn reset() {
+    let mut cx = context::new();
+
+    let result =
+        crate::json::DEFAULT.from_slice_with::<_, Person>(&cx, br#"{"name": "Aristotle"}"#);
+    assert!(result.is_err());
+    assert_eq!(cx.errors().count(), 1);
+
+    cx.reset();
+    assert_eq!(cx.errors().count(), 0);
+    assert_eq!(cx.report().to_string(), "");
+
+    let result = crate::json::DEFAULT.from_slice_with::<_, Person>(&cx, br#"{"name": true}"#);
+    assert!(result.is_err());
+    assert_eq!(
+        cx.report().to_string(),
+        ".name: Invalid numeric (at bytes 9-10)\n"
+    );
+}
+
+#[test]
+fn pooled() {
+    let report = context::pooled(|cx| {
+        cx.include_type()
+            .with_limits(Limits::new().with_max_depth(0));
+        let result = crate::json::DEFAULT.from_slice_with::<_, Person>(cx, br#"{"name": 1}"#);
+        assert!(result.is_err());
+        cx.report().to_string()
+    });
+
+    assert_eq!(
+        report,
+        "Person: Values are nested deeper than the maximum depth of 0\n"
+    );
+
+    // Configuration and errors from a previous use do not survive.
+    context::pooled(|cx| {
+        assert_eq!(cx.errors().count(), 0);
+        let person: Person = crate::json::DEFAULT
+            .from_slice_with(cx, br#"{"name": 1}"#)
+            .unwrap();
+        assert_eq!(person, Person { name: 1 });
+    });
+
+    // Nested uses borrow separate contexts.
+    context::pooled(|a| {
+        let result = crate::json::DEFAULT.from_slice_with::<_, Person>(a, b"[]");
+        assert!(result.is_err());
+
+        context::pooled::<Text, _>(|b| {
+            assert_eq!(b.errors().count(), 0);
+        });
+
+        assert_eq!(a.errors().count(), 1);
+    });
+}
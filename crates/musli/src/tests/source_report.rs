@@ -0,0 +1,145 @@
+use rust_alloc::string::ToString;
+use rust_alloc::vec::Vec;
+
+use crate::context;
+use crate::mode::{Binary, Text};
+use crate::{Context, Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+struct Person {
+    name: u32,
+}
+
+#[test]
+fn tabs_and_crlf() {
+    let input = "{\r\n\t\"name\": true\r\n}";
+
+    let cx = context::new::<Text>();
+    let result = crate::json::DEFAULT.from_str_with::<_, Person>(&cx, input);
+    assert!(result.is_err());
+
+    assert_eq!(
+        cx.report().with_source(input.as_bytes()).to_string(),
+        ".name: Invalid numeric (at line 2, column 10)\n  |\n2 | \t\"name\": true\n  | \t        ^\n"
+    );
+}
+
+#[test]
+fn unicode_columns() {
+    let input = "{\"åäö\": 0, \"name\": true}";
+
+    let cx = context::new::<Text>();
+    let result = crate::json::DEFAULT.from_str_with::<_, Person>(&cx, input);
+    assert!(result.is_err());
+
+    let error = cx.errors().next().unwrap();
+    assert_eq!(error.range(), 22..23);
+    assert_eq!(error.line_column(input.as_bytes()), Some((1, 20)));
+}
+
+#[test]
+fn line_numbers_widen_gutter() {
+    let input = "[\n0,\n1,\n2,\n3,\n4,\n5,\n6,\n7,\n8,\ntrue]";
+
+    let cx = context::new::<Text>();
+    let result = crate::json::DEFAULT.from_str_with::<_, Vec<u32>>(&cx, input);
+    assert!(result.is_err());
+
+    assert_eq!(
+        cx.report().with_source(input.as_bytes()).to_string(),
+        "[9]: Invalid numeric (at line 11, column 1)\n   |\n11 | true]\n   | ^\n"
+    );
+}
+
+#[test]
+fn first_character() {
+    let input = "\"x\"";
+
+    let cx = context::new::<Text>();
+    let result = crate::json::DEFAULT.from_str_with::<_, Vec<u32>>(&cx, input);
+    assert!(result.is_err());
+
+    let error = cx.errors().next().unwrap();
+    assert_eq!(error.range(), 0..0);
+    assert_eq!(error.line_column(input.as_bytes()), Some((1, 1)));
+
+    assert_eq!(
+        cx.report().with_source(input.as_bytes()).to_string(),
+        ": Expected opening bracket, was `\"` (at line 1, column 1)\n  |\n1 | \"x\"\n  | ^\n"
+    );
+
+    let input = "{\"a\": }";
+
+    let cx = context::new::<Text>();
+    let result = crate::json::DEFAULT.from_str_with::<_, Vec<u32>>(&cx, input);
+    assert!(result.is_err());
+
+    let error = cx.errors().next().unwrap();
+    assert_eq!(error.line_column(input.as_bytes()), Some((1, 1)));
+}
+
+#[test]
+fn without_position() {
+    let cx = context::new::<Binary>();
+    cx.message("Not positioned");
+
+    let error = cx.errors().next().unwrap();
+    assert_eq!(error.line_column(b""), None);
+    assert_eq!(
+        cx.report().with_source(b"").to_string(),
+        ": Not positioned\n"
+    );
+}
+
+#[test]
+fn hexdump_window() {
+    let input = (0..=255u8).collect::<Vec<_>>();
+
+    let cx = context::new::<Binary>();
+    cx.advance(40);
+    let mark = cx.mark();
+    cx.advance(3);
+    _ = cx.marked_message(mark, "Corrupt");
+
+    assert_eq!(
+        cx.report().with_hexdump(&input).to_string(),
+        "\
+: Corrupt (at bytes 40-43)
+00000010: 10 11 12 13 14 15 16 17 18 19 1a 1b 1c 1d 1e 1f  |................|
+00000020: 20 21 22 23 24 25 26 27 28 29 2a 2b 2c 2d 2e 2f  | !\"#$%&'()*+,-./|
+                                  ^^ ^^ ^^
+00000030: 30 31 32 33 34 35 36 37 38 39 3a 3b 3c 3d 3e 3f  |0123456789:;<=>?|
+"
+    );
+}
+
+#[test]
+fn hexdump_elides_long_ranges() {
+    let input = [0u8; 256];
+
+    let cx = context::new::<Binary>();
+    let mark = cx.mark();
+    cx.advance(200);
+    _ = cx.marked_message(mark, "Corrupt");
+
+    let report = cx.report().with_hexdump(&input).to_string();
+    let lines = report.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines[0], ": Corrupt (at bytes 0-200)");
+    assert_eq!(lines[9], "...");
+    assert!(lines[10].starts_with("000000a0:"));
+    assert!(lines[lines.len() - 3].starts_with("000000c0:"));
+    assert_eq!(lines[lines.len() - 2], "          ^^ ^^ ^^ ^^ ^^ ^^ ^^ ^^");
+    assert!(lines[lines.len() - 1].starts_with("000000d0:"));
+}
+
+#[test]
+fn hexdump_end_of_input() {
+    let cx = context::new::<Binary>();
+    let result = crate::storage::DEFAULT.from_slice_with::<_, u32>(&cx, &[0xff, 0xff]);
+    assert!(result.is_err());
+
+    let report = cx.report().with_hexdump(&[0xff, 0xff]).to_string();
+    assert!(report.contains("00000000: ff ff"), "{report}");
+}
@@ -0,0 +1,204 @@
+use core::fmt::{self, Write};
+use std::sync::{Arc, Mutex};
+
+use rust_alloc::format;
+use rust_alloc::string::{String, ToString};
+use rust_alloc::vec::Vec;
+
+use ::tracing::field::{Field, Visit};
+use ::tracing::span::{Attributes, Id, Record};
+use ::tracing::{Event, Metadata, Subscriber};
+
+use crate::context::{self, Tracing};
+use crate::mode::Text;
+use crate::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+struct Person {
+    name: String,
+    values: Vec<u32>,
+}
+
+#[derive(Default)]
+struct State {
+    /// Spans by id, with their parent.
+    spans: Vec<(String, Option<usize>)>,
+    stack: Vec<usize>,
+    events: Vec<String>,
+}
+
+impl State {
+    /// The parent of a new span or event.
+    fn parent(&self, explicit: Option<&Id>, contextual: bool) -> Option<usize> {
+        if contextual {
+            return self.stack.last().copied();
+        }
+
+        explicit.map(|id| id.into_u64() as usize)
+    }
+
+    fn path(&self, mut parent: Option<usize>) -> String {
+        let mut path = Vec::new();
+
+        while let Some(id) = parent {
+            let (name, next) = &self.spans[id - 1];
+            path.push(name.as_str());
+            parent = *next;
+        }
+
+        path.reverse();
+        path.join(" > ")
+    }
+}
+
+/// A subscriber which records the path of spans in which events are emitted.
+#[derive(Clone, Default)]
+struct Recorder {
+    state: Arc<Mutex<State>>,
+}
+
+impl Recorder {
+    fn events(&self) -> Vec<String> {
+        self.state.lock().unwrap().events.clone()
+    }
+}
+
+struct Fields(String);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if !self.0.is_empty() {
+            self.0.push_str(", ");
+        }
+
+        _ = write!(self.0, "{}={value:?}", field.name());
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut fields = Fields(String::new());
+        span.record(&mut fields);
+
+        let mut state = self.state.lock().unwrap();
+        let parent = state.parent(span.parent(), span.is_contextual());
+        let name = format!("{}({})", span.metadata().name(), fields.0);
+        state.spans.push((name, parent));
+        Id::from_u64(state.spans.len() as u64)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields(String::new());
+        event.record(&mut fields);
+
+        let mut state = self.state.lock().unwrap();
+
+        let parent = state.parent(event.parent(), event.is_contextual());
+        let path = state.path(parent);
+
+        let event = format!("{path}: {}", fields.0);
+        state.events.push(event);
+    }
+
+    fn enter(&self, span: &Id) {
+        let mut state = self.state.lock().unwrap();
+        state.stack.push(span.into_u64() as usize);
+    }
+
+    fn exit(&self, _: &Id) {
+        let mut state = self.state.lock().unwrap();
+        state.stack.pop();
+    }
+}
+
+const INPUT: &str = r#"{"name": "Aristotle", "values": [1, true]}"#;
+
+#[test]
+fn error_path() {
+    let recorder = Recorder::default();
+
+    ::tracing::subscriber::with_default(recorder.clone(), || {
+        let cx = Tracing::new(context::new::<Text>());
+        let result = crate::json::DEFAULT.from_str_with::<_, Person>(&cx, INPUT);
+        assert!(result.is_err());
+    });
+
+    assert_eq!(
+        recorder.events(),
+        [
+            "struct(name=\"Person\") > field(name=\"values\", tag=values) > index(index=1): message=Invalid numeric"
+        ]
+    );
+}
+
+#[test]
+fn max_depth() {
+    let recorder = Recorder::default();
+
+    ::tracing::subscriber::with_default(recorder.clone(), || {
+        let cx = Tracing::new(context::new::<Text>()).with_max_depth(1);
+        let result = crate::json::DEFAULT.from_str_with::<_, Person>(&cx, INPUT);
+        assert!(result.is_err());
+
+        let error = cx.inner().errors().next().unwrap();
+        assert_eq!(error.path().to_string(), ".values[1]");
+    });
+
+    assert_eq!(
+        recorder.events(),
+        ["struct(name=\"Person\"): message=Invalid numeric"]
+    );
+}
+
+#[test]
+fn spans_are_balanced() {
+    let recorder = Recorder::default();
+
+    ::tracing::subscriber::with_default(recorder.clone(), || {
+        let cx = Tracing::new(context::new::<Text>());
+        let person = crate::json::DEFAULT
+            .from_str_with::<_, Person>(&cx, r#"{"name": "Aristotle", "values": [1, 2]}"#)
+            .unwrap();
+        assert_eq!(person.values, [1, 2]);
+    });
+
+    let state = recorder.state.lock().unwrap();
+    assert!(state.stack.is_empty());
+    assert!(state.events.is_empty());
+    assert_eq!(state.spans.len(), 5);
+}
+
+#[test]
+fn spans_are_not_entered() {
+    let recorder = Recorder::default();
+
+    ::tracing::subscriber::with_default(recorder.clone(), || {
+        let cx = Tracing::new(context::new::<Text>());
+        let result = crate::json::DEFAULT.from_str_with::<_, Person>(&cx, INPUT);
+        assert!(result.is_err());
+
+        // Logging by the caller isn't attributed to spans left open by the
+        // failed decode.
+        ::tracing::info!(message = "failed");
+    });
+
+    let state = recorder.state.lock().unwrap();
+    assert!(state.stack.is_empty());
+
+    assert_eq!(
+        state.events,
+        [
+            "struct(name=\"Person\") > field(name=\"values\", tag=values) > index(index=1): message=Invalid numeric",
+            ": message=\"failed\"",
+        ]
+    );
+}
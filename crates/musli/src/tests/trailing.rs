@@ -0,0 +1,100 @@
+use rust_alloc::string::{String, ToString};
+use rust_alloc::vec::Vec;
+
+use crate::context;
+use crate::mode::{Binary, Text};
+use crate::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+struct Record {
+    name: String,
+    value: u32,
+}
+
+fn record() -> Record {
+    Record {
+        name: String::from("Aristotle"),
+        value: 61,
+    }
+}
+
+#[test]
+fn binary() {
+    let mut data = crate::storage::to_vec(&record()).unwrap();
+    let len = data.len();
+
+    assert_eq!(
+        crate::storage::from_slice_exact::<Record>(&data).unwrap(),
+        record()
+    );
+
+    data.extend_from_slice(&[1, 2, 3]);
+
+    // The lenient variant ignores what follows the value.
+    assert_eq!(
+        crate::storage::from_slice::<Record>(&data).unwrap(),
+        record()
+    );
+
+    let error = crate::storage::from_slice_exact::<Record>(&data).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Expected end of input, but found 3 trailing bytes"
+    );
+
+    // The error is marked at the first trailing byte.
+    let cx = context::new::<Binary>();
+    let result = crate::storage::DEFAULT.from_slice_exact_with::<_, Record>(&cx, &data);
+    assert!(result.is_err());
+
+    let error = cx.errors().next().unwrap();
+    assert_eq!(error.range(), len..len);
+
+    let mut data = crate::wire::to_vec(&record()).unwrap();
+    let len = data.len();
+    data.extend_from_slice(&crate::wire::to_vec(&record()).unwrap());
+
+    let cx = context::new::<Binary>();
+    let result = crate::wire::DEFAULT.from_slice_exact_with::<_, Record>(&cx, &data);
+    assert!(result.is_err());
+
+    let error = cx.errors().next().unwrap();
+    assert_eq!(error.range(), len..len);
+}
+
+#[test]
+fn json() {
+    let value =
+        crate::json::from_slice_exact::<Record>(b" {\"name\": \"Aristotle\", \"value\": 61} \n\t")
+            .unwrap();
+    assert_eq!(value, record());
+
+    let error =
+        crate::json::from_slice_exact::<Record>(b"{\"name\": \"Aristotle\", \"value\": 61} x")
+            .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Expected end of input, but found 1 trailing byte"
+    );
+
+    let cx = context::new::<Text>();
+    let result = crate::json::DEFAULT
+        .from_slice_exact_with::<_, Record>(&cx, b"{\"name\": \"Aristotle\", \"value\": 61} x");
+    assert!(result.is_err());
+
+    let error = cx.errors().next().unwrap();
+    assert_eq!(error.range(), 35..35);
+
+    let data = crate::json::to_vec(&Vec::from([1u32, 2, 3])).unwrap();
+    let value = crate::json::from_slice_exact::<Vec<u32>>(&data).unwrap();
+    assert_eq!(value, [1, 2, 3]);
+}
+
+#[test]
+fn csv() {
+    let records = Vec::from([record(), record()]);
+    let data = crate::csv::to_vec(&records).unwrap();
+    let value = crate::csv::from_slice_exact::<Vec<Record>>(&data).unwrap();
+    assert_eq!(value, records);
+}
@@ -0,0 +1,377 @@
+use core::marker::PhantomData;
+
+use std::io;
+
+use bytes::{Buf, BytesMut};
+use rust_alloc::vec::Vec;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+#[cfg(feature = "futures")]
+use tokio_util::compat::{FuturesAsyncReadCompatExt, FuturesAsyncWriteCompatExt};
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::context::Same;
+use crate::de::DecodeOwned;
+use crate::framing::{self, Framing};
+use crate::mode::Binary;
+use crate::{Encode, Options};
+
+use super::Format;
+
+/// The largest possible length prefix, which is a variable-length encoded
+/// 64-bit length.
+const MAX_HEADER: usize = 16;
+
+/// A length-delimited codec which encodes and decodes values of type `T` using
+/// the encoding `E`.
+///
+/// This implements [`Encoder`] and [`Decoder`] so that it can be used with
+/// [`Framed`], and also provides [`Codec::read_from`] and [`Codec::write_to`]
+/// to read and write a single value without one.
+///
+/// Frames are delimited using a [`Framing`] configuration, which by default is
+/// [`framing::DEFAULT`].
+///
+/// [`Framed`]: tokio_util::codec::Framed
+///
+/// # Examples
+///
+/// ```
+/// use musli::{Decode, Encode};
+/// use musli::tokio::Codec;
+/// use tokio_util::codec::{Decoder, Encoder};
+/// use bytes::BytesMut;
+///
+/// #[derive(Debug, PartialEq, Encode, Decode)]
+/// struct Request {
+///     id: u32,
+///     path: String,
+/// }
+///
+/// let mut codec = Codec::<_, Request>::new(musli::wire::DEFAULT);
+/// let mut buf = BytesMut::new();
+///
+/// let request = Request { id: 1, path: String::from("/") };
+/// codec.encode(&request, &mut buf)?;
+///
+/// let mut partial = buf.split_to(3);
+/// assert_eq!(codec.decode(&mut partial)?, None);
+///
+/// partial.unsplit(buf);
+/// assert_eq!(codec.decode(&mut partial)?, Some(request));
+/// assert!(partial.is_empty());
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub struct Codec<E, T, const OPT: Options = { framing::OPTIONS }> {
+    encoding: E,
+    framing: Framing<OPT>,
+    buffer: Vec<u8>,
+    /// Data which has been read by [`Codec::read_from`], starting at
+    /// `read_pos`.
+    read: Vec<u8>,
+    read_pos: usize,
+    _marker: PhantomData<fn(T) -> T>,
+}
+
+impl<E, T> Codec<E, T> {
+    /// Construct a new codec using the given encoding and the default
+    /// framing.
+    #[inline]
+    pub const fn new(encoding: E) -> Self {
+        Self {
+            encoding,
+            framing: Framing::new(),
+            buffer: Vec::new(),
+            read: Vec::new(),
+            read_pos: 0,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<E, T, const OPT: Options> Codec<E, T, OPT> {
+    /// Change the framing used by the codec.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::framing::Framing;
+    /// use musli::tokio::Codec;
+    ///
+    /// let codec = Codec::<_, String>::new(musli::storage::DEFAULT)
+    ///     .with_framing(Framing::new().with_max_frame_size(1024));
+    /// ```
+    #[inline]
+    pub fn with_framing<const U: Options>(self, framing: Framing<U>) -> Codec<E, T, U> {
+        Codec {
+            encoding: self.encoding,
+            framing,
+            buffer: self.buffer,
+            read: self.read,
+            read_pos: self.read_pos,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Read a single value from the given reader.
+    ///
+    /// Data is read in chunks, so more than one frame might be read at a time.
+    /// Anything read past the end of the returned frame is kept in the codec
+    /// and used by the next call, so the same codec should be used for every
+    /// read from a reader.
+    ///
+    /// Returns `None` if the reader reached its end before the start of a
+    /// frame, and an error if it did so in the middle of one.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe. If it is used in a branch of
+    /// `tokio::select!` and some other branch completes first, no data has
+    /// been lost and calling it again resumes reading the same frame.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Decode, Encode};
+    /// use musli::tokio::Codec;
+    ///
+    /// #[derive(Debug, PartialEq, Encode, Decode)]
+    /// struct Message {
+    ///     body: String,
+    /// }
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> std::io::Result<()> {
+    /// let (mut a, mut b) = tokio::io::duplex(64);
+    /// let mut codec = Codec::<_, Message>::new(musli::wire::DEFAULT);
+    ///
+    /// let message = Message { body: String::from("Hello") };
+    /// codec.write_to(&mut a, &message).await?;
+    /// drop(a);
+    ///
+    /// assert_eq!(codec.read_from(&mut b).await?, Some(message));
+    /// assert_eq!(codec.read_from(&mut b).await?, None);
+    /// # Ok(()) }
+    /// ```
+    pub async fn read_from<R>(&mut self, reader: &mut R) -> io::Result<Option<T>>
+    where
+        R: ?Sized + AsyncRead + Unpin,
+        E: Format,
+        T: DecodeOwned<E::Mode>,
+    {
+        loop {
+            if let Some(value) = self.read_buffered()? {
+                return Ok(Some(value));
+            }
+
+            if reader.read_buf(&mut self.read).await? == 0 {
+                if self.read.is_empty() {
+                    return Ok(None);
+                }
+
+                self.read.clear();
+                return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+            }
+        }
+    }
+
+    /// Write a single value to the given writer.
+    ///
+    /// The writer is not flushed.
+    pub async fn write_to<W>(&mut self, writer: &mut W, value: &T) -> io::Result<()>
+    where
+        W: ?Sized + AsyncWrite + Unpin,
+        E: Format,
+        T: Encode<E::Mode>,
+    {
+        let (header, len) = self.encode_frame(value)?;
+        writer.write_all(&header[..len]).await?;
+        writer.write_all(&self.buffer).await?;
+        Ok(())
+    }
+
+    /// Read a single value from the given [`futures_io::AsyncRead`].
+    ///
+    /// This behaves the same as [`Codec::read_from`], including being cancel
+    /// safe.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::tokio::Codec;
+    /// use tokio_util::compat::TokioAsyncReadCompatExt;
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> std::io::Result<()> {
+    /// let (mut a, b) = tokio::io::duplex(64);
+    /// let mut codec = Codec::<_, String>::new(musli::wire::DEFAULT);
+    ///
+    /// codec.write_to(&mut a, &String::from("Hello")).await?;
+    /// drop(a);
+    ///
+    /// // Any reader implementing `futures_io::AsyncRead` can be used.
+    /// let mut b = b.compat();
+    /// assert_eq!(codec.read_from_futures(&mut b).await?.as_deref(), Some("Hello"));
+    /// assert_eq!(codec.read_from_futures(&mut b).await?, None);
+    /// # Ok(()) }
+    /// ```
+    #[cfg(feature = "futures")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "futures")))]
+    pub async fn read_from_futures<R>(&mut self, reader: &mut R) -> io::Result<Option<T>>
+    where
+        R: ?Sized + futures_io::AsyncRead + Unpin,
+        E: Format,
+        T: DecodeOwned<E::Mode>,
+    {
+        self.read_from(&mut reader.compat()).await
+    }
+
+    /// Write a single value to the given [`futures_io::AsyncWrite`].
+    ///
+    /// The writer is not flushed.
+    #[cfg(feature = "futures")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "futures")))]
+    pub async fn write_to_futures<W>(&mut self, writer: &mut W, value: &T) -> io::Result<()>
+    where
+        W: ?Sized + futures_io::AsyncWrite + Unpin,
+        E: Format,
+        T: Encode<E::Mode>,
+    {
+        self.write_to(&mut writer.compat_write(), value).await
+    }
+
+    /// Decode the next value from data which has already been read, if it
+    /// contains a whole frame.
+    fn read_buffered(&mut self) -> io::Result<Option<T>>
+    where
+        E: Format,
+        T: DecodeOwned<E::Mode>,
+    {
+        let bytes = &self.read[self.read_pos..];
+
+        let Some((prefix, len)) = self.header(bytes)? else {
+            self.compact(0);
+            return Ok(None);
+        };
+
+        if bytes.len() < prefix + len {
+            self.compact(prefix + len);
+            return Ok(None);
+        }
+
+        let result = self.encoding.decode_from_slice(&bytes[prefix..prefix + len]);
+        self.read_pos += prefix + len;
+
+        if self.read_pos == self.read.len() {
+            self.read.clear();
+            self.read_pos = 0;
+        }
+
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(error) => Err(io::Error::new(io::ErrorKind::InvalidData, error)),
+        }
+    }
+
+    /// Move unconsumed data to the start of the read buffer and make room for
+    /// a frame of the given size.
+    fn compact(&mut self, frame: usize) {
+        self.read.drain(..self.read_pos);
+        self.read_pos = 0;
+        self.read.reserve(frame.saturating_sub(self.read.len()));
+    }
+
+    /// Read the length prefix at the start of the given bytes.
+    fn header(&self, bytes: &[u8]) -> io::Result<Option<(usize, usize)>> {
+        let result = crate::alloc::default!(|alloc| {
+            let cx = Same::<Binary, framing::Error, _>::with_alloc(alloc);
+            self.framing.header(&cx, bytes)
+        });
+
+        result.map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+
+    /// Encode the given value into the internal buffer, returning its length
+    /// prefix.
+    fn encode_frame(&mut self, value: &T) -> io::Result<([u8; MAX_HEADER], usize)>
+    where
+        E: Format,
+        T: Encode<E::Mode>,
+    {
+        self.buffer.clear();
+
+        if let Err(error) = self.encoding.encode_to_vec(&mut self.buffer, value) {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, error));
+        }
+
+        let mut header = [0; MAX_HEADER];
+
+        let result = crate::alloc::default!(|alloc| {
+            let cx = Same::<Binary, framing::Error, _>::with_alloc(alloc);
+            let mut out = &mut header[..];
+            let result = self.framing.write_header(&cx, &mut out, self.buffer.len());
+            result.map(|()| MAX_HEADER - out.len())
+        });
+
+        match result {
+            Ok(len) => Ok((header, len)),
+            Err(error) => Err(io::Error::new(io::ErrorKind::InvalidInput, error)),
+        }
+    }
+}
+
+impl<E, T, const OPT: Options> Decoder for Codec<E, T, OPT>
+where
+    E: Format,
+    T: DecodeOwned<E::Mode>,
+{
+    type Item = T;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> io::Result<Option<T>> {
+        let Some((prefix, len)) = self.header(src)? else {
+            return Ok(None);
+        };
+
+        if src.len() < prefix + len {
+            src.reserve(prefix + len - src.len());
+            return Ok(None);
+        }
+
+        let result = self.encoding.decode_from_slice(&src[prefix..prefix + len]);
+        src.advance(prefix + len);
+
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(error) => Err(io::Error::new(io::ErrorKind::InvalidData, error)),
+        }
+    }
+}
+
+impl<E, T, const OPT: Options> Encoder<&T> for Codec<E, T, OPT>
+where
+    E: Format,
+    T: Encode<E::Mode>,
+{
+    type Error = io::Error;
+
+    fn encode(&mut self, item: &T, dst: &mut BytesMut) -> io::Result<()> {
+        let (header, len) = self.encode_frame(item)?;
+        dst.reserve(len + self.buffer.len());
+        dst.extend_from_slice(&header[..len]);
+        dst.extend_from_slice(&self.buffer);
+        Ok(())
+    }
+}
+
+impl<E, T, const OPT: Options> Encoder<T> for Codec<E, T, OPT>
+where
+    E: Format,
+    T: Encode<E::Mode>,
+{
+    type Error = io::Error;
+
+    #[inline]
+    fn encode(&mut self, item: T, dst: &mut BytesMut) -> io::Result<()> {
+        Encoder::<&T>::encode(self, &item, dst)
+    }
+}
@@ -0,0 +1,71 @@
+use rust_alloc::vec::Vec;
+
+use crate::de::DecodeOwned;
+use crate::Encode;
+
+/// An encoding which can be used to encode and decode the frames of a
+/// [`Codec`].
+///
+/// This is implemented for the `Encoding` type of each enabled format, such as
+/// [`wire::Encoding`].
+///
+/// [`Codec`]: super::Codec
+/// [`wire::Encoding`]: crate::wire::Encoding
+pub trait Format: Copy {
+    /// The mode used when encoding and decoding values.
+    type Mode: 'static;
+
+    /// The error raised by the encoding.
+    type Error: 'static + Send + Sync + std::error::Error;
+
+    /// Encode the given value into the given buffer.
+    fn encode_to_vec<T>(self, out: &mut Vec<u8>, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Encode<Self::Mode>;
+
+    /// Decode a value from the given frame.
+    fn decode_from_slice<T>(self, bytes: &[u8]) -> Result<T, Self::Error>
+    where
+        T: DecodeOwned<Self::Mode>;
+}
+
+macro_rules! implement {
+    ($($feature:literal, $what:ident $(, $opt:ident)?;)*) => {
+        $(
+            #[cfg(feature = $feature)]
+            impl<$(const $opt: crate::Options,)? M> Format for crate::$what::Encoding<$($opt,)? M>
+            where
+                M: 'static,
+            {
+                type Mode = M;
+                type Error = crate::$what::Error;
+
+                #[inline]
+                fn encode_to_vec<T>(self, out: &mut Vec<u8>, value: &T) -> Result<(), Self::Error>
+                where
+                    T: ?Sized + Encode<M>,
+                {
+                    self.encode(out, value)
+                }
+
+                #[inline]
+                fn decode_from_slice<T>(self, bytes: &[u8]) -> Result<T, Self::Error>
+                where
+                    T: DecodeOwned<M>,
+                {
+                    self.from_slice(bytes)
+                }
+            }
+        )*
+    };
+}
+
+implement! {
+    "storage", storage, OPT;
+    "wire", wire, OPT;
+    "descriptive", descriptive, OPT;
+    "msgpack", msgpack, OPT;
+    "cbor", cbor, OPT;
+    "protobuf", protobuf, OPT;
+    "json", json;
+}
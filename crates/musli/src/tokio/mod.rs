@@ -0,0 +1,76 @@
+//! Integration with [`tokio`] for encoding and decoding streams of values.
+//!
+//! The main type in this module is [`Codec`], which delimits values using
+//! [`framing`] so that they can be written back to back to a stream. It
+//! implements the [`Encoder`] and [`Decoder`] traits from [`tokio_util`], so it
+//! can be used with [`Framed`], [`FramedRead`] and [`FramedWrite`].
+//! [`Codec::read_from`] and [`Codec::write_to`] read and write single values
+//! directly from types implementing [`AsyncRead`] and [`AsyncWrite`]. With the
+//! `futures` feature enabled, [`Codec::read_from_futures`] and
+//! [`Codec::write_to_futures`] do the same for the traits in [`futures_io`].
+//!
+//! Any encoding which implements [`Format`] can be used, which includes the
+//! `Encoding` type of each binary format as well as [`json`].
+//!
+//! [`tokio`]: https://docs.rs/tokio
+//! [`tokio_util`]: https://docs.rs/tokio-util
+//! [`futures_io`]: https://docs.rs/futures-io
+//! [`framing`]: crate::framing
+//! [`json`]: crate::json
+//! [`Encoder`]: tokio_util::codec::Encoder
+//! [`Decoder`]: tokio_util::codec::Decoder
+//! [`Framed`]: tokio_util::codec::Framed
+//! [`FramedRead`]: tokio_util::codec::FramedRead
+//! [`FramedWrite`]: tokio_util::codec::FramedWrite
+//! [`AsyncRead`]: tokio::io::AsyncRead
+//! [`AsyncWrite`]: tokio::io::AsyncWrite
+//!
+//! ```
+//! use musli::{Decode, Encode};
+//! use musli::tokio::Codec;
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! enum Message {
+//!     Ping { id: u32 },
+//!     Pong { id: u32 },
+//! }
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> std::io::Result<()> {
+//! let (client, server) = tokio::io::duplex(1024);
+//!
+//! let task = tokio::spawn(async move {
+//!     let (mut reader, mut writer) = tokio::io::split(server);
+//!     let mut codec = Codec::<_, Message>::new(musli::wire::DEFAULT);
+//!
+//!     while let Some(Message::Ping { id }) = codec.read_from(&mut reader).await? {
+//!         codec.write_to(&mut writer, &Message::Pong { id }).await?;
+//!     }
+//!
+//!     Ok::<_, std::io::Error>(())
+//! });
+//!
+//! let (mut reader, mut writer) = tokio::io::split(client);
+//! let mut codec = Codec::<_, Message>::new(musli::wire::DEFAULT);
+//!
+//! codec.write_to(&mut writer, &Message::Ping { id: 42 }).await?;
+//! assert_eq!(codec.read_from(&mut reader).await?, Some(Message::Pong { id: 42 }));
+//!
+//! drop((reader, writer));
+//! task.await??;
+//! # Ok(()) }
+//! ```
+
+#![cfg(feature = "tokio")]
+#![cfg_attr(doc_cfg, doc(cfg(feature = "tokio")))]
+
+#[cfg(test)]
+mod tests;
+
+mod codec;
+mod format;
+
+#[doc(inline)]
+pub use self::codec::Codec;
+#[doc(inline)]
+pub use self::format::Format;
@@ -0,0 +1,187 @@
+use std::io;
+
+use bytes::BytesMut;
+use rust_alloc::string::{String, ToString};
+use rust_alloc::vec::Vec;
+use tokio::io::AsyncWriteExt;
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::framing::Framing;
+use crate::options::{self, ByteOrder, Integer, Options, Width};
+use crate::{Decode, Encode};
+
+use super::Codec;
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Message {
+    id: u32,
+    body: String,
+}
+
+fn message(id: u32) -> Message {
+    Message {
+        id,
+        body: "x".repeat(id as usize),
+    }
+}
+
+#[test]
+fn codec() {
+    const FIXED: Options = options::new()
+        .with_length(Integer::Fixed)
+        .with_length_width(Width::U32)
+        .with_byte_order(ByteOrder::Big)
+        .build();
+
+    let mut encoder = Codec::<_, Message>::new(crate::storage::DEFAULT)
+        .with_framing(Framing::new().with_options::<FIXED>());
+
+    let mut buf = BytesMut::new();
+
+    for id in 0..32 {
+        encoder.encode(message(id), &mut buf).unwrap();
+    }
+
+    let first = crate::storage::to_vec(&message(0)).unwrap();
+    assert_eq!(buf[..4], (first.len() as u32).to_be_bytes());
+    assert_eq!(buf[4..4 + first.len()], first);
+
+    for chunk in [1, 3, 16, 1024] {
+        let mut decoder = Codec::<_, Message>::new(crate::storage::DEFAULT)
+            .with_framing(Framing::new().with_options::<FIXED>());
+
+        let mut src = BytesMut::new();
+        let mut decoded = Vec::new();
+
+        for bytes in buf.chunks(chunk) {
+            src.extend_from_slice(bytes);
+
+            while let Some(message) = decoder.decode(&mut src).unwrap() {
+                decoded.push(message);
+            }
+        }
+
+        assert!(src.is_empty());
+        assert_eq!(decoded, (0..32).map(message).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn codec_errors() {
+    let mut codec = Codec::<_, Message>::new(crate::wire::DEFAULT)
+        .with_framing(Framing::new().with_max_frame_size(8));
+
+    let mut buf = BytesMut::new();
+    codec.encode(&message(2), &mut buf).unwrap();
+
+    let error = codec.encode(&message(16), &mut buf).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+
+    let mut src = BytesMut::from(&b"\x20"[..]);
+    let error = codec.decode(&mut src).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+    assert_eq!(
+        error.to_string(),
+        "Frame of 32 bytes exceeds the maximum frame size of 8 bytes"
+    );
+
+    // A frame which fails to decode is consumed.
+    let mut src = BytesMut::from(&b"\x01\xff\x00"[..]);
+    let error = codec.decode(&mut src).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(&src[..], b"\x00");
+}
+
+#[tokio::test]
+async fn read_write() {
+    let (mut a, mut b) = tokio::io::duplex(16);
+
+    let writer = tokio::spawn(async move {
+        let mut codec = Codec::<_, Message>::new(crate::wire::DEFAULT);
+
+        for id in 0..8 {
+            codec.write_to(&mut a, &message(id * 10)).await?;
+        }
+
+        // An incomplete frame.
+        a.write_all(b"\x10abc").await?;
+        Ok::<_, io::Error>(())
+    });
+
+    let mut codec = Codec::<_, Message>::new(crate::wire::DEFAULT);
+
+    for id in 0..8 {
+        let actual = codec.read_from(&mut b).await.unwrap();
+        assert_eq!(actual, Some(message(id * 10)));
+    }
+
+    writer.await.unwrap().unwrap();
+
+    let error = codec.read_from(&mut b).await.unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    assert_eq!(codec.read_from(&mut b).await.unwrap(), None);
+
+    let value = "x".to_string();
+    let mut codec = Codec::<_, String>::new(crate::json::DEFAULT);
+    let (mut a, mut b) = tokio::io::duplex(16);
+    codec.write_to(&mut a, &value).await.unwrap();
+    drop(a);
+    assert_eq!(codec.read_from(&mut b).await.unwrap(), Some(value));
+}
+
+#[tokio::test]
+async fn read_cancelled() {
+    let (mut a, mut b) = tokio::io::duplex(64);
+
+    let mut buf = BytesMut::new();
+    let mut codec = Codec::<_, Message>::new(crate::wire::DEFAULT);
+    codec.encode(message(4), &mut buf).unwrap();
+    codec.encode(message(8), &mut buf).unwrap();
+
+    let (first, rest) = buf.split_at(3);
+    a.write_all(first).await.unwrap();
+
+    // Reading is cancelled after the start of the frame has been read.
+    tokio::select! {
+        biased;
+        _ = codec.read_from(&mut b) => panic!("frame is incomplete"),
+        _ = core::future::ready(()) => {}
+    }
+
+    a.write_all(rest).await.unwrap();
+    drop(a);
+
+    assert_eq!(codec.read_from(&mut b).await.unwrap(), Some(message(4)));
+    assert_eq!(codec.read_from(&mut b).await.unwrap(), Some(message(8)));
+    assert_eq!(codec.read_from(&mut b).await.unwrap(), None);
+}
+
+#[cfg(feature = "futures")]
+#[tokio::test]
+async fn read_write_futures() {
+    use tokio_util::compat::TokioAsyncReadCompatExt;
+
+    let (a, b) = tokio::io::duplex(16);
+    let (mut a, mut b) = (a.compat(), b.compat());
+
+    let writer = tokio::spawn(async move {
+        let mut codec = Codec::<_, Message>::new(crate::wire::DEFAULT);
+
+        for id in 0..8 {
+            codec.write_to_futures(&mut a, &message(id * 10)).await?;
+        }
+
+        Ok::<_, io::Error>(())
+    });
+
+    let mut codec = Codec::<_, Message>::new(crate::wire::DEFAULT);
+
+    for id in 0..8 {
+        let actual = codec.read_from_futures(&mut b).await.unwrap();
+        assert_eq!(actual, Some(message(id * 10)));
+    }
+
+    writer.await.unwrap().unwrap();
+    assert_eq!(codec.read_from_futures(&mut b).await.unwrap(), None);
+}
@@ -0,0 +1,570 @@
+use core::fmt;
+use core::slice;
+
+use rust_alloc::vec::Vec;
+
+use crate::de::{
+    Decode, DecodeUnsized, Decoder, EntriesDecoder, EntryDecoder, MapDecoder, SequenceDecoder,
+    SizeHint, Skip, UnsizedVisitor, VariantDecoder, Visitor,
+};
+use crate::value::{Number, Value};
+use crate::Context;
+
+/// The value decoded as the data of a unit variant, which is written as a
+/// plain string.
+static EMPTY: Value = Value::Map(Vec::new());
+
+/// Decoder for a value in a parsed TOML document.
+pub struct TomlDecoder<'a, 'de, C: ?Sized> {
+    cx: &'a C,
+    value: &'de Value,
+    /// Whether the value is a key, in which case it's always a string which
+    /// might have to be parsed as a number.
+    key: bool,
+}
+
+impl<'a, 'de, C: ?Sized> TomlDecoder<'a, 'de, C> {
+    /// Construct a new decoder over a parsed value.
+    #[inline]
+    pub(crate) fn new(cx: &'a C, value: &'de Value) -> Self {
+        Self {
+            cx,
+            value,
+            key: false,
+        }
+    }
+
+    #[inline]
+    fn key(cx: &'a C, value: &'de Value) -> Self {
+        Self {
+            cx,
+            value,
+            key: true,
+        }
+    }
+}
+
+impl<'a, 'de, C> TomlDecoder<'a, 'de, C>
+where
+    C: ?Sized + Context,
+{
+    /// Construct an error for when the value isn't the expected one.
+    #[inline]
+    fn expected(&self, what: &str) -> C::Error {
+        self.cx.message(format_args!(
+            "Expected {what}, but found {}",
+            Kind(self.value)
+        ))
+    }
+
+    /// Decode an integer, which is either stored as an integer or as a string
+    /// if it's a key.
+    #[inline]
+    fn integer<T>(self, what: &str) -> Result<T, C::Error>
+    where
+        T: TryFrom<i64> + core::str::FromStr,
+    {
+        match self.value {
+            Value::Number(Number::I64(n)) => match T::try_from(*n) {
+                Ok(value) => Ok(value),
+                Err(..) => Err(self
+                    .cx
+                    .message(format_args!("Integer {n} is out of range for {what}"))),
+            },
+            Value::String(string) if self.key => match string.parse() {
+                Ok(value) => Ok(value),
+                Err(..) => Err(self.expected(what)),
+            },
+            _ => Err(self.expected(what)),
+        }
+    }
+
+    /// Decode a float, which can also be stored as an integer.
+    #[inline]
+    fn float(self, what: &str) -> Result<f64, C::Error> {
+        match self.value {
+            Value::Number(Number::F64(n)) => Ok(*n),
+            Value::Number(Number::I64(n)) => Ok(*n as f64),
+            Value::String(string) if self.key => match string.parse() {
+                Ok(n) => Ok(n),
+                Err(..) => Err(self.expected(what)),
+            },
+            _ => Err(self.expected(what)),
+        }
+    }
+}
+
+#[crate::decoder(crate)]
+impl<'a, 'de, C> Decoder<'de> for TomlDecoder<'a, 'de, C>
+where
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = C::Mode;
+    type WithContext<'this, U> = TomlDecoder<'this, 'de, U> where U: 'this + Context;
+    type DecodeSome = Self;
+    type DecodeSequence = SequenceTomlDecoder<'a, 'de, C>;
+    type DecodeMap = TableDecoder<'a, 'de, C>;
+    type DecodeMapEntries = TableDecoder<'a, 'de, C>;
+    type DecodeVariant = VariantTomlDecoder<'a, 'de, C>;
+
+    #[inline]
+    fn cx(&self) -> &Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn with_context<U>(self, cx: &U) -> Result<Self::WithContext<'_, U>, C::Error>
+    where
+        U: Context,
+    {
+        Ok(TomlDecoder {
+            cx,
+            value: self.value,
+            key: self.key,
+        })
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "value that can be decoded from TOML")
+    }
+
+    #[inline]
+    fn decode<T>(self) -> Result<T, Self::Error>
+    where
+        T: Decode<'de, Self::Mode>,
+    {
+        self.cx.decode(self)
+    }
+
+    #[inline]
+    fn decode_unsized<T, F, O>(self, f: F) -> Result<O, Self::Error>
+    where
+        T: ?Sized + DecodeUnsized<'de, Self::Mode>,
+        F: FnOnce(&T) -> Result<O, Self::Error>,
+    {
+        self.cx.decode_unsized(self, f)
+    }
+
+    #[inline]
+    fn skip(self) -> Result<(), C::Error> {
+        Ok(())
+    }
+
+    #[inline]
+    fn try_skip(self) -> Result<Skip, C::Error> {
+        Ok(Skip::Skipped)
+    }
+
+    #[inline]
+    fn decode_empty(self) -> Result<(), C::Error> {
+        match self.value {
+            Value::Unit => Ok(()),
+            Value::Map(entries) if entries.is_empty() => Ok(()),
+            _ => Err(self.expected("empty table")),
+        }
+    }
+
+    #[inline]
+    fn decode_bool(self) -> Result<bool, C::Error> {
+        match self.value {
+            Value::Bool(value) => Ok(*value),
+            Value::String(string) if self.key => match string.as_str() {
+                "true" => Ok(true),
+                "false" => Ok(false),
+                _ => Err(self.expected("boolean")),
+            },
+            _ => Err(self.expected("boolean")),
+        }
+    }
+
+    #[inline]
+    fn decode_char(self) -> Result<char, C::Error> {
+        let Value::String(string) = self.value else {
+            return Err(self.expected("character"));
+        };
+
+        let mut chars = string.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(self.expected("character")),
+        }
+    }
+
+    #[inline]
+    fn decode_u8(self) -> Result<u8, C::Error> {
+        self.integer("u8")
+    }
+
+    #[inline]
+    fn decode_u16(self) -> Result<u16, C::Error> {
+        self.integer("u16")
+    }
+
+    #[inline]
+    fn decode_u32(self) -> Result<u32, C::Error> {
+        self.integer("u32")
+    }
+
+    #[inline]
+    fn decode_u64(self) -> Result<u64, C::Error> {
+        self.integer("u64")
+    }
+
+    #[inline]
+    fn decode_u128(self) -> Result<u128, C::Error> {
+        self.integer("u128")
+    }
+
+    #[inline]
+    fn decode_i8(self) -> Result<i8, C::Error> {
+        self.integer("i8")
+    }
+
+    #[inline]
+    fn decode_i16(self) -> Result<i16, C::Error> {
+        self.integer("i16")
+    }
+
+    #[inline]
+    fn decode_i32(self) -> Result<i32, C::Error> {
+        self.integer("i32")
+    }
+
+    #[inline]
+    fn decode_i64(self) -> Result<i64, C::Error> {
+        self.integer("i64")
+    }
+
+    #[inline]
+    fn decode_i128(self) -> Result<i128, C::Error> {
+        self.integer("i128")
+    }
+
+    #[inline]
+    fn decode_usize(self) -> Result<usize, C::Error> {
+        self.integer("usize")
+    }
+
+    #[inline]
+    fn decode_isize(self) -> Result<isize, C::Error> {
+        self.integer("isize")
+    }
+
+    #[inline]
+    fn decode_f32(self) -> Result<f32, C::Error> {
+        Ok(self.float("f32")? as f32)
+    }
+
+    #[inline]
+    fn decode_f64(self) -> Result<f64, C::Error> {
+        self.float("f64")
+    }
+
+    #[inline]
+    fn decode_bytes<V>(self, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: UnsizedVisitor<'de, C, [u8]>,
+    {
+        let Value::Sequence(values) = self.value else {
+            return Err(self.expected("array of bytes"));
+        };
+
+        let mut bytes = Vec::with_capacity(values.len());
+
+        for value in values {
+            bytes.push(TomlDecoder::new(self.cx, value).integer("u8")?);
+        }
+
+        visitor.visit_ref(self.cx, &bytes)
+    }
+
+    #[inline]
+    fn decode_string<V>(self, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: UnsizedVisitor<'de, C, str>,
+    {
+        let Value::String(string) = self.value else {
+            return Err(self.expected("string"));
+        };
+
+        visitor.visit_borrowed(self.cx, string)
+    }
+
+    #[inline]
+    fn decode_option(self) -> Result<Option<Self::DecodeSome>, C::Error> {
+        // TOML can't represent an absent value, so anything which is present
+        // is a value.
+        Ok(Some(self))
+    }
+
+    #[inline]
+    fn decode_sequence<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeSequence) -> Result<O, C::Error>,
+    {
+        let Value::Sequence(values) = self.value else {
+            return Err(self.expected("array"));
+        };
+
+        f(&mut SequenceTomlDecoder::new(self.cx, values))
+    }
+
+    #[inline]
+    fn decode_map<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeMap) -> Result<O, C::Error>,
+    {
+        let Value::Map(entries) = self.value else {
+            return Err(self.expected("table"));
+        };
+
+        f(&mut TableDecoder::new(self.cx, entries))
+    }
+
+    #[inline]
+    fn decode_map_entries<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeMapEntries) -> Result<O, C::Error>,
+    {
+        self.decode_map(f)
+    }
+
+    #[inline]
+    fn decode_variant<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeVariant) -> Result<O, C::Error>,
+    {
+        let (tag, value) = match self.value {
+            Value::String(..) => (self.value, &EMPTY),
+            Value::Map(entries) => match &entries[..] {
+                [(tag, value)] => (tag, value),
+                _ => return Err(self.expected("table with a single key for the variant")),
+            },
+            _ => return Err(self.expected("variant")),
+        };
+
+        f(&mut VariantTomlDecoder {
+            cx: self.cx,
+            tag,
+            value,
+        })
+    }
+
+    #[inline]
+    fn decode_any<V>(self, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: Visitor<'de, C>,
+    {
+        match self.value {
+            Value::Bool(value) => visitor.visit_bool(self.cx, *value),
+            Value::Number(Number::I64(value)) => visitor.visit_i64(self.cx, *value),
+            Value::Number(Number::F64(value)) => visitor.visit_f64(self.cx, *value),
+            Value::String(string) => {
+                let visitor = visitor.visit_string(self.cx, SizeHint::exact(string.len()))?;
+                visitor.visit_borrowed(self.cx, string)
+            }
+            Value::Sequence(values) => {
+                visitor.visit_sequence(self.cx, &mut SequenceTomlDecoder::new(self.cx, values))
+            }
+            Value::Map(entries) => {
+                visitor.visit_map(self.cx, &mut TableDecoder::new(self.cx, entries))
+            }
+            _ => visitor.visit_empty(self.cx),
+        }
+    }
+}
+
+/// Decoder for the values of an array.
+pub struct SequenceTomlDecoder<'a, 'de, C: ?Sized> {
+    cx: &'a C,
+    iter: slice::Iter<'de, Value>,
+}
+
+impl<'a, 'de, C: ?Sized> SequenceTomlDecoder<'a, 'de, C> {
+    #[inline]
+    fn new(cx: &'a C, values: &'de [Value]) -> Self {
+        Self {
+            cx,
+            iter: values.iter(),
+        }
+    }
+}
+
+impl<'a, 'de, C> SequenceDecoder<'de> for SequenceTomlDecoder<'a, 'de, C>
+where
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeNext<'this> = TomlDecoder<'a, 'de, C> where Self: 'this;
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::exact(self.iter.len())
+    }
+
+    #[inline]
+    fn try_decode_next(&mut self) -> Result<Option<Self::DecodeNext<'_>>, C::Error> {
+        Ok(self
+            .iter
+            .next()
+            .map(|value| TomlDecoder::new(self.cx, value)))
+    }
+
+    #[inline]
+    fn decode_next(&mut self) -> Result<Self::DecodeNext<'_>, C::Error> {
+        let Some(value) = self.iter.next() else {
+            return Err(self.cx.message("Expected another value in array"));
+        };
+
+        Ok(TomlDecoder::new(self.cx, value))
+    }
+}
+
+/// Decoder for the entries of a table.
+pub struct TableDecoder<'a, 'de, C: ?Sized> {
+    cx: &'a C,
+    iter: slice::Iter<'de, (Value, Value)>,
+}
+
+impl<'a, 'de, C: ?Sized> TableDecoder<'a, 'de, C> {
+    #[inline]
+    fn new(cx: &'a C, entries: &'de [(Value, Value)]) -> Self {
+        Self {
+            cx,
+            iter: entries.iter(),
+        }
+    }
+}
+
+impl<'a, 'de, C> MapDecoder<'de> for TableDecoder<'a, 'de, C>
+where
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeEntry<'this> = EntryTomlDecoder<'a, 'de, C> where Self: 'this;
+    type DecodeRemainingEntries<'this> = TableDecoder<'a, 'de, C> where Self: 'this;
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::exact(self.iter.len())
+    }
+
+    #[inline]
+    fn decode_entry(&mut self) -> Result<Option<Self::DecodeEntry<'_>>, C::Error> {
+        Ok(self
+            .iter
+            .next()
+            .map(|entry| EntryTomlDecoder { cx: self.cx, entry }))
+    }
+
+    #[inline]
+    fn decode_remaining_entries(&mut self) -> Result<Self::DecodeRemainingEntries<'_>, C::Error> {
+        Ok(TableDecoder::new(self.cx, self.iter.as_slice()))
+    }
+}
+
+impl<'a, 'de, C> EntriesDecoder<'de> for TableDecoder<'a, 'de, C>
+where
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeEntryKey<'this> = TomlDecoder<'a, 'de, C> where Self: 'this;
+    type DecodeEntryValue<'this> = TomlDecoder<'a, 'de, C> where Self: 'this;
+
+    #[inline]
+    fn decode_entry_key(&mut self) -> Result<Option<Self::DecodeEntryKey<'_>>, C::Error> {
+        let Some((key, _)) = self.iter.clone().next() else {
+            return Ok(None);
+        };
+
+        Ok(Some(TomlDecoder::key(self.cx, key)))
+    }
+
+    #[inline]
+    fn decode_entry_value(&mut self) -> Result<Self::DecodeEntryValue<'_>, C::Error> {
+        let Some((_, value)) = self.iter.next() else {
+            return Err(self.cx.message("Expected another value in table"));
+        };
+
+        Ok(TomlDecoder::new(self.cx, value))
+    }
+
+    #[inline]
+    fn end_entries(self) -> Result<(), C::Error> {
+        Ok(())
+    }
+}
+
+/// Decoder for a single entry in a table.
+pub struct EntryTomlDecoder<'a, 'de, C: ?Sized> {
+    cx: &'a C,
+    entry: &'de (Value, Value),
+}
+
+impl<'a, 'de, C> EntryDecoder<'de> for EntryTomlDecoder<'a, 'de, C>
+where
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeKey<'this> = TomlDecoder<'a, 'de, C> where Self: 'this;
+    type DecodeValue = TomlDecoder<'a, 'de, C>;
+
+    #[inline]
+    fn decode_key(&mut self) -> Result<Self::DecodeKey<'_>, C::Error> {
+        Ok(TomlDecoder::key(self.cx, &self.entry.0))
+    }
+
+    #[inline]
+    fn decode_value(self) -> Result<Self::DecodeValue, C::Error> {
+        Ok(TomlDecoder::new(self.cx, &self.entry.1))
+    }
+}
+
+/// Decoder for a variant, which is either a string for unit variants or a
+/// table with a single key.
+pub struct VariantTomlDecoder<'a, 'de, C: ?Sized> {
+    cx: &'a C,
+    tag: &'de Value,
+    value: &'de Value,
+}
+
+impl<'a, 'de, C> VariantDecoder<'de> for VariantTomlDecoder<'a, 'de, C>
+where
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeTag<'this> = TomlDecoder<'a, 'de, C> where Self: 'this;
+    type DecodeValue<'this> = TomlDecoder<'a, 'de, C> where Self: 'this;
+
+    #[inline]
+    fn decode_tag(&mut self) -> Result<Self::DecodeTag<'_>, C::Error> {
+        Ok(TomlDecoder::key(self.cx, self.tag))
+    }
+
+    #[inline]
+    fn decode_value(&mut self) -> Result<Self::DecodeValue<'_>, C::Error> {
+        Ok(TomlDecoder::new(self.cx, self.value))
+    }
+}
+
+/// Helper to describe a value in errors.
+struct Kind<'a>(&'a Value);
+
+impl fmt::Display for Kind<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Value::Bool(value) => write!(f, "boolean `{value}`"),
+            Value::Number(Number::I64(value)) => write!(f, "integer `{value}`"),
+            Value::Number(Number::F64(value)) => write!(f, "float `{value}`"),
+            Value::String(string) => write!(f, "string {string:?}"),
+            Value::Sequence(..) => write!(f, "array"),
+            Value::Map(..) => write!(f, "table"),
+            _ => write!(f, "nothing"),
+        }
+    }
+}
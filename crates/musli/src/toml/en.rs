@@ -0,0 +1,348 @@
+//! Writer which renders a [`Value`] produced by encoding as a TOML document.
+//!
+//! Since TOML requires the plain keys of a table to precede any nested tables,
+//! values are first encoded into a [`Value`] and then written out in an order
+//! which satisfies this.
+
+use core::fmt::Write;
+use core::slice;
+
+use rust_alloc::string::String;
+use rust_alloc::vec::Vec;
+
+use crate::value::{Number, Value};
+use crate::Context;
+
+use super::parser::is_bare;
+
+/// How a value is written as part of a table.
+enum Kind<'a> {
+    /// The value is absent and is omitted.
+    Skip,
+    /// The value is written as a `[table]`.
+    Table(&'a [(Value, Value)]),
+    /// The value is written as an `[[array.of.tables]]`.
+    Tables(&'a [Value]),
+    /// The value is written inline after a key.
+    Inline(&'a Value),
+}
+
+/// Write a document.
+pub(crate) fn write_document<C>(cx: &C, out: &mut String, value: &Value) -> Result<(), C::Error>
+where
+    C: ?Sized + Context,
+{
+    let Some(entries) = table(value) else {
+        return Err(cx.message("Expected a table at the top level of a TOML document"));
+    };
+
+    let mut writer = Writer {
+        cx,
+        out,
+        path: Vec::new(),
+    };
+
+    writer.table(entries, None)
+}
+
+struct Writer<'a, 'b, 'v, C: ?Sized> {
+    cx: &'a C,
+    out: &'b mut String,
+    /// The keys of the table currently being written.
+    path: Vec<&'v Value>,
+}
+
+impl<'v, C> Writer<'_, '_, 'v, C>
+where
+    C: ?Sized + Context,
+{
+    /// Write a table, preceded by a header unless it is the root table.
+    ///
+    /// The header of a table which only contains other tables is omitted,
+    /// since it is implicitly defined by them.
+    fn table(
+        &mut self,
+        entries: &'v [(Value, Value)],
+        array: Option<bool>,
+    ) -> Result<(), C::Error> {
+        let mut inline = false;
+        let mut nested = false;
+
+        for (_, value) in entries {
+            match classify(value) {
+                Kind::Skip => {}
+                Kind::Inline(..) => inline = true,
+                Kind::Table(..) | Kind::Tables(..) => nested = true,
+            }
+        }
+
+        if let Some(array) = array {
+            if array || inline || !nested {
+                self.header(array)?;
+            }
+        }
+
+        for (key, value) in entries {
+            if let Kind::Inline(value) = classify(value) {
+                self.key(key)?;
+                self.out.push_str(" = ");
+                self.inline(value)?;
+                self.out.push('\n');
+            }
+        }
+
+        for (key, value) in entries {
+            match classify(value) {
+                Kind::Table(entries) => {
+                    self.path.push(key);
+                    self.table(entries, Some(false))?;
+                    self.path.pop();
+                }
+                Kind::Tables(values) => {
+                    self.path.push(key);
+
+                    for value in values {
+                        let entries = table(value).unwrap_or_default();
+                        self.table(entries, Some(true))?;
+                    }
+
+                    self.path.pop();
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn header(&mut self, array: bool) -> Result<(), C::Error> {
+        if !self.out.is_empty() {
+            self.out.push('\n');
+        }
+
+        self.out.push_str(if array { "[[" } else { "[" });
+
+        for n in 0..self.path.len() {
+            if n > 0 {
+                self.out.push('.');
+            }
+
+            self.key(self.path[n])?;
+        }
+
+        self.out.push_str(if array { "]]\n" } else { "]\n" });
+        Ok(())
+    }
+
+    fn key(&mut self, key: &Value) -> Result<(), C::Error> {
+        match key {
+            Value::String(string) => self.key_str(string),
+            Value::Char(c) => self.key_str(c.encode_utf8(&mut [0; 4])),
+            Value::Bool(b) => self.out.push_str(if *b { "true" } else { "false" }),
+            Value::Number(number) if !matches!(number, Number::F32(..) | Number::F64(..)) => {
+                self.number(number)?;
+            }
+            _ => return Err(self.cx.message("TOML keys must be strings or integers")),
+        }
+
+        Ok(())
+    }
+
+    fn key_str(&mut self, key: &str) {
+        if !key.is_empty() && key.bytes().all(is_bare) {
+            self.out.push_str(key);
+        } else {
+            self.string(key);
+        }
+    }
+
+    fn inline(&mut self, value: &Value) -> Result<(), C::Error> {
+        match value {
+            Value::Unit => self.out.push_str("{}"),
+            Value::Bool(b) => self.out.push_str(if *b { "true" } else { "false" }),
+            Value::Char(c) => self.string(c.encode_utf8(&mut [0; 4])),
+            Value::Number(number) => self.number(number)?,
+            Value::Bytes(bytes) => {
+                self.out.push('[');
+
+                for (n, b) in bytes.iter().enumerate() {
+                    if n > 0 {
+                        self.out.push_str(", ");
+                    }
+
+                    self.out.push_str(itoa::Buffer::new().format(*b));
+                }
+
+                self.out.push(']');
+            }
+            Value::String(string) => self.string(string),
+            Value::Sequence(values) => {
+                self.out.push('[');
+
+                for (n, value) in values.iter().enumerate() {
+                    if n > 0 {
+                        self.out.push_str(", ");
+                    }
+
+                    let Some(value) = present(value) else {
+                        return Err(self.cx.message("TOML arrays cannot contain absent values"));
+                    };
+
+                    self.inline(value)?;
+                }
+
+                self.out.push(']');
+            }
+            Value::Map(entries) => self.inline_table(entries)?,
+            Value::Variant(variant) if is_unit(&variant.1) => self.inline(&variant.0)?,
+            Value::Variant(variant) => self.inline_table(slice::from_ref(&**variant))?,
+            Value::Option(Some(value)) => self.inline(value)?,
+            Value::Option(None) => {
+                return Err(self.cx.message("TOML cannot represent an absent value"));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn inline_table(&mut self, entries: &[(Value, Value)]) -> Result<(), C::Error> {
+        let mut first = true;
+
+        for (key, value) in entries {
+            let Some(value) = present(value) else {
+                continue;
+            };
+
+            self.out.push_str(if first { "{ " } else { ", " });
+            first = false;
+
+            self.key(key)?;
+            self.out.push_str(" = ");
+            self.inline(value)?;
+        }
+
+        self.out.push_str(if first { "{}" } else { " }" });
+        Ok(())
+    }
+
+    fn number(&mut self, number: &Number) -> Result<(), C::Error> {
+        let n = match *number {
+            Number::U8(n) => i64::from(n),
+            Number::U16(n) => i64::from(n),
+            Number::U32(n) => i64::from(n),
+            Number::I8(n) => i64::from(n),
+            Number::I16(n) => i64::from(n),
+            Number::I32(n) => i64::from(n),
+            Number::I64(n) => n,
+            Number::U64(n) => self.in_range(n)?,
+            Number::U128(n) => self.in_range(n)?,
+            Number::I128(n) => self.in_range(n)?,
+            Number::Usize(n) => self.in_range(n)?,
+            Number::Isize(n) => self.in_range(n)?,
+            Number::F32(n) => {
+                self.float(f64::from(n), |out| {
+                    out.push_str(ryu::Buffer::new().format_finite(n))
+                });
+                return Ok(());
+            }
+            Number::F64(n) => {
+                self.float(n, |out| out.push_str(ryu::Buffer::new().format_finite(n)));
+                return Ok(());
+            }
+        };
+
+        self.out.push_str(itoa::Buffer::new().format(n));
+        Ok(())
+    }
+
+    fn in_range<T>(&self, n: T) -> Result<i64, C::Error>
+    where
+        T: Copy + core::fmt::Display + TryInto<i64>,
+    {
+        match n.try_into() {
+            Ok(n) => Ok(n),
+            Err(..) => Err(self
+                .cx
+                .message(format_args!("Integer {n} is out of range for TOML"))),
+        }
+    }
+
+    fn float(&mut self, n: f64, finite: impl FnOnce(&mut String)) {
+        if n.is_nan() {
+            self.out.push_str("nan");
+        } else if n.is_infinite() {
+            self.out.push_str(if n > 0.0 { "inf" } else { "-inf" });
+        } else {
+            finite(self.out);
+        }
+    }
+
+    fn string(&mut self, string: &str) {
+        self.out.push('"');
+
+        for c in string.chars() {
+            match c {
+                '"' => self.out.push_str("\\\""),
+                '\\' => self.out.push_str("\\\\"),
+                '\n' => self.out.push_str("\\n"),
+                '\r' => self.out.push_str("\\r"),
+                '\t' => self.out.push_str("\\t"),
+                '\u{8}' => self.out.push_str("\\b"),
+                '\u{c}' => self.out.push_str("\\f"),
+                c if c.is_ascii_control() => {
+                    _ = write!(self.out, "\\u{:04X}", c as u32);
+                }
+                c => self.out.push(c),
+            }
+        }
+
+        self.out.push('"');
+    }
+}
+
+/// Classify how a value is written.
+fn classify(value: &Value) -> Kind<'_> {
+    let Some(value) = present(value) else {
+        return Kind::Skip;
+    };
+
+    if let Some(entries) = table(value) {
+        return Kind::Table(entries);
+    }
+
+    if let Value::Sequence(values) = value {
+        if !values.is_empty() && values.iter().all(|value| table(value).is_some()) {
+            return Kind::Tables(values);
+        }
+    }
+
+    Kind::Inline(value)
+}
+
+/// Get the entries of a value which is written as a table.
+fn table(value: &Value) -> Option<&[(Value, Value)]> {
+    match present(value)? {
+        Value::Map(entries) => Some(entries),
+        Value::Variant(variant) if !is_unit(&variant.1) => Some(slice::from_ref(&**variant)),
+        _ => None,
+    }
+}
+
+/// Test if a value is the data of a unit variant, which is written as only
+/// the name of the variant.
+fn is_unit(value: &Value) -> bool {
+    match value {
+        Value::Unit => true,
+        Value::Map(entries) => entries.is_empty(),
+        _ => false,
+    }
+}
+
+/// Unwrap optional values, returning `None` if the value is absent.
+fn present(mut value: &Value) -> Option<&Value> {
+    while let Value::Option(option) = value {
+        value = option.as_deref()?;
+    }
+
+    Some(value)
+}
@@ -0,0 +1,348 @@
+//! Module that defines [`Encoding`] whith allows for customization of the
+//! encoding format, and the [`DEFAULT`] encoding configuration.
+
+use core::marker;
+
+use rust_alloc::string::String;
+
+use crate::de::DecodeOwned;
+use crate::en::Encoder;
+use crate::mode::Text;
+use crate::options::{self, Options};
+use crate::value::{Value, ValueEncoder};
+use crate::{Context, Encode};
+
+use super::de::TomlDecoder;
+use super::error::Error;
+
+/// The options used to encode values before they are written.
+const OPTIONS: Options = options::new().build();
+
+/// The default configuration.
+pub const DEFAULT: Encoding = Encoding::new();
+
+/// Encode the given value to a [`String`] using the [`DEFAULT`] [`Encoding`].
+///
+/// # Examples
+///
+/// ```
+/// use musli::{Decode, Encode};
+/// use musli::toml;
+/// # use musli::toml::Error;
+///
+/// #[derive(Decode, Encode)]
+/// struct Server {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// #[derive(Decode, Encode)]
+/// struct Config {
+///     name: String,
+///     server: Server,
+/// }
+///
+/// let data = toml::to_string(&Config {
+///     name: "app".to_string(),
+///     server: Server {
+///         host: "localhost".to_string(),
+///         port: 8080,
+///     },
+/// })?;
+///
+/// assert_eq!(data, "name = \"app\"\n\n[server]\nhost = \"localhost\"\nport = 8080\n");
+/// # Ok::<(), Error>(())
+/// ```
+#[inline]
+pub fn to_string<T>(value: &T) -> Result<String, Error>
+where
+    T: ?Sized + Encode<Text>,
+{
+    DEFAULT.to_string(value)
+}
+
+/// Decode the given type `T` from the given string using the [`DEFAULT`]
+/// [`Encoding`].
+///
+/// # Examples
+///
+/// ```
+/// use musli::{Decode, Encode};
+/// use musli::toml;
+/// # use musli::toml::Error;
+///
+/// #[derive(Decode, Encode)]
+/// struct Server {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// #[derive(Decode, Encode)]
+/// struct Config {
+///     name: String,
+///     server: Server,
+/// }
+///
+/// let config: Config = toml::from_str(r#"
+/// name = "app"
+///
+/// [server]
+/// host = "localhost"
+/// port = 8080
+/// "#)?;
+///
+/// assert_eq!(config.name, "app");
+/// assert_eq!(config.server.port, 8080);
+/// # Ok::<(), Error>(())
+/// ```
+#[inline]
+pub fn from_str<T>(string: &str) -> Result<T, Error>
+where
+    T: DecodeOwned<Text>,
+{
+    DEFAULT.from_str(string)
+}
+
+/// Setting up encoding with parameters.
+pub struct Encoding<M = Text>
+where
+    M: 'static,
+{
+    _marker: marker::PhantomData<M>,
+}
+
+impl Default for Encoding<Text> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Encoding<Text> {
+    /// Construct a new [`Encoding`].
+    ///
+    /// You can modify this using the available factory methods:
+    ///
+    /// ```
+    /// use musli::{Encode, Decode};
+    /// use musli::toml::Encoding;
+    /// # use musli::toml::Error;
+    ///
+    /// const CONFIG: Encoding<Config> = Encoding::new().with_mode();
+    ///
+    /// // Mode marker indicating that some attributes should
+    /// // only apply when we're encoding configuration files.
+    /// enum Config {}
+    ///
+    /// #[derive(Debug, PartialEq, Encode, Decode)]
+    /// #[musli(mode = Config, name_all = "kebab-case")]
+    /// struct Person {
+    ///     first_name: String,
+    /// }
+    ///
+    /// let expected = Person {
+    ///     first_name: "Aristotle".to_string(),
+    /// };
+    ///
+    /// let out = CONFIG.to_string(&expected)?;
+    /// assert_eq!(out, "first-name = \"Aristotle\"\n");
+    ///
+    /// let actual = CONFIG.from_str(&out)?;
+    /// assert_eq!(expected, actual);
+    /// # Ok::<(), Error>(())
+    /// ```
+    #[inline]
+    pub const fn new() -> Self {
+        Encoding {
+            _marker: marker::PhantomData,
+        }
+    }
+}
+
+impl<M> Encoding<M>
+where
+    M: 'static,
+{
+    /// Change the mode of the encoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::toml::Encoding;
+    ///
+    /// enum Custom {}
+    ///
+    /// const CONFIG: Encoding<Custom> = Encoding::new().with_mode();
+    /// ```
+    pub const fn with_mode<T>(self) -> Encoding<T> {
+        Encoding {
+            _marker: marker::PhantomData,
+        }
+    }
+
+    /// Encode the given value to a [`String`] using the current [`Encoding`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Decode, Encode};
+    /// use musli::toml;
+    /// # use musli::toml::Error;
+    ///
+    /// const ENCODING: toml::Encoding = toml::Encoding::new();
+    ///
+    /// #[derive(Decode, Encode)]
+    /// struct Person {
+    ///     name: String,
+    ///     age: u32,
+    /// }
+    ///
+    /// let data = ENCODING.to_string(&Person {
+    ///     name: "Aristotle".to_string(),
+    ///     age: 61,
+    /// })?;
+    ///
+    /// assert_eq!(data, "name = \"Aristotle\"\nage = 61\n");
+    /// # Ok::<(), Error>(())
+    /// ```
+    #[inline]
+    pub fn to_string<T>(self, value: &T) -> Result<String, Error>
+    where
+        T: ?Sized + Encode<M>,
+    {
+        crate::alloc::default!(|alloc| {
+            let cx = crate::context::Same::with_alloc(alloc);
+            self.to_string_with(&cx, value)
+        })
+    }
+
+    /// Encode the given value to a [`String`] using the current [`Encoding`].
+    ///
+    /// This is the same as [`Encoding::to_string`] but allows for using a
+    /// configurable [`Context`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Decode, Encode};
+    /// use musli::toml;
+    /// use musli::context::Same;
+    /// # use musli::toml::Error;
+    ///
+    /// const ENCODING: toml::Encoding = toml::Encoding::new();
+    ///
+    /// #[derive(Decode, Encode)]
+    /// struct Person {
+    ///     name: String,
+    ///     age: u32,
+    /// }
+    ///
+    /// let cx = Same::new();
+    ///
+    /// let data = ENCODING.to_string_with(&cx, &Person {
+    ///     name: "Aristotle".to_string(),
+    ///     age: 61,
+    /// })?;
+    ///
+    /// let person: Person = ENCODING.from_str_with(&cx, &data)?;
+    /// assert_eq!(person.name, "Aristotle");
+    /// assert_eq!(person.age, 61);
+    /// # Ok::<(), Error>(())
+    /// ```
+    #[inline]
+    pub fn to_string_with<T, C>(self, cx: &C, value: &T) -> Result<String, C::Error>
+    where
+        C: ?Sized + Context<Mode = M>,
+        T: ?Sized + Encode<M>,
+    {
+        cx.clear();
+        let mut output = Value::Unit;
+        ValueEncoder::<OPTIONS, _, _>::new(cx, &mut output).encode(value)?;
+        let mut string = String::new();
+        super::en::write_document(cx, &mut string, &output)?;
+        Ok(string)
+    }
+
+    /// Decode the given type `T` from the given string using the current
+    /// [`Encoding`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Decode, Encode};
+    /// use musli::toml;
+    /// # use musli::toml::Error;
+    ///
+    /// const ENCODING: toml::Encoding = toml::Encoding::new();
+    ///
+    /// #[derive(Decode, Encode)]
+    /// struct Person {
+    ///     name: String,
+    ///     age: u32,
+    /// }
+    ///
+    /// let person: Person = ENCODING.from_str("name = 'Aristotle'\nage = 61")?;
+    /// assert_eq!(person.name, "Aristotle");
+    /// assert_eq!(person.age, 61);
+    /// # Ok::<(), Error>(())
+    /// ```
+    #[inline]
+    pub fn from_str<T>(self, string: &str) -> Result<T, Error>
+    where
+        T: DecodeOwned<M>,
+    {
+        crate::alloc::default!(|alloc| {
+            let cx = crate::context::Same::with_alloc(alloc);
+            self.from_str_with(&cx, string)
+        })
+    }
+
+    /// Decode the given type `T` from the given string using the current
+    /// [`Encoding`].
+    ///
+    /// This is the same as [`Encoding::from_str`] but allows for using a
+    /// configurable [`Context`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Decode, Encode};
+    /// use musli::toml;
+    /// use musli::context::Same;
+    /// # use musli::toml::Error;
+    ///
+    /// const ENCODING: toml::Encoding = toml::Encoding::new();
+    ///
+    /// #[derive(Decode, Encode)]
+    /// struct Person {
+    ///     name: String,
+    ///     age: u32,
+    /// }
+    ///
+    /// let cx = Same::new();
+    ///
+    /// let person: Person = ENCODING.from_str_with(&cx, "name = 'Aristotle'\nage = 61")?;
+    /// assert_eq!(person.name, "Aristotle");
+    /// assert_eq!(person.age, 61);
+    /// # Ok::<(), Error>(())
+    /// ```
+    #[inline]
+    pub fn from_str_with<C, T>(self, cx: &C, string: &str) -> Result<T, C::Error>
+    where
+        C: ?Sized + Context<Mode = M>,
+        T: DecodeOwned<M>,
+    {
+        cx.clear();
+        let document = super::parser::parse(cx, string)?;
+        T::decode(cx, TomlDecoder::new(cx, &document))
+    }
+}
+
+impl<M> Clone for Encoding<M> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M> Copy for Encoding<M> {}
@@ -0,0 +1,21 @@
+crate::macros::error_type! {
+    /// Error raised during TOML encoding.
+    pub struct Error;
+
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::Decode;
+    /// use musli::context::ErrorKind;
+    ///
+    /// #[derive(Debug, Decode)]
+    /// struct Config {
+    ///     port: u16,
+    /// }
+    ///
+    /// let error = musli::toml::from_str::<Config>("host = \"localhost\"").unwrap_err();
+    /// assert_eq!(error.kind(), ErrorKind::MissingField);
+    /// ```
+    pub fn kind;
+}
@@ -0,0 +1,83 @@
+//! Support for the [TOML] configuration format.
+//!
+//! Structs are encoded as tables, where nested structs become `[tables]` and
+//! sequences of structs become `[[arrays.of.tables]]`. Decoding supports the
+//! whole syntax of the format, including dotted keys and inline tables.
+//!
+//! * ✔ Can tolerate missing fields if they are annotated with
+//!   `#[musli(default)]`. Since TOML has no representation of an absent value,
+//!   this is also how optional fields are omitted.
+//! * ✔ Can skip over unknown fields.
+//! * ✔ Can be fully upgraded to [`Value`] through `decode_any`.
+//!
+//! Datetimes are decoded as strings, and strings are always encoded as strings
+//! so a datetime should be stored in a `String` or in a type which converts
+//! from one. Unit variants are encoded as strings, and other variants as a
+//! table with a single key naming the variant.
+//!
+//! Documents are parsed or built in full before being decoded or written, so
+//! this module requires the `alloc` feature.
+//!
+//! [TOML]: https://toml.io
+//! [`Value`]: crate::value::Value
+//!
+//! ```
+//! use musli::{Encode, Decode};
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! struct Backend {
+//!     name: String,
+//!     weight: u32,
+//! }
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! struct Config {
+//!     title: String,
+//!     deployed: String,
+//!     #[musli(default)]
+//!     replicas: Option<u32>,
+//!     backends: Vec<Backend>,
+//! }
+//!
+//! let config: Config = musli::toml::from_str(r#"
+//! title = "Production"
+//! deployed = 2024-05-27T07:32:00Z
+//!
+//! [[backends]]
+//! name = "primary"
+//! weight = 3
+//!
+//! [[backends]]
+//! name = "secondary"
+//! weight = 1
+//! "#)?;
+//!
+//! assert_eq!(config.deployed, "2024-05-27T07:32:00Z");
+//! assert_eq!(config.replicas, None);
+//! assert_eq!(config.backends.len(), 2);
+//!
+//! let out = musli::toml::to_string(&config)?;
+//! let config2: Config = musli::toml::from_str(&out)?;
+//! assert_eq!(config, config2);
+//! # Ok::<_, musli::toml::Error>(())
+//! ```
+
+#![cfg(feature = "toml")]
+#![cfg_attr(doc_cfg, doc(cfg(feature = "toml")))]
+
+#[cfg(test)]
+mod tests;
+
+mod de;
+mod en;
+mod encoding;
+mod error;
+mod parser;
+
+/// Convenient result alias for use with `musli::toml`.
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+#[doc(inline)]
+pub use self::encoding::{from_str, to_string, Encoding, DEFAULT};
+#[doc(inline)]
+pub use self::error::Error;
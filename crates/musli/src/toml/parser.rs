@@ -0,0 +1,774 @@
+//! Parser which reads a TOML document into a [`Value`].
+//!
+//! Datetimes are not interpreted and are stored as strings.
+
+use core::fmt;
+use core::slice;
+
+use rust_alloc::string::String;
+use rust_alloc::vec::Vec;
+
+use crate::value::{Number, Value};
+use crate::Context;
+
+/// Parse a whole document into a table.
+pub(crate) fn parse<C>(cx: &C, input: &str) -> Result<Value, C::Error>
+where
+    C: ?Sized + Context,
+{
+    let mut parser = Parser { cx, input, pos: 0 };
+
+    let mut root = Vec::new();
+    let mut current = Vec::new();
+    // Headers of tables which have been defined, since a table can only be
+    // defined once.
+    let mut defined = Vec::<Vec<String>>::new();
+
+    loop {
+        parser.skip_ws();
+
+        match parser.peek() {
+            None => break,
+            Some(b'#' | b'\r' | b'\n') => {}
+            Some(b'[') => {
+                parser.pos += 1;
+                let array = parser.eat(b'[');
+                parser.skip_ws();
+                let keys = parser.parse_keys()?;
+
+                if !parser.eat(b']') || (array && !parser.eat(b']')) {
+                    return Err(parser.error("Expected `]` after table header"));
+                }
+
+                let (last, parent) = keys.split_last().unwrap_or_else(|| unreachable!());
+                let table = parser.table(&mut root, parent)?;
+
+                if array {
+                    parser.push_table(table, last)?;
+                    // Tables below a new element of an array of tables can be
+                    // defined again.
+                    defined.retain(|header| !header.starts_with(&keys));
+                } else {
+                    if defined.contains(&keys) {
+                        return Err(
+                            parser.error(format_args!("Duplicate table {:?}", keys.join(".")))
+                        );
+                    }
+
+                    if let Some(index) = find(table, last) {
+                        if let Value::Sequence(..) = table[index].1 {
+                            return Err(
+                                parser.error(format_args!("Key {last:?} is an array of tables"))
+                            );
+                        }
+                    }
+
+                    parser.table(table, slice::from_ref(last))?;
+                    defined.push(keys.clone());
+                }
+
+                current = keys;
+            }
+            Some(_) => {
+                let table = parser.table(&mut root, &current)?;
+                parser.parse_key_value(table)?;
+            }
+        }
+
+        parser.end_of_line()?;
+    }
+
+    Ok(Value::Map(root))
+}
+
+struct Parser<'a, 'de, C: ?Sized> {
+    cx: &'a C,
+    input: &'de str,
+    pos: usize,
+}
+
+impl<'a, 'de, C> Parser<'a, 'de, C>
+where
+    C: ?Sized + Context,
+{
+    /// Construct an error which points to the current position.
+    fn error(&self, message: impl fmt::Display) -> C::Error {
+        let consumed = &self.input.as_bytes()[..self.pos];
+        let line = consumed.iter().filter(|&&b| b == b'\n').count() + 1;
+        let column = match consumed.iter().rposition(|&b| b == b'\n') {
+            Some(n) => self.pos - n,
+            None => self.pos + 1,
+        };
+
+        self.cx
+            .message(format_args!("{message} (at line {line}, column {column})"))
+    }
+
+    #[inline]
+    fn peek(&self) -> Option<u8> {
+        self.input.as_bytes().get(self.pos).copied()
+    }
+
+    #[inline]
+    fn peek_at(&self, n: usize) -> Option<u8> {
+        self.input.as_bytes().get(self.pos + n).copied()
+    }
+
+    #[inline]
+    fn starts_with(&self, prefix: &str) -> bool {
+        self.input[self.pos..].starts_with(prefix)
+    }
+
+    /// Consume the given byte if it's next in the input.
+    #[inline]
+    fn eat(&mut self, b: u8) -> bool {
+        if self.peek() == Some(b) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Skip over spaces and tabs.
+    #[inline]
+    fn skip_ws(&mut self) {
+        while let Some(b' ' | b'\t') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    /// Skip over a comment, if present.
+    fn skip_comment(&mut self) -> Result<(), C::Error> {
+        if !self.eat(b'#') {
+            return Ok(());
+        }
+
+        while let Some(b) = self.peek() {
+            match b {
+                b'\n' => break,
+                b'\r' if self.peek_at(1) == Some(b'\n') => break,
+                b'\t' => {}
+                0..=0x1f | 0x7f => {
+                    return Err(self.error("Control characters are not allowed in comments"))
+                }
+                _ => {}
+            }
+
+            self.pos += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Consume a newline, if present.
+    #[inline]
+    fn newline(&mut self) -> bool {
+        if self.eat(b'\n') {
+            return true;
+        }
+
+        if self.starts_with("\r\n") {
+            self.pos += 2;
+            return true;
+        }
+
+        false
+    }
+
+    /// Skip over whitespace, comments and newlines, which may separate the
+    /// values of an array.
+    fn skip_ws_comments_newlines(&mut self) -> Result<(), C::Error> {
+        loop {
+            self.skip_ws();
+            self.skip_comment()?;
+
+            if !self.newline() {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Expect the end of the current line.
+    fn end_of_line(&mut self) -> Result<(), C::Error> {
+        self.skip_ws();
+        self.skip_comment()?;
+
+        if self.peek().is_none() || self.newline() {
+            return Ok(());
+        }
+
+        Err(self.error("Expected newline"))
+    }
+
+    /// Parse a dotted key.
+    fn parse_keys(&mut self) -> Result<Vec<String>, C::Error> {
+        let mut keys = Vec::new();
+
+        loop {
+            self.skip_ws();
+            keys.push(self.parse_key()?);
+            self.skip_ws();
+
+            if !self.eat(b'.') {
+                return Ok(keys);
+            }
+        }
+    }
+
+    /// Parse a single bare or quoted key.
+    fn parse_key(&mut self) -> Result<String, C::Error> {
+        match self.peek() {
+            Some(b'"') => {
+                self.pos += 1;
+                self.parse_basic_string()
+            }
+            Some(b'\'') => {
+                self.pos += 1;
+                self.parse_literal_string()
+            }
+            _ => {
+                let start = self.pos;
+
+                while let Some(b) = self.peek() {
+                    if !is_bare(b) {
+                        break;
+                    }
+
+                    self.pos += 1;
+                }
+
+                if start == self.pos {
+                    return Err(self.error("Expected key"));
+                }
+
+                Ok(self.input[start..self.pos].into())
+            }
+        }
+    }
+
+    /// Parse a key and value pair into the given table.
+    fn parse_key_value(&mut self, table: &mut Vec<(Value, Value)>) -> Result<(), C::Error> {
+        let keys = self.parse_keys()?;
+
+        if !self.eat(b'=') {
+            return Err(self.error("Expected `=` after key"));
+        }
+
+        self.skip_ws();
+        let value = self.parse_value()?;
+        let (last, parent) = keys.split_last().unwrap_or_else(|| unreachable!());
+        let table = self.table(table, parent)?;
+
+        if find(table, last).is_some() {
+            return Err(self.error(format_args!("Duplicate key {last:?}")));
+        }
+
+        table.push((Value::String(last.clone()), value));
+        Ok(())
+    }
+
+    fn parse_value(&mut self) -> Result<Value, C::Error> {
+        match self.peek() {
+            Some(b'"') => {
+                if self.starts_with("\"\"\"") {
+                    self.pos += 3;
+                    Ok(Value::String(self.parse_multiline_basic_string()?))
+                } else {
+                    self.pos += 1;
+                    Ok(Value::String(self.parse_basic_string()?))
+                }
+            }
+            Some(b'\'') => {
+                if self.starts_with("'''") {
+                    self.pos += 3;
+                    Ok(Value::String(self.parse_multiline_literal_string()?))
+                } else {
+                    self.pos += 1;
+                    Ok(Value::String(self.parse_literal_string()?))
+                }
+            }
+            Some(b'[') => {
+                self.pos += 1;
+                self.parse_array()
+            }
+            Some(b'{') => {
+                self.pos += 1;
+                self.parse_inline_table()
+            }
+            Some(b't') if self.starts_with("true") => {
+                self.pos += 4;
+                Ok(Value::Bool(true))
+            }
+            Some(b'f') if self.starts_with("false") => {
+                self.pos += 5;
+                Ok(Value::Bool(false))
+            }
+            Some(b) if b.is_ascii_digit() || matches!(b, b'+' | b'-' | b'i' | b'n') => {
+                self.parse_number_or_datetime()
+            }
+            _ => Err(self.error("Expected value")),
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<Value, C::Error> {
+        let mut values = Vec::new();
+
+        loop {
+            self.skip_ws_comments_newlines()?;
+
+            if self.eat(b']') {
+                break;
+            }
+
+            values.push(self.parse_value()?);
+            self.skip_ws_comments_newlines()?;
+
+            if self.eat(b']') {
+                break;
+            }
+
+            if !self.eat(b',') {
+                return Err(self.error("Expected `,` or `]` in array"));
+            }
+        }
+
+        Ok(Value::Sequence(values))
+    }
+
+    fn parse_inline_table(&mut self) -> Result<Value, C::Error> {
+        let mut table = Vec::new();
+
+        self.skip_ws();
+
+        if self.eat(b'}') {
+            return Ok(Value::Map(table));
+        }
+
+        loop {
+            self.parse_key_value(&mut table)?;
+            self.skip_ws();
+
+            if self.eat(b'}') {
+                break;
+            }
+
+            if !self.eat(b',') {
+                return Err(self.error("Expected `,` or `}` in inline table"));
+            }
+
+            self.skip_ws();
+        }
+
+        Ok(Value::Map(table))
+    }
+
+    fn parse_basic_string(&mut self) -> Result<String, C::Error> {
+        let mut string = String::new();
+
+        loop {
+            let start = self.pos;
+
+            while let Some(b) = self.peek() {
+                if matches!(b, b'"' | b'\\') || (b.is_ascii_control() && b != b'\t') {
+                    break;
+                }
+
+                self.pos += 1;
+            }
+
+            string.push_str(&self.input[start..self.pos]);
+
+            match self.peek() {
+                Some(b'"') => {
+                    self.pos += 1;
+                    return Ok(string);
+                }
+                Some(b'\\') => {
+                    self.pos += 1;
+                    string.push(self.parse_escape()?);
+                }
+                Some(b'\n' | b'\r') | None => return Err(self.error("Unterminated string")),
+                Some(_) => return Err(self.error("Control characters are not allowed in strings")),
+            }
+        }
+    }
+
+    fn parse_multiline_basic_string(&mut self) -> Result<String, C::Error> {
+        let mut string = String::new();
+        self.newline();
+
+        loop {
+            let start = self.pos;
+
+            while let Some(b) = self.peek() {
+                if matches!(b, b'"' | b'\\' | b'\r')
+                    || (b.is_ascii_control() && !matches!(b, b'\t' | b'\n'))
+                {
+                    break;
+                }
+
+                self.pos += 1;
+            }
+
+            string.push_str(&self.input[start..self.pos]);
+
+            match self.peek() {
+                Some(b'"') => {
+                    if self.end_multiline(&mut string, "\"\"\"") {
+                        return Ok(string);
+                    }
+                }
+                Some(b'\\') => {
+                    self.pos += 1;
+
+                    // A line ending backslash trims all following whitespace.
+                    let before = self.pos;
+                    self.skip_ws();
+
+                    if self.newline() {
+                        while self.newline() || matches!(self.peek(), Some(b' ' | b'\t')) {
+                            self.skip_ws();
+                        }
+                    } else {
+                        self.pos = before;
+                        string.push(self.parse_escape()?);
+                    }
+                }
+                Some(b'\r') if self.newline() => {
+                    string.push_str("\r\n");
+                }
+                None => return Err(self.error("Unterminated string")),
+                Some(_) => return Err(self.error("Control characters are not allowed in strings")),
+            }
+        }
+    }
+
+    fn parse_literal_string(&mut self) -> Result<String, C::Error> {
+        let start = self.pos;
+
+        loop {
+            match self.peek() {
+                Some(b'\'') => break,
+                Some(b'\n' | b'\r') | None => return Err(self.error("Unterminated string")),
+                Some(b) if b.is_ascii_control() && b != b'\t' => {
+                    return Err(self.error("Control characters are not allowed in strings"));
+                }
+                Some(_) => self.pos += 1,
+            }
+        }
+
+        let string = self.input[start..self.pos].into();
+        self.pos += 1;
+        Ok(string)
+    }
+
+    fn parse_multiline_literal_string(&mut self) -> Result<String, C::Error> {
+        let mut string = String::new();
+        self.newline();
+
+        loop {
+            let start = self.pos;
+
+            while let Some(b) = self.peek() {
+                if b == b'\'' || b == b'\r' || (b.is_ascii_control() && !matches!(b, b'\t' | b'\n'))
+                {
+                    break;
+                }
+
+                self.pos += 1;
+            }
+
+            string.push_str(&self.input[start..self.pos]);
+
+            match self.peek() {
+                Some(b'\'') => {
+                    if self.end_multiline(&mut string, "'''") {
+                        return Ok(string);
+                    }
+                }
+                Some(b'\r') if self.newline() => {
+                    string.push_str("\r\n");
+                }
+                None => return Err(self.error("Unterminated string")),
+                Some(_) => return Err(self.error("Control characters are not allowed in strings")),
+            }
+        }
+    }
+
+    /// Handle a quote in a multiline string, returning `true` if it ends the
+    /// string. Up to two quotes are allowed to immediately precede the
+    /// delimiter.
+    fn end_multiline(&mut self, string: &mut String, delimiter: &str) -> bool {
+        let quote = delimiter.as_bytes()[0];
+        let mut count = 0;
+
+        while self.peek_at(count) == Some(quote) {
+            count += 1;
+        }
+
+        if (3..=5).contains(&count) {
+            string.push_str(&delimiter[..count - 3]);
+            self.pos += count;
+            return true;
+        }
+
+        string.push_str(&self.input[self.pos..self.pos + count]);
+        self.pos += count;
+        false
+    }
+
+    /// Parse an escape sequence following a backslash.
+    fn parse_escape(&mut self) -> Result<char, C::Error> {
+        let Some(b) = self.peek() else {
+            return Err(self.error("Unterminated string"));
+        };
+
+        self.pos += 1;
+
+        let len = match b {
+            b'b' => return Ok('\u{8}'),
+            b't' => return Ok('\t'),
+            b'n' => return Ok('\n'),
+            b'f' => return Ok('\u{c}'),
+            b'r' => return Ok('\r'),
+            b'"' => return Ok('"'),
+            b'\\' => return Ok('\\'),
+            b'u' => 4,
+            b'U' => 8,
+            _ => {
+                self.pos -= 1;
+                return Err(self.error("Invalid escape sequence"));
+            }
+        };
+
+        let digits = self.input.get(self.pos..self.pos + len).unwrap_or_default();
+
+        let c = if digits.len() == len && digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            u32::from_str_radix(digits, 16)
+                .ok()
+                .and_then(char::from_u32)
+        } else {
+            None
+        };
+
+        let Some(c) = c else {
+            return Err(self.error("Invalid unicode escape sequence"));
+        };
+
+        self.pos += len;
+        Ok(c)
+    }
+
+    fn parse_number_or_datetime(&mut self) -> Result<Value, C::Error> {
+        let start = self.pos;
+
+        while let Some(b) = self.peek() {
+            if !(b.is_ascii_alphanumeric() || matches!(b, b'_' | b'+' | b'-' | b'.' | b':')) {
+                break;
+            }
+
+            self.pos += 1;
+        }
+
+        let token = &self.input[start..self.pos];
+
+        if is_date(token) || is_time(token) {
+            // A date and a time may be separated by a space.
+            if is_date(token) && self.peek() == Some(b' ') && is_time(&self.input[self.pos + 1..]) {
+                self.pos += 1;
+
+                while let Some(b) = self.peek() {
+                    if !(b.is_ascii_alphanumeric() || matches!(b, b'+' | b'-' | b'.' | b':')) {
+                        break;
+                    }
+
+                    self.pos += 1;
+                }
+            }
+
+            return Ok(Value::String(self.input[start..self.pos].into()));
+        }
+
+        let Some(number) = parse_number(token) else {
+            self.pos = start;
+            return Err(self.error(format_args!("Invalid number {token:?}")));
+        };
+
+        Ok(Value::Number(number))
+    }
+
+    /// Get the table at the given path relative to `table`, creating any
+    /// missing tables along the way. Arrays of tables resolve to their last
+    /// element.
+    fn table<'t>(
+        &self,
+        mut table: &'t mut Vec<(Value, Value)>,
+        keys: &[String],
+    ) -> Result<&'t mut Vec<(Value, Value)>, C::Error> {
+        for key in keys {
+            let index = match find(table, key) {
+                Some(index) => index,
+                None => {
+                    table.push((Value::String(key.clone()), Value::Map(Vec::new())));
+                    table.len() - 1
+                }
+            };
+
+            table = match &mut table[index].1 {
+                Value::Map(map) => map,
+                Value::Sequence(values) => match values.last_mut() {
+                    Some(Value::Map(map)) => map,
+                    _ => return Err(self.error(format_args!("Key {key:?} is not a table"))),
+                },
+                _ => return Err(self.error(format_args!("Key {key:?} is not a table"))),
+            };
+        }
+
+        Ok(table)
+    }
+
+    /// Push a new table to the array of tables with the given key.
+    fn push_table(&self, table: &mut Vec<(Value, Value)>, key: &String) -> Result<(), C::Error> {
+        let Some(index) = find(table, key) else {
+            let tables = Value::Sequence(rust_alloc::vec![Value::Map(Vec::new())]);
+            table.push((Value::String(key.clone()), tables));
+            return Ok(());
+        };
+
+        match &mut table[index].1 {
+            Value::Sequence(values) if values.iter().all(|v| matches!(v, Value::Map(..))) => {
+                values.push(Value::Map(Vec::new()));
+                Ok(())
+            }
+            _ => Err(self.error(format_args!("Key {key:?} is not an array of tables"))),
+        }
+    }
+}
+
+/// Find the index of the entry with the given key.
+#[inline]
+fn find(table: &[(Value, Value)], key: &str) -> Option<usize> {
+    table
+        .iter()
+        .position(|(k, _)| matches!(k, Value::String(k) if k == key))
+}
+
+/// Test if the given byte can be used in a bare key.
+#[inline]
+pub(crate) fn is_bare(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || matches!(b, b'_' | b'-')
+}
+
+/// Test if the string starts with a date, like `1979-05-27`.
+#[inline]
+fn is_date(s: &str) -> bool {
+    match s.as_bytes() {
+        [a, b, c, d, b'-', e, f, b'-', g, h, ..] => {
+            [a, b, c, d, e, f, g, h].iter().all(|b| b.is_ascii_digit())
+        }
+        _ => false,
+    }
+}
+
+/// Test if the string starts with a time, like `07:32:00`.
+#[inline]
+fn is_time(s: &str) -> bool {
+    match s.as_bytes() {
+        [a, b, b':', c, d, ..] => [a, b, c, d].iter().all(|b| b.is_ascii_digit()),
+        _ => false,
+    }
+}
+
+/// Parse an integer or a float.
+fn parse_number(token: &str) -> Option<Number> {
+    let (sign, body) = match token.as_bytes().first()? {
+        b'+' => ("+", &token[1..]),
+        b'-' => ("-", &token[1..]),
+        _ => ("", token),
+    };
+
+    match body {
+        "inf" if sign == "-" => return Some(Number::F64(f64::NEG_INFINITY)),
+        "inf" => return Some(Number::F64(f64::INFINITY)),
+        "nan" => return Some(Number::F64(f64::NAN)),
+        _ => {}
+    }
+
+    let radix = match body.get(..2) {
+        Some("0x") => 16,
+        Some("0o") => 8,
+        Some("0b") => 2,
+        _ => 10,
+    };
+
+    if radix != 10 {
+        if !sign.is_empty() {
+            return None;
+        }
+
+        let digits = strip_underscores(&body[2..], |b| b.is_ascii_alphanumeric())?;
+        return i64::from_str_radix(&digits, radix).ok().map(Number::I64);
+    }
+
+    let digits = strip_underscores(body, |b| b.is_ascii_digit())?;
+    let bytes = digits.as_bytes();
+
+    if !bytes.first()?.is_ascii_digit() {
+        return None;
+    }
+
+    let int = bytes
+        .iter()
+        .position(|b| !b.is_ascii_digit())
+        .unwrap_or(bytes.len());
+
+    if int > 1 && bytes[0] == b'0' {
+        return None;
+    }
+
+    if int == bytes.len() {
+        let mut string = String::from(sign);
+        string.push_str(&digits);
+        return string.parse().ok().map(Number::I64);
+    }
+
+    // A decimal point must be surrounded by digits.
+    for (n, &b) in bytes.iter().enumerate() {
+        if b == b'.'
+            && !(n > 0
+                && bytes[n - 1].is_ascii_digit()
+                && bytes.get(n + 1).is_some_and(u8::is_ascii_digit))
+        {
+            return None;
+        }
+    }
+
+    let mut string = String::from(sign);
+    string.push_str(&digits);
+    string.parse().ok().map(Number::F64)
+}
+
+/// Remove underscores from a number, each of which must be surrounded by
+/// digits.
+fn strip_underscores(s: &str, is_digit: impl Fn(&u8) -> bool) -> Option<String> {
+    let bytes = s.as_bytes();
+    let mut out = String::with_capacity(s.len());
+
+    for (n, &b) in bytes.iter().enumerate() {
+        if b == b'_' {
+            let before = n > 0 && is_digit(&bytes[n - 1]);
+            let after = bytes.get(n + 1).is_some_and(&is_digit);
+
+            if !(before && after) {
+                return None;
+            }
+
+            continue;
+        }
+
+        out.push(char::from(b));
+    }
+
+    Some(out)
+}
@@ -0,0 +1,354 @@
+use core::fmt;
+
+use rust_alloc::collections::BTreeMap;
+use rust_alloc::string::{String, ToString};
+use rust_alloc::vec;
+use rust_alloc::vec::Vec;
+
+use crate::mode::Text;
+use crate::value::Value;
+use crate::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+enum Protocol {
+    Http,
+    Tcp { port: u16 },
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Limits {
+    connections: u32,
+    ratio: f64,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Backend {
+    name: String,
+    protocol: Protocol,
+    #[musli(default)]
+    tags: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Config {
+    title: String,
+    enabled: bool,
+    #[musli(default)]
+    timeout: Option<i64>,
+    limits: Limits,
+    backends: Vec<Backend>,
+    env: BTreeMap<String, String>,
+}
+
+fn config() -> Config {
+    let mut env = BTreeMap::new();
+    env.insert("RUST_LOG".to_string(), "debug".to_string());
+
+    Config {
+        title: "Say \"hello\"\n".to_string(),
+        enabled: true,
+        timeout: None,
+        limits: Limits {
+            connections: 64,
+            ratio: 0.5,
+        },
+        backends: vec![
+            Backend {
+                name: "a".to_string(),
+                protocol: Protocol::Http,
+                tags: vec!["x".to_string(), "y".to_string()],
+            },
+            Backend {
+                name: "b".to_string(),
+                protocol: Protocol::Tcp { port: 22 },
+                tags: Vec::new(),
+            },
+        ],
+        env,
+    }
+}
+
+#[track_caller]
+fn roundtrips<T>(value: T, expected: &str)
+where
+    T: Encode<Text> + for<'de> Decode<'de, Text> + fmt::Debug + PartialEq,
+{
+    let out = super::to_string(&value).expect("failed to encode");
+    assert_eq!(out, expected, "encoding {value:?}");
+    let actual: T = super::from_str(expected).expect("failed to decode");
+    assert_eq!(actual, value);
+}
+
+#[track_caller]
+fn fails<T>(input: &str, message: &str)
+where
+    T: for<'de> Decode<'de, Text> + fmt::Debug,
+{
+    let error = super::from_str::<T>(input).expect_err("expected decoding to fail");
+    assert_eq!(error.to_string(), message);
+}
+
+#[test]
+fn document() {
+    roundtrips(
+        config(),
+        r#"title = "Say \"hello\"\n"
+enabled = true
+
+[limits]
+connections = 64
+ratio = 0.5
+
+[[backends]]
+name = "a"
+protocol = "Http"
+tags = ["x", "y"]
+
+[[backends]]
+name = "b"
+tags = []
+
+[backends.protocol.Tcp]
+port = 22
+
+[env]
+RUST_LOG = "debug"
+"#,
+    );
+}
+
+#[test]
+fn syntax() {
+    let actual: Config = super::from_str(
+        r#"
+# A comment.
+title = """
+Say "hello"
+"""
+enabled = true # trailing comment
+timeout = -1_000
+backends = [
+    { name = 'a', protocol = "Http", tags = [ "x", "y", ] },
+    { name = "b", protocol.Tcp.port = 0x16 },
+]
+
+[limits]
+"connections" = 64
+ratio = 5e-1
+
+[env]
+RUST_LOG = '''debug'''
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        actual,
+        Config {
+            timeout: Some(-1000),
+            ..config()
+        }
+    );
+}
+
+#[test]
+fn nested_tables() {
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    struct Inner {
+        value: u32,
+    }
+
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    struct Middle {
+        inner: Inner,
+    }
+
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    struct Outer {
+        middle: Middle,
+        #[musli(default)]
+        empty: BTreeMap<String, u32>,
+    }
+
+    roundtrips(
+        Outer {
+            middle: Middle {
+                inner: Inner { value: 1 },
+            },
+            empty: BTreeMap::new(),
+        },
+        "[middle.inner]\nvalue = 1\n\n[empty]\n",
+    );
+
+    let actual: Outer = super::from_str("middle.inner.value = 2").unwrap();
+    assert_eq!(actual.middle.inner.value, 2);
+
+    #[derive(Debug, PartialEq, Decode)]
+    struct Items {
+        items: Vec<Middle>,
+    }
+
+    let actual: Items =
+        super::from_str("[[items]]\n[items.inner]\nvalue = 1\n[[items]]\n[items.inner]\nvalue = 2")
+            .unwrap();
+
+    assert_eq!(
+        actual.items,
+        [
+            Middle {
+                inner: Inner { value: 1 }
+            },
+            Middle {
+                inner: Inner { value: 2 }
+            },
+        ]
+    );
+}
+
+#[test]
+fn strings() {
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    struct Strings {
+        values: Vec<String>,
+    }
+
+    let actual: Strings = super::from_str(
+        "values = [\"\\u00e9\\t\\\\\", 'C:\\path', \"\"\"\nline \\\n    continued\"\"\", '''\n''quoted'''']",
+    )
+    .unwrap();
+
+    assert_eq!(
+        actual.values,
+        ["é\t\\", "C:\\path", "line continued", "''quoted'"]
+    );
+
+    roundtrips(
+        Strings {
+            values: vec!["\u{1}\u{7f}ü".to_string()],
+        },
+        "values = [\"\\u0001\\u007Fü\"]\n",
+    );
+}
+
+#[test]
+fn datetimes() {
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    struct Times {
+        values: Vec<String>,
+    }
+
+    let actual: Times = super::from_str(
+        "values = [1979-05-27T07:32:00-08:00, 1979-05-27 07:32:00.999, 1979-05-27, 07:32:00]",
+    )
+    .unwrap();
+
+    assert_eq!(
+        actual.values,
+        [
+            "1979-05-27T07:32:00-08:00",
+            "1979-05-27 07:32:00.999",
+            "1979-05-27",
+            "07:32:00",
+        ]
+    );
+}
+
+#[test]
+fn decode_any() {
+    let value: Value = super::from_str("a = 1\nb = [true, 'x']\n\n[c]\nd = 1.5").unwrap();
+
+    let expected = Value::Map(vec![
+        (
+            Value::String("a".to_string()),
+            Value::Number(crate::value::Number::I64(1)),
+        ),
+        (
+            Value::String("b".to_string()),
+            Value::Sequence(vec![Value::Bool(true), Value::String("x".to_string())]),
+        ),
+        (
+            Value::String("c".to_string()),
+            Value::Map(vec![(
+                Value::String("d".to_string()),
+                Value::Number(crate::value::Number::F64(1.5)),
+            )]),
+        ),
+    ]);
+
+    assert_eq!(value, expected);
+}
+
+#[test]
+fn numbers() {
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    struct Numbers {
+        values: Vec<f64>,
+        keys: BTreeMap<u32, u8>,
+    }
+
+    let actual: Numbers =
+        super::from_str("values = [1, -2.5, +inf, 1e3, 0o17]\nkeys = { 1 = 2, 30 = 40 }").unwrap();
+
+    assert_eq!(actual.values[..2], [1.0, -2.5]);
+    assert_eq!(actual.values[2], f64::INFINITY);
+    assert_eq!(actual.values[3..], [1000.0, 15.0]);
+    assert_eq!(actual.keys.get(&30), Some(&40));
+
+    fails::<Numbers>(
+        "values = [01]",
+        "Invalid number \"01\" (at line 1, column 11)",
+    );
+    fails::<Numbers>(
+        "values = []\nkeys = { 1 = 300 }",
+        "Integer 300 is out of range for u8",
+    );
+}
+
+#[test]
+fn errors() {
+    #[derive(Debug, Decode)]
+    struct Table {
+        #[allow(unused)]
+        a: u32,
+    }
+
+    fails::<Table>("a = 1\na = 2", "Duplicate key \"a\" (at line 2, column 6)");
+    fails::<Table>("a = \"x", "Unterminated string (at line 1, column 7)");
+    fails::<Table>("a = 1 2", "Expected newline (at line 1, column 7)");
+    fails::<Table>(
+        "a = 1\n[a]",
+        "Key \"a\" is not a table (at line 2, column 4)",
+    );
+    fails::<Table>("a = 'x'", "Expected u32, but found string \"x\"");
+    fails::<Table>(
+        "a = 1\n[b]\n[b]",
+        "Duplicate table \"b\" (at line 3, column 4)",
+    );
+    fails::<Table>(
+        "a = 1\n[b.c]\n[b]\n[b.c]",
+        "Duplicate table \"b.c\" (at line 4, column 6)",
+    );
+    fails::<Table>(
+        "a = 1\n[[b]]\n[b]",
+        "Key \"b\" is an array of tables (at line 3, column 4)",
+    );
+
+    #[derive(Debug, Encode)]
+    struct Absent {
+        values: Vec<Option<u32>>,
+    }
+
+    let error = super::to_string(&Absent { values: vec![None] }).unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "TOML arrays cannot contain absent values"
+    );
+
+    let error = super::to_string(&1u32).unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "Expected a table at the top level of a TOML document"
+    );
+}
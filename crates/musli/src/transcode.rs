@@ -0,0 +1,408 @@
+//! Generic transcoding between any two formats.
+//!
+//! See [`transcode`] for details.
+
+use core::fmt;
+
+use crate::de::{
+    Decoder, EntryDecoder, MapDecoder, SequenceDecoder, SizeHint, UnsizedVisitor, VariantDecoder,
+    Visitor,
+};
+use crate::en::{Encoder, EntryEncoder, MapEncoder, SequenceEncoder, VariantEncoder};
+use crate::hint::{MapHint, SequenceHint};
+use crate::Context;
+
+/// Transcode the value produced by a self-describing [`Decoder`] directly into
+/// the given [`Encoder`].
+///
+/// This drives [`Decoder::decode_any`] and maps every visited value onto the
+/// corresponding encoder method. Sequences, maps and variants are streamed
+/// through [`SequenceEncoder`], [`MapEncoder`] and [`VariantEncoder`] without
+/// first being materialized into a [`Value`].
+///
+/// The source format must be self-describing, such as [`descriptive`] or
+/// [`json`]. Formats which do not support [`Decoder::decode_any`] will produce
+/// an error.
+///
+/// Many target formats need to know the length of a collection up front, which
+/// is indicated by [`Encoder::is_size_required`]. If the source format doesn't
+/// encode lengths, like [`json`], it is asked to count the elements through
+/// [`SequenceDecoder::count_remaining`] and [`MapDecoder::count_remaining`]
+/// in a first pass over its input, after which the collection is streamed as
+/// usual. Sources which can't do this have the elements of that collection
+/// buffered in a [`Value`] as a fallback before they are encoded. This
+/// requires the `value` and `alloc` features, without them transcoding such
+/// collections is an error. Targets which don't need lengths, like [`json`]
+/// itself, always have collections streamed into them.
+///
+/// [`Value`]: crate::value::Value
+/// [`descriptive`]: crate::descriptive
+/// [`json`]: crate::json
+///
+/// # Examples
+///
+/// ```
+/// use musli::{Decode, Encode};
+/// use musli::context::Same;
+/// use musli::mode::Binary;
+/// use musli::{descriptive, json};
+///
+/// const DESCRIPTIVE: descriptive::Encoding = descriptive::Encoding::new();
+/// const JSON: json::Encoding<Binary> = json::Encoding::new().with_mode();
+///
+/// #[derive(Decode, Encode)]
+/// #[musli(mode = Binary, name_all = "name")]
+/// struct Person {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// let data = DESCRIPTIVE.to_vec(&Person {
+///     name: "Aristotle".to_string(),
+///     age: 61,
+/// })?;
+///
+/// let cx = Same::<Binary, descriptive::Error, _>::new();
+/// let mut out = Vec::new();
+///
+/// let decoder = DESCRIPTIVE.decoder_with(&cx, &data[..]);
+/// let encoder = JSON.encoder_with(&cx, &mut out);
+/// musli::transcode(decoder, encoder)?;
+///
+/// assert_eq!(out, br#"{"name":"Aristotle","age":61}"#);
+/// # Ok::<(), descriptive::Error>(())
+/// ```
+pub fn transcode<'de, D, E>(decoder: D, encoder: E) -> Result<E::Ok, D::Error>
+where
+    D: Decoder<'de>,
+    E: Encoder<Cx = D::Cx, Error = D::Error, Mode = D::Mode>,
+{
+    decoder.decode_any(AnyVisitor::new(encoder))
+}
+
+struct AnyVisitor<E> {
+    encoder: E,
+}
+
+impl<E> AnyVisitor<E> {
+    #[inline]
+    const fn new(encoder: E) -> Self {
+        Self { encoder }
+    }
+}
+
+#[crate::visitor(crate)]
+impl<'de, C, E> Visitor<'de, C> for AnyVisitor<E>
+where
+    C: ?Sized + Context,
+    E: Encoder<Cx = C, Error = C::Error, Mode = C::Mode>,
+{
+    type Ok = E::Ok;
+    type String = StringVisitor<E>;
+    type Bytes = BytesVisitor<E>;
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "value that can be transcoded, ")?;
+        self.encoder.expecting(f)
+    }
+
+    #[inline]
+    fn visit_empty(self, _: &C) -> Result<Self::Ok, C::Error> {
+        self.encoder.encode_empty()
+    }
+
+    #[inline]
+    fn visit_bool(self, _: &C, value: bool) -> Result<Self::Ok, C::Error> {
+        self.encoder.encode_bool(value)
+    }
+
+    #[inline]
+    fn visit_char(self, _: &C, value: char) -> Result<Self::Ok, C::Error> {
+        self.encoder.encode_char(value)
+    }
+
+    #[inline]
+    fn visit_u8(self, _: &C, value: u8) -> Result<Self::Ok, C::Error> {
+        self.encoder.encode_u8(value)
+    }
+
+    #[inline]
+    fn visit_u16(self, _: &C, value: u16) -> Result<Self::Ok, C::Error> {
+        self.encoder.encode_u16(value)
+    }
+
+    #[inline]
+    fn visit_u32(self, _: &C, value: u32) -> Result<Self::Ok, C::Error> {
+        self.encoder.encode_u32(value)
+    }
+
+    #[inline]
+    fn visit_u64(self, _: &C, value: u64) -> Result<Self::Ok, C::Error> {
+        self.encoder.encode_u64(value)
+    }
+
+    #[inline]
+    fn visit_u128(self, _: &C, value: u128) -> Result<Self::Ok, C::Error> {
+        self.encoder.encode_u128(value)
+    }
+
+    #[inline]
+    fn visit_i8(self, _: &C, value: i8) -> Result<Self::Ok, C::Error> {
+        self.encoder.encode_i8(value)
+    }
+
+    #[inline]
+    fn visit_i16(self, _: &C, value: i16) -> Result<Self::Ok, C::Error> {
+        self.encoder.encode_i16(value)
+    }
+
+    #[inline]
+    fn visit_i32(self, _: &C, value: i32) -> Result<Self::Ok, C::Error> {
+        self.encoder.encode_i32(value)
+    }
+
+    #[inline]
+    fn visit_i64(self, _: &C, value: i64) -> Result<Self::Ok, C::Error> {
+        self.encoder.encode_i64(value)
+    }
+
+    #[inline]
+    fn visit_i128(self, _: &C, value: i128) -> Result<Self::Ok, C::Error> {
+        self.encoder.encode_i128(value)
+    }
+
+    #[inline]
+    fn visit_usize(self, _: &C, value: usize) -> Result<Self::Ok, C::Error> {
+        self.encoder.encode_usize(value)
+    }
+
+    #[inline]
+    fn visit_isize(self, _: &C, value: isize) -> Result<Self::Ok, C::Error> {
+        self.encoder.encode_isize(value)
+    }
+
+    #[inline]
+    fn visit_f32(self, _: &C, value: f32) -> Result<Self::Ok, C::Error> {
+        self.encoder.encode_f32(value)
+    }
+
+    #[inline]
+    fn visit_f64(self, _: &C, value: f64) -> Result<Self::Ok, C::Error> {
+        self.encoder.encode_f64(value)
+    }
+
+    #[inline]
+    fn visit_option<D>(self, _: &C, decoder: Option<D>) -> Result<Self::Ok, C::Error>
+    where
+        D: Decoder<'de, Cx = C, Error = C::Error, Mode = C::Mode>,
+    {
+        match decoder {
+            Some(decoder) => transcode(decoder, self.encoder.encode_some()?),
+            None => self.encoder.encode_none(),
+        }
+    }
+
+    #[inline]
+    fn visit_sequence<D>(self, cx: &C, seq: &mut D) -> Result<Self::Ok, C::Error>
+    where
+        D: ?Sized + SequenceDecoder<'de, Cx = C>,
+    {
+        let size = match seq.size_hint().into_option() {
+            Some(size) => size,
+            None if !self.encoder.is_size_required() => 0,
+            None => match seq.count_remaining()? {
+                Some(size) => size,
+                None => return buffered::sequence(cx, seq, self.encoder),
+            },
+        };
+
+        let hint = SequenceHint::with_size(size);
+        let mut encoder = self.encoder.encode_sequence(&hint)?;
+
+        while let Some(decoder) = seq.try_decode_next()? {
+            transcode(decoder, encoder.encode_next()?)?;
+        }
+
+        encoder.finish_sequence()
+    }
+
+    #[inline]
+    fn visit_map<D>(self, cx: &C, map: &mut D) -> Result<Self::Ok, C::Error>
+    where
+        D: ?Sized + MapDecoder<'de, Cx = C>,
+    {
+        let size = match map.size_hint().into_option() {
+            Some(size) => size,
+            None if !self.encoder.is_size_required() => 0,
+            None => match map.count_remaining()? {
+                Some(size) => size,
+                None => return buffered::map(cx, map, self.encoder),
+            },
+        };
+
+        let hint = MapHint::with_size(size);
+        let mut encoder = self.encoder.encode_map(&hint)?;
+
+        while let Some(mut entry) = map.decode_entry()? {
+            let mut output = encoder.encode_entry()?;
+            transcode(entry.decode_key()?, output.encode_key()?)?;
+            transcode(entry.decode_value()?, output.encode_value()?)?;
+            output.finish_entry()?;
+        }
+
+        encoder.finish_map()
+    }
+
+    #[inline]
+    fn visit_string(self, _: &C, _: SizeHint) -> Result<Self::String, C::Error> {
+        Ok(StringVisitor {
+            encoder: self.encoder,
+        })
+    }
+
+    #[inline]
+    fn visit_bytes(self, _: &C, _: SizeHint) -> Result<Self::Bytes, C::Error> {
+        Ok(BytesVisitor {
+            encoder: self.encoder,
+        })
+    }
+
+    #[inline]
+    fn visit_variant<D>(self, _: &C, variant: &mut D) -> Result<Self::Ok, C::Error>
+    where
+        D: VariantDecoder<'de, Cx = C>,
+    {
+        let mut encoder = self.encoder.encode_variant()?;
+        transcode(variant.decode_tag()?, encoder.encode_tag()?)?;
+        transcode(variant.decode_value()?, encoder.encode_data()?)?;
+        encoder.finish_variant()
+    }
+}
+
+struct StringVisitor<E> {
+    encoder: E,
+}
+
+impl<'de, C, E> UnsizedVisitor<'de, C, str> for StringVisitor<E>
+where
+    C: ?Sized + Context,
+    E: Encoder<Cx = C, Error = C::Error, Mode = C::Mode>,
+{
+    type Ok = E::Ok;
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "string")
+    }
+
+    #[inline]
+    fn visit_ref(self, _: &C, string: &str) -> Result<Self::Ok, C::Error> {
+        self.encoder.encode_string(string)
+    }
+}
+
+struct BytesVisitor<E> {
+    encoder: E,
+}
+
+impl<'de, C, E> UnsizedVisitor<'de, C, [u8]> for BytesVisitor<E>
+where
+    C: ?Sized + Context,
+    E: Encoder<Cx = C, Error = C::Error, Mode = C::Mode>,
+{
+    type Ok = E::Ok;
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "bytes")
+    }
+
+    #[inline]
+    fn visit_ref(self, _: &C, bytes: &[u8]) -> Result<Self::Ok, C::Error> {
+        self.encoder.encode_bytes(bytes)
+    }
+}
+
+/// Fallbacks for collections whose length is not known up front.
+#[cfg(all(feature = "value", feature = "alloc"))]
+mod buffered {
+    use rust_alloc::vec::Vec;
+
+    use crate::de::{Decoder, EntryDecoder, MapDecoder, SequenceDecoder};
+    use crate::en::{Encoder, MapEncoder, SequenceEncoder};
+    use crate::hint::{MapHint, SequenceHint};
+    use crate::value::Value;
+    use crate::Context;
+
+    pub(super) fn sequence<'de, C, D, E>(_: &C, seq: &mut D, encoder: E) -> Result<E::Ok, C::Error>
+    where
+        C: ?Sized + Context,
+        D: ?Sized + SequenceDecoder<'de, Cx = C>,
+        E: Encoder<Cx = C, Error = C::Error, Mode = C::Mode>,
+    {
+        let mut values = Vec::new();
+
+        while let Some(value) = seq.try_next::<Value>()? {
+            values.push(value);
+        }
+
+        let hint = SequenceHint::with_size(values.len());
+        let mut encoder = encoder.encode_sequence(&hint)?;
+
+        for value in &values {
+            encoder.push(value)?;
+        }
+
+        encoder.finish_sequence()
+    }
+
+    pub(super) fn map<'de, C, D, E>(_: &C, map: &mut D, encoder: E) -> Result<E::Ok, C::Error>
+    where
+        C: ?Sized + Context,
+        D: ?Sized + MapDecoder<'de, Cx = C>,
+        E: Encoder<Cx = C, Error = C::Error, Mode = C::Mode>,
+    {
+        let mut entries = Vec::new();
+
+        while let Some(mut entry) = map.decode_entry()? {
+            let key = entry.decode_key()?.decode::<Value>()?;
+            let value = entry.decode_value()?.decode::<Value>()?;
+            entries.push((key, value));
+        }
+
+        let hint = MapHint::with_size(entries.len());
+        let mut encoder = encoder.encode_map(&hint)?;
+
+        for (key, value) in &entries {
+            encoder.insert_entry(key, value)?;
+        }
+
+        encoder.finish_map()
+    }
+}
+
+#[cfg(not(all(feature = "value", feature = "alloc")))]
+mod buffered {
+    use crate::de::{MapDecoder, SequenceDecoder};
+    use crate::en::Encoder;
+    use crate::Context;
+
+    pub(super) fn sequence<'de, C, D, E>(cx: &C, _: &mut D, _: E) -> Result<E::Ok, C::Error>
+    where
+        C: ?Sized + Context,
+        D: ?Sized + SequenceDecoder<'de, Cx = C>,
+        E: Encoder<Cx = C, Error = C::Error, Mode = C::Mode>,
+    {
+        Err(cx.message("Can only transcode sequences with known lengths"))
+    }
+
+    pub(super) fn map<'de, C, D, E>(cx: &C, _: &mut D, _: E) -> Result<E::Ok, C::Error>
+    where
+        C: ?Sized + Context,
+        D: ?Sized + MapDecoder<'de, Cx = C>,
+        E: Encoder<Cx = C, Error = C::Error, Mode = C::Mode>,
+    {
+        Err(cx.message("Can only transcode maps with known lengths"))
+    }
+}
@@ -0,0 +1,949 @@
+use core::fmt;
+
+use crate::alloc::Vec;
+use crate::de::{
+    Decode, DecodeUnsized, Decoder, EntriesDecoder, EntryDecoder, MapDecoder, SequenceDecoder,
+    Skip, UnsizedVisitor,
+};
+#[cfg(not(feature = "parse-full"))]
+use crate::json::parser::integer::{
+    parse_signed_base as parse_signed, parse_unsigned_base as parse_unsigned,
+};
+#[cfg(feature = "parse-full")]
+use crate::json::parser::integer::{
+    parse_signed_full as parse_signed, parse_unsigned_full as parse_unsigned,
+};
+use crate::json::parser::{Parser, SliceParser};
+use crate::{Context, Reader};
+
+/// The root decoder of a query string.
+pub struct UrlEncodedDecoder<'a, R, C: ?Sized> {
+    cx: &'a C,
+    reader: R,
+}
+
+impl<'a, R, C: ?Sized> UrlEncodedDecoder<'a, R, C> {
+    /// Construct a new query string decoder.
+    #[inline]
+    pub fn new(cx: &'a C, reader: R) -> Self {
+        Self { cx, reader }
+    }
+}
+
+/// The location of a decoded name and value pair.
+#[derive(Clone, Copy)]
+struct Pair {
+    start: usize,
+    mid: usize,
+    end: usize,
+    /// The index of the next pair with the same name, or `usize::MAX` if there
+    /// is none.
+    next: usize,
+    /// If this is the first pair with its name.
+    first: bool,
+}
+
+/// All name and value pairs in a query string.
+///
+/// Since the values of a sequence are stored as repeated pairs which do not
+/// have to be adjacent, the whole query string is decoded up front.
+pub struct Pairs<'a, C>
+where
+    C: ?Sized + Context,
+{
+    cx: &'a C,
+    /// Percent-decoded names and values, stored back to back.
+    data: Vec<'a, u8, C::Allocator>,
+    pairs: Vec<'a, Pair, C::Allocator>,
+    /// The index of the next pair to consider as an entry.
+    index: usize,
+    /// The pair of the entry whose key was most recently decoded.
+    current: usize,
+}
+
+impl<'a, C> Pairs<'a, C>
+where
+    C: ?Sized + Context,
+{
+    fn new<'de, R>(cx: &'a C, mut reader: R) -> Result<Self, C::Error>
+    where
+        R: Reader<'de>,
+    {
+        let mut this = Self {
+            cx,
+            data: Vec::new_in(cx.alloc()),
+            pairs: Vec::new_in(cx.alloc()),
+            index: 0,
+            current: 0,
+        };
+
+        let mut start = 0;
+        let mut mid = None;
+
+        while let Some(b) = reader.peek() {
+            let mark = cx.mark();
+            reader.skip(cx, 1)?;
+
+            let b = match b {
+                b'&' => {
+                    this.push_pair(start, mid)?;
+                    start = this.data.len();
+                    mid = None;
+                    continue;
+                }
+                b'=' if mid.is_none() => {
+                    mid = Some(this.data.len());
+                    continue;
+                }
+                b'+' => b' ',
+                b'%' => {
+                    let mut value = 0;
+
+                    for _ in 0..2 {
+                        let Some(digit) = reader.peek().and_then(hex) else {
+                            return Err(cx.marked_message(mark, "Invalid percent-encoded byte"));
+                        };
+
+                        reader.skip(cx, 1)?;
+                        value = (value << 4) | digit;
+                    }
+
+                    value
+                }
+                b => b,
+            };
+
+            if !this.data.push(b) {
+                return Err(cx.alloc_failed());
+            }
+        }
+
+        this.push_pair(start, mid)?;
+        this.link()?;
+        Ok(this)
+    }
+
+    /// Push a pair which ends at the current end of data, ignoring empty
+    /// segments such as the one produced by an empty query string.
+    #[inline]
+    fn push_pair(&mut self, start: usize, mid: Option<usize>) -> Result<(), C::Error> {
+        let end = self.data.len();
+
+        if start == end && mid.is_none() {
+            return Ok(());
+        }
+
+        let pair = Pair {
+            start,
+            mid: mid.unwrap_or(end),
+            end,
+            next: usize::MAX,
+            first: false,
+        };
+
+        if !self.pairs.push(pair) {
+            return Err(self.cx.alloc_failed());
+        }
+
+        Ok(())
+    }
+
+    /// Link every pair to the next pair with the same name, so that entries
+    /// and repeated values can be looked up without rescanning all pairs.
+    fn link(&mut self) -> Result<(), C::Error> {
+        let mut order = Vec::new_in(self.cx.alloc());
+
+        for index in 0..self.pairs.len() {
+            if !order.push(index) {
+                return Err(self.cx.alloc_failed());
+            }
+        }
+
+        order
+            .as_mut_slice()
+            .sort_unstable_by(|&a, &b| self.key(a).cmp(self.key(b)).then(a.cmp(&b)));
+
+        let mut prev = None;
+
+        for &index in order.as_slice() {
+            match prev {
+                Some(prev) if self.key(prev) == self.key(index) => {
+                    self.pairs.as_mut_slice()[prev].next = index;
+                }
+                _ => {
+                    self.pairs.as_mut_slice()[index].first = true;
+                }
+            }
+
+            prev = Some(index);
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn key(&self, index: usize) -> &[u8] {
+        let pair = self.pairs.as_slice()[index];
+        &self.data.as_slice()[pair.start..pair.mid]
+    }
+
+    #[inline]
+    fn value(&self, index: usize) -> &[u8] {
+        let pair = self.pairs.as_slice()[index];
+        &self.data.as_slice()[pair.mid..pair.end]
+    }
+
+    /// Get the index of the next pair with the same name as `index`.
+    #[inline]
+    fn next(&self, index: usize) -> Option<usize> {
+        let next = self.pairs.as_slice()[index].next;
+        (next != usize::MAX).then_some(next)
+    }
+
+    /// Advance to the next pair whose key hasn't been seen before.
+    #[inline]
+    fn next_entry(&mut self) -> Option<usize> {
+        while self.index < self.pairs.len() {
+            let index = self.index;
+            self.index += 1;
+
+            if self.pairs.as_slice()[index].first {
+                self.current = index;
+                return Some(index);
+            }
+        }
+
+        None
+    }
+}
+
+/// Decoder for a field, which consists of every pair with the same name.
+pub struct PairDecoder<'a, 'b, C>
+where
+    C: ?Sized + Context,
+{
+    cx: &'a C,
+    pairs: &'b Pairs<'a, C>,
+    index: usize,
+}
+
+/// Decoder for the name of a field.
+pub struct KeyDecoder<'a, 'b, C: ?Sized> {
+    cx: &'a C,
+    key: &'b [u8],
+}
+
+/// Decoder for the value of a field.
+pub struct ValueDecoder<'a, 'b, C>
+where
+    C: ?Sized + Context,
+{
+    cx: &'a C,
+    pairs: &'b Pairs<'a, C>,
+    index: usize,
+}
+
+impl<'a, 'b, C> ValueDecoder<'a, 'b, C>
+where
+    C: ?Sized + Context,
+{
+    /// Get a decoder for the only value of the field.
+    #[inline]
+    fn single(self) -> Result<ScalarDecoder<'a, 'b, C>, C::Error> {
+        if self.pairs.next(self.index).is_some() {
+            return Err(self.cx.message(format_args!(
+                "Expected a single value for field {:?}",
+                Printable(self.pairs.key(self.index))
+            )));
+        }
+
+        Ok(ScalarDecoder {
+            cx: self.cx,
+            key: self.pairs.key(self.index),
+            value: self.pairs.value(self.index),
+        })
+    }
+}
+
+/// Decoder for the repeated values of a field.
+pub struct ValuesDecoder<'a, 'b, C>
+where
+    C: ?Sized + Context,
+{
+    cx: &'a C,
+    pairs: &'b Pairs<'a, C>,
+    next: Option<usize>,
+}
+
+/// Decoder for a single value.
+pub struct ScalarDecoder<'a, 'b, C: ?Sized> {
+    cx: &'a C,
+    key: &'b [u8],
+    value: &'b [u8],
+}
+
+impl<'a, 'b, C> ScalarDecoder<'a, 'b, C>
+where
+    C: ?Sized + Context,
+{
+    #[inline]
+    fn as_str(&self) -> Result<&'b str, C::Error> {
+        crate::str::from_utf8(self.value).map_err(crate::str::map_utf8_error(self.cx))
+    }
+
+    /// Parse a number which must span the whole value.
+    #[inline]
+    fn number<T>(
+        self,
+        parse: impl FnOnce(&C, &mut SliceParser<'b>) -> Result<T, C::Error>,
+    ) -> Result<T, C::Error> {
+        if self.value.is_empty() {
+            return Err(self.cx.message(format_args!(
+                "Expected number for field {:?}, but found empty value",
+                Printable(self.key)
+            )));
+        }
+
+        let mut parser = SliceParser::new(self.value);
+        let value = parse(self.cx, &mut parser)?;
+
+        if parser.index != self.value.len() {
+            return Err(self.cx.message(format_args!(
+                "Expected number for field {:?}, but found {:?}",
+                Printable(self.key),
+                Printable(self.value)
+            )));
+        }
+
+        Ok(value)
+    }
+}
+
+#[crate::decoder(crate)]
+impl<'a, 'de, R, C> Decoder<'de> for UrlEncodedDecoder<'a, R, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = C::Mode;
+    type WithContext<'this, U> = UrlEncodedDecoder<'this, R, U> where U: 'this + Context;
+    type DecodeMap = Pairs<'a, C>;
+    type DecodeMapEntries = Pairs<'a, C>;
+
+    #[inline]
+    fn cx(&self) -> &Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn with_context<U>(self, cx: &U) -> Result<Self::WithContext<'_, U>, C::Error>
+    where
+        U: Context,
+    {
+        Ok(UrlEncodedDecoder::new(cx, self.reader))
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "struct or map that can be decoded from a query string")
+    }
+
+    #[inline]
+    fn decode<T>(self) -> Result<T, Self::Error>
+    where
+        T: Decode<'de, Self::Mode>,
+    {
+        self.cx.decode(self)
+    }
+
+    #[inline]
+    fn decode_unsized<T, F, O>(self, f: F) -> Result<O, Self::Error>
+    where
+        T: ?Sized + DecodeUnsized<'de, Self::Mode>,
+        F: FnOnce(&T) -> Result<O, Self::Error>,
+    {
+        self.cx.decode_unsized(self, f)
+    }
+
+    #[inline]
+    fn decode_map<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeMap) -> Result<O, C::Error>,
+    {
+        let mut pairs = Pairs::new(self.cx, self.reader)?;
+        f(&mut pairs)
+    }
+
+    #[inline]
+    fn decode_map_entries<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeMapEntries) -> Result<O, C::Error>,
+    {
+        self.decode_map(f)
+    }
+}
+
+impl<'a, 'de, C> MapDecoder<'de> for Pairs<'a, C>
+where
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeEntry<'this> = PairDecoder<'a, 'this, C> where Self: 'this;
+    type DecodeRemainingEntries<'this> = &'this mut Self where Self: 'this;
+
+    #[inline]
+    fn decode_entry(&mut self) -> Result<Option<Self::DecodeEntry<'_>>, C::Error> {
+        let Some(index) = self.next_entry() else {
+            return Ok(None);
+        };
+
+        Ok(Some(PairDecoder {
+            cx: self.cx,
+            pairs: self,
+            index,
+        }))
+    }
+
+    #[inline]
+    fn decode_remaining_entries(&mut self) -> Result<Self::DecodeRemainingEntries<'_>, C::Error> {
+        Ok(self)
+    }
+}
+
+impl<'a, 'de, C> EntriesDecoder<'de> for Pairs<'a, C>
+where
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeEntryKey<'this> = KeyDecoder<'a, 'this, C> where Self: 'this;
+    type DecodeEntryValue<'this> = ValueDecoder<'a, 'this, C> where Self: 'this;
+
+    #[inline]
+    fn decode_entry_key(&mut self) -> Result<Option<Self::DecodeEntryKey<'_>>, C::Error> {
+        let Some(index) = self.next_entry() else {
+            return Ok(None);
+        };
+
+        Ok(Some(KeyDecoder {
+            cx: self.cx,
+            key: self.key(index),
+        }))
+    }
+
+    #[inline]
+    fn decode_entry_value(&mut self) -> Result<Self::DecodeEntryValue<'_>, C::Error> {
+        Ok(ValueDecoder {
+            cx: self.cx,
+            pairs: self,
+            index: self.current,
+        })
+    }
+
+    #[inline]
+    fn end_entries(self) -> Result<(), C::Error> {
+        Ok(())
+    }
+}
+
+impl<'a, 'b, 'de, C> EntriesDecoder<'de> for &'b mut Pairs<'a, C>
+where
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeEntryKey<'this> = KeyDecoder<'a, 'this, C> where Self: 'this;
+    type DecodeEntryValue<'this> = ValueDecoder<'a, 'this, C> where Self: 'this;
+
+    #[inline]
+    fn decode_entry_key(&mut self) -> Result<Option<Self::DecodeEntryKey<'_>>, C::Error> {
+        (**self).decode_entry_key()
+    }
+
+    #[inline]
+    fn decode_entry_value(&mut self) -> Result<Self::DecodeEntryValue<'_>, C::Error> {
+        (**self).decode_entry_value()
+    }
+
+    #[inline]
+    fn end_entries(self) -> Result<(), C::Error> {
+        Ok(())
+    }
+}
+
+impl<'a, 'b, 'de, C> EntryDecoder<'de> for PairDecoder<'a, 'b, C>
+where
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeKey<'this> = KeyDecoder<'a, 'this, C> where Self: 'this;
+    type DecodeValue = ValueDecoder<'a, 'b, C>;
+
+    #[inline]
+    fn decode_key(&mut self) -> Result<Self::DecodeKey<'_>, C::Error> {
+        Ok(KeyDecoder {
+            cx: self.cx,
+            key: self.pairs.key(self.index),
+        })
+    }
+
+    #[inline]
+    fn decode_value(self) -> Result<Self::DecodeValue, C::Error> {
+        Ok(ValueDecoder {
+            cx: self.cx,
+            pairs: self.pairs,
+            index: self.index,
+        })
+    }
+}
+
+#[crate::decoder(crate)]
+impl<'a, 'b, 'de, C> Decoder<'de> for KeyDecoder<'a, 'b, C>
+where
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = C::Mode;
+
+    #[inline]
+    fn cx(&self) -> &Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "field name that can be decoded from a query string")
+    }
+
+    #[inline]
+    fn decode<T>(self) -> Result<T, Self::Error>
+    where
+        T: Decode<'de, Self::Mode>,
+    {
+        self.cx.decode(self)
+    }
+
+    #[inline]
+    fn decode_unsized<T, F, O>(self, f: F) -> Result<O, Self::Error>
+    where
+        T: ?Sized + DecodeUnsized<'de, Self::Mode>,
+        F: FnOnce(&T) -> Result<O, Self::Error>,
+    {
+        self.cx.decode_unsized(self, f)
+    }
+
+    #[inline]
+    fn skip(self) -> Result<(), C::Error> {
+        Ok(())
+    }
+
+    #[inline]
+    fn try_skip(self) -> Result<Skip, C::Error> {
+        Ok(Skip::Skipped)
+    }
+
+    #[inline]
+    fn decode_string<V>(self, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: UnsizedVisitor<'de, C, str>,
+    {
+        let string =
+            crate::str::from_utf8(self.key).map_err(crate::str::map_utf8_error(self.cx))?;
+        visitor.visit_ref(self.cx, string)
+    }
+}
+
+#[crate::decoder(crate)]
+impl<'a, 'b, 'de, C> Decoder<'de> for ValueDecoder<'a, 'b, C>
+where
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = C::Mode;
+    type DecodeSome = Self;
+    type DecodeSequence = ValuesDecoder<'a, 'b, C>;
+
+    #[inline]
+    fn cx(&self) -> &Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "value that can be decoded from a query string")
+    }
+
+    #[inline]
+    fn decode<T>(self) -> Result<T, Self::Error>
+    where
+        T: Decode<'de, Self::Mode>,
+    {
+        self.cx.decode(self)
+    }
+
+    #[inline]
+    fn decode_unsized<T, F, O>(self, f: F) -> Result<O, Self::Error>
+    where
+        T: ?Sized + DecodeUnsized<'de, Self::Mode>,
+        F: FnOnce(&T) -> Result<O, Self::Error>,
+    {
+        self.cx.decode_unsized(self, f)
+    }
+
+    #[inline]
+    fn skip(self) -> Result<(), C::Error> {
+        Ok(())
+    }
+
+    #[inline]
+    fn try_skip(self) -> Result<Skip, C::Error> {
+        Ok(Skip::Skipped)
+    }
+
+    #[inline]
+    fn decode_empty(self) -> Result<(), C::Error> {
+        self.single()?.decode_empty()
+    }
+
+    #[inline]
+    fn decode_bool(self) -> Result<bool, C::Error> {
+        self.single()?.decode_bool()
+    }
+
+    #[inline]
+    fn decode_char(self) -> Result<char, C::Error> {
+        self.single()?.decode_char()
+    }
+
+    #[inline]
+    fn decode_u8(self) -> Result<u8, C::Error> {
+        self.single()?.decode_u8()
+    }
+
+    #[inline]
+    fn decode_u16(self) -> Result<u16, C::Error> {
+        self.single()?.decode_u16()
+    }
+
+    #[inline]
+    fn decode_u32(self) -> Result<u32, C::Error> {
+        self.single()?.decode_u32()
+    }
+
+    #[inline]
+    fn decode_u64(self) -> Result<u64, C::Error> {
+        self.single()?.decode_u64()
+    }
+
+    #[inline]
+    fn decode_u128(self) -> Result<u128, C::Error> {
+        self.single()?.decode_u128()
+    }
+
+    #[inline]
+    fn decode_i8(self) -> Result<i8, C::Error> {
+        self.single()?.decode_i8()
+    }
+
+    #[inline]
+    fn decode_i16(self) -> Result<i16, C::Error> {
+        self.single()?.decode_i16()
+    }
+
+    #[inline]
+    fn decode_i32(self) -> Result<i32, C::Error> {
+        self.single()?.decode_i32()
+    }
+
+    #[inline]
+    fn decode_i64(self) -> Result<i64, C::Error> {
+        self.single()?.decode_i64()
+    }
+
+    #[inline]
+    fn decode_i128(self) -> Result<i128, C::Error> {
+        self.single()?.decode_i128()
+    }
+
+    #[inline]
+    fn decode_usize(self) -> Result<usize, C::Error> {
+        self.single()?.decode_usize()
+    }
+
+    #[inline]
+    fn decode_isize(self) -> Result<isize, C::Error> {
+        self.single()?.decode_isize()
+    }
+
+    #[inline]
+    fn decode_f32(self) -> Result<f32, C::Error> {
+        self.single()?.decode_f32()
+    }
+
+    #[inline]
+    fn decode_f64(self) -> Result<f64, C::Error> {
+        self.single()?.decode_f64()
+    }
+
+    #[inline]
+    fn decode_string<V>(self, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: UnsizedVisitor<'de, C, str>,
+    {
+        self.single()?.decode_string(visitor)
+    }
+
+    #[inline]
+    fn decode_option(self) -> Result<Option<Self::DecodeSome>, C::Error> {
+        Ok(Some(self))
+    }
+
+    #[inline]
+    fn decode_sequence<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeSequence) -> Result<O, C::Error>,
+    {
+        f(&mut ValuesDecoder {
+            cx: self.cx,
+            pairs: self.pairs,
+            next: Some(self.index),
+        })
+    }
+}
+
+impl<'a, 'b, 'de, C> SequenceDecoder<'de> for ValuesDecoder<'a, 'b, C>
+where
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type DecodeNext<'this> = ScalarDecoder<'a, 'b, C> where Self: 'this;
+
+    #[inline]
+    fn decode_next(&mut self) -> Result<Self::DecodeNext<'_>, C::Error> {
+        let Some(decoder) = self.try_decode_next()? else {
+            return Err(self.cx.message("Expected another value"));
+        };
+
+        Ok(decoder)
+    }
+
+    #[inline]
+    fn try_decode_next(&mut self) -> Result<Option<Self::DecodeNext<'_>>, C::Error> {
+        let Some(index) = self.next else {
+            return Ok(None);
+        };
+
+        self.next = self.pairs.next(index);
+
+        Ok(Some(ScalarDecoder {
+            cx: self.cx,
+            key: self.pairs.key(index),
+            value: self.pairs.value(index),
+        }))
+    }
+}
+
+#[crate::decoder(crate)]
+impl<'a, 'b, 'de, C> Decoder<'de> for ScalarDecoder<'a, 'b, C>
+where
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Mode = C::Mode;
+    type WithContext<'this, U> = ScalarDecoder<'this, 'b, U> where U: 'this + Context;
+    type DecodeSome = Self;
+
+    #[inline]
+    fn cx(&self) -> &Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn with_context<U>(self, cx: &U) -> Result<Self::WithContext<'_, U>, C::Error>
+    where
+        U: Context,
+    {
+        Ok(ScalarDecoder {
+            cx,
+            key: self.key,
+            value: self.value,
+        })
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "value that can be decoded from a query string")
+    }
+
+    #[inline]
+    fn decode<T>(self) -> Result<T, Self::Error>
+    where
+        T: Decode<'de, Self::Mode>,
+    {
+        self.cx.decode(self)
+    }
+
+    #[inline]
+    fn decode_unsized<T, F, O>(self, f: F) -> Result<O, Self::Error>
+    where
+        T: ?Sized + DecodeUnsized<'de, Self::Mode>,
+        F: FnOnce(&T) -> Result<O, Self::Error>,
+    {
+        self.cx.decode_unsized(self, f)
+    }
+
+    #[inline]
+    fn skip(self) -> Result<(), C::Error> {
+        Ok(())
+    }
+
+    #[inline]
+    fn try_skip(self) -> Result<Skip, C::Error> {
+        Ok(Skip::Skipped)
+    }
+
+    #[inline]
+    fn decode_empty(self) -> Result<(), C::Error> {
+        if !self.value.is_empty() {
+            return Err(self.cx.message("Expected empty value"));
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn decode_bool(self) -> Result<bool, C::Error> {
+        match self.value {
+            b"true" => Ok(true),
+            b"false" => Ok(false),
+            value => Err(self.cx.message(format_args!(
+                "Expected boolean, but found {:?}",
+                Printable(value)
+            ))),
+        }
+    }
+
+    #[inline]
+    fn decode_char(self) -> Result<char, C::Error> {
+        let mut chars = self.as_str()?.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(self.cx.message(format_args!(
+                "Expected character, but found {:?}",
+                Printable(self.value)
+            ))),
+        }
+    }
+
+    #[inline]
+    fn decode_u8(self) -> Result<u8, C::Error> {
+        self.number(|cx, p| parse_unsigned(cx, p))
+    }
+
+    #[inline]
+    fn decode_u16(self) -> Result<u16, C::Error> {
+        self.number(|cx, p| parse_unsigned(cx, p))
+    }
+
+    #[inline]
+    fn decode_u32(self) -> Result<u32, C::Error> {
+        self.number(|cx, p| parse_unsigned(cx, p))
+    }
+
+    #[inline]
+    fn decode_u64(self) -> Result<u64, C::Error> {
+        self.number(|cx, p| parse_unsigned(cx, p))
+    }
+
+    #[inline]
+    fn decode_u128(self) -> Result<u128, C::Error> {
+        self.number(|cx, p| parse_unsigned(cx, p))
+    }
+
+    #[inline]
+    fn decode_i8(self) -> Result<i8, C::Error> {
+        self.number(|cx, p| parse_signed(cx, p))
+    }
+
+    #[inline]
+    fn decode_i16(self) -> Result<i16, C::Error> {
+        self.number(|cx, p| parse_signed(cx, p))
+    }
+
+    #[inline]
+    fn decode_i32(self) -> Result<i32, C::Error> {
+        self.number(|cx, p| parse_signed(cx, p))
+    }
+
+    #[inline]
+    fn decode_i64(self) -> Result<i64, C::Error> {
+        self.number(|cx, p| parse_signed(cx, p))
+    }
+
+    #[inline]
+    fn decode_i128(self) -> Result<i128, C::Error> {
+        self.number(|cx, p| parse_signed(cx, p))
+    }
+
+    #[inline]
+    fn decode_usize(self) -> Result<usize, C::Error> {
+        self.number(|cx, p| parse_unsigned(cx, p))
+    }
+
+    #[inline]
+    fn decode_isize(self) -> Result<isize, C::Error> {
+        self.number(|cx, p| parse_signed(cx, p))
+    }
+
+    #[inline]
+    fn decode_f32(self) -> Result<f32, C::Error> {
+        self.number(|cx, p| p.parse_f32(cx))
+    }
+
+    #[inline]
+    fn decode_f64(self) -> Result<f64, C::Error> {
+        self.number(|cx, p| p.parse_f64(cx))
+    }
+
+    #[inline]
+    fn decode_string<V>(self, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: UnsizedVisitor<'de, C, str>,
+    {
+        let string = self.as_str()?;
+        visitor.visit_ref(self.cx, string)
+    }
+
+    #[inline]
+    fn decode_option(self) -> Result<Option<Self::DecodeSome>, C::Error> {
+        Ok(Some(self))
+    }
+}
+
+/// Parse a single hexadecimal digit.
+#[inline]
+fn hex(b: u8) -> Option<u8> {
+    match b {
+        b'0'..=b'9' => Some(b - b'0'),
+        b'a'..=b'f' => Some(b - b'a' + 10),
+        b'A'..=b'F' => Some(b - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Helper to display bytes which are most likely a string in errors.
+struct Printable<'a>(&'a [u8]);
+
+impl fmt::Debug for Printable<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match core::str::from_utf8(self.0) {
+            Ok(string) => string.fmt(f),
+            Err(..) => self.0.fmt(f),
+        }
+    }
+}
@@ -0,0 +1,574 @@
+use core::fmt;
+
+use crate::alloc::Vec;
+use crate::en::{Encoder, EntriesEncoder, EntryEncoder, MapEncoder, SequenceEncoder};
+use crate::hint::{MapHint, SequenceHint};
+use crate::{Context, Encode, Writer};
+
+/// Hexadecimal digits used in percent-encoding.
+const HEX: &[u8; 16] = b"0123456789ABCDEF";
+
+/// The root encoder of a query string.
+pub struct UrlEncodedEncoder<'a, W, C: ?Sized> {
+    cx: &'a C,
+    writer: W,
+}
+
+impl<'a, W, C: ?Sized> UrlEncodedEncoder<'a, W, C> {
+    /// Construct a new query string encoder.
+    #[inline]
+    pub fn new(cx: &'a C, writer: W) -> Self {
+        Self { cx, writer }
+    }
+}
+
+/// Encoder for the name and value pairs of a query string.
+pub struct PairsEncoder<'a, W, C>
+where
+    C: ?Sized + Context,
+{
+    cx: &'a C,
+    writer: W,
+    /// Whether no pair has been written yet.
+    first: bool,
+    /// The name of the field currently being encoded.
+    key: Vec<'a, u8, C::Allocator>,
+}
+
+/// Encoder for a single field, which might result in any number of pairs.
+pub struct PairEncoder<'a, 'b, W, C>
+where
+    C: ?Sized + Context,
+{
+    cx: &'a C,
+    writer: W,
+    first: &'b mut bool,
+    key: &'b mut Vec<'a, u8, C::Allocator>,
+}
+
+/// Encoder for the name of a field.
+pub struct KeyEncoder<'a, 'b, C>
+where
+    C: ?Sized + Context,
+{
+    cx: &'a C,
+    key: &'b mut Vec<'a, u8, C::Allocator>,
+}
+
+impl<'a, 'b, C> KeyEncoder<'a, 'b, C>
+where
+    C: ?Sized + Context,
+{
+    #[inline]
+    fn store(self, bytes: &[u8]) -> Result<(), C::Error> {
+        self.key.clear();
+
+        if !self.key.write(bytes) {
+            return Err(self.cx.message("Scratch buffer overflow"));
+        }
+
+        Ok(())
+    }
+}
+
+/// Encoder for the value of a field.
+///
+/// Every value is written together with the name of its field, which allows
+/// sequences to be encoded as repeated pairs.
+pub struct ValueEncoder<'a, 'b, W, C: ?Sized> {
+    cx: &'a C,
+    writer: W,
+    first: &'b mut bool,
+    key: &'b [u8],
+    /// Whether the value is an element of a sequence.
+    element: bool,
+}
+
+impl<'a, 'b, W, C> ValueEncoder<'a, 'b, W, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    #[inline]
+    fn new(cx: &'a C, writer: W, first: &'b mut bool, key: &'b [u8], element: bool) -> Self {
+        Self {
+            cx,
+            writer,
+            first,
+            key,
+            element,
+        }
+    }
+
+    /// Write a single name and value pair.
+    #[inline]
+    fn pair(mut self, value: &[u8]) -> Result<(), C::Error> {
+        if !*self.first {
+            self.writer.write_byte(self.cx, b'&')?;
+        }
+
+        *self.first = false;
+        escape(self.cx, self.writer.borrow_mut(), self.key)?;
+        self.writer.write_byte(self.cx, b'=')?;
+        escape(self.cx, self.writer.borrow_mut(), value)
+    }
+}
+
+#[crate::encoder(crate)]
+impl<'a, W, C> Encoder for UrlEncodedEncoder<'a, W, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Ok = ();
+    type Mode = C::Mode;
+    type WithContext<'this, U> = UrlEncodedEncoder<'this, W, U> where U: 'this + Context;
+    type EncodeMap = PairsEncoder<'a, W, C>;
+    type EncodeMapEntries = PairsEncoder<'a, W, C>;
+
+    #[inline]
+    fn cx(&self) -> &Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn with_context<U>(self, cx: &U) -> Result<Self::WithContext<'_, U>, C::Error>
+    where
+        U: Context,
+    {
+        Ok(UrlEncodedEncoder::new(cx, self.writer))
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "struct or map that can be encoded as a query string")
+    }
+
+    #[inline]
+    fn encode<T>(self, value: T) -> Result<Self::Ok, Self::Error>
+    where
+        T: Encode<Self::Mode>,
+    {
+        value.encode(self.cx, self)
+    }
+
+    #[inline]
+    fn encode_map(self, _: &MapHint) -> Result<Self::EncodeMap, C::Error> {
+        Ok(PairsEncoder {
+            cx: self.cx,
+            writer: self.writer,
+            first: true,
+            key: Vec::new_in(self.cx.alloc()),
+        })
+    }
+
+    #[inline]
+    fn encode_map_entries(self, hint: &MapHint) -> Result<Self::EncodeMapEntries, C::Error> {
+        self.encode_map(hint)
+    }
+}
+
+impl<'a, W, C> MapEncoder for PairsEncoder<'a, W, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Ok = ();
+    type EncodeEntry<'this> = PairEncoder<'a, 'this, W::Mut<'this>, C> where Self: 'this;
+
+    #[inline]
+    fn encode_entry(&mut self) -> Result<Self::EncodeEntry<'_>, C::Error> {
+        Ok(PairEncoder {
+            cx: self.cx,
+            writer: self.writer.borrow_mut(),
+            first: &mut self.first,
+            key: &mut self.key,
+        })
+    }
+
+    #[inline]
+    fn finish_map(self) -> Result<Self::Ok, C::Error> {
+        Ok(())
+    }
+}
+
+impl<'a, W, C> EntriesEncoder for PairsEncoder<'a, W, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Ok = ();
+    type EncodeEntryKey<'this> = KeyEncoder<'a, 'this, C> where Self: 'this;
+    type EncodeEntryValue<'this> = ValueEncoder<'a, 'this, W::Mut<'this>, C> where Self: 'this;
+
+    #[inline]
+    fn encode_entry_key(&mut self) -> Result<Self::EncodeEntryKey<'_>, C::Error> {
+        Ok(KeyEncoder {
+            cx: self.cx,
+            key: &mut self.key,
+        })
+    }
+
+    #[inline]
+    fn encode_entry_value(&mut self) -> Result<Self::EncodeEntryValue<'_>, C::Error> {
+        Ok(ValueEncoder::new(
+            self.cx,
+            self.writer.borrow_mut(),
+            &mut self.first,
+            self.key.as_slice(),
+            false,
+        ))
+    }
+
+    #[inline]
+    fn finish_entries(self) -> Result<Self::Ok, C::Error> {
+        Ok(())
+    }
+}
+
+impl<'a, 'b, W, C> EntryEncoder for PairEncoder<'a, 'b, W, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Ok = ();
+    type EncodeKey<'this> = KeyEncoder<'a, 'this, C> where Self: 'this;
+    type EncodeValue<'this> = ValueEncoder<'a, 'this, W::Mut<'this>, C> where Self: 'this;
+
+    #[inline]
+    fn encode_key(&mut self) -> Result<Self::EncodeKey<'_>, C::Error> {
+        Ok(KeyEncoder {
+            cx: self.cx,
+            key: &mut *self.key,
+        })
+    }
+
+    #[inline]
+    fn encode_value(&mut self) -> Result<Self::EncodeValue<'_>, C::Error> {
+        Ok(ValueEncoder::new(
+            self.cx,
+            self.writer.borrow_mut(),
+            &mut *self.first,
+            self.key.as_slice(),
+            false,
+        ))
+    }
+
+    #[inline]
+    fn finish_entry(self) -> Result<Self::Ok, C::Error> {
+        Ok(())
+    }
+}
+
+#[crate::encoder(crate)]
+impl<'a, 'b, C> Encoder for KeyEncoder<'a, 'b, C>
+where
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Ok = ();
+    type Mode = C::Mode;
+
+    #[inline]
+    fn cx(&self) -> &Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "field name that can be encoded in a query string")
+    }
+
+    #[inline]
+    fn encode<T>(self, value: T) -> Result<Self::Ok, Self::Error>
+    where
+        T: Encode<Self::Mode>,
+    {
+        value.encode(self.cx, self)
+    }
+
+    #[inline]
+    fn encode_string(self, string: &str) -> Result<Self::Ok, C::Error> {
+        self.store(string.as_bytes())
+    }
+
+    #[inline]
+    fn collect_string<T>(self, value: &T) -> Result<Self::Ok, C::Error>
+    where
+        T: ?Sized + fmt::Display,
+    {
+        let buf = self.cx.collect_string(value)?;
+        self.store(buf.as_ref().as_bytes())
+    }
+
+    #[inline]
+    fn encode_u8(self, value: u8) -> Result<Self::Ok, C::Error> {
+        self.encode_usize(value as usize)
+    }
+
+    #[inline]
+    fn encode_u16(self, value: u16) -> Result<Self::Ok, C::Error> {
+        self.encode_usize(value as usize)
+    }
+
+    #[inline]
+    fn encode_u32(self, value: u32) -> Result<Self::Ok, C::Error> {
+        self.encode_usize(value as usize)
+    }
+
+    #[inline]
+    fn encode_usize(self, value: usize) -> Result<Self::Ok, C::Error> {
+        let mut buffer = itoa::Buffer::new();
+        self.store(buffer.format(value).as_bytes())
+    }
+}
+
+#[crate::encoder(crate)]
+impl<'a, 'b, W, C> Encoder for ValueEncoder<'a, 'b, W, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Error = C::Error;
+    type Ok = ();
+    type Mode = C::Mode;
+    type WithContext<'this, U> = ValueEncoder<'this, 'b, W, U> where U: 'this + Context;
+    type EncodeSome = Self;
+    type EncodeSequence = Self;
+
+    #[inline]
+    fn cx(&self) -> &Self::Cx {
+        self.cx
+    }
+
+    #[inline]
+    fn with_context<U>(self, cx: &U) -> Result<Self::WithContext<'_, U>, C::Error>
+    where
+        U: Context,
+    {
+        Ok(ValueEncoder::new(
+            cx,
+            self.writer,
+            self.first,
+            self.key,
+            self.element,
+        ))
+    }
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "value that can be encoded in a query string")
+    }
+
+    #[inline]
+    fn encode<T>(self, value: T) -> Result<Self::Ok, Self::Error>
+    where
+        T: Encode<Self::Mode>,
+    {
+        value.encode(self.cx, self)
+    }
+
+    #[inline]
+    fn encode_empty(self) -> Result<Self::Ok, C::Error> {
+        self.pair(b"")
+    }
+
+    #[inline]
+    fn encode_bool(self, value: bool) -> Result<Self::Ok, C::Error> {
+        self.pair(if value { b"true" } else { b"false" })
+    }
+
+    #[inline]
+    fn encode_char(self, value: char) -> Result<Self::Ok, C::Error> {
+        self.pair(value.encode_utf8(&mut [0, 0, 0, 0]).as_bytes())
+    }
+
+    #[inline]
+    fn encode_u8(self, value: u8) -> Result<Self::Ok, C::Error> {
+        let mut buffer = itoa::Buffer::new();
+        self.pair(buffer.format(value).as_bytes())
+    }
+
+    #[inline]
+    fn encode_u16(self, value: u16) -> Result<Self::Ok, C::Error> {
+        let mut buffer = itoa::Buffer::new();
+        self.pair(buffer.format(value).as_bytes())
+    }
+
+    #[inline]
+    fn encode_u32(self, value: u32) -> Result<Self::Ok, C::Error> {
+        let mut buffer = itoa::Buffer::new();
+        self.pair(buffer.format(value).as_bytes())
+    }
+
+    #[inline]
+    fn encode_u64(self, value: u64) -> Result<Self::Ok, C::Error> {
+        let mut buffer = itoa::Buffer::new();
+        self.pair(buffer.format(value).as_bytes())
+    }
+
+    #[inline]
+    fn encode_u128(self, value: u128) -> Result<Self::Ok, C::Error> {
+        let mut buffer = itoa::Buffer::new();
+        self.pair(buffer.format(value).as_bytes())
+    }
+
+    #[inline]
+    fn encode_i8(self, value: i8) -> Result<Self::Ok, C::Error> {
+        let mut buffer = itoa::Buffer::new();
+        self.pair(buffer.format(value).as_bytes())
+    }
+
+    #[inline]
+    fn encode_i16(self, value: i16) -> Result<Self::Ok, C::Error> {
+        let mut buffer = itoa::Buffer::new();
+        self.pair(buffer.format(value).as_bytes())
+    }
+
+    #[inline]
+    fn encode_i32(self, value: i32) -> Result<Self::Ok, C::Error> {
+        let mut buffer = itoa::Buffer::new();
+        self.pair(buffer.format(value).as_bytes())
+    }
+
+    #[inline]
+    fn encode_i64(self, value: i64) -> Result<Self::Ok, C::Error> {
+        let mut buffer = itoa::Buffer::new();
+        self.pair(buffer.format(value).as_bytes())
+    }
+
+    #[inline]
+    fn encode_i128(self, value: i128) -> Result<Self::Ok, C::Error> {
+        let mut buffer = itoa::Buffer::new();
+        self.pair(buffer.format(value).as_bytes())
+    }
+
+    #[inline]
+    fn encode_usize(self, value: usize) -> Result<Self::Ok, C::Error> {
+        let mut buffer = itoa::Buffer::new();
+        self.pair(buffer.format(value).as_bytes())
+    }
+
+    #[inline]
+    fn encode_isize(self, value: isize) -> Result<Self::Ok, C::Error> {
+        let mut buffer = itoa::Buffer::new();
+        self.pair(buffer.format(value).as_bytes())
+    }
+
+    #[inline]
+    fn encode_f32(self, value: f32) -> Result<Self::Ok, C::Error> {
+        let mut buffer = ryu::Buffer::new();
+        self.pair(buffer.format(value).as_bytes())
+    }
+
+    #[inline]
+    fn encode_f64(self, value: f64) -> Result<Self::Ok, C::Error> {
+        let mut buffer = ryu::Buffer::new();
+        self.pair(buffer.format(value).as_bytes())
+    }
+
+    #[inline]
+    fn encode_string(self, string: &str) -> Result<Self::Ok, C::Error> {
+        self.pair(string.as_bytes())
+    }
+
+    #[inline]
+    fn collect_string<T>(self, value: &T) -> Result<Self::Ok, C::Error>
+    where
+        T: ?Sized + fmt::Display,
+    {
+        let buf = self.cx.collect_string(value)?;
+        self.pair(buf.as_ref().as_bytes())
+    }
+
+    #[inline]
+    fn encode_some(self) -> Result<Self::EncodeSome, C::Error> {
+        Ok(self)
+    }
+
+    #[inline]
+    fn encode_none(self) -> Result<Self::Ok, C::Error> {
+        Ok(())
+    }
+
+    #[inline]
+    fn encode_sequence(self, _: &SequenceHint) -> Result<Self::EncodeSequence, C::Error> {
+        if self.element {
+            return Err(self
+                .cx
+                .message("Nested sequences cannot be encoded in a query string"));
+        }
+
+        Ok(self)
+    }
+}
+
+impl<'a, 'b, W, C> SequenceEncoder for ValueEncoder<'a, 'b, W, C>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    type Cx = C;
+    type Ok = ();
+    type EncodeNext<'this> = ValueEncoder<'a, 'this, W::Mut<'this>, C> where Self: 'this;
+
+    #[inline]
+    fn encode_next(&mut self) -> Result<Self::EncodeNext<'_>, C::Error> {
+        Ok(ValueEncoder::new(
+            self.cx,
+            self.writer.borrow_mut(),
+            &mut *self.first,
+            self.key,
+            true,
+        ))
+    }
+
+    #[inline]
+    fn finish_sequence(self) -> Result<Self::Ok, C::Error> {
+        Ok(())
+    }
+}
+
+/// Test if the given byte can be written as-is by the
+/// `application/x-www-form-urlencoded` serializer.
+#[inline]
+fn is_unreserved(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || matches!(b, b'*' | b'-' | b'.' | b'_')
+}
+
+/// Write the given bytes percent-encoded, with spaces written as `+`.
+fn escape<W, C>(cx: &C, mut writer: W, mut bytes: &[u8]) -> Result<(), C::Error>
+where
+    W: Writer,
+    C: ?Sized + Context,
+{
+    loop {
+        let n = bytes
+            .iter()
+            .position(|&b| !is_unreserved(b))
+            .unwrap_or(bytes.len());
+
+        let (head, tail) = bytes.split_at(n);
+        writer.write_bytes(cx, head)?;
+
+        let Some((&b, tail)) = tail.split_first() else {
+            return Ok(());
+        };
+
+        if b == b' ' {
+            writer.write_byte(cx, b'+')?;
+        } else {
+            let escaped = [b'%', HEX[(b >> 4) as usize], HEX[(b & 0xf) as usize]];
+            writer.write_bytes(cx, &escaped)?;
+        }
+
+        bytes = tail;
+    }
+}
@@ -0,0 +1,272 @@
+//! Module that defines [`Encoding`] whith allows for customization of the
+//! encoding format, and the [`DEFAULT`] encoding configuration.
+
+use core::marker;
+
+#[cfg(feature = "alloc")]
+use rust_alloc::string::String;
+#[cfg(feature = "alloc")]
+use rust_alloc::vec::Vec;
+
+use crate::mode::Text;
+#[cfg(feature = "alloc")]
+use crate::{Context, Encode};
+use crate::{Decode, IntoReader};
+
+use super::de::UrlEncodedDecoder;
+use super::en::UrlEncodedEncoder;
+use super::error::Error;
+
+/// The default configuration.
+pub const DEFAULT: Encoding = Encoding::new();
+
+crate::macros::bare_encoding!(Text, DEFAULT, urlencoded, IntoReader, to_end = true);
+
+/// Encode the given value to a [`String`] using the [`DEFAULT`] [`Encoding`].
+///
+/// # Examples
+///
+/// ```
+/// use musli::{Decode, Encode};
+/// use musli::urlencoded;
+/// # use musli::urlencoded::Error;
+///
+/// #[derive(Debug, PartialEq, Decode, Encode)]
+/// struct Search {
+///     query: String,
+///     page: u32,
+///     tags: Vec<String>,
+/// }
+///
+/// let search = Search {
+///     query: "hello world".to_string(),
+///     page: 2,
+///     tags: vec!["a&b".to_string(), "c".to_string()],
+/// };
+///
+/// let data = urlencoded::to_string(&search)?;
+/// assert_eq!(data, "query=hello+world&page=2&tags=a%26b&tags=c");
+///
+/// let decoded: Search = urlencoded::from_str(&data)?;
+/// assert_eq!(decoded, search);
+/// # Ok::<(), Error>(())
+/// ```
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn to_string<T>(value: &T) -> Result<String, Error>
+where
+    T: ?Sized + Encode<Text>,
+{
+    DEFAULT.to_string(value)
+}
+
+/// Decode the given type `T` from the given string using the [`DEFAULT`]
+/// [`Encoding`].
+///
+/// # Examples
+///
+/// ```
+/// use musli::{Decode, Encode};
+/// use musli::urlencoded;
+/// # use musli::urlencoded::Error;
+///
+/// #[derive(Decode, Encode)]
+/// struct Search {
+///     query: String,
+///     page: u32,
+///     tags: Vec<String>,
+/// }
+///
+/// let search: Search = urlencoded::from_str("tags=a&query=hello%20world&page=2&tags=b")?;
+/// assert_eq!(search.query, "hello world");
+/// assert_eq!(search.page, 2);
+/// assert_eq!(search.tags, ["a", "b"]);
+/// # Ok::<(), Error>(())
+/// ```
+#[inline]
+pub fn from_str<'de, T>(string: &'de str) -> Result<T, Error>
+where
+    T: Decode<'de, Text>,
+{
+    DEFAULT.from_str(string)
+}
+
+/// Setting up encoding with parameters.
+pub struct Encoding<M = Text>
+where
+    M: 'static,
+{
+    _marker: marker::PhantomData<M>,
+}
+
+impl Default for Encoding<Text> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Encoding<Text> {
+    /// Construct a new [`Encoding`].
+    ///
+    /// You can modify this using the available factory methods:
+    ///
+    /// ```
+    /// use musli::{Encode, Decode};
+    /// use musli::urlencoded::Encoding;
+    /// # use musli::urlencoded::Error;
+    ///
+    /// const CONFIG: Encoding<Query> = Encoding::new().with_mode();
+    ///
+    /// // Mode marker indicating that some attributes should
+    /// // only apply when we're encoding query strings.
+    /// enum Query {}
+    ///
+    /// #[derive(Debug, PartialEq, Encode, Decode)]
+    /// #[musli(mode = Query, name_all = "kebab-case")]
+    /// struct Search {
+    ///     search_query: String,
+    /// }
+    ///
+    /// let expected = Search {
+    ///     search_query: "Aristotle".to_string(),
+    /// };
+    ///
+    /// let out = CONFIG.to_string(&expected)?;
+    /// assert_eq!(out, "search-query=Aristotle");
+    ///
+    /// let actual = CONFIG.from_str(&out)?;
+    /// assert_eq!(expected, actual);
+    /// # Ok::<(), Error>(())
+    /// ```
+    #[inline]
+    pub const fn new() -> Self {
+        Encoding {
+            _marker: marker::PhantomData,
+        }
+    }
+}
+
+impl<M> Encoding<M>
+where
+    M: 'static,
+{
+    /// Change the mode of the encoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::urlencoded::Encoding;
+    ///
+    /// enum Custom {}
+    ///
+    /// const CONFIG: Encoding<Custom> = Encoding::new().with_mode();
+    /// ```
+    pub const fn with_mode<T>(self) -> Encoding<T> {
+        Encoding {
+            _marker: marker::PhantomData,
+        }
+    }
+
+    crate::macros::encoding_impls!(
+        M,
+        urlencoded,
+        UrlEncodedEncoder::new,
+        UrlEncodedDecoder::new,
+        IntoReader::into_reader,
+        Reader,
+        to_end = true,
+    );
+
+    /// Encode the given value to a [`String`] using the current [`Encoding`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Decode, Encode};
+    /// use musli::urlencoded;
+    /// # use musli::urlencoded::Error;
+    ///
+    /// const ENCODING: urlencoded::Encoding = urlencoded::Encoding::new();
+    ///
+    /// #[derive(Decode, Encode)]
+    /// struct Person {
+    ///     name: String,
+    ///     age: u32,
+    /// }
+    ///
+    /// let data = ENCODING.to_string(&Person {
+    ///     name: "Aristotle".to_string(),
+    ///     age: 61,
+    /// })?;
+    ///
+    /// assert_eq!(data, "name=Aristotle&age=61");
+    /// # Ok::<(), Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn to_string<T>(self, value: &T) -> Result<String, Error>
+    where
+        T: ?Sized + Encode<M>,
+    {
+        crate::alloc::default!(|alloc| {
+            let cx = crate::context::Same::with_alloc(alloc);
+            self.to_string_with(&cx, value)
+        })
+    }
+
+    /// Encode the given value to a [`String`] using the current [`Encoding`].
+    ///
+    /// This is the same as [`Encoding::to_string`] but allows for using a
+    /// configurable [`Context`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Decode, Encode};
+    /// use musli::urlencoded;
+    /// use musli::context::Same;
+    /// # use musli::urlencoded::Error;
+    ///
+    /// const ENCODING: urlencoded::Encoding = urlencoded::Encoding::new();
+    ///
+    /// #[derive(Decode, Encode)]
+    /// struct Person {
+    ///     name: String,
+    ///     age: u32,
+    /// }
+    ///
+    /// let cx = Same::new();
+    ///
+    /// let data = ENCODING.to_string_with(&cx, &Person {
+    ///     name: "Aristotle".to_string(),
+    ///     age: 61,
+    /// })?;
+    ///
+    /// let person: Person = ENCODING.from_str_with(&cx, &data[..])?;
+    /// assert_eq!(person.name, "Aristotle");
+    /// assert_eq!(person.age, 61);
+    /// # Ok::<(), Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn to_string_with<T, C>(self, cx: &C, value: &T) -> Result<String, C::Error>
+    where
+        C: ?Sized + Context<Mode = M>,
+        T: ?Sized + Encode<M>,
+    {
+        cx.clear();
+        let mut data = Vec::with_capacity(128);
+        T::encode(value, cx, UrlEncodedEncoder::new(cx, &mut data))?;
+        crate::str::from_utf8_owned(data).map_err(crate::str::map_utf8_error(cx))
+    }
+}
+
+impl<M> Clone for Encoding<M> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M> Copy for Encoding<M> {}
@@ -0,0 +1,21 @@
+crate::macros::error_type! {
+    /// Error raised during query string encoding.
+    pub struct Error;
+
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::Decode;
+    /// use musli::context::ErrorKind;
+    ///
+    /// #[derive(Debug, Decode)]
+    /// struct Query {
+    ///     page: u32,
+    /// }
+    ///
+    /// let error = musli::urlencoded::from_str::<Query>("size=10").unwrap_err();
+    /// assert_eq!(error.kind(), ErrorKind::MissingField);
+    /// ```
+    pub fn kind;
+}
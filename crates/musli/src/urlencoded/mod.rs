@@ -0,0 +1,86 @@
+//! Support for the [`application/x-www-form-urlencoded`] format, which is used
+//! by URL query strings and HTML forms.
+//!
+//! Only flat structs can be encoded, where each field is written as a
+//! `name=value` pair. Field names are decided by the [`Text`] mode, so they
+//! can be customized with `#[musli(name_all = "..")]` or `#[musli(name =
+//! "..")]`. Sequences such as `Vec` are encoded by repeating the name of the
+//! field once for each element, and `None` values are omitted entirely.
+//!
+//! * ✔ Can tolerate missing fields if they are annotated with
+//!   `#[musli(default)]`, which is necessary for optional fields and for
+//!   sequences which might be empty.
+//! * ✔ Can skip over unknown fields.
+//!
+//! Numbers are parsed the same way as in [`musli::json`], and names and values
+//! are percent-encoded with spaces written as `+`.
+//!
+//! Since a query string extends until the end of the input, decoding always
+//! consumes all of it.
+//!
+//! [`application/x-www-form-urlencoded`]: https://url.spec.whatwg.org/#application/x-www-form-urlencoded
+//! [`Text`]: crate::mode::Text
+//! [`musli::json`]: crate::json
+//!
+//! ```
+//! use musli::{Encode, Decode};
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! #[musli(name_all = "camelCase")]
+//! struct Filter {
+//!     search_term: String,
+//!     #[musli(default)]
+//!     max_price: Option<f64>,
+//!     #[musli(default)]
+//!     categories: Vec<u32>,
+//! }
+//!
+//! let filter = Filter {
+//!     search_term: String::from("café & bar"),
+//!     max_price: None,
+//!     categories: vec![1, 7],
+//! };
+//!
+//! let query = musli::urlencoded::to_string(&filter)?;
+//! assert_eq!(query, "searchTerm=caf%C3%A9+%26+bar&categories=1&categories=7");
+//!
+//! let decoded: Filter = musli::urlencoded::from_str(&query)?;
+//! assert_eq!(decoded, filter);
+//! # Ok::<_, musli::urlencoded::Error>(())
+//! ```
+
+#![cfg(feature = "urlencoded")]
+#![cfg_attr(doc_cfg, doc(cfg(feature = "urlencoded")))]
+
+#[cfg(test)]
+mod tests;
+
+mod de;
+mod en;
+mod encoding;
+mod error;
+
+#[cfg(feature = "test")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "test")))]
+#[doc(hidden)]
+pub mod test;
+
+/// Convenient result alias for use with `musli::urlencoded`.
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[doc(inline)]
+pub use self::encoding::to_string;
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[doc(inline)]
+pub use self::encoding::to_vec;
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+#[doc(inline)]
+pub use self::encoding::to_writer;
+#[doc(inline)]
+pub use self::encoding::{decode, encode, from_slice, from_str, to_fixed_bytes, Encoding, DEFAULT};
+#[doc(inline)]
+pub use self::error::Error;
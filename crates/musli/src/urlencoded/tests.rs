@@ -0,0 +1,212 @@
+use core::fmt;
+
+use rust_alloc::string::{String, ToString};
+use rust_alloc::vec;
+use rust_alloc::vec::Vec;
+
+use crate::mode::Text;
+use crate::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+enum Order {
+    Ascending,
+    Descending,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(name_all = "snake_case")]
+struct Query {
+    search_term: String,
+    page: u32,
+    offset: i64,
+    ratio: f32,
+    exact: bool,
+    order: Order,
+    #[musli(default)]
+    limit: Option<u16>,
+    #[musli(default)]
+    tags: Vec<String>,
+}
+
+fn query() -> Query {
+    Query {
+        search_term: "hello world".to_string(),
+        page: 2,
+        offset: -10,
+        ratio: 0.5,
+        exact: true,
+        order: Order::Descending,
+        limit: None,
+        tags: Vec::new(),
+    }
+}
+
+#[track_caller]
+fn roundtrips<T>(value: T, expected: &str)
+where
+    T: Encode<Text> + for<'de> Decode<'de, Text> + fmt::Debug + PartialEq,
+{
+    let out = super::to_string(&value).expect("failed to encode");
+    assert_eq!(out, expected, "encoding {value:?}");
+    let actual: T = super::from_str(expected).expect("failed to decode");
+    assert_eq!(actual, value);
+}
+
+#[track_caller]
+fn fails<T>(input: &str, message: &str)
+where
+    T: for<'de> Decode<'de, Text> + fmt::Debug,
+{
+    let error = super::from_str::<T>(input).expect_err("expected decoding to fail");
+    assert_eq!(error.to_string(), message);
+}
+
+#[test]
+fn fields() {
+    roundtrips(
+        query(),
+        "search_term=hello+world&page=2&offset=-10&ratio=0.5&exact=true&order=Descending",
+    );
+
+    roundtrips(
+        Query {
+            limit: Some(50),
+            tags: vec!["rust".to_string(), "serialization".to_string()],
+            ..query()
+        },
+        "search_term=hello+world&page=2&offset=-10&ratio=0.5&exact=true&order=Descending&limit=50&tags=rust&tags=serialization",
+    );
+}
+
+#[test]
+fn escaping() {
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    struct Escaped {
+        #[musli(mode = Text, name = "a b&c")]
+        value: String,
+    }
+
+    roundtrips(
+        Escaped {
+            value: "x=1&y=2 ~ 100% ü*-._".to_string(),
+        },
+        "a+b%26c=x%3D1%26y%3D2+%7E+100%25+%C3%BC*-._",
+    );
+
+    let value: Escaped = super::from_str("a%20b%26c=%7e%7E+").unwrap();
+    assert_eq!(value.value, "~~ ");
+}
+
+#[test]
+fn decode_unordered() {
+    let actual: Query = super::from_str(
+        "tags=a&exact=false&order=Ascending&unknown=1&ratio=1e2&tags=b&page=0&offset=7&search_term=",
+    )
+    .unwrap();
+
+    assert_eq!(
+        actual,
+        Query {
+            search_term: String::new(),
+            page: 0,
+            offset: 7,
+            ratio: 100.0,
+            exact: false,
+            order: Order::Ascending,
+            limit: None,
+            tags: vec!["a".to_string(), "b".to_string()],
+        }
+    );
+}
+
+#[test]
+fn empty() {
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    struct Empty {
+        #[musli(default)]
+        values: Vec<u32>,
+    }
+
+    roundtrips(Empty { values: Vec::new() }, "");
+
+    let actual: Empty = super::from_str("&values=1&&values=2&").unwrap();
+    assert_eq!(actual.values, [1, 2]);
+
+    fails::<Empty>(
+        "values=1&values=",
+        "Expected number for field \"values\", but found empty value",
+    );
+}
+
+#[test]
+fn errors() {
+    #[derive(Debug, Decode)]
+    struct Page {
+        #[allow(unused)]
+        page: u32,
+    }
+
+    fails::<Page>(
+        "page=1&page=2",
+        "Expected a single value for field \"page\"",
+    );
+    fails::<Page>(
+        "page=1x",
+        "Expected number for field \"page\", but found \"1x\"",
+    );
+    fails::<Page>(
+        "page=",
+        "Expected number for field \"page\", but found empty value",
+    );
+    fails::<Page>(
+        "page",
+        "Expected number for field \"page\", but found empty value",
+    );
+    fails::<Page>("page=%4", "Invalid percent-encoded byte");
+    fails::<Page>("page=%zz", "Invalid percent-encoded byte");
+}
+
+#[test]
+fn nested_sequences() {
+    #[derive(Debug, Encode)]
+    struct Nested {
+        values: Vec<Vec<u32>>,
+    }
+
+    let error = super::to_string(&Nested {
+        values: vec![vec![1]],
+    })
+    .expect_err("expected encoding to fail");
+
+    assert_eq!(
+        error.to_string(),
+        "Nested sequences cannot be encoded in a query string"
+    );
+}
+
+#[test]
+fn interleaved_repeated_values() {
+    use rust_alloc::collections::BTreeMap;
+    use rust_alloc::format;
+
+    let mut input = String::new();
+
+    for n in 0..10_000u32 {
+        if !input.is_empty() {
+            input.push('&');
+        }
+
+        input.push_str(&format!("k{}={n}", n % 3));
+    }
+
+    let actual: BTreeMap<String, Vec<u32>> = super::from_str(&input).unwrap();
+    assert_eq!(actual.len(), 3);
+
+    for (key, values) in actual {
+        let k = key[1..].parse::<u32>().unwrap();
+        assert!(values
+            .iter()
+            .copied()
+            .eq((0..10_000).filter(|n| n % 3 == k)));
+    }
+}
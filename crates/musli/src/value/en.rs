@@ -113,6 +113,11 @@ where
         write!(f, "value that can be encoded")
     }
 
+    #[inline]
+    fn is_size_required(&self) -> bool {
+        false
+    }
+
     #[inline]
     fn encode<T>(self, value: T) -> Result<Self::Ok, C::Error>
     where
@@ -22,9 +22,12 @@ pub use self::value::{AsValueDecoder, Value};
 #[doc(inline)]
 pub use error::Error;
 
+pub(crate) use self::en::ValueEncoder;
+#[cfg(feature = "toml")]
+pub(crate) use self::value::Number;
+
 use crate::alloc;
 use crate::mode::Binary;
-use crate::value::en::ValueEncoder;
 use crate::{Decode, Encode, Options};
 
 const OPTIONS: Options = crate::options::new().build();
@@ -4,9 +4,10 @@ use core::mem::take;
 #[cfg(feature = "alloc")]
 use rust_alloc::vec::Vec;
 
+use crate::context::ErrorKind;
 use crate::de::{
     Decode, DecodeUnsized, Decoder, EntriesDecoder, EntryDecoder, MapDecoder, SequenceDecoder,
-    SizeHint, Skip, UnsizedVisitor, VariantDecoder,
+    SizeHint, Skip, UnsizedVisitor, VariantDecoder, Visitor,
 };
 use crate::hint::{MapHint, SequenceHint};
 use crate::int::continuation as c;
@@ -102,31 +103,43 @@ where
     fn decode_sequence_len(&mut self) -> Result<usize, C::Error> {
         let tag = Tag::from_byte(self.reader.read_byte(self.cx)?);
 
-        match tag.kind() {
-            Kind::Sequence => Ok(if let Some(len) = tag.data() {
-                len as usize
-            } else {
-                crate::int::decode_usize::<_, _, OPT>(self.cx, self.reader.borrow_mut())?
-            }),
-            _ => Err(self.cx.message(Expected {
-                expected: Kind::Sequence,
-                actual: tag,
-            })),
-        }
+        let len = match tag.kind() {
+            Kind::Sequence => {
+                if let Some(len) = tag.data() {
+                    len as usize
+                } else {
+                    crate::int::decode_usize::<_, _, OPT>(self.cx, self.reader.borrow_mut())?
+                }
+            }
+            _ => {
+                return Err(self.cx.message_with_kind(
+                    ErrorKind::InvalidTag,
+                    Expected {
+                        expected: Kind::Sequence,
+                        actual: tag,
+                    },
+                ))
+            }
+        };
+
+        self.cx.check_length(len)?;
+        Ok(len)
     }
 
     // Standard function for decoding a pair sequence.
     #[inline]
     fn shared_decode_pair_sequence(
         mut self,
-    ) -> Result<RemainingWireDecoder<'a, R, OPT, C>, C::Error> {
+    ) -> Result<RemainingWireDecoder<'a, 'de, R, OPT, C>, C::Error> {
         let len = self.decode_sequence_len()?;
         Ok(RemainingWireDecoder::new(self.cx, self.reader, len / 2))
     }
 
     // Standard function for decoding a pair sequence.
     #[inline]
-    fn shared_decode_sequence(mut self) -> Result<RemainingWireDecoder<'a, R, OPT, C>, C::Error> {
+    fn shared_decode_sequence(
+        mut self,
+    ) -> Result<RemainingWireDecoder<'a, 'de, R, OPT, C>, C::Error> {
         let len = self.decode_sequence_len()?;
         Ok(RemainingWireDecoder::new(self.cx, self.reader, len))
     }
@@ -154,13 +167,19 @@ where
 /// This simplifies implementing decoders that do not have any special handling
 /// for length-prefixed types.
 #[doc(hidden)]
-pub struct RemainingWireDecoder<'a, R, const OPT: Options, C: ?Sized> {
+pub struct RemainingWireDecoder<'a, 'de, R, const OPT: Options, C>
+where
+    R: Reader<'de>,
+    C: ?Sized + Context,
+{
     cx: &'a C,
     reader: R,
     remaining: usize,
+    /// Where the last entry started, used to recover from errors.
+    entry: Option<(R::Checkpoint, C::Mark)>,
 }
 
-impl<'a, 'de, R, const OPT: Options, C> RemainingWireDecoder<'a, R, OPT, C>
+impl<'a, 'de, R, const OPT: Options, C> RemainingWireDecoder<'a, 'de, R, OPT, C>
 where
     C: ?Sized + Context,
     R: Reader<'de>,
@@ -171,6 +190,7 @@ where
             cx,
             reader,
             remaining,
+            entry: None,
         }
     }
 
@@ -214,9 +234,9 @@ where
     type WithContext<'this, U> = WireDecoder<'this, R, OPT, U> where U: 'this + Context;
     type DecodePack = WireDecoder<'a, Limit<R>, OPT, C>;
     type DecodeSome = Self;
-    type DecodeSequence = RemainingWireDecoder<'a, R, OPT, C>;
-    type DecodeMap = RemainingWireDecoder<'a, R, OPT, C>;
-    type DecodeMapEntries = RemainingWireDecoder<'a, R, OPT, C>;
+    type DecodeSequence = RemainingWireDecoder<'a, 'de, R, OPT, C>;
+    type DecodeMap = RemainingWireDecoder<'a, 'de, R, OPT, C>;
+    type DecodeMapEntries = RemainingWireDecoder<'a, 'de, R, OPT, C>;
     type DecodeVariant = Self;
 
     #[inline]
@@ -277,9 +297,11 @@ where
     {
         let mark = self.cx.mark();
         let len = self.decode_len(mark)?;
+        self.cx.enter_nested()?;
         let mut decoder = WireDecoder::new(self.cx, self.reader.limit(len));
         let output = f(&mut decoder)?;
         decoder.end()?;
+        self.cx.leave_nested();
         Ok(output)
     }
 
@@ -308,6 +330,7 @@ where
     {
         let mark = self.cx.mark();
         let len = self.decode_len(mark)?;
+        self.cx.check_size(len)?;
         self.reader.read_bytes(self.cx, len, visitor)
     }
 
@@ -333,19 +356,22 @@ where
             #[cfg(feature = "alloc")]
             #[inline]
             fn visit_owned(self, cx: &C, bytes: Vec<u8>) -> Result<Self::Ok, C::Error> {
-                let string = crate::str::from_utf8_owned(bytes).map_err(cx.map())?;
+                let string =
+                    crate::str::from_utf8_owned(bytes).map_err(crate::str::map_utf8_error(cx))?;
                 self.0.visit_owned(cx, string)
             }
 
             #[inline]
             fn visit_borrowed(self, cx: &C, bytes: &'de [u8]) -> Result<Self::Ok, C::Error> {
-                let string = crate::str::from_utf8(bytes).map_err(cx.map())?;
+                let string =
+                    crate::str::from_utf8(bytes).map_err(crate::str::map_utf8_error(cx))?;
                 self.0.visit_borrowed(cx, string)
             }
 
             #[inline]
             fn visit_ref(self, cx: &C, bytes: &[u8]) -> Result<Self::Ok, C::Error> {
-                let string = crate::str::from_utf8(bytes).map_err(cx.map())?;
+                let string =
+                    crate::str::from_utf8(bytes).map_err(crate::str::map_utf8_error(cx))?;
                 self.0.visit_ref(cx, string)
             }
         }
@@ -363,7 +389,9 @@ where
         match tag {
             FALSE => Ok(false),
             TRUE => Ok(true),
-            tag => Err(self.cx.message(BadBoolean { actual: tag })),
+            tag => Err(self
+                .cx
+                .message_with_kind(ErrorKind::InvalidTag, BadBoolean { actual: tag })),
         }
     }
 
@@ -465,7 +493,9 @@ where
         match tag {
             NONE => Ok(None),
             SOME => Ok(Some(self)),
-            tag => Err(self.cx.message(ExpectedOption { tag })),
+            tag => Err(self
+                .cx
+                .message_with_kind(ErrorKind::InvalidTag, ExpectedOption { tag })),
         }
     }
 
@@ -474,9 +504,12 @@ where
     where
         F: FnOnce(&mut Self::DecodeSequence) -> Result<O, <Self::Cx as Context>::Error>,
     {
+        let cx = self.cx;
+        cx.enter_nested()?;
         let mut decoder = self.shared_decode_sequence()?;
         let output = f(&mut decoder)?;
         decoder.skip_sequence_remaining()?;
+        cx.leave_nested();
         Ok(output)
     }
 
@@ -493,9 +526,12 @@ where
     where
         F: FnOnce(&mut Self::DecodeMap) -> Result<O, C::Error>,
     {
+        let cx = self.cx;
+        cx.enter_nested()?;
         let mut decoder = self.shared_decode_pair_sequence()?;
         let output = f(&mut decoder)?;
         decoder.skip_remaining_entries()?;
+        cx.leave_nested();
         Ok(output)
     }
 
@@ -523,13 +559,73 @@ where
         let tag = Tag::from_byte(self.reader.read_byte(self.cx)?);
 
         if tag != Tag::new(Kind::Sequence, 2) {
-            return Err(self.cx.message(Expected {
-                expected: Kind::Sequence,
-                actual: tag,
-            }));
+            return Err(self.cx.message_with_kind(
+                ErrorKind::InvalidTag,
+                Expected {
+                    expected: Kind::Sequence,
+                    actual: tag,
+                },
+            ));
         }
 
-        f(&mut self)
+        self.cx.enter_nested()?;
+        let output = f(&mut self)?;
+        self.cx.leave_nested();
+        Ok(output)
+    }
+
+    /// Decode a value on a best-effort basis, since the wire format only
+    /// records enough information to skip over values.
+    ///
+    /// * Numbers, including booleans and characters, are reported as unsigned
+    ///   integers.
+    /// * Prefixed data, such as strings, packs and fixed-width integers, is
+    ///   reported as bytes.
+    /// * Sequences, maps, variants and optional values are all reported as
+    ///   sequences.
+    #[inline]
+    fn decode_any<V>(mut self, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: Visitor<'de, C>,
+    {
+        let cx = self.cx;
+
+        let Some(tag) = self.reader.peek().map(Tag::from_byte) else {
+            return Err(cx.message("Expected tag in input"));
+        };
+
+        match tag.kind() {
+            Kind::Prefix => {
+                let mark = cx.mark();
+                let len = self.decode_len(mark)?;
+                cx.check_size(len)?;
+                let visitor = visitor.visit_bytes(cx, SizeHint::exact(len))?;
+                self.reader.read_bytes(cx, len, visitor)
+            }
+            Kind::Sequence => {
+                cx.enter_nested()?;
+                let mut sequence = self.shared_decode_sequence()?;
+                let output = visitor.visit_sequence(cx, &mut sequence)?;
+                sequence.skip_sequence_remaining()?;
+                cx.leave_nested();
+                Ok(output)
+            }
+            Kind::Continuation => {
+                self.reader.skip(cx, 1)?;
+
+                if let Some(data) = tag.data() {
+                    return visitor.visit_u64(cx, u64::from(data));
+                }
+
+                let value = c::decode::<_, _, u128>(cx, self.reader.borrow_mut())?;
+
+                match u64::try_from(value) {
+                    Ok(value) => visitor.visit_u64(cx, value),
+                    Err(..) => visitor.visit_u128(cx, value),
+                }
+            }
+            kind => Err(cx.message(format_args!("Cannot decode kind {kind:?}"))),
+        }
     }
 }
 
@@ -552,7 +648,8 @@ where
     }
 }
 
-impl<'a, 'de, R, const OPT: Options, C> SequenceDecoder<'de> for RemainingWireDecoder<'a, R, OPT, C>
+impl<'a, 'de, R, const OPT: Options, C> SequenceDecoder<'de>
+    for RemainingWireDecoder<'a, 'de, R, OPT, C>
 where
     C: ?Sized + Context,
     R: Reader<'de>,
@@ -608,7 +705,7 @@ where
     }
 }
 
-impl<'a, 'de, R, const OPT: Options, C> MapDecoder<'de> for RemainingWireDecoder<'a, R, OPT, C>
+impl<'a, 'de, R, const OPT: Options, C> MapDecoder<'de> for RemainingWireDecoder<'a, 'de, R, OPT, C>
 where
     C: ?Sized + Context,
     R: Reader<'de>,
@@ -617,7 +714,10 @@ where
     type DecodeEntry<'this> = WireDecoder<'a, R::Mut<'this>, OPT, C>
     where
         Self: 'this;
-    type DecodeRemainingEntries<'this> = RemainingWireDecoder<'a, R::Mut<'this>, OPT, C> where Self: 'this;
+    type DecodeRemainingEntries<'this>
+        = RemainingWireDecoder<'a, 'de, R::Mut<'this>, OPT, C>
+    where
+        Self: 'this;
 
     #[inline]
     fn size_hint(&self) -> SizeHint {
@@ -630,10 +730,28 @@ where
             return Ok(None);
         }
 
+        if self.cx.is_lenient() {
+            self.entry = Some((self.reader.checkpoint(), self.cx.mark()));
+        }
+
         self.remaining -= 1;
         Ok(Some(WireDecoder::new(self.cx, self.reader.borrow_mut())))
     }
 
+    #[inline]
+    fn try_recover(&mut self) -> Result<bool, C::Error> {
+        let Some((checkpoint, mark)) = self.entry.take() else {
+            return Ok(false);
+        };
+
+        self.reader.rewind(checkpoint);
+        self.cx.restore(mark);
+
+        WireDecoder::<_, OPT, _>::new(self.cx, self.reader.borrow_mut()).skip()?;
+        WireDecoder::<_, OPT, _>::new(self.cx, self.reader.borrow_mut()).skip()?;
+        Ok(true)
+    }
+
     #[inline]
     fn decode_remaining_entries(&mut self) -> Result<Self::DecodeRemainingEntries<'_>, C::Error> {
         Ok(RemainingWireDecoder::new(
@@ -664,7 +782,8 @@ where
     }
 }
 
-impl<'a, 'de, R, const OPT: Options, C> EntriesDecoder<'de> for RemainingWireDecoder<'a, R, OPT, C>
+impl<'a, 'de, R, const OPT: Options, C> EntriesDecoder<'de>
+    for RemainingWireDecoder<'a, 'de, R, OPT, C>
 where
     C: ?Sized + Context,
     R: Reader<'de>,
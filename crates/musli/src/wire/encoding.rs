@@ -127,6 +127,7 @@ where
         WireEncoder::<_, OPT, _>::new,
         WireDecoder::<_, OPT, _>::new,
         IntoReader::into_reader,
+        Reader,
     );
 }
 
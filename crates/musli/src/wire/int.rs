@@ -1,3 +1,4 @@
+use crate::context::ErrorKind;
 use crate::int::continuation as c;
 use crate::int::zigzag as zig;
 use crate::int::{Signed, Unsigned, UnsignedOps};
@@ -34,7 +35,10 @@ where
             macro_rules! fixed {
                 ($ty:ty) => {{
                     let Ok(value) = <$ty>::try_from(value) else {
-                        return Err(cx.message("Numerical value out of bounds for usize"));
+                        return Err(cx.message_with_kind(
+                            ErrorKind::NumberOverflow,
+                            "Numerical value out of bounds for usize",
+                        ));
                     };
 
                     value.write_bytes(cx, writer, bo)
@@ -88,7 +92,10 @@ where
                 ($ty:ty) => {{
                     #[allow(irrefutable_let_patterns)]
                     let Ok(value) = usize::try_from(<$ty>::read_bytes(cx, reader, bo)?) else {
-                        return Err(cx.message("Value type out of bounds for usize"));
+                        return Err(cx.message_with_kind(
+                            ErrorKind::NumberOverflow,
+                            "Value type out of bounds for usize",
+                        ));
                     };
 
                     Ok(value)
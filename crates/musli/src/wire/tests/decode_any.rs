@@ -0,0 +1,62 @@
+#![cfg(feature = "value")]
+
+use rust_alloc::string::String;
+use rust_alloc::vec;
+
+use crate::options::{self, Integer, Options};
+use crate::value::Value;
+use crate::wire::Encoding;
+use crate::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(crate)]
+struct Person {
+    name: String,
+    age: u32,
+    score: i32,
+    large: u128,
+    #[musli(packed)]
+    pair: (u8, u8),
+}
+
+#[test]
+fn decode_any() {
+    let out = crate::wire::to_vec(&Person {
+        name: String::from("Aristotle"),
+        age: 61,
+        score: -2,
+        large: u128::MAX,
+        pair: (1, 2),
+    })
+    .expect("failed to encode");
+
+    let value: Value = crate::wire::from_slice(&out).expect("failed to decode");
+
+    let u64 = |n: u64| Value::Number(n.into());
+
+    assert_eq!(
+        value,
+        Value::Sequence(vec![
+            u64(0),
+            Value::Bytes(b"Aristotle".to_vec()),
+            u64(1),
+            u64(61),
+            u64(2),
+            u64(3),
+            u64(3),
+            Value::Number(u128::MAX.into()),
+            u64(4),
+            Value::Bytes(vec![1, 2]),
+        ])
+    );
+}
+
+#[test]
+fn decode_any_fixed() {
+    const OPTIONS: Options = options::new().with_integer(Integer::Fixed).build();
+    const ENCODING: Encoding<OPTIONS> = Encoding::new().with_options();
+
+    let out = ENCODING.to_vec(&42u32).expect("failed to encode");
+    let value: Value = ENCODING.from_slice(&out).expect("failed to decode");
+    assert_eq!(value, Value::Bytes(42u32.to_ne_bytes().to_vec()));
+}
@@ -1,3 +1,4 @@
 mod basic;
+mod decode_any;
 mod numbers;
 mod struct_unpack;
@@ -157,6 +157,7 @@ impl Writer for &mut [u8] {
         let (this, next) = next.split_at_mut(bytes.len());
         this.copy_from_slice(bytes);
         *self = next;
+        cx.advance(bytes.len());
         Ok(())
     }
 
@@ -174,6 +175,7 @@ impl Writer for &mut [u8] {
 
         self[0] = b;
         *self = &mut take(self)[1..];
+        cx.advance(1);
         Ok(())
     }
 }
@@ -0,0 +1,65 @@
+//! Tests that ensure that MessagePack extension values are byte-compatible
+//! with rmp-serde.
+
+use musli::msgpack::Timestamp;
+use serde::{Serialize, Serializer};
+
+/// An extension value as it is represented by rmp-serde.
+#[derive(Serialize)]
+#[serde(rename = "_ExtStruct")]
+struct Ext<'a>((i8, Payload<'a>));
+
+struct Payload<'a>(&'a [u8]);
+
+impl Serialize for Payload<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+#[test]
+fn timestamps() {
+    let cases = [
+        // 32-bit form.
+        (0, 0, 0u32.to_be_bytes().to_vec()),
+        (42, 0, 42u32.to_be_bytes().to_vec()),
+        (u32::MAX as i64, 0, u32::MAX.to_be_bytes().to_vec()),
+        // 64-bit form.
+        (42, 1, ((1u64 << 34) | 42).to_be_bytes().to_vec()),
+        (
+            (1 << 34) - 1,
+            999_999_999,
+            ((999_999_999u64 << 34) | ((1 << 34) - 1))
+                .to_be_bytes()
+                .to_vec(),
+        ),
+        // 96-bit form.
+        (
+            -1,
+            1,
+            [&1u32.to_be_bytes()[..], &(-1i64).to_be_bytes()[..]].concat(),
+        ),
+        (
+            1 << 34,
+            0,
+            [&0u32.to_be_bytes()[..], &(1i64 << 34).to_be_bytes()[..]].concat(),
+        ),
+    ];
+
+    for (seconds, nanoseconds, payload) in cases {
+        let timestamp = Timestamp {
+            seconds,
+            nanoseconds,
+        };
+
+        let expected = rmp_serde::to_vec(&Ext((-1, Payload(&payload)))).unwrap();
+        let actual = musli::msgpack::to_vec(&timestamp).unwrap();
+        assert_eq!(actual, expected, "{timestamp:?}");
+
+        let decoded: Timestamp = musli::msgpack::from_slice(&expected).unwrap();
+        assert_eq!(decoded, timestamp);
+    }
+}
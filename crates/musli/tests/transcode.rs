@@ -0,0 +1,179 @@
+//! Tests that ensure that values can be transcoded between formats.
+
+use std::collections::HashMap;
+
+use musli::context::Same;
+use musli::de::{Decoder, MapDecoder, SequenceDecoder};
+use musli::mode::Binary;
+use musli::{descriptive, json};
+use musli::{Decode, Encode};
+
+const DESCRIPTIVE: descriptive::Encoding = descriptive::Encoding::new();
+const JSON: json::Encoding<Binary> = json::Encoding::new().with_mode();
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(mode = Binary, name_all = "name")]
+enum Status {
+    Active,
+    #[musli(mode = Binary, name_all = "name")]
+    Retired {
+        since: u32,
+    },
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(mode = Binary, name_all = "name")]
+struct Person {
+    name: String,
+    age: u32,
+    nickname: Option<String>,
+    scores: Vec<i16>,
+    tags: HashMap<String, u64>,
+    status: Status,
+}
+
+fn person() -> Person {
+    Person {
+        name: String::from("Aristotle"),
+        age: 61,
+        nickname: None,
+        scores: vec![1, -2, 3],
+        tags: HashMap::from([(String::from("school"), 1)]),
+        status: Status::Retired { since: 322 },
+    }
+}
+
+/// JSON doesn't preserve optional values or variants, so this is the subset
+/// of `Person` which survives a roundtrip through it.
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(mode = Binary, name_all = "name")]
+struct Record {
+    name: String,
+    age: u32,
+    scores: Vec<i16>,
+    tags: HashMap<String, u64>,
+}
+
+fn record() -> Record {
+    Record {
+        name: String::from("Aristotle"),
+        age: 61,
+        scores: vec![1, -2, 3],
+        tags: HashMap::from([(String::from("school"), 1)]),
+    }
+}
+
+#[test]
+fn descriptive_to_json() {
+    let expected = person();
+    let data = DESCRIPTIVE.to_vec(&expected).unwrap();
+
+    let cx = Same::<Binary, json::Error, _>::new();
+    let mut out = Vec::new();
+
+    let decoder = DESCRIPTIVE.decoder_with(&cx, &data[..]);
+    let encoder = JSON.encoder_with(&cx, &mut out);
+    musli::transcode(decoder, encoder).unwrap();
+
+    assert_eq!(
+        std::str::from_utf8(&out).unwrap(),
+        r#"{"name":"Aristotle","age":61,"nickname":null,"scores":[1,-2,3],"tags":{"school":1},"status":{"Retired":{"since":322}}}"#
+    );
+
+    let actual: Person = JSON.from_slice(&out).unwrap();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn json_to_descriptive() {
+    let expected = record();
+    let data = JSON.to_vec(&expected).unwrap();
+
+    let cx = Same::<Binary, descriptive::Error, _>::new();
+    let mut out = Vec::new();
+
+    let decoder = JSON.decoder_with(&cx, &data[..]);
+    let encoder = DESCRIPTIVE.encoder_with(&cx, &mut out);
+    musli::transcode(decoder, encoder).unwrap();
+
+    let actual: Record = DESCRIPTIVE.from_slice(&out).unwrap();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn json_counts_remaining() {
+    let data = br#"[1, [2, 3], {"a": [4], "b": {}}, "c"]"#;
+
+    let cx = Same::<Binary, json::Error, _>::new();
+    let decoder = JSON.decoder_with(&cx, &data[..]);
+
+    decoder
+        .decode_sequence(|seq| {
+            assert_eq!(seq.count_remaining()?, Some(4));
+            assert_eq!(seq.next::<u32>()?, 1);
+            assert_eq!(seq.count_remaining()?, Some(3));
+            assert_eq!(seq.next::<Vec<u32>>()?, [2, 3]);
+
+            seq.decode_next()?.decode_map(|map| {
+                assert_eq!(map.count_remaining()?, Some(2));
+                assert_eq!(
+                    map.entry::<String, Vec<u32>>()?,
+                    Some((String::from("a"), vec![4]))
+                );
+                assert_eq!(map.count_remaining()?, Some(1));
+                map.entry::<String, HashMap<String, u32>>()?;
+                assert_eq!(map.count_remaining()?, Some(0));
+                Ok(())
+            })?;
+
+            assert_eq!(seq.next::<String>()?, "c");
+            assert_eq!(seq.count_remaining()?, Some(0));
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn json_to_json() {
+    let data =
+        br#"{"name": "Aristotle", "scores": [[1, 2], [], [-3]], "tags": {"school": {"id": 1}}}"#;
+
+    let cx = Same::<Binary, json::Error, _>::new();
+    let mut out = Vec::new();
+
+    let decoder = JSON.decoder_with(&cx, &data[..]);
+    let encoder = JSON.encoder_with(&cx, &mut out);
+    musli::transcode(decoder, encoder).unwrap();
+
+    assert_eq!(
+        std::str::from_utf8(&out).unwrap(),
+        r#"{"name":"Aristotle","scores":[[1,2],[],[-3]],"tags":{"school":{"id":1}}}"#
+    );
+}
+
+#[test]
+fn descriptive_to_descriptive() {
+    let expected = person();
+    let data = DESCRIPTIVE.to_vec(&expected).unwrap();
+
+    let cx = Same::<Binary, descriptive::Error, _>::new();
+    let mut out = Vec::new();
+
+    let decoder = DESCRIPTIVE.decoder_with(&cx, &data[..]);
+    let encoder = DESCRIPTIVE.encoder_with(&cx, &mut out);
+    musli::transcode(decoder, encoder).unwrap();
+
+    assert_eq!(out, data);
+}
+
+#[test]
+fn not_self_describing() {
+    let data = musli::storage::to_vec(&person()).unwrap();
+
+    let cx = Same::<Binary, json::Error, _>::new();
+    let mut out = Vec::new();
+
+    let decoder = musli::storage::DEFAULT.decoder_with(&cx, &data[..]);
+    let encoder = JSON.encoder_with(&cx, &mut out);
+    assert!(musli::transcode(decoder, encoder).is_err());
+}
@@ -0,0 +1,191 @@
+//! Tests that ensure that the bincode and postcard presets for the storage
+//! encoding are byte-compatible with the respective crates.
+
+#![cfg(feature = "musli-storage")]
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use musli::mode::Binary;
+use musli::options::{self, Options};
+use musli::storage::Encoding;
+use musli::{Decode, Encode};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+const BINCODE_OPTIONS: Options = options::bincode().build();
+const BINCODE: Encoding<BINCODE_OPTIONS> = Encoding::new().with_options();
+
+const POSTCARD_OPTIONS: Options = options::postcard().build();
+const POSTCARD: Encoding<POSTCARD_OPTIONS> = Encoding::new().with_options();
+
+#[derive(Debug, PartialEq, Encode, Decode, Serialize, Deserialize)]
+#[musli(packed)]
+struct Unit;
+
+#[derive(Debug, PartialEq, Encode, Decode, Serialize, Deserialize)]
+#[musli(packed)]
+struct Primitives {
+    bool: bool,
+    char: char,
+    u8: u8,
+    u16: u16,
+    u32: u32,
+    u64: u64,
+    u128: u128,
+    i8: i8,
+    i16: i16,
+    i32: i32,
+    i64: i64,
+    i128: i128,
+    usize: usize,
+    isize: isize,
+    f32: f32,
+    f64: f64,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, Serialize, Deserialize)]
+#[musli(packed)]
+struct Newtype(u32);
+
+#[derive(Debug, PartialEq, Encode, Decode, Serialize, Deserialize)]
+enum Enum {
+    #[musli(packed)]
+    Unit,
+    #[musli(packed)]
+    Newtype(String),
+    #[musli(packed)]
+    Tuple(u16, i64),
+    #[musli(packed)]
+    Struct { a: u32, b: Option<String> },
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, Serialize, Deserialize)]
+#[musli(packed)]
+struct Complex {
+    primitives: Primitives,
+    unit: Unit,
+    empty: (),
+    newtype: Newtype,
+    string: String,
+    bytes: Vec<u8>,
+    list: Vec<u32>,
+    #[musli(packed)]
+    tuple: (u8, String, i32),
+    #[musli(packed)]
+    array: [u16; 3],
+    some: Option<u64>,
+    none: Option<u64>,
+    map: BTreeMap<String, i32>,
+    enums: Vec<Enum>,
+}
+
+fn primitives(sign: i8) -> Primitives {
+    Primitives {
+        bool: true,
+        char: 'ä',
+        u8: u8::MAX,
+        u16: 300,
+        u32: 70_000,
+        u64: u64::MAX,
+        u128: u128::MAX / 3,
+        i8: sign.wrapping_mul(i8::MAX),
+        i16: i16::from(sign) * 300,
+        i32: i32::from(sign) * 70_000,
+        i64: i64::from(sign) * i64::MAX,
+        i128: i128::from(sign) * (i128::MAX / 3),
+        usize: 1 << 20,
+        isize: isize::from(sign) * (1 << 20),
+        f32: f32::from(sign) * 3.5,
+        f64: f64::from(sign) * core::f64::consts::PI,
+    }
+}
+
+fn complex() -> Complex {
+    Complex {
+        primitives: primitives(-1),
+        unit: Unit,
+        empty: (),
+        newtype: Newtype(42),
+        string: String::from("Aristotle"),
+        bytes: vec![0xde, 0xad, 0xbe, 0xef],
+        list: vec![1, 1 << 10, 1 << 20, u32::MAX],
+        tuple: (1, String::from("two"), -3),
+        array: [1, 300, u16::MAX],
+        some: Some(1 << 40),
+        none: None,
+        map: [(String::from("a"), -1), (String::from("b"), 1 << 16)]
+            .into_iter()
+            .collect(),
+        enums: vec![
+            Enum::Unit,
+            Enum::Newtype(String::from("newtype")),
+            Enum::Tuple(1, -2),
+            Enum::Struct {
+                a: 128,
+                b: Some(String::from("struct")),
+            },
+        ],
+    }
+}
+
+#[track_caller]
+fn test_bincode<T>(value: T)
+where
+    T: fmt::Debug + PartialEq + Encode<Binary> + for<'de> Decode<'de, Binary>,
+    T: Serialize + DeserializeOwned,
+{
+    let expected = bincode::serialize(&value).unwrap();
+    let actual = BINCODE.to_vec(&value).unwrap();
+    assert_eq!(actual, expected, "{value:?}: bincode encoding differs");
+
+    let decoded: T = BINCODE.from_slice(&expected).unwrap();
+    assert_eq!(decoded, value);
+
+    let decoded: T = bincode::deserialize(&actual).unwrap();
+    assert_eq!(decoded, value);
+}
+
+#[track_caller]
+fn test_postcard<T>(value: T)
+where
+    T: fmt::Debug + PartialEq + Encode<Binary> + for<'de> Decode<'de, Binary>,
+    T: Serialize + DeserializeOwned,
+{
+    let mut buf = [0; 1024];
+    let expected = postcard::to_slice(&value, &mut buf).unwrap();
+    let actual = POSTCARD.to_vec(&value).unwrap();
+    assert_eq!(actual, expected, "{value:?}: postcard encoding differs");
+
+    let decoded: T = POSTCARD.from_slice(expected).unwrap();
+    assert_eq!(decoded, value);
+
+    let decoded: T = postcard::from_bytes(&actual).unwrap();
+    assert_eq!(decoded, value);
+}
+
+#[test]
+fn bincode_compat() {
+    test_bincode(primitives(1));
+    test_bincode(primitives(-1));
+    test_bincode(Unit);
+    test_bincode(Newtype(u32::MAX));
+    test_bincode('a');
+    test_bincode('\u{10ffff}');
+    test_bincode(Enum::Unit);
+    test_bincode(Enum::Struct { a: 1, b: None });
+    test_bincode(complex());
+}
+
+#[test]
+fn postcard_compat() {
+    test_postcard(primitives(1));
+    test_postcard(primitives(-1));
+    test_postcard(Unit);
+    test_postcard(Newtype(u32::MAX));
+    test_postcard('a');
+    test_postcard('\u{10ffff}');
+    test_postcard(Enum::Unit);
+    test_postcard(Enum::Struct { a: 1, b: None });
+    test_postcard(complex());
+}